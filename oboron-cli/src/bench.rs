@@ -0,0 +1,113 @@
+//! On-host throughput measurement for `ob bench`.
+//!
+//! Each format is timed with the library's public API over one random
+//! plaintext: `iters` encryptions, then `iters` decryptions of the last
+//! obtext.  This is a quick indication of what an application would see on
+//! this machine, not a substitute for the criterion benchmarks.
+
+use anyhow::{ensure, Result};
+use oboron::{Encoding, Format, Ob, Scheme};
+use rand::distributions::{Alphanumeric, DistString};
+use std::hint::black_box;
+use std::io::Write;
+use std::time::{Duration, Instant};
+
+/// Rate of one timed operation
+struct Throughput {
+    ops_per_sec: f64,
+    mb_per_sec: f64,
+}
+
+impl Throughput {
+    fn new(iters: u32, bytes: usize, elapsed: Duration) -> Self {
+        // A coarse clock can read zero for very short runs
+        let ops_per_sec = f64::from(iters) / elapsed.as_secs_f64().max(1e-9);
+        Self {
+            ops_per_sec,
+            mb_per_sec: ops_per_sec * bytes as f64 / 1e6,
+        }
+    }
+}
+
+/// Time enc and dec of a random `size`-byte plaintext under `key` for
+/// every scheme and built-in encoding, writing one line per format to
+/// `writer`.
+/// MB/s counts plaintext bytes (1 MB = 10^6 bytes).
+pub fn run_bench<W: Write>(
+    key: &str,
+    schemes: &[Scheme],
+    size: usize,
+    iters: u32,
+    writer: &mut W,
+) -> Result<()> {
+    ensure!(size > 0, "--size must be at least 1");
+    ensure!(iters > 0, "--iters must be at least 1");
+    let plaintext = Alphanumeric.sample_string(&mut rand::thread_rng(), size);
+
+    writeln!(
+        writer,
+        "{:<10} {:>12} {:>10} {:>12} {:>10}",
+        "format", "enc ops/s", "enc MB/s", "dec ops/s", "dec MB/s"
+    )?;
+    for &scheme in schemes {
+        for encoding in Encoding::ALL {
+            let ob = Ob::new(Format::new(scheme, encoding), key)?;
+
+            let start = Instant::now();
+            let mut obtext = String::new();
+            for _ in 0..iters {
+                obtext = ob.enc(black_box(&plaintext))?;
+            }
+            let enc = Throughput::new(iters, size, start.elapsed());
+
+            let start = Instant::now();
+            for _ in 0..iters {
+                black_box(ob.dec(black_box(&obtext))?);
+            }
+            let dec = Throughput::new(iters, size, start.elapsed());
+
+            writeln!(
+                writer,
+                "{:<10} {:>12.0} {:>10.2} {:>12.0} {:>10.2}",
+                ob.format().to_string(),
+                enc.ops_per_sec,
+                enc.mb_per_sec,
+                dec.ops_per_sec,
+                dec.mb_per_sec
+            )?;
+        }
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bench_lines_per_format() {
+        let key = oboron::generate_key();
+        let schemes: Vec<Scheme> = ["aasv", "apsv"]
+            .iter()
+            .map(|s| s.parse().unwrap())
+            .collect();
+        let mut output = Vec::new();
+        run_bench(&key, &schemes, 32, 3, &mut output).unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 1 + 2 * Encoding::ALL.len());
+        assert!(lines[0].starts_with("format"));
+        assert!(lines[1].starts_with("aasv.c32 "));
+        assert!(lines[10].starts_with("apsv.hexu "));
+    }
+
+    #[test]
+    fn test_bench_rejects_zero_sizes() {
+        let key = oboron::generate_key();
+        let schemes = ["aasv".parse().unwrap()];
+        assert!(run_bench(&key, &schemes, 0, 1, &mut Vec::new()).is_err());
+        assert!(run_bench(&key, &schemes, 1, 0, &mut Vec::new()).is_err());
+    }
+}
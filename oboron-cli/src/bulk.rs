@@ -0,0 +1,307 @@
+//! Bulk decryption of obtext files selected by a glob pattern, e.g. to
+//! migrate a directory of stored tokens in one run.
+//!
+//! Each matching file holds a single obtext (surrounding whitespace is
+//! ignored).  Its plaintext is written to the output directory under the
+//! same file name.  A file that fails is reported and skipped, so one bad
+//! token does not stop the rest of the migration.
+//!
+//! Matches inside the output directory are refused, so an input file is
+//! never overwritten by its own plaintext.  Plaintext files are written
+//! through [`crate::outfile`], so they are readable only by their owner.
+
+use anyhow::{Context, Result};
+use std::collections::HashSet;
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+/// Outcome counts of a [`dec_glob`] run
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Summary {
+    pub decrypted: usize,
+    pub failed: usize,
+}
+
+/// Decrypt every file matching `pattern` with `dec`, writing each
+/// plaintext to `output_dir` (created if missing) under the file's name.
+///
+/// One line per file is written to `report`: `ok <path>` on success
+/// (omitted when `quiet`) and `FAILED <path>: <error>` on failure.
+/// Errors only for an invalid pattern, a pattern matching nothing, or an
+/// output directory that cannot be created; per-file failures are counted
+/// in the returned [`Summary`].
+///
+/// The pattern is expanded before anything is written, so files written by
+/// this run are never matched themselves.
+pub fn dec_glob<F, W>(
+    pattern: &str,
+    output_dir: &Path,
+    mut dec: F,
+    report: &mut W,
+    quiet: bool,
+) -> Result<Summary>
+where
+    F: FnMut(&str) -> Result<String>,
+    W: Write,
+{
+    let paths: Vec<_> = glob::glob(pattern)
+        .context("Invalid glob pattern")?
+        .collect();
+    if paths.is_empty() {
+        anyhow::bail!("No files match '{}'", pattern);
+    }
+    fs::create_dir_all(output_dir).context(format!(
+        "Failed to create output directory {}",
+        output_dir.display()
+    ))?;
+    let output_dir = fs::canonicalize(output_dir).context(format!(
+        "Failed to resolve output directory {}",
+        output_dir.display()
+    ))?;
+
+    let mut summary = Summary::default();
+    // Files from different directories may share a name; only the first
+    // is written, so no plaintext is silently overwritten
+    let mut written = HashSet::new();
+    for entry in paths {
+        let path = match entry {
+            Ok(path) => path,
+            // Unreadable directory entry: there is no usable path to name
+            Err(e) => {
+                summary.failed += 1;
+                writeln!(report, "FAILED {}", e)?;
+                continue;
+            }
+        };
+        match dec_file(&path, &output_dir, &mut dec, &mut written) {
+            Ok(()) => {
+                summary.decrypted += 1;
+                if !quiet {
+                    writeln!(report, "ok {}", path.display())?;
+                }
+            }
+            Err(e) => {
+                summary.failed += 1;
+                writeln!(report, "FAILED {}: {:#}", path.display(), e)?;
+            }
+        }
+    }
+
+    Ok(summary)
+}
+
+/// Decrypt one file into `output_dir`, which must be canonical
+fn dec_file<F>(
+    path: &Path,
+    output_dir: &Path,
+    dec: &mut F,
+    written: &mut HashSet<std::ffi::OsString>,
+) -> Result<()>
+where
+    F: FnMut(&str) -> Result<String>,
+{
+    let name = path.file_name().context("not a file")?;
+    if written.contains(name) {
+        anyhow::bail!(
+            "another file named {} was already decrypted",
+            Path::new(name).display()
+        );
+    }
+    // Covers an output path equal to the input path, too
+    let input = fs::canonicalize(path).context("failed to resolve path")?;
+    if input.starts_with(output_dir) {
+        anyhow::bail!("file is inside the output directory, refusing to overwrite it");
+    }
+    let text = fs::read_to_string(path).context("failed to read file (must be valid UTF-8)")?;
+    let obtext = text.trim();
+    if obtext.is_empty() {
+        anyhow::bail!("file is empty");
+    }
+    let decd = dec(obtext)?;
+    let out_path = output_dir.join(name);
+    crate::outfile::write_private(&out_path, |out| Ok(out.write_all(decd.as_bytes())?))
+        .context(format!("failed to write {}", out_path.display()))?;
+    written.insert(name.to_os_string());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Stand-in for decryption: obtexts are the plaintext reversed, and
+    /// anything starting with "bad" fails
+    fn reverse(obtext: &str) -> Result<String> {
+        if obtext.starts_with("bad") {
+            anyhow::bail!("cannot decrypt");
+        }
+        Ok(obtext.chars().rev().collect())
+    }
+
+    fn run(dir: &Path, pattern: &str, quiet: bool) -> (Result<Summary>, String) {
+        let mut report = Vec::new();
+        let pattern = dir.join(pattern);
+        let summary = dec_glob(
+            pattern.to_str().unwrap(),
+            &dir.join("out"),
+            reverse,
+            &mut report,
+            quiet,
+        );
+        (summary, String::from_utf8(report).unwrap())
+    }
+
+    #[test]
+    fn test_decrypts_matching_files_only() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("a.token"), "olleh\n").unwrap();
+        fs::write(dir.path().join("b.token"), "dlrow").unwrap();
+        fs::write(dir.path().join("c.other"), "ignored").unwrap();
+
+        let (summary, report) = run(dir.path(), "*.token", false);
+        assert_eq!(
+            summary.unwrap(),
+            Summary {
+                decrypted: 2,
+                failed: 0
+            }
+        );
+        let out = dir.path().join("out");
+        assert_eq!(fs::read_to_string(out.join("a.token")).unwrap(), "hello");
+        assert_eq!(fs::read_to_string(out.join("b.token")).unwrap(), "world");
+        assert!(!out.join("c.other").exists());
+        assert_eq!(report.lines().count(), 2);
+        assert!(report.lines().all(|line| line.starts_with("ok ")));
+    }
+
+    #[test]
+    fn test_failures_are_reported_and_skipped() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("good.token"), "ko").unwrap();
+        fs::write(dir.path().join("bad.token"), "bad obtext").unwrap();
+        fs::write(dir.path().join("empty.token"), "  \n").unwrap();
+
+        let (summary, report) = run(dir.path(), "*.token", true);
+        assert_eq!(
+            summary.unwrap(),
+            Summary {
+                decrypted: 1,
+                failed: 2
+            }
+        );
+        assert!(dir.path().join("out/good.token").exists());
+        assert!(!dir.path().join("out/bad.token").exists());
+        // quiet drops the "ok" lines but keeps failures
+        assert_eq!(report.lines().count(), 2);
+        assert!(report.contains("bad.token: cannot decrypt"));
+        assert!(report.contains("empty.token: file is empty"));
+    }
+
+    #[test]
+    fn test_duplicate_names_are_not_overwritten() {
+        let dir = tempfile::tempdir().unwrap();
+        for sub in ["x", "y"] {
+            fs::create_dir(dir.path().join(sub)).unwrap();
+            fs::write(dir.path().join(sub).join("t.token"), sub).unwrap();
+        }
+
+        let (summary, report) = run(dir.path(), "*/t.token", false);
+        assert_eq!(
+            summary.unwrap(),
+            Summary {
+                decrypted: 1,
+                failed: 1
+            }
+        );
+        // glob yields paths in sorted order
+        assert_eq!(
+            fs::read_to_string(dir.path().join("out/t.token")).unwrap(),
+            "x"
+        );
+        assert!(report.contains("already decrypted"));
+    }
+
+    #[test]
+    fn test_matches_inside_output_dir_are_refused() {
+        let dir = tempfile::tempdir().unwrap();
+        let tokens = dir.path().join("tokens");
+        fs::create_dir(&tokens).unwrap();
+        fs::write(tokens.join("a.token"), "olleh").unwrap();
+
+        // Output directory is the input directory: nothing is overwritten
+        let mut report = Vec::new();
+        let summary = dec_glob(
+            tokens.join("*.token").to_str().unwrap(),
+            &tokens,
+            reverse,
+            &mut report,
+            false,
+        )
+        .unwrap();
+        assert_eq!(
+            summary,
+            Summary {
+                decrypted: 0,
+                failed: 1
+            }
+        );
+        assert_eq!(fs::read_to_string(tokens.join("a.token")).unwrap(), "olleh");
+        let report = String::from_utf8(report).unwrap();
+        assert!(report.contains("inside the output directory"));
+
+        // A recursive pattern does not pick up this run's own output
+        let (summary, report) = run(dir.path(), "**/*.token", false);
+        assert_eq!(
+            summary.unwrap(),
+            Summary {
+                decrypted: 1,
+                failed: 0
+            },
+            "{}",
+            report
+        );
+        // A second run matches the first run's output, and refuses it
+        let (summary, report) = run(dir.path(), "**/*.token", false);
+        assert_eq!(
+            summary.unwrap(),
+            Summary {
+                decrypted: 1,
+                failed: 1
+            },
+            "{}",
+            report
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_output_is_owner_only() {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("a.token"), "olleh").unwrap();
+        run(dir.path(), "*.token", false).0.unwrap();
+
+        let out = dir.path().join("out");
+        let mode = fs::metadata(out.join("a.token"))
+            .unwrap()
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o777, 0o600);
+        // No temporary files are left behind
+        assert_eq!(fs::read_dir(&out).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_no_matches_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let (summary, _) = run(dir.path(), "*.token", false);
+        assert!(summary.unwrap_err().to_string().contains("No files match"));
+
+        let (summary, _) = run(dir.path(), "[", false);
+        assert!(summary
+            .unwrap_err()
+            .to_string()
+            .contains("Invalid glob pattern"));
+    }
+}
@@ -1,9 +1,8 @@
 use anyhow::{Context, Result};
-use data_encoding::BASE64URL_NOPAD;
 use oboron::generate_key;
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 const CONFIG_DIR: &str = ".ob";
 const PROFILES_SUBDIR: &str = "profiles";
@@ -24,6 +23,13 @@ pub struct KeyProfile {
     pub key: Option<String>,
 }
 
+/// Portable profile document produced by `ob profile export`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProfileExport {
+    pub name: String,
+    pub key: String,
+}
+
 pub fn config_path() -> PathBuf {
     dirs::home_dir()
         .expect("Failed to get home directory")
@@ -156,7 +162,7 @@ pub fn load_profile(name: &str) -> Result<KeyProfile> {
     Ok(profile)
 }
 
-pub fn save_key_profile(name: &str, profile: &KeyProfile) -> Result<()> {
+pub fn save_key_profile(name: &str, profile: &KeyProfile, quiet: bool) -> Result<()> {
     validate_profile_name(name)?;
     let path = profile_path(name);
 
@@ -166,7 +172,9 @@ pub fn save_key_profile(name: &str, profile: &KeyProfile) -> Result<()> {
 
     if path.exists() {
         let backup_path = backup_profile(name)?;
-        println!("Backed up existing profile to:  {}", backup_path.display());
+        if !quiet {
+            println!("Backed up existing profile to:  {}", backup_path.display());
+        }
     }
 
     let content = serde_json::to_string_pretty(profile).context("Failed to serialize profile")?;
@@ -184,7 +192,7 @@ pub fn save_key_profile(name: &str, profile: &KeyProfile) -> Result<()> {
     Ok(())
 }
 
-pub fn init_command(name: &str) -> Result<()> {
+pub fn init_command(name: &str, quiet: bool) -> Result<()> {
     validate_profile_name(name)?;
     let path = profile_path(name);
     if path.exists() {
@@ -214,7 +222,7 @@ pub fn init_command(name: &str) -> Result<()> {
         key: Some(key.clone()),
     };
 
-    save_key_profile(name, &profile)?;
+    save_key_profile(name, &profile, quiet)?;
 
     let config = Config {
         profile: name.to_string(),
@@ -224,6 +232,9 @@ pub fn init_command(name: &str) -> Result<()> {
 
     save_config(&config)?;
 
+    if quiet {
+        return Ok(());
+    }
     println!("✓ Configuration saved to {}", config_path().display());
     println!("\nYour profile '{}':", name);
     println!("  Default scheme:    aasv");
@@ -255,20 +266,17 @@ pub fn config_show_command(public_profile: bool) -> Result<()> {
     Ok(())
 }
 
-pub fn profile_list_command() -> Result<()> {
-    let profile_dir = profile_dir();
-
-    if !profile_dir.exists() {
-        println!("No profiles found.  Run 'ob init' to create one.");
-        return Ok(());
+/// List the names of all profiles in `dir`, sorted.
+///
+/// Returns an empty list if the directory does not exist.
+pub fn list_profiles_in(dir: &Path) -> Result<Vec<String>> {
+    if !dir.exists() {
+        return Ok(Vec::new());
     }
 
-    let entries = fs::read_dir(&profile_dir)?;
     let mut profiles = Vec::new();
-
-    for entry in entries {
-        let entry = entry?;
-        let path = entry.path();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
 
         if path.extension().and_then(|s| s.to_str()) == Some("json") {
             if let Some(name) = path.file_stem().and_then(|s| s.to_str()) {
@@ -277,13 +285,28 @@ pub fn profile_list_command() -> Result<()> {
         }
     }
 
+    profiles.sort();
+    Ok(profiles)
+}
+
+/// List the names of all profiles in the profile directory, sorted.
+pub fn list_profiles() -> Result<Vec<String>> {
+    list_profiles_in(&profile_dir())
+}
+
+pub fn profile_list_command() -> Result<()> {
+    if !profile_dir().exists() {
+        println!("No profiles found.  Run 'ob init' to create one.");
+        return Ok(());
+    }
+
+    let profiles = list_profiles()?;
+
     if profiles.is_empty() {
         println!("No profiles found.");
         return Ok(());
     }
 
-    profiles.sort();
-
     let config = load_config().ok();
     let active_profile = config.as_ref().map(|c| c.profile.as_str());
 
@@ -318,7 +341,7 @@ pub fn profile_show_command(name: Option<&str>) -> Result<()> {
     Ok(())
 }
 
-pub fn profile_activate_command(name: &str) -> Result<()> {
+pub fn profile_activate_command(name: &str, quiet: bool) -> Result<()> {
     validate_profile_name(name)?;
     load_profile(name)?;
 
@@ -331,31 +354,34 @@ pub fn profile_activate_command(name: &str) -> Result<()> {
     config.profile = name.to_string();
     save_config(&config)?;
 
-    println!("✓ Activated profile '{}'", name);
+    if !quiet {
+        println!("✓ Activated profile '{}'", name);
+    }
 
     Ok(())
 }
 
-pub fn profile_create_command(name: &str, key: Option<&str>) -> Result<()> {
+pub fn profile_create_command(name: &str, key: Option<&str>, quiet: bool) -> Result<()> {
     validate_profile_name(name)?;
     let key_str = if let Some(k) = key {
-        validate_base64_key(k)?;
-        k.to_string()
+        validate_base64_key(k)?
     } else {
         generate_key()
     };
     let profile = KeyProfile { key: Some(key_str.clone()) };
 
-    save_key_profile(name, &profile)?;
+    save_key_profile(name, &profile, quiet)?;
 
-    println!("✓ Created profile '{}'", name);
-    println!("  Key: {}", key_str);
-    println!("\n⚠️  Keep this profile secure!");
+    if !quiet {
+        println!("✓ Created profile '{}'", name);
+        println!("  Key: {}", key_str);
+        println!("\n⚠️  Keep this profile secure!");
+    }
 
     Ok(())
 }
 
-pub fn profile_delete_command(name: &str) -> Result<()> {
+pub fn profile_delete_command(name: &str, quiet: bool) -> Result<()> {
     validate_profile_name(name)?;
     let path = profile_path(name);
 
@@ -390,13 +416,15 @@ pub fn profile_delete_command(name: &str) -> Result<()> {
     let backup_path = backup_profile(name)?;
     fs::remove_file(&path)?;
 
-    println!("✓ Deleted profile '{}'", name);
-    println!("  Backup saved to: {}", backup_path.display());
+    if !quiet {
+        println!("✓ Deleted profile '{}'", name);
+        println!("  Backup saved to: {}", backup_path.display());
+    }
 
     Ok(())
 }
 
-pub fn profile_rename_command(old_name: &str, new_name: &str) -> Result<()> {
+pub fn profile_rename_command(old_name: &str, new_name: &str, quiet: bool) -> Result<()> {
     validate_profile_name(old_name)?;
     validate_profile_name(new_name)?;
     let old_path = profile_path(old_name);
@@ -420,10 +448,17 @@ pub fn profile_rename_command(old_name: &str, new_name: &str) -> Result<()> {
         old_name, new_name
     ))?;
 
+    let mut active_updated = false;
     if let Ok(mut config) = load_config() {
         if config.profile == old_name {
             config.profile = new_name.to_string();
             save_config(&config)?;
+            active_updated = true;
+        }
+    }
+
+    if !quiet {
+        if active_updated {
             println!(
                 "✓ Renamed profile '{}' to '{}' (active profile updated)",
                 old_name, new_name
@@ -431,52 +466,107 @@ pub fn profile_rename_command(old_name: &str, new_name: &str) -> Result<()> {
         } else {
             println!("✓ Renamed profile '{}' to '{}'", old_name, new_name);
         }
-    } else {
-        println!("✓ Renamed profile '{}' to '{}'", old_name, new_name);
+        println!("  Backup saved to: {}", backup_path.display());
     }
 
-    println!("  Backup saved to: {}", backup_path.display());
-
     Ok(())
 }
 
-pub fn profile_set_command(name: &str, key: Option<&str>) -> Result<()> {
+pub fn profile_set_command(name: &str, key: Option<&str>, quiet: bool) -> Result<()> {
     validate_profile_name(name)?;
     let mut profile = load_profile(name)?;
 
     if let Some(k) = key {
-        validate_base64_key(k)?;
-        profile.key = Some(k.to_string());
+        profile.key = Some(validate_base64_key(k)?);
     } else {
         anyhow::bail!("--key must be provided");
     }
 
-    save_key_profile(name, &profile)?;
+    save_key_profile(name, &profile, quiet)?;
+
+    if !quiet {
+        println!("✓ Updated profile '{}'", name);
+    }
+
+    Ok(())
+}
+
+pub fn profile_export_command(name: &str, output: Option<&Path>, quiet: bool) -> Result<()> {
+    let profile = load_profile(name)?;
+    let key = profile
+        .key
+        .ok_or_else(|| anyhow::anyhow!("Profile '{}' has no key", name))?;
+
+    let export = ProfileExport {
+        name: name.to_string(),
+        key,
+    };
+    let content =
+        serde_json::to_string_pretty(&export).context("Failed to serialize profile export")?;
+
+    if let Some(path) = output {
+        // Created owner-only, and never over an existing file
+        let mut file = crate::outfile::create_private(path).map_err(|e| {
+            if e.kind() == std::io::ErrorKind::AlreadyExists {
+                anyhow::anyhow!("Output file {} already exists", path.display())
+            } else {
+                anyhow::Error::new(e).context("Failed to write export file")
+            }
+        })?;
+        if let Err(e) = std::io::Write::write_all(&mut file, content.as_bytes()) {
+            let _ = fs::remove_file(path);
+            return Err(anyhow::Error::new(e).context("Failed to write export file"));
+        }
 
-    println!("✓ Updated profile '{}'", name);
+        if !quiet {
+            println!("✓ Exported profile '{}' to {}", name, path.display());
+            println!("\n⚠️  The export file contains the key.  Keep it secure!");
+        }
+    } else {
+        println!("{}", content);
+    }
 
     Ok(())
 }
 
-fn validate_base64_key(key_str: &str) -> Result<()> {
-    if key_str.len() != 86 {
-        anyhow::bail!("Key must be 86 base64 chars, got {} chars", key_str.len());
+pub fn profile_import_command(file: &Path, quiet: bool) -> Result<()> {
+    let content = fs::read_to_string(file)
+        .context(format!("Failed to read export file {}", file.display()))?;
+    let export: ProfileExport =
+        serde_json::from_str(&content).context("Failed to parse profile export")?;
+
+    validate_profile_name(&export.name)?;
+    let key = validate_base64_key(&export.key)?;
+
+    if profile_path(&export.name).exists() {
+        anyhow::bail!(
+            "Profile '{}' already exists. Delete or rename it before importing.",
+            export.name
+        );
     }
 
-    let key_bytes = BASE64URL_NOPAD
-        .decode(key_str.as_bytes())
-        .context("Invalid key base64 encoding")?;
+    save_key_profile(&export.name, &KeyProfile { key: Some(key) }, quiet)?;
 
-    if key_bytes.len() != 64 {
-        anyhow::bail!("Key must decode to 64 bytes, got {} bytes", key_bytes.len());
+    if !quiet {
+        println!("✓ Imported profile '{}'", export.name);
     }
 
     Ok(())
 }
 
+/// Validate a base64 key and return it in canonical form.
+///
+/// Accepts URL-safe or standard base64, with or without `=` padding; the
+/// returned key is always unpadded URL-safe base64 (86 chars).  Errors
+/// tell a key of the wrong length from one with invalid characters.
+pub fn validate_base64_key(key_str: &str) -> Result<String> {
+    Ok(oboron::Omnib::new(key_str)?.key())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use data_encoding::{BASE64URL_NOPAD, BASE64_NOPAD};
 
     #[test]
     fn test_config_serialization() {
@@ -506,6 +596,39 @@ mod tests {
         assert_eq!(profile.key, deserialized.key);
     }
 
+    #[test]
+    fn test_list_profiles_in() {
+        let dir =
+            std::env::temp_dir().join(format!("ob_test_list_profiles_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+
+        for name in ["work", "default", "alt-1"] {
+            fs::write(dir.join(format!("{}.json", name)), "{}").unwrap();
+        }
+        fs::write(dir.join("notes.txt"), "not a profile").unwrap();
+
+        let profiles = list_profiles_in(&dir).unwrap();
+        assert_eq!(profiles, vec!["alt-1", "default", "work"]);
+
+        fs::remove_dir_all(&dir).unwrap();
+        assert!(list_profiles_in(&dir).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_profile_export_serialization() {
+        let export = ProfileExport {
+            name: "work".to_string(),
+            key: oboron::generate_key(),
+        };
+
+        let json = serde_json::to_string(&export).unwrap();
+        let deserialized: ProfileExport = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(export.name, deserialized.name);
+        assert_eq!(export.key, deserialized.key);
+    }
+
     #[test]
     fn test_validate_base64_key_valid() {
         let key_str = oboron::generate_key();
@@ -517,4 +640,55 @@ mod tests {
         let key_str = "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA";
         assert!(validate_base64_key(key_str).is_err());
     }
+
+    #[test]
+    fn test_validate_base64_key_accepts_padded_and_standard() {
+        let key_str = oboron::generate_key();
+        let key_bytes = BASE64URL_NOPAD.decode(key_str.as_bytes()).unwrap();
+
+        let padded = format!("{}==", key_str);
+        assert_eq!(validate_base64_key(&padded).unwrap(), key_str);
+
+        let standard = data_encoding::BASE64.encode(&key_bytes);
+        assert_eq!(validate_base64_key(&standard).unwrap(), key_str);
+
+        let standard_nopad = BASE64_NOPAD.encode(&key_bytes);
+        assert_eq!(validate_base64_key(&standard_nopad).unwrap(), key_str);
+    }
+
+    #[test]
+    fn test_validate_base64_key_distinct_errors() {
+        let key_str = oboron::generate_key();
+        let error = |key: &str| {
+            validate_base64_key(key)
+                .unwrap_err()
+                .downcast::<oboron::Error>()
+                .unwrap()
+        };
+        assert_eq!(
+            error(&key_str[1..]),
+            oboron::Error::KeyWrongLength {
+                expected: 86,
+                got: 85
+            }
+        );
+        assert_eq!(
+            error(&format!("{}A", key_str)),
+            oboron::Error::KeyWrongLength {
+                expected: 86,
+                got: 87
+            }
+        );
+        assert_eq!(
+            error(&format!("{}!", &key_str[1..])),
+            oboron::Error::KeyInvalidEncoding
+        );
+    }
+
+    #[test]
+    fn test_validate_base64_key_invalid() {
+        let key_str = oboron::generate_key();
+        assert!(validate_base64_key(&format!("{}===", key_str)).is_err());
+        assert!(validate_base64_key(&format!("{}!", &key_str[1..])).is_err());
+    }
 }
@@ -0,0 +1,76 @@
+//! Process exit codes for `ob`.
+//!
+//! | Code | Meaning                                             |
+//! |------|-----------------------------------------------------|
+//! | 0    | Success                                             |
+//! | 1    | Any other failure, including command-line misuse    |
+//! | 2    | Obtext could not be decoded or decrypted            |
+//! | 3    | No usable key, or a profile/configuration error     |
+//!
+//! Errors are tagged with an [`ExitKind`] where they arise; untagged errors
+//! exit with code 1.
+
+use std::fmt;
+use std::process::ExitCode;
+
+/// Failure category, determining the exit code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitKind {
+    /// Any other failure
+    General = 1,
+    /// Obtext could not be decoded or decrypted
+    Decode = 2,
+    /// No usable key, or a profile/configuration error
+    KeyConfig = 3,
+}
+
+impl From<ExitKind> for ExitCode {
+    fn from(kind: ExitKind) -> Self {
+        ExitCode::from(kind as u8)
+    }
+}
+
+/// An error tagged with its [`ExitKind`]; displays as the inner error.
+#[derive(Debug)]
+struct Tagged {
+    kind: ExitKind,
+    error: anyhow::Error,
+}
+
+impl fmt::Display for Tagged {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.error, f)
+    }
+}
+
+impl std::error::Error for Tagged {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.error.source()
+    }
+}
+
+/// Tag the error of a `Result` with an [`ExitKind`].
+pub trait ResultExt<T> {
+    /// Tag the error with `kind`, unless it already carries one.
+    fn exit_kind(self, kind: ExitKind) -> anyhow::Result<T>;
+}
+
+impl<T, E: Into<anyhow::Error>> ResultExt<T> for Result<T, E> {
+    fn exit_kind(self, kind: ExitKind) -> anyhow::Result<T> {
+        self.map_err(|e| {
+            let error = e.into();
+            if error.is::<Tagged>() {
+                error
+            } else {
+                Tagged { kind, error }.into()
+            }
+        })
+    }
+}
+
+/// The exit kind of an error returned by a command.
+pub fn kind_of(error: &anyhow::Error) -> ExitKind {
+    error
+        .downcast_ref::<Tagged>()
+        .map_or(ExitKind::General, |t| t.kind)
+}
@@ -0,0 +1,444 @@
+//! Framed container for encrypting large files in fixed-size frames.
+//!
+//! Small inputs are encrypted as a single obtext.  Inputs larger than
+//! [`FRAMING_THRESHOLD`] are split into frames so that both `ob enc` and
+//! `ob dec` can process them without buffering the whole file.
+//!
+//! The container is line-oriented text:
+//!
+//! ```text
+//! OBFRAMES 1 <format> <frame-size>     header: magic, version, format, frame size in bytes
+//! <obtext>                             one line per frame
+//! ...
+//! END <frame-count>                    trailer: number of frames written
+//! ```
+//!
+//! Each frame encrypts `"<stream-id>:<index>:<last>:<chunk>"`:
+//!
+//! - `stream-id` is 32 random hex digits, the same for every frame of one
+//!   container, so frames spliced in from another container are rejected;
+//! - `index` is the 0-based frame number, so dropped or reordered frames are
+//!   rejected;
+//! - `last` is `1` on the final frame and `0` on every other, so a container
+//!   truncated at a frame boundary is rejected even if the trailer is
+//!   rewritten to match;
+//! - `chunk` holds at most `frame-size` bytes of input, split on a UTF-8
+//!   character boundary.
+//!
+//! Every container has at least one frame (empty input gives one empty final
+//! frame).  The header and trailer are not encrypted: only the frames are
+//! authenticated, and only by the authenticated schemes, so the header format
+//! must match the format the caller asked for.  With `upbc`, which does not
+//! authenticate, none of these checks are reliable against tampering.
+
+use anyhow::{Context, Result};
+use data_encoding::HEXLOWER;
+use oboron::{Format, Ob, Omnib};
+use std::io::{BufRead, ErrorKind, Read, Write};
+
+/// Magic string at the start of a framed container.
+pub const MAGIC: &str = "OBFRAMES";
+
+/// Container layout version.
+pub const VERSION: u32 = 1;
+
+/// Inputs larger than this many bytes are written as a framed container.
+pub const FRAMING_THRESHOLD: u64 = 1024 * 1024;
+
+/// Default plaintext bytes per frame.
+pub const DEFAULT_FRAME_SIZE: usize = 64 * 1024;
+
+/// Smallest allowed frame size (must hold any UTF-8 character).
+const MIN_FRAME_SIZE: usize = 4;
+
+const TRAILER_PREFIX: &str = "END ";
+
+/// Random bytes of the per-container stream ID.
+const STREAM_ID_LEN: usize = 16;
+
+/// Parsed container header.
+#[derive(Debug, PartialEq)]
+pub struct Header {
+    pub format: Format,
+    pub frame_size: usize,
+}
+
+impl Header {
+    fn parse(line: &str) -> Result<Self> {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        match fields.as_slice() {
+            [magic, version, format, frame_size] if *magic == MAGIC => {
+                let version: u32 = version.parse().context("Invalid frame header version")?;
+                if version != VERSION {
+                    anyhow::bail!("Unsupported frame container version {}", version);
+                }
+                let format = Format::from_str(format).map_err(|e| anyhow::anyhow!("{}", e))?;
+                let frame_size = frame_size.parse().context("Invalid frame size in header")?;
+                Ok(Self { format, frame_size })
+            }
+            _ => anyhow::bail!("Invalid frame container header"),
+        }
+    }
+}
+
+impl std::fmt::Display for Header {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {} {} {}",
+            MAGIC, VERSION, self.format, self.frame_size
+        )
+    }
+}
+
+/// Check whether the reader starts with a framed container header,
+/// without consuming any input.
+pub fn is_framed<R: BufRead>(reader: &mut R) -> Result<bool> {
+    let buf = reader.fill_buf().context("Failed to read input")?;
+    Ok(buf.starts_with(MAGIC.as_bytes()))
+}
+
+/// Encrypt `reader` into a framed container written to `writer`.
+///
+/// Returns the number of frames written.
+pub fn write_framed<R: Read, W: Write>(
+    ob: &Ob,
+    mut reader: R,
+    writer: &mut W,
+    frame_size: usize,
+) -> Result<u64> {
+    if frame_size < MIN_FRAME_SIZE {
+        anyhow::bail!("Frame size must be at least {} bytes", MIN_FRAME_SIZE);
+    }
+
+    let header = Header {
+        format: oboron::ObtextCodec::format(ob),
+        frame_size,
+    };
+    writeln!(writer, "{}", header)?;
+
+    let stream_id = HEXLOWER.encode(&rand::random::<[u8; STREAM_ID_LEN]>());
+    let mut buf = vec![0u8; frame_size];
+    let mut filled = 0;
+    let mut index: u64 = 0;
+    // Each chunk is held back until the next one is read, so that the
+    // final frame can be flagged as such
+    let mut pending: Option<String> = None;
+
+    loop {
+        filled += read_full(&mut reader, &mut buf[filled..])?;
+        if filled == 0 {
+            break;
+        }
+        let eof = filled < frame_size;
+
+        // Cut on a character boundary; carry an incomplete trailing
+        // character over to the next frame.
+        let valid = match std::str::from_utf8(&buf[..filled]) {
+            Ok(_) => filled,
+            Err(e) if e.error_len().is_none() && !eof => e.valid_up_to(),
+            Err(_) => anyhow::bail!("Input is not valid UTF-8"),
+        };
+        let chunk = std::str::from_utf8(&buf[..valid]).expect("validated above");
+
+        if let Some(prev) = pending.replace(chunk.to_string()) {
+            write_frame(ob, writer, &stream_id, index, false, &prev)?;
+            index += 1;
+        }
+
+        buf.copy_within(valid..filled, 0);
+        filled -= valid;
+        if eof {
+            break;
+        }
+    }
+
+    write_frame(
+        ob,
+        writer,
+        &stream_id,
+        index,
+        true,
+        &pending.unwrap_or_default(),
+    )?;
+    index += 1;
+
+    writeln!(writer, "{}{}", TRAILER_PREFIX, index)?;
+    writer.flush()?;
+
+    Ok(index)
+}
+
+fn write_frame<W: Write>(
+    ob: &Ob,
+    writer: &mut W,
+    stream_id: &str,
+    index: u64,
+    last: bool,
+    chunk: &str,
+) -> Result<()> {
+    let framed = format!("{}:{}:{}:{}", stream_id, index, u8::from(last), chunk);
+    writeln!(writer, "{}", ob.enc(&framed)?)?;
+    Ok(())
+}
+
+/// Decrypt a framed container from `reader`, writing the plaintext to `writer`.
+///
+/// Frames are decoded with `format`; a header naming another format is an
+/// error.  Without a format (`ob dec --auto`), the header's is used.  Every
+/// frame must belong to the same stream, in order, ending with the final
+/// frame; otherwise an error is returned, after part of the plaintext may
+/// already have been written.
+///
+/// Returns the number of frames read.
+pub fn read_framed<R: BufRead, W: Write + ?Sized>(
+    omb: &Omnib,
+    format: Option<Format>,
+    reader: R,
+    writer: &mut W,
+) -> Result<u64> {
+    let mut lines = reader.lines();
+
+    let header_line = lines
+        .next()
+        .context("Empty frame container")?
+        .context("Failed to read frame header")?;
+    let header = Header::parse(&header_line)?;
+    let format = match format {
+        Some(format) if format != header.format => anyhow::bail!(
+            "Frame container format {} does not match the requested format {}",
+            header.format,
+            format
+        ),
+        _ => header.format,
+    };
+
+    let mut stream_id: Option<String> = None;
+    let mut index: u64 = 0;
+    let mut ended = false;
+    for line in lines {
+        let line = line.context("Failed to read frame")?;
+
+        if let Some(count) = line.strip_prefix(TRAILER_PREFIX) {
+            let count: u64 = count.trim().parse().context("Invalid frame trailer")?;
+            if !ended {
+                anyhow::bail!("Frame container is truncated (final frame missing)");
+            }
+            if count != index {
+                anyhow::bail!(
+                    "Frame count mismatch: trailer says {}, read {}",
+                    count,
+                    index
+                );
+            }
+            writer.flush()?;
+            return Ok(index);
+        }
+        if ended {
+            anyhow::bail!("Unexpected frame after the final frame");
+        }
+
+        let framed = omb
+            .dec(&line, format)
+            .map_err(|e| anyhow::anyhow!("Failed to decode frame {}: {}", index, e))?;
+        let corrupted = || anyhow::anyhow!("Frame {} is out of order or corrupted", index);
+        let mut fields = framed.splitn(4, ':');
+        let (Some(id), Some(i), Some(last), Some(chunk)) =
+            (fields.next(), fields.next(), fields.next(), fields.next())
+        else {
+            return Err(corrupted());
+        };
+        match &stream_id {
+            None => stream_id = Some(id.to_string()),
+            Some(expected) if expected == id => {}
+            Some(_) => anyhow::bail!("Frame {} belongs to another container", index),
+        }
+        if i.parse::<u64>().ok() != Some(index) {
+            return Err(corrupted());
+        }
+        ended = match last {
+            "0" => false,
+            "1" => true,
+            _ => return Err(corrupted()),
+        };
+        if chunk.len() > header.frame_size {
+            anyhow::bail!("Frame {} exceeds the declared frame size", index);
+        }
+        writer.write_all(chunk.as_bytes())?;
+        index += 1;
+    }
+
+    anyhow::bail!(
+        "Frame container is truncated (missing trailer after {} frames)",
+        index
+    )
+}
+
+/// Read until `buf` is full or EOF; returns the number of bytes read.
+fn read_full<R: Read>(reader: &mut R, buf: &mut [u8]) -> Result<usize> {
+    let mut total = 0;
+    while total < buf.len() {
+        match reader.read(&mut buf[total..]) {
+            Ok(0) => break,
+            Ok(n) => total += n,
+            Err(e) if e.kind() == ErrorKind::Interrupted => {}
+            Err(e) => return Err(e).context("Failed to read input"),
+        }
+    }
+    Ok(total)
+}
+
+#[cfg(all(test, feature = "aasv"))]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn test_ob() -> Ob {
+        Ob::new_keyless("aasv.b64").unwrap()
+    }
+
+    fn test_omnib() -> Omnib {
+        Omnib::new_keyless().unwrap()
+    }
+
+    fn test_format() -> Format {
+        Format::from_str("aasv.b64").unwrap()
+    }
+
+    /// Frame lines (header, frames, trailer) of a container of `input`
+    fn framed_lines(input: &str, frame_size: usize) -> Vec<String> {
+        let mut framed = Vec::new();
+        write_framed(&test_ob(), input.as_bytes(), &mut framed, frame_size).unwrap();
+        String::from_utf8(framed)
+            .unwrap()
+            .lines()
+            .map(String::from)
+            .collect()
+    }
+
+    fn read_lines(lines: &[&str]) -> Result<u64> {
+        let mut output = Vec::new();
+        read_framed(
+            &test_omnib(),
+            Some(test_format()),
+            lines.join("\n").as_bytes(),
+            &mut output,
+        )
+    }
+
+    fn roundtrip(input: &str, frame_size: usize) -> (Vec<u8>, u64) {
+        let mut framed = Vec::new();
+        let written = write_framed(&test_ob(), input.as_bytes(), &mut framed, frame_size).unwrap();
+
+        let mut reader = Cursor::new(framed);
+        assert!(is_framed(&mut reader).unwrap());
+
+        let mut output = Vec::new();
+        let read = read_framed(&test_omnib(), Some(test_format()), reader, &mut output).unwrap();
+        assert_eq!(written, read);
+        (output, read)
+    }
+
+    #[test]
+    fn test_roundtrip_splits_on_char_boundaries() {
+        let input = "héllo wörld → ünïcode ✓ ".repeat(50);
+        let (output, frames) = roundtrip(&input, 7);
+        assert_eq!(output, input.as_bytes());
+        assert!(frames > 1);
+    }
+
+    #[test]
+    fn test_roundtrip_exact_multiple_and_empty() {
+        let (output, frames) = roundtrip("abcdefgh", 4);
+        assert_eq!(output, b"abcdefgh");
+        assert_eq!(frames, 2);
+
+        // Empty input still has a final frame
+        let (output, frames) = roundtrip("", 4);
+        assert!(output.is_empty());
+        assert_eq!(frames, 1);
+    }
+
+    #[test]
+    fn test_header_roundtrip() {
+        let header = Header {
+            format: Format::from_str("aasv.b64").unwrap(),
+            frame_size: 65536,
+        };
+        assert_eq!(header.to_string(), "OBFRAMES 1 aasv.b64 65536");
+        assert_eq!(Header::parse(&header.to_string()).unwrap(), header);
+        assert!(Header::parse("OBFRAMES 2 aasv.b64 65536").is_err());
+    }
+
+    #[test]
+    fn test_detects_reordered_and_truncated_frames() {
+        let lines = framed_lines("aaaabbbbcccc", 4);
+        let lines: Vec<&str> = lines.iter().map(String::as_str).collect();
+        assert_eq!(read_lines(&lines).unwrap(), 3);
+
+        // Swap two frames
+        let swapped = [lines[0], lines[2], lines[1], lines[3], lines[4]];
+        assert!(read_lines(&swapped).is_err());
+
+        // Drop a frame in the middle, with the trailer count fixed up
+        let dropped = [lines[0], lines[1], lines[3], "END 2"];
+        assert!(read_lines(&dropped).is_err());
+
+        // Drop the trailer
+        assert!(read_lines(&lines[..4]).is_err());
+
+        // Truncate at a frame boundary and rewrite the trailer to match
+        let err = read_lines(&[lines[0], lines[1], lines[2], "END 2"]).unwrap_err();
+        assert!(err.to_string().contains("final frame missing"));
+    }
+
+    #[test]
+    fn test_detects_frames_spliced_from_another_container() {
+        let first = framed_lines("aaaabbbb", 4);
+        let second = framed_lines("aaaabbbb", 4);
+        // Same key, format and plaintext: only the stream ID differs
+        let spliced = [&first[0], &first[1], &second[2], &first[3]];
+        let spliced: Vec<&str> = spliced.iter().map(|l| l.as_str()).collect();
+        let err = read_lines(&spliced).unwrap_err();
+        assert!(err.to_string().contains("another container"));
+    }
+
+    #[test]
+    fn test_header_must_match_requested_format() {
+        let lines = framed_lines("aaaabbbb", 4);
+        let mut output = Vec::new();
+        let other = Format::from_str("aasv.c32").unwrap();
+        let err = read_framed(
+            &test_omnib(),
+            Some(other),
+            lines.join("\n").as_bytes(),
+            &mut output,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("does not match"));
+        assert!(output.is_empty());
+
+        // Without a requested format (--auto), the header's is used
+        read_framed(
+            &test_omnib(),
+            None,
+            lines.join("\n").as_bytes(),
+            &mut output,
+        )
+        .unwrap();
+        assert_eq!(output, b"aaaabbbb");
+    }
+
+    #[test]
+    fn test_is_framed_false_for_plain_obtext() {
+        let mut reader = Cursor::new(b"abc123".to_vec());
+        assert!(!is_framed(&mut reader).unwrap());
+    }
+
+    #[test]
+    fn test_rejects_invalid_utf8() {
+        let input: &[u8] = &[b'a', 0xff, b'b'];
+        let mut framed = Vec::new();
+        assert!(write_framed(&test_ob(), input, &mut framed, 8).is_err());
+    }
+}
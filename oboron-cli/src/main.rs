@@ -1,13 +1,27 @@
 //! CLI application for oboron secure schemes (a-tier and u-tier)
 
+mod bench;
+mod bulk;
 mod completions;
 mod config;
+mod exit;
+mod framing;
+mod outfile;
+mod redact;
+mod secret;
 
 use anyhow::{Context, Result};
-use clap::{Args, Parser, Subcommand};
+use clap::builder::PossibleValue;
+use clap::{Args, CommandFactory, Parser, Subcommand, ValueEnum};
+use clap_complete::{ArgValueCandidates, CompleteEnv};
 use config::Config;
+use exit::{ExitKind, ResultExt};
 use oboron::{Encoding, Format, Scheme};
-use std::io::{self, Read};
+use secret::KeyString;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Write};
+use std::path::PathBuf;
+use std::process::ExitCode;
 
 #[derive(Parser)]
 #[command(name = "ob")]
@@ -15,10 +29,86 @@ use std::io::{self, Read};
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+
+    /// Suppress success messages (output data is unaffected)
+    #[arg(short, long, global = true)]
+    quiet: bool,
+}
+
+/// Scheme value for `--scheme`, limited to the schemes handled by `ob`.
+#[derive(Debug, Clone, Copy)]
+struct SchemeArg(Scheme);
+
+impl ValueEnum for SchemeArg {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[
+            #[cfg(feature = "aasv")]
+            SchemeArg(Scheme::Aasv),
+            #[cfg(feature = "apsv")]
+            SchemeArg(Scheme::Apsv),
+            #[cfg(feature = "aags")]
+            SchemeArg(Scheme::Aags),
+            #[cfg(feature = "apgs")]
+            SchemeArg(Scheme::Apgs),
+            #[cfg(feature = "apgc")]
+            SchemeArg(Scheme::Apgc),
+            #[cfg(feature = "upbc")]
+            SchemeArg(Scheme::Upbc),
+            #[cfg(feature = "mock")]
+            SchemeArg(Scheme::Mock1),
+            #[cfg(feature = "mock")]
+            SchemeArg(Scheme::Mock2),
+            #[cfg(feature = "mock")]
+            SchemeArg(Scheme::Mock3),
+            #[cfg(feature = "mock")]
+            SchemeArg(Scheme::Mock4),
+        ]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        // Aliases (e.g. `32`, `ob32`) come from the library, as accepted by
+        // `Scheme::from_str`
+        let value = PossibleValue::new(self.0.as_str()).aliases(self.0.aliases());
+        Some(match self.0 {
+            #[cfg(feature = "mock")]
+            Scheme::Mock1 | Scheme::Mock2 | Scheme::Mock3 | Scheme::Mock4 => value.hide(true),
+            #[allow(unreachable_patterns)]
+            _ => value,
+        })
+    }
 }
 
+/// Encoding value for `--encoding`.
+#[derive(Debug, Clone, Copy)]
+struct EncodingArg(Encoding);
+
+impl ValueEnum for EncodingArg {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[
+            EncodingArg(Encoding::C32),
+            EncodingArg(Encoding::B32),
+            EncodingArg(Encoding::B64),
+            EncodingArg(Encoding::Hex),
+            EncodingArg(Encoding::HexUpper),
+        ]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        // Long names and aliases (e.g. `base64`, `b64url`) come from the
+        // library, as accepted by `Encoding::from_str`
+        Some(PossibleValue::new(self.0.as_str()).aliases(self.0.aliases()))
+    }
+}
+
+/// Scheme selection: `--scheme <SCHEME>` or one of the per-scheme flags.
+/// At most one may be given (enforced by clap).
 #[derive(Args, Debug)]
+#[group(id = "scheme_flags", multiple = false)]
 struct SchemeFlags {
+    /// Scheme to use
+    #[arg(long = "scheme", value_name = "SCHEME")]
+    scheme: Option<SchemeArg>,
+
     /// Use upbc scheme (probabilistic unauthenticated)
     #[cfg(feature = "upbc")]
     #[arg(short = 'u', long, alias = "21p")]
@@ -34,6 +124,11 @@ struct SchemeFlags {
     #[arg(short = 'G', long, alias = "31p")]
     apgs: bool,
 
+    /// Use apgc scheme (probabilistic AES-GCM, for interop)
+    #[cfg(feature = "apgc")]
+    #[arg(long)]
+    apgc: bool,
+
     /// Use aasv scheme (deterministic AES-SIV)
     #[cfg(feature = "aasv")]
     #[arg(short = 's', long, alias = "32")]
@@ -53,91 +148,81 @@ struct SchemeFlags {
     #[cfg(feature = "mock")]
     #[arg(long, alias = "71", hide = true)]
     mock2: bool,
+
+    /// Use mock3 scheme (testing, length-preserving keyed substitution)
+    #[cfg(feature = "mock")]
+    #[arg(long, alias = "72", hide = true)]
+    mock3: bool,
+
+    /// Use mock4 scheme (demo, word characters substituted, delimiters kept)
+    #[cfg(feature = "mock")]
+    #[arg(long, alias = "73", hide = true)]
+    mock4: bool,
 }
 
 impl SchemeFlags {
-    fn to_scheme(&self) -> Result<Option<Scheme>> {
-        let mut count = 0;
-        let mut scheme = None;
+    fn to_scheme(&self) -> Option<Scheme> {
+        if let Some(SchemeArg(scheme)) = self.scheme {
+            return Some(scheme);
+        }
 
         #[cfg(feature = "upbc")]
         if self.upbc {
-            count += 1;
-            scheme = Some(Scheme::Upbc);
+            return Some(Scheme::Upbc);
         }
         #[cfg(feature = "aags")]
         if self.aags {
-            count += 1;
-            scheme = Some(Scheme::Aags);
+            return Some(Scheme::Aags);
         }
         #[cfg(feature = "apgs")]
         if self.apgs {
-            count += 1;
-            scheme = Some(Scheme::Apgs);
+            return Some(Scheme::Apgs);
+        }
+        #[cfg(feature = "apgc")]
+        if self.apgc {
+            return Some(Scheme::Apgc);
         }
         #[cfg(feature = "aasv")]
         if self.aasv {
-            count += 1;
-            scheme = Some(Scheme::Aasv);
+            return Some(Scheme::Aasv);
         }
         #[cfg(feature = "apsv")]
         if self.apsv {
-            count += 1;
-            scheme = Some(Scheme::Apsv);
+            return Some(Scheme::Apsv);
         }
         #[cfg(feature = "mock")]
         if self.mock1 {
-            count += 1;
-            scheme = Some(Scheme::Mock1);
+            return Some(Scheme::Mock1);
         }
         #[cfg(feature = "mock")]
         if self.mock2 {
-            count += 1;
-            scheme = Some(Scheme::Mock2);
-        }
-
-        if count > 1 {
-            anyhow::bail!("Only one scheme flag can be specified at a time");
-        }
-
-        Ok(scheme)
-    }
-
-    fn is_set(&self) -> bool {
-        #[cfg(feature = "upbc")]
-        if self.upbc {
-            return true;
-        }
-        #[cfg(feature = "aags")]
-        if self.aags {
-            return true;
-        }
-        #[cfg(feature = "apgs")]
-        if self.apgs {
-            return true;
-        }
-        #[cfg(feature = "aasv")]
-        if self.aasv {
-            return true;
-        }
-        #[cfg(feature = "apsv")]
-        if self.apsv {
-            return true;
+            return Some(Scheme::Mock2);
         }
         #[cfg(feature = "mock")]
-        if self.mock1 {
-            return true;
+        if self.mock3 {
+            return Some(Scheme::Mock3);
         }
         #[cfg(feature = "mock")]
-        if self.mock2 {
-            return true;
+        if self.mock4 {
+            return Some(Scheme::Mock4);
         }
-        false
+        None
+    }
+
+    fn is_set(&self) -> bool {
+        self.to_scheme().is_some()
     }
 }
 
+/// Encoding selection: `--encoding <ENCODING>` or one of the per-encoding
+/// flags.  At most one may be given (enforced by clap).
 #[derive(Args, Debug)]
+#[group(id = "encoding_flags", multiple = false)]
 struct EncodingFlags {
+    /// Encoding to use
+    #[arg(long = "encoding", value_name = "ENCODING")]
+    encoding: Option<EncodingArg>,
+
     /// Use c32 encoding
     #[arg(short = 'c', long, alias = "base32crockford")]
     c32: bool,
@@ -156,38 +241,24 @@ struct EncodingFlags {
 }
 
 impl EncodingFlags {
-    /// Convert flags to Option<Encoding>, returning error if multiple are set
-    fn to_encoding(&self) -> Result<Option<Encoding>> {
-        let mut count = 0;
-        let mut encoding = None;
-
-        if self.c32 {
-            count += 1;
-            encoding = Some(Encoding::C32);
-        }
-        if self.b32 {
-            count += 1;
-            encoding = Some(Encoding::B32);
-        }
-        if self.b64 {
-            count += 1;
-            encoding = Some(Encoding::B64);
-        }
-        if self.hex {
-            count += 1;
-            encoding = Some(Encoding::Hex);
-        }
-
-        if count > 1 {
-            anyhow::bail!("Only one encoding flag can be specified at a time");
+    fn to_encoding(&self) -> Option<Encoding> {
+        if let Some(EncodingArg(encoding)) = self.encoding {
+            Some(encoding)
+        } else if self.c32 {
+            Some(Encoding::C32)
+        } else if self.b32 {
+            Some(Encoding::B32)
+        } else if self.b64 {
+            Some(Encoding::B64)
+        } else if self.hex {
+            Some(Encoding::Hex)
+        } else {
+            None
         }
-
-        Ok(encoding)
     }
 
-    /// Check if any encoding flag is set
     fn is_set(&self) -> bool {
-        self.c32 || self.b32 || self.b64 || self.hex
+        self.to_encoding().is_some()
     }
 }
 
@@ -199,44 +270,57 @@ struct FormatSpec {
 }
 
 impl FormatSpec {
-    /// Parse format from --format string, scheme flags, encoding flags, and config
-    /// Validates that --format doesn't conflict with individual flags
+    /// Parse format from --format string, positional FORMAT, scheme flags,
+    /// encoding flags, and config (clap rejects --format combined with scheme
+    /// or encoding flags; the positional FORMAT is checked here)
     fn parse(
         format_str: Option<String>,
+        format_arg: Option<String>,
         scheme_flags: &SchemeFlags,
         encoding_flags: &EncodingFlags,
         config: Option<&Config>,
     ) -> Result<Self> {
-        // Check for conflicts between --format and individual flags
-        if format_str.is_some() && scheme_flags.is_set() {
-            anyhow::bail!("Cannot use --format together with scheme flags");
-        }
-        if format_str.is_some() && encoding_flags.is_set() {
-            anyhow::bail!("Cannot use --format together with encoding flags");
+        if format_arg.is_some()
+            && (format_str.is_some() || scheme_flags.is_set() || encoding_flags.is_set())
+        {
+            anyhow::bail!(
+                "a FORMAT argument cannot be combined with --format or scheme/encoding flags"
+            );
         }
 
-        // Parse --format if provided
-        if let Some(fmt_str) = format_str {
-            let format = Format::from_str(&fmt_str).map_err(|e| anyhow::anyhow!("{}", e))?;
-            validate_secure_scheme(format.scheme())?;
-            return Ok(Self {
-                scheme: format.scheme(),
-                encoding: format.encoding(),
-            });
+        // Parse --format or positional FORMAT if provided
+        if let Some(fmt_str) = format_str.or(format_arg) {
+            let (scheme, encoding) = parse_format_str(&fmt_str)?;
+            validate_secure_scheme(scheme)?;
+            // A bare scheme keeps the configured encoding, if any
+            let encoding = match (encoding, config) {
+                (Some(encoding), _) => encoding,
+                (None, Some(_)) => get_encoding(None, config)?,
+                (None, None) => Format::with_scheme(scheme).encoding(),
+            };
+            return Ok(Self { scheme, encoding });
         }
 
         // Otherwise get scheme and encoding from flags or config
-        let scheme = get_scheme(scheme_flags.to_scheme()?, config)?;
-        let encoding = get_encoding(encoding_flags.to_encoding()?, config)?;
+        let scheme = get_scheme(scheme_flags.to_scheme(), config)?;
+        let encoding = get_encoding(encoding_flags.to_encoding(), config)?;
 
         Ok(Self { scheme, encoding })
     }
 }
 
+/// Parse a format string into its scheme and its encoding, which is `None`
+/// when the string names only the scheme (e.g. "aasv")
+fn parse_format_str(fmt_str: &str) -> Result<(Scheme, Option<Encoding>)> {
+    let format = Format::from_str(fmt_str).map_err(|e| anyhow::anyhow!("{}", e))?;
+    let encoding = fmt_str.contains('.').then(|| format.encoding());
+    Ok((format.scheme(), encoding))
+}
+
 impl std::fmt::Display for FormatSpec {
     /// Format as a format string (e.g., "zrbcx.b64")
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}.{}", self.scheme.as_str(), self.encoding.as_str())
+        write!(f, "{}", Format::from((self.scheme, self.encoding)))
     }
 }
 
@@ -245,15 +329,28 @@ enum Commands {
     /// Encrypt+encode a plaintext string
     #[command(visible_alias = "e")]
     Enc {
+        /// Format (e.g. "aasv.b64") when followed by TEXT, otherwise TEXT
+        #[arg(value_name = "FORMAT")]
+        format_or_text: Option<String>,
+
         /// Plaintext string (reads from stdin if not provided)
         text: Option<String>,
 
+        /// Read input from file instead of TEXT/stdin (files over 1 MiB are framed)
+        #[arg(short = 'i', long, value_name = "PATH", conflicts_with_all = ["format_or_text", "text"])]
+        input_file: Option<PathBuf>,
+
+        /// Write output to file instead of stdout
+        #[arg(short = 'o', long, value_name = "PATH")]
+        output_file: Option<PathBuf>,
+
         /// Encryption key (86 base64 chars, for non-ztier schemes)
         #[arg(short, long, conflicts_with = "profile", conflicts_with = "keyless")]
         key: Option<String>,
 
         /// Use named key profile
-        #[arg(short, long, conflicts_with = "key", conflicts_with = "keyless")]
+        #[arg(short, long, conflicts_with = "key", conflicts_with = "keyless",
+              add = ArgValueCandidates::new(completions::profile_candidates))]
         profile: Option<String>,
 
         /// Use hardcoded key (INSECURE - testing only)
@@ -262,7 +359,7 @@ enum Commands {
 
         /// Format specification (e.g., "zrbcx.b64", "aags.b32")
         /// Cannot be combined with scheme or encoding flags
-        #[arg(short, long)]
+        #[arg(short, long, conflicts_with_all = ["scheme_flags", "encoding_flags"])]
         format: Option<String>,
 
         /// Scheme selection
@@ -277,24 +374,58 @@ enum Commands {
     /// Decode+decrypt an obtext string
     #[command(visible_alias = "d")]
     Dec {
+        /// Format (e.g. "aasv.b64") when followed by TEXT, otherwise TEXT
+        #[arg(value_name = "FORMAT")]
+        format_or_text: Option<String>,
+
         /// Obtext string (reads from stdin if not provided)
         text: Option<String>,
 
+        /// Read input from file instead of TEXT/stdin (plain obtext or framed container)
+        #[arg(short = 'i', long, value_name = "PATH", conflicts_with_all = ["format_or_text", "text"])]
+        input_file: Option<PathBuf>,
+
+        /// Write output to file instead of stdout
+        #[arg(short = 'o', long, value_name = "PATH")]
+        output_file: Option<PathBuf>,
+
         /// Encryption key (86 base64 chars, for non-ztier schemes)
         #[arg(short, long, conflicts_with = "profile", conflicts_with = "keyless")]
         key: Option<String>,
 
         /// Use named key profile
-        #[arg(short, long, conflicts_with = "key", conflicts_with = "keyless")]
+        #[arg(short, long, conflicts_with = "key", conflicts_with = "keyless",
+              add = ArgValueCandidates::new(completions::profile_candidates))]
         profile: Option<String>,
 
         /// Use hardcoded key (INSECURE - testing only)
         #[arg(short = 'K', long, conflicts_with = "key", conflicts_with = "profile")]
         keyless: bool,
 
+        /// Detect both scheme and encoding from the obtext; no format or
+        /// configured defaults are needed
+        #[arg(short = 'a', long, conflicts_with_all = ["format", "scheme_flags", "encoding_flags"])]
+        auto: bool,
+
+        /// Try the key of every profile in turn until one decrypts the
+        /// obtext, reporting the matching profile on stderr
+        #[arg(long, conflicts_with_all = ["key", "profile", "keyless"])]
+        try_all_profiles: bool,
+
+        /// Decrypt every file matching a glob pattern (e.g. "tokens/*.token"),
+        /// each holding one obtext, into --output-dir
+        #[arg(long, value_name = "PATTERN", requires = "output_dir",
+              conflicts_with_all = ["format_or_text", "text", "input_file", "output_file", "try_all_profiles"])]
+        glob: Option<String>,
+
+        /// Directory receiving the plaintext of each --glob file, under the
+        /// same file name
+        #[arg(long, value_name = "DIR", requires = "glob")]
+        output_dir: Option<PathBuf>,
+
         /// Format specification (e.g., "zrbcx.b64", "aags.b32")
         /// Cannot be combined with scheme or encoding flags
-        #[arg(short, long)]
+        #[arg(short, long, conflicts_with_all = ["scheme_flags", "encoding_flags"])]
         format: Option<String>,
 
         /// Scheme selection
@@ -306,6 +437,88 @@ enum Commands {
         encoding: EncodingFlags,
     },
 
+    /// Encrypt every match of a pattern in text, line by line, leaving the
+    /// rest of each line untouched (e.g. to redact logs)
+    Redact {
+        /// Regular expression whose matches are replaced by their obtext
+        pattern: String,
+
+        /// Read input from file instead of stdin
+        #[arg(short = 'i', long, value_name = "PATH")]
+        input_file: Option<PathBuf>,
+
+        /// Write output to file instead of stdout
+        #[arg(short = 'o', long, value_name = "PATH")]
+        output_file: Option<PathBuf>,
+
+        /// Encryption key (86 base64 chars, for non-ztier schemes)
+        #[arg(short, long, conflicts_with = "profile", conflicts_with = "keyless")]
+        key: Option<String>,
+
+        /// Use named key profile
+        #[arg(short, long, conflicts_with = "key", conflicts_with = "keyless",
+              add = ArgValueCandidates::new(completions::profile_candidates))]
+        profile: Option<String>,
+
+        /// Use hardcoded key (INSECURE - testing only)
+        #[arg(short = 'K', long, conflicts_with = "key", conflicts_with = "profile")]
+        keyless: bool,
+
+        /// Format specification (e.g., "aasv.b64", "aags.b32")
+        /// Cannot be combined with scheme or encoding flags
+        #[arg(short, long, conflicts_with_all = ["scheme_flags", "encoding_flags"])]
+        format: Option<String>,
+
+        /// Scheme selection
+        #[command(flatten)]
+        scheme: SchemeFlags,
+
+        /// Encoding selection
+        #[command(flatten)]
+        encoding: EncodingFlags,
+    },
+
+    /// Convert obtext to another encoding; no key is needed
+    Transcode {
+        /// Obtext string (reads from stdin if not provided)
+        text: Option<String>,
+
+        /// Read input from file instead of TEXT/stdin
+        #[arg(short = 'i', long, value_name = "PATH", conflicts_with = "text")]
+        input_file: Option<PathBuf>,
+
+        /// Write output to file instead of stdout
+        #[arg(short = 'o', long, value_name = "PATH")]
+        output_file: Option<PathBuf>,
+
+        /// Encoding of the input obtext
+        #[arg(long, value_name = "ENCODING")]
+        from: EncodingArg,
+
+        /// Encoding to convert to
+        #[arg(long, value_name = "ENCODING")]
+        to: EncodingArg,
+
+        /// Transcode each line as a separate obtext (blank lines are kept)
+        #[arg(short, long)]
+        lines: bool,
+    },
+
+    /// Measure enc/dec throughput on this machine with a generated key
+    Bench {
+        /// Scheme to measure; repeat for several (default: every scheme)
+        #[arg(long = "scheme", value_name = "SCHEME")]
+        schemes: Vec<SchemeArg>,
+
+        /// Plaintext size in bytes
+        #[arg(long, value_name = "N", default_value_t = 1024)]
+        size: usize,
+
+        /// Operations timed per format, for each of enc and dec
+        #[arg(long, value_name = "M", default_value_t = 1000)]
+        iters: u32,
+    },
+
     /// Initialize configuration with random profile
     #[command(visible_alias = "i")]
     Init {
@@ -336,7 +549,7 @@ enum Commands {
     #[command(visible_alias = "k")]
     Key {
         /// Use named key profile
-        #[arg(short, long)]
+        #[arg(short, long, add = ArgValueCandidates::new(completions::profile_candidates))]
         profile: Option<String>,
 
         /// Use hardcoded key (INSECURE - testing only)
@@ -369,8 +582,13 @@ enum ConfigCommands {
         #[command(flatten)]
         encoding: EncodingFlags,
 
+        /// Format specification (e.g., "aasv.b64"), setting scheme and encoding
+        /// Cannot be combined with scheme or encoding flags
+        #[arg(short, long, conflicts_with_all = ["scheme_flags", "encoding_flags"])]
+        format: Option<String>,
+
         /// Set default key profile
-        #[arg(short, long)]
+        #[arg(short, long, add = ArgValueCandidates::new(completions::profile_candidates))]
         profile: Option<String>,
     },
 }
@@ -385,6 +603,7 @@ enum ProfileCommands {
     #[command(visible_alias = "g")]
     Show {
         /// Profile name (shows default if not provided)
+        #[arg(add = ArgValueCandidates::new(completions::profile_candidates))]
         name: Option<String>,
     },
     /// Set a profile as the default
@@ -392,6 +611,7 @@ enum ProfileCommands {
     #[command(visible_alias = "use")]
     Activate {
         /// Profile name
+        #[arg(add = ArgValueCandidates::new(completions::profile_candidates))]
         name: String,
     },
     /// Create a new key profile
@@ -408,6 +628,7 @@ enum ProfileCommands {
     #[command(visible_alias = "d")]
     Delete {
         /// Profile name
+        #[arg(add = ArgValueCandidates::new(completions::profile_candidates))]
         name: String,
     },
     /// Rename a key profile
@@ -415,14 +636,31 @@ enum ProfileCommands {
     #[command(visible_alias = "mv")]
     Rename {
         /// Current profile name
+        #[arg(add = ArgValueCandidates::new(completions::profile_candidates))]
         old_name: String,
 
         /// New profile name
         new_name: String,
     },
+    /// Export a profile (name and key) as portable JSON
+    Export {
+        /// Profile name
+        #[arg(add = ArgValueCandidates::new(completions::profile_candidates))]
+        name: String,
+
+        /// Write to file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Import a profile from an exported JSON file
+    Import {
+        /// Path to the exported profile file
+        file: PathBuf,
+    },
     /// Set key for a profile
     Set {
         /// Profile name
+        #[arg(add = ArgValueCandidates::new(completions::profile_candidates))]
         name: String,
 
         /// Encryption key (86 base64 chars)
@@ -431,12 +669,42 @@ enum ProfileCommands {
     },
 }
 
-fn main() -> Result<()> {
-    let cli = Cli::parse();
+fn main() -> ExitCode {
+    // Dynamic completions: handles `COMPLETE=<shell> ob ...` callbacks and exits
+    CompleteEnv::with_factory(Cli::command).complete();
+
+    let cli = match Cli::try_parse() {
+        Ok(cli) => cli,
+        Err(e) => {
+            let _ = e.print();
+            // Usage errors exit with 1 rather than clap's 2, which is kept
+            // for decode failures
+            return if e.use_stderr() {
+                ExitKind::General.into()
+            } else {
+                ExitCode::SUCCESS
+            };
+        }
+    };
+
+    match run(cli) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {:?}", e);
+            exit::kind_of(&e).into()
+        }
+    }
+}
+
+fn run(cli: Cli) -> Result<()> {
+    let quiet = cli.quiet;
 
     match cli.command {
         Commands::Enc {
+            format_or_text,
             text,
+            input_file,
+            output_file,
             key,
             profile,
             keyless,
@@ -445,26 +713,128 @@ fn main() -> Result<()> {
             encoding,
         } => {
             let cfg = config::load_config().ok();
-            let format_spec = FormatSpec::parse(format, &scheme, &encoding, cfg.as_ref())?;
-            enc_command(text, key, profile, keyless, format_spec, cfg)
+            let (format_arg, text) = split_format_arg(format_or_text, text);
+            let format_spec =
+                FormatSpec::parse(format, format_arg, &scheme, &encoding, cfg.as_ref())?;
+            let io_files = IoFiles {
+                input: input_file,
+                output: output_file,
+            };
+            enc_command(text, io_files, key, profile, keyless, format_spec, cfg)
         }
 
         Commands::Dec {
+            format_or_text,
             text,
+            input_file,
+            output_file,
             key,
             profile,
             keyless,
+            auto,
+            try_all_profiles,
+            glob,
+            output_dir,
             format,
             scheme,
             encoding,
         } => {
             let cfg = config::load_config().ok();
-            let scheme_is_explicit = scheme.is_set() || format.is_some();
-            let format_spec = FormatSpec::parse(format, &scheme, &encoding, cfg.as_ref())?;
-            dec_command(text, key, profile, keyless, format_spec, scheme_is_explicit, cfg)
+            let (format_arg, text) = split_format_arg(format_or_text, text);
+            let scheme_is_explicit = scheme.is_set() || format.is_some() || format_arg.is_some();
+            let format_spec = if auto {
+                if format_arg.is_some() {
+                    anyhow::bail!("a FORMAT argument cannot be combined with --auto");
+                }
+                None
+            } else {
+                Some(FormatSpec::parse(
+                    format,
+                    format_arg,
+                    &scheme,
+                    &encoding,
+                    cfg.as_ref(),
+                )?)
+            };
+            if let (Some(pattern), Some(output_dir)) = (glob, output_dir) {
+                let omb = make_omnib(key, profile, keyless, cfg.as_ref())?;
+                return dec_glob_command(
+                    &omb,
+                    &pattern,
+                    &output_dir,
+                    format_spec,
+                    scheme_is_explicit,
+                    quiet,
+                );
+            }
+            let io_files = IoFiles {
+                input: input_file,
+                output: output_file,
+            };
+            let keys = if try_all_profiles {
+                KeySource::AllProfiles
+            } else {
+                KeySource::Single {
+                    key,
+                    profile,
+                    keyless,
+                }
+            };
+            dec_command(
+                text,
+                io_files,
+                keys,
+                format_spec,
+                scheme_is_explicit,
+                cfg,
+                quiet,
+            )
         }
 
-        Commands::Init { name } => config::init_command(&name),
+        Commands::Redact {
+            pattern,
+            input_file,
+            output_file,
+            key,
+            profile,
+            keyless,
+            format,
+            scheme,
+            encoding,
+        } => {
+            let cfg = config::load_config().ok();
+            let format_spec = FormatSpec::parse(format, None, &scheme, &encoding, cfg.as_ref())?;
+            let io_files = IoFiles {
+                input: input_file,
+                output: output_file,
+            };
+            redact_command(&pattern, io_files, key, profile, keyless, format_spec, cfg)
+        }
+
+        Commands::Transcode {
+            text,
+            input_file,
+            output_file,
+            from: EncodingArg(from),
+            to: EncodingArg(to),
+            lines,
+        } => {
+            let io_files = IoFiles {
+                input: input_file,
+                output: output_file,
+            };
+            transcode_command(text, io_files, from, to, lines)
+        }
+
+        Commands::Bench {
+            schemes,
+            size,
+            iters,
+        } => bench_command(schemes, size, iters),
+
+        Commands::Init { name } => {
+            config::init_command(&name, quiet).exit_kind(ExitKind::KeyConfig)
+        }
 
         Commands::Config { command, keyless } => match command {
             Some(ConfigCommands::Show) | None => {
@@ -473,35 +843,47 @@ fn main() -> Result<()> {
             Some(ConfigCommands::Set {
                 scheme,
                 encoding,
+                format,
                 profile,
             }) => {
-                let scheme_override = scheme.to_scheme()?;
-                let encoding_override = encoding.to_encoding()?;
-                config_set_command(scheme_override, encoding_override, profile)
+                let (scheme_override, encoding_override) = match format {
+                    Some(fmt_str) => {
+                        let (scheme, encoding) = parse_format_str(&fmt_str)?;
+                        (Some(scheme), encoding)
+                    }
+                    None => (scheme.to_scheme(), encoding.to_encoding()),
+                };
+                config_set_command(scheme_override, encoding_override, profile, quiet)
             }
-        },
+        }
+        .exit_kind(ExitKind::KeyConfig),
 
         Commands::Profile { command } => match command {
             ProfileCommands::List => config::profile_list_command(),
             ProfileCommands::Show { name } => config::profile_show_command(name.as_deref()),
-            ProfileCommands::Activate { name } => config::profile_activate_command(&name),
+            ProfileCommands::Activate { name } => config::profile_activate_command(&name, quiet),
             ProfileCommands::Create { name, key } => {
-                config::profile_create_command(&name, key.as_deref())
+                config::profile_create_command(&name, key.as_deref(), quiet)
             }
-            ProfileCommands::Delete { name } => config::profile_delete_command(&name),
+            ProfileCommands::Delete { name } => config::profile_delete_command(&name, quiet),
             ProfileCommands::Rename { old_name, new_name } => {
-                config::profile_rename_command(&old_name, &new_name)
+                config::profile_rename_command(&old_name, &new_name, quiet)
             }
+            ProfileCommands::Export { name, output } => {
+                config::profile_export_command(&name, output.as_deref(), quiet)
+            }
+            ProfileCommands::Import { file } => config::profile_import_command(&file, quiet),
             ProfileCommands::Set { name, key } => {
-                config::profile_set_command(&name, key.as_deref())
+                config::profile_set_command(&name, key.as_deref(), quiet)
             }
-        },
+        }
+        .exit_kind(ExitKind::KeyConfig),
 
         Commands::Key {
             profile,
             keyless,
             hex,
-        } => key_command(profile, keyless, hex),
+        } => key_command(profile, keyless, hex).exit_kind(ExitKind::KeyConfig),
 
         Commands::Completion { shell } => {
             completions::generate_completion(shell);
@@ -510,77 +892,426 @@ fn main() -> Result<()> {
     }
 }
 
+/// Sort out the `enc`/`dec` positionals: `[FORMAT] TEXT` when both are
+/// given, otherwise the single positional is the text.
+fn split_format_arg(
+    format_or_text: Option<String>,
+    text: Option<String>,
+) -> (Option<String>, Option<String>) {
+    match text {
+        Some(text) => (format_or_text, Some(text)),
+        None => (None, format_or_text),
+    }
+}
+
+/// Optional input/output files for `enc`/`dec`
+struct IoFiles {
+    input: Option<PathBuf>,
+    output: Option<PathBuf>,
+}
+
+impl IoFiles {
+    /// Open the output file, or stdout if none was given
+    fn writer(&self) -> Result<Box<dyn Write>> {
+        Ok(match &self.output {
+            Some(path) => Box::new(BufWriter::new(File::create(path).context(format!(
+                "Failed to create output file {}",
+                path.display()
+            ))?)),
+            None => Box::new(BufWriter::new(io::stdout().lock())),
+        })
+    }
+
+    /// Run `write` on the output: an output file is written privately
+    /// through [`outfile::write_private`], so a failure leaves no partial
+    /// output behind and other users cannot read the result
+    fn write_atomically<T>(&self, write: impl FnOnce(&mut dyn Write) -> Result<T>) -> Result<T> {
+        match &self.output {
+            Some(path) => outfile::write_private(path, write),
+            None => {
+                let mut out = self.writer()?;
+                let value = write(&mut out)?;
+                out.flush()?;
+                Ok(value)
+            }
+        }
+    }
+
+    fn open_input(path: &PathBuf) -> Result<File> {
+        File::open(path).context(format!("Failed to open input file {}", path.display()))
+    }
+}
+
+fn make_ob(
+    format: &str,
+    key: Option<String>,
+    profile: Option<String>,
+    keyless: bool,
+    cfg: Option<&Config>,
+) -> Result<oboron::Ob> {
+    let key = key.map(KeyString::new);
+    if keyless {
+        oboron::Ob::new_keyless(format).exit_kind(ExitKind::KeyConfig)
+    } else {
+        let mut b64_key =
+            get_key(key.as_ref(), profile.as_deref(), cfg).exit_kind(ExitKind::KeyConfig)?;
+        let ob = oboron::Ob::new(format, b64_key.as_str());
+        b64_key.wipe();
+        ob.exit_kind(ExitKind::KeyConfig)
+    }
+}
+
+fn make_omnib(
+    key: Option<String>,
+    profile: Option<String>,
+    keyless: bool,
+    cfg: Option<&Config>,
+) -> Result<oboron::Omnib> {
+    let key = key.map(KeyString::new);
+    if keyless {
+        oboron::Omnib::new_keyless().exit_kind(ExitKind::KeyConfig)
+    } else {
+        let mut b64_key =
+            get_key(key.as_ref(), profile.as_deref(), cfg).exit_kind(ExitKind::KeyConfig)?;
+        let omb = oboron::Omnib::new(b64_key.as_str());
+        b64_key.wipe();
+        omb.exit_kind(ExitKind::KeyConfig)
+    }
+}
+
 fn enc_command(
     text: Option<String>,
+    io_files: IoFiles,
     key: Option<String>,
     profile: Option<String>,
     keyless: bool,
     format_spec: FormatSpec,
     cfg: Option<Config>,
 ) -> Result<()> {
-    // Get text from argument or stdin
-    let text = get_text_input(text)?;
-
     // Create format
     let format = format_spec.to_string();
 
     // Get ob instance
-    if keyless {
-        let ob = oboron::Ob::new_keyless(&format)?;
-        let encd = ob.enc(&text)?;
-        println!("{}", encd);
-    } else {
-        let b64_key = get_key(key.as_ref(), profile.as_deref(), cfg.as_ref())?;
-        let ob = oboron::Ob::new(&format, &b64_key)?;
-        let encd = ob.enc(&text)?;
-        println!("{}", encd);
-    }
+    let ob = make_ob(&format, key, profile, keyless, cfg.as_ref())?;
+
+    let text = match &io_files.input {
+        Some(path) => {
+            let file = IoFiles::open_input(path)?;
+            if file.metadata()?.len() > framing::FRAMING_THRESHOLD {
+                // Large input: stream through the framed container
+                let mut out = io_files.writer()?;
+                framing::write_framed(
+                    &ob,
+                    BufReader::new(file),
+                    &mut out,
+                    framing::DEFAULT_FRAME_SIZE,
+                )?;
+                return Ok(());
+            }
+            read_text_file(file)?
+        }
+        // Get text from argument or stdin
+        None => get_text_input(text)?,
+    };
+
+    let encd = ob.enc(&text)?;
+    let mut out = io_files.writer()?;
+    writeln!(out, "{}", encd)?;
+    out.flush()?;
 
     Ok(())
 }
 
-fn dec_command(
-    text: Option<String>,
+fn redact_command(
+    pattern: &str,
+    io_files: IoFiles,
     key: Option<String>,
     profile: Option<String>,
     keyless: bool,
     format_spec: FormatSpec,
-    scheme_is_explicit: bool,
     cfg: Option<Config>,
 ) -> Result<()> {
-    // Get text from argument or stdin
-    let text = get_text_input(text)?;
-
-    // Create format
+    let pattern = regex::Regex::new(pattern).context("Invalid pattern")?;
     let format = format_spec.to_string();
+    let ob = make_ob(&format, key, profile, keyless, cfg.as_ref())?;
 
-    // Get ob instance and decode
-    if keyless {
-        let ob = oboron::Ob::new_keyless(&format)?;
-        let decd = if scheme_is_explicit {
-            ob.dec(&text)?
-        } else {
-            ob.autodec(&text)?
+    let mut out = io_files.writer()?;
+    match &io_files.input {
+        Some(path) => {
+            let reader = BufReader::new(IoFiles::open_input(path)?);
+            redact::redact_stream(&ob, reader, &mut out, &pattern)?;
+        }
+        None => {
+            redact::redact_stream(&ob, io::stdin().lock(), &mut out, &pattern)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn transcode_command(
+    text: Option<String>,
+    io_files: IoFiles,
+    from: Encoding,
+    to: Encoding,
+    lines: bool,
+) -> Result<()> {
+    let transcode = |obtext: &str| oboron::encoding::transcode(obtext, from, to);
+
+    let mut out = io_files.writer()?;
+    if lines {
+        let reader: Box<dyn BufRead> = match (&io_files.input, text) {
+            (Some(path), _) => Box::new(BufReader::new(IoFiles::open_input(path)?)),
+            (None, Some(text)) => Box::new(io::Cursor::new(text)),
+            (None, None) => Box::new(io::stdin().lock()),
         };
-        println!("{}", decd);
+        for (number, line) in reader.lines().enumerate() {
+            let line = line.context("Failed to read input")?;
+            let obtext = line.trim();
+            if obtext.is_empty() {
+                writeln!(out)?;
+                continue;
+            }
+            let converted = transcode(obtext)
+                .with_context(|| format!("line {}", number + 1))
+                .exit_kind(ExitKind::Decode)?;
+            writeln!(out, "{}", converted)?;
+        }
     } else {
-        let b64_key = get_key(key.as_ref(), profile.as_deref(), cfg.as_ref())?;
-        let ob = oboron::Ob::new(&format, &b64_key)?;
-        let decd = if scheme_is_explicit {
-            ob.dec(&text)?
-        } else {
-            ob.autodec(&text)?
+        let text = match &io_files.input {
+            Some(path) => read_text_file(IoFiles::open_input(path)?)?
+                .trim()
+                .to_string(),
+            None => get_text_input(text)?,
+        };
+        let converted = transcode(&text).exit_kind(ExitKind::Decode)?;
+        writeln!(out, "{}", converted)?;
+    }
+    out.flush()?;
+
+    Ok(())
+}
+
+fn bench_command(schemes: Vec<SchemeArg>, size: usize, iters: u32) -> Result<()> {
+    let schemes: Vec<Scheme> = if schemes.is_empty() {
+        // Every scheme offered by `--scheme`, except the hidden mock schemes
+        SchemeArg::value_variants()
+            .iter()
+            .filter(|arg| !arg.to_possible_value().is_some_and(|v| v.is_hide_set()))
+            .map(|arg| arg.0)
+            .collect()
+    } else {
+        schemes.into_iter().map(|arg| arg.0).collect()
+    };
+    let key = KeyString::new(oboron::generate_key());
+    let mut out = io::stdout().lock();
+    bench::run_bench(key.as_str(), &schemes, size, iters, &mut out)
+}
+
+/// Where `ob dec` gets its key from
+enum KeySource {
+    /// `--key`, `$OBORON_KEY`, `--profile`, the default profile, or `--keyless`
+    Single {
+        key: Option<String>,
+        profile: Option<String>,
+        keyless: bool,
+    },
+    /// `--try-all-profiles`: every profile in the profile directory
+    AllProfiles,
+}
+
+/// One `Omnib` per profile that has a key, named after the profile
+fn profile_omnibs() -> Result<Vec<(String, oboron::Omnib)>> {
+    let mut omnibs = Vec::new();
+    for name in config::list_profiles()? {
+        if let Some(key) = config::load_profile(&name)?.key.map(KeyString::new) {
+            let key = config::validate_base64_key(key.as_str())
+                .map(KeyString::new)
+                .with_context(|| format!("Profile '{}' has an invalid key", name))?;
+            omnibs.push((name, oboron::Omnib::new(key.as_str())?));
+        }
+    }
+    if omnibs.is_empty() {
+        anyhow::bail!("No profiles with a key found: run 'ob init' or 'ob profile create'");
+    }
+    Ok(omnibs)
+}
+
+/// Decode `text` with one key: with a format whose scheme was given
+/// explicitly, decrypt with exactly that format; otherwise (including
+/// --auto) detect both scheme and encoding
+fn dec_text(
+    omb: &oboron::Omnib,
+    format_spec: Option<&FormatSpec>,
+    scheme_is_explicit: bool,
+    text: &str,
+) -> Result<String, oboron::Error> {
+    match format_spec {
+        None => omb.autodec(text),
+        Some(spec) if scheme_is_explicit => {
+            omb.dec(text, Format::from((spec.scheme, spec.encoding)))
+        }
+        Some(_) => omb.autodec(text),
+    }
+}
+
+/// Decode `text` with the first profile whose key succeeds, naming it on
+/// stderr.  A successful decryption only identifies the key for
+/// authenticated schemes; otherwise a wrong key may also "succeed", so
+/// the match is reported as ambiguous.
+fn dec_any_profile(
+    profiles: &[(String, oboron::Omnib)],
+    format_spec: Option<&FormatSpec>,
+    scheme_is_explicit: bool,
+    text: &str,
+    quiet: bool,
+) -> Result<String> {
+    let mut last_err = None;
+    for (name, omb) in profiles {
+        let decd = match dec_text(omb, format_spec, scheme_is_explicit, text) {
+            Ok(decd) => decd,
+            Err(e) => {
+                last_err = Some(e);
+                continue;
+            }
         };
-        println!("{}", decd);
+        let scheme = match format_spec {
+            Some(spec) if scheme_is_explicit => Some(spec.scheme),
+            _ => oboron::detect_format(text).map(|f| f.scheme()),
+        };
+        if !scheme.is_some_and(|s| s.is_authenticated()) {
+            eprintln!(
+                "Warning: profile '{}' decrypted the obtext, but the scheme is not \
+                 authenticated, so another profile's key may also decrypt it",
+                name
+            );
+        } else if !quiet {
+            eprintln!("Decrypted with profile '{}'", name);
+        }
+        return Ok(decd);
     }
+    let err = last_err.map_or_else(String::new, |e| format!(": {}", e));
+    Err(anyhow::anyhow!(
+        "No profile decrypts the obtext (tried {}){}",
+        profiles.len(),
+        err
+    ))
+    .exit_kind(ExitKind::Decode)
+}
+
+fn dec_command(
+    text: Option<String>,
+    io_files: IoFiles,
+    keys: KeySource,
+    format_spec: Option<FormatSpec>,
+    scheme_is_explicit: bool,
+    cfg: Option<Config>,
+    quiet: bool,
+) -> Result<()> {
+    // With --try-all-profiles there is no single key: `omb` is None and
+    // every profile is tried in turn
+    let (omb, profiles) = match keys {
+        KeySource::Single {
+            key,
+            profile,
+            keyless,
+        } => (
+            Some(make_omnib(key, profile, keyless, cfg.as_ref())?),
+            Vec::new(),
+        ),
+        KeySource::AllProfiles => (None, profile_omnibs().exit_kind(ExitKind::KeyConfig)?),
+    };
+
+    let text = match &io_files.input {
+        Some(path) => {
+            let mut reader = BufReader::new(IoFiles::open_input(path)?);
+            if framing::is_framed(&mut reader)? {
+                let Some(omb) = &omb else {
+                    anyhow::bail!("--try-all-profiles cannot be used with a framed container");
+                };
+                // Framed container: decoded with the requested format, which
+                // the header must match (with --auto, the header's is used)
+                let format = format_spec
+                    .as_ref()
+                    .map(|spec| Format::from((spec.scheme, spec.encoding)));
+                io_files.write_atomically(|out| {
+                    framing::read_framed(omb, format, reader, out).exit_kind(ExitKind::Decode)
+                })?;
+                return Ok(());
+            }
+            read_text_file(reader)?.trim().to_string()
+        }
+        // Get text from argument or stdin
+        None => get_text_input(text)?,
+    };
+
+    let decd = match &omb {
+        Some(omb) => dec_text(omb, format_spec.as_ref(), scheme_is_explicit, &text)
+            .exit_kind(ExitKind::Decode)?,
+        None => dec_any_profile(
+            &profiles,
+            format_spec.as_ref(),
+            scheme_is_explicit,
+            &text,
+            quiet,
+        )?,
+    };
+
+    io_files.write_atomically(|out| {
+        if io_files.output.is_some() {
+            // Write files verbatim
+            out.write_all(decd.as_bytes())?;
+        } else {
+            writeln!(out, "{}", decd)?;
+        }
+        Ok(())
+    })
+}
 
+/// `ob dec --glob`: decrypt each matching file into `output_dir`, reporting
+/// every file and a final summary on stderr
+fn dec_glob_command(
+    omb: &oboron::Omnib,
+    pattern: &str,
+    output_dir: &std::path::Path,
+    format_spec: Option<FormatSpec>,
+    scheme_is_explicit: bool,
+    quiet: bool,
+) -> Result<()> {
+    let dec = |obtext: &str| {
+        dec_text(omb, format_spec.as_ref(), scheme_is_explicit, obtext).map_err(Into::into)
+    };
+    let summary = bulk::dec_glob(pattern, output_dir, dec, &mut io::stderr().lock(), quiet)?;
+    if summary.failed > 0 {
+        return Err(anyhow::anyhow!(
+            "{} decrypted, {} failed",
+            summary.decrypted,
+            summary.failed
+        ))
+        .exit_kind(ExitKind::Decode);
+    }
+    if !quiet {
+        eprintln!("{} decrypted, 0 failed", summary.decrypted);
+    }
     Ok(())
 }
 
+fn read_text_file<R: Read>(mut reader: R) -> Result<String> {
+    let mut buffer = String::new();
+    reader
+        .read_to_string(&mut buffer)
+        .context("failed to read input file (must be valid UTF-8)")?;
+    if buffer.is_empty() {
+        anyhow::bail!("input file is empty");
+    }
+    Ok(buffer)
+}
+
 fn config_set_command(
     scheme_override: Option<Scheme>,
     encoding_override: Option<Encoding>,
     profile: Option<String>,
+    quiet: bool,
 ) -> Result<()> {
     let mut config = config::load_config().unwrap_or(Config {
         profile: "default".to_string(),
@@ -606,10 +1337,12 @@ fn config_set_command(
 
     config::save_config(&config)?;
 
-    println!("✓ Configuration updated");
-    println!("  Profile:  {}", config.profile);
-    println!("  Scheme:   {}", config.scheme);
-    println!("  Encoding: {}", config.encoding);
+    if !quiet {
+        println!("✓ Configuration updated");
+        println!("  Profile:  {}", config.profile);
+        println!("  Scheme:   {}", config.scheme);
+        println!("  Encoding: {}", config.encoding);
+    }
 
     Ok(())
 }
@@ -639,29 +1372,15 @@ fn key_command(profile: Option<String>, keyless: bool, hex: bool) -> Result<()>
         .or_else(|| cfg.as_ref().map(|c| c.profile.as_str()))
     {
         let profile = config::load_profile(prof)?;
-        if let Some(k) = &profile.key {
-            println!(
-                "{}",
-                if hex {
-                    let key_bytes = BASE64URL_NOPAD.decode(k.as_bytes())?;
-                    HEXLOWER.encode(&key_bytes)
-                } else {
-                    k.clone()
-                }
-            );
+        if let Some(k) = profile.key.map(KeyString::new) {
+            println!("{}", key_output(k, hex)?.as_str());
         } else {
             anyhow::bail!("Profile '{}' has no key", prof);
         }
     } else if let Ok(env_key) = std::env::var("OBORON_KEY") {
-        println!(
-            "{}",
-            if hex {
-                let key_bytes = BASE64URL_NOPAD.decode(env_key.as_bytes())?;
-                HEXLOWER.encode(&key_bytes)
-            } else {
-                env_key
-            }
-        );
+        let env_key = KeyString::new(env_key);
+        let env_key = KeyString::new(config::validate_base64_key(env_key.as_str())?);
+        println!("{}", key_output(env_key, hex)?.as_str());
     } else {
         anyhow::bail!(
             "No key specified: provide --profile, set $OBORON_KEY, or run 'ob init'"
@@ -671,17 +1390,36 @@ fn key_command(profile: Option<String>, keyless: bool, hex: bool) -> Result<()>
     Ok(())
 }
 
-fn get_key(key: Option<&String>, profile: Option<&str>, config: Option<&Config>) -> Result<String> {
+/// A base64 key as printed by `ob key`: unchanged, or as hex with `--hex`
+fn key_output(key: KeyString, hex: bool) -> Result<KeyString> {
+    use data_encoding::{BASE64URL_NOPAD, HEXLOWER};
+    use zeroize::Zeroize;
+
+    if !hex {
+        return Ok(key);
+    }
+    let mut key_bytes = BASE64URL_NOPAD.decode(key.as_str().as_bytes())?;
+    let hex_key = KeyString::new(HEXLOWER.encode(&key_bytes));
+    key_bytes.zeroize();
+    Ok(hex_key)
+}
+
+/// Resolve the base64 key; the returned `KeyString` is wiped on drop, and
+/// intermediate copies (e.g. of `$OBORON_KEY`) are wiped before returning
+fn get_key(
+    key: Option<&KeyString>,
+    profile: Option<&str>,
+    config: Option<&Config>,
+) -> Result<KeyString> {
     // 1. Explicit --key flag
     if let Some(key_str) = key {
-        validate_base64_key(key_str)?;
-        return Ok(key_str.clone());
+        return config::validate_base64_key(key_str.as_str()).map(KeyString::new);
     }
 
     // 2. Environment variable
     if let Ok(env_key) = std::env::var("OBORON_KEY") {
-        validate_base64_key(&env_key)?;
-        return Ok(env_key);
+        let env_key = KeyString::new(env_key);
+        return config::validate_base64_key(env_key.as_str()).map(KeyString::new);
     }
 
     // 3-4. Profile (explicit --profile or default from config)
@@ -689,9 +1427,8 @@ fn get_key(key: Option<&String>, profile: Option<&str>, config: Option<&Config>)
 
     if let Some(name) = profile_name {
         let profile = config::load_profile(name)?;
-        if let Some(k) = &profile.key {
-            validate_base64_key(k)?;
-            return Ok(k.clone());
+        if let Some(k) = profile.key.map(KeyString::new) {
+            return config::validate_base64_key(k.as_str()).map(KeyString::new);
         }
         anyhow::bail!("Profile '{}' has no key", name);
     }
@@ -701,31 +1438,6 @@ fn get_key(key: Option<&String>, profile: Option<&str>, config: Option<&Config>)
     ))
 }
 
-fn validate_base64_key(key_str: &str) -> Result<()> {
-    // Check length
-    if key_str.len() != 86 {
-        return Err(anyhow::anyhow!(
-            "Key must be 86 base64 chars, got {} chars",
-            key_str.len()
-        ));
-    }
-
-    // Validate base64 encoding and length
-    use data_encoding::BASE64URL_NOPAD;
-    let key_bytes = BASE64URL_NOPAD
-        .decode(key_str.as_bytes())
-        .context("Invalid key base64 encoding")?;
-
-    if key_bytes.len() != 64 {
-        return Err(anyhow::anyhow!(
-            "Key must decode to 64 bytes, got {} bytes",
-            key_bytes.len()
-        ));
-    }
-
-    Ok(())
-}
-
 fn get_text_input(text: Option<String>) -> Result<String> {
     match text {
         Some(t) => Ok(t),
@@ -786,6 +1498,8 @@ fn validate_secure_scheme(scheme: Scheme) -> Result<()> {
         Scheme::Aags => Ok(()),
         #[cfg(feature = "apgs")]
         Scheme::Apgs => Ok(()),
+        #[cfg(feature = "apgc")]
+        Scheme::Apgc => Ok(()),
         #[cfg(feature = "aasv")]
         Scheme::Aasv => Ok(()),
         #[cfg(feature = "apsv")]
@@ -796,6 +1510,10 @@ fn validate_secure_scheme(scheme: Scheme) -> Result<()> {
         Scheme::Mock1 => Ok(()),
         #[cfg(feature = "mock")]
         Scheme::Mock2 => Ok(()),
+        #[cfg(feature = "mock")]
+        Scheme::Mock3 => Ok(()),
+        #[cfg(feature = "mock")]
+        Scheme::Mock4 => Ok(()),
         _ => Err(anyhow::anyhow!(
             "Invalid secure scheme: {}.  Use ob for secure schemes (aags, aasv, etc.) or obz for z-tier schemes",
             scheme.as_str()
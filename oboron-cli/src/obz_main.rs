@@ -159,10 +159,8 @@ impl FormatSpec {
         if let Some(fmt_str) = format_str {
             let format = Format::from_str(&fmt_str).map_err(|e| anyhow::anyhow!("{}", e))?;
             validate_ztier_scheme(format.scheme())?;
-            return Ok(Self {
-                scheme: format.scheme(),
-                encoding: format.encoding(),
-            });
+            let (scheme, encoding) = format.into_parts();
+            return Ok(Self { scheme, encoding });
         }
 
         let scheme = get_scheme(scheme_flags.to_scheme()?, config)?;
@@ -189,7 +187,7 @@ impl FormatSpec {
 impl std::fmt::Display for FormatSpec {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         // Use Format::Display so Legacy emits "legacy" (not "legacy.b32")
-        write!(f, "{}", Format::new(self.scheme, self.encoding))
+        write!(f, "{}", Format::from((self.scheme, self.encoding)))
     }
 }
 
@@ -0,0 +1,103 @@
+//! Output files for plaintext, written so that other users cannot read them.
+//!
+//! The contents go to a temporary file next to the destination, created
+//! fresh (never following or truncating an existing file) with owner-only
+//! permissions, and renamed over the destination only once fully written.
+//! A failure leaves neither partial output nor the temporary file behind.
+
+use anyhow::{Context, Result};
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+/// Run `write` on a private temporary file and move it into place at `path`
+/// if `write` succeeds.
+pub fn write_private<T>(path: &Path, write: impl FnOnce(&mut dyn Write) -> Result<T>) -> Result<T> {
+    let tmp_path = tmp_path(path);
+    let file = create_private(&tmp_path)
+        .context(format!("Failed to create output file {}", path.display()))?;
+
+    let result = (|| {
+        let mut out = BufWriter::new(file);
+        let value = write(&mut out)?;
+        out.into_inner().map_err(|e| e.into_error())?.sync_all()?;
+        fs::rename(&tmp_path, path)
+            .context(format!("Failed to create output file {}", path.display()))?;
+        Ok(value)
+    })();
+    if result.is_err() {
+        // Only reached once the temporary file is known to be ours
+        let _ = fs::remove_file(&tmp_path);
+    }
+    result
+}
+
+/// `.<name>.<pid>.tmp` in the destination's directory, so that the final
+/// rename stays on one filesystem
+fn tmp_path(path: &Path) -> PathBuf {
+    let mut tmp_name = std::ffi::OsString::from(".");
+    tmp_name.push(path.file_name().unwrap_or_default());
+    tmp_name.push(format!(".{}.tmp", std::process::id()));
+    path.with_file_name(tmp_name)
+}
+
+/// Create `path` for writing with owner-only permissions, failing if it
+/// already exists
+pub fn create_private(path: &Path) -> std::io::Result<File> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options.open(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_output_is_owner_only() {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("plain.txt");
+        write_private(&path, |out| Ok(out.write_all(b"secret")?)).unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "secret");
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_failure_keeps_existing_output() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("plain.txt");
+        fs::write(&path, "old").unwrap();
+
+        let result = write_private(&path, |out| -> Result<()> {
+            out.write_all(b"partial")?;
+            anyhow::bail!("write failed")
+        });
+        assert_eq!(result.unwrap_err().to_string(), "write failed");
+        assert_eq!(fs::read_to_string(&path).unwrap(), "old");
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_existing_tmp_file_is_not_reused() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("plain.txt");
+        let tmp = tmp_path(&path);
+        fs::write(&tmp, "not ours").unwrap();
+
+        let result = write_private(&path, |out| Ok(out.write_all(b"secret")?));
+        assert!(result.is_err());
+        assert!(!path.exists());
+        // Neither truncated nor removed
+        assert_eq!(fs::read_to_string(&tmp).unwrap(), "not ours");
+    }
+}
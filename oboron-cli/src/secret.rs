@@ -0,0 +1,50 @@
+//! Key strings that are wiped from memory once no longer needed.
+//!
+//! A key resolved from `--key`, `$OBORON_KEY` or a profile is only needed
+//! until the encoder is built.  Holding it in a [`KeyString`] zeroes the
+//! heap buffer when the key is wiped or dropped, rather than leaving it in
+//! freed memory.
+
+use zeroize::Zeroize;
+
+/// A secret string, zeroed by [`wipe`](Self::wipe) and on drop.
+pub struct KeyString(String);
+
+impl KeyString {
+    pub fn new(key: String) -> Self {
+        Self(key)
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Zero the contents and leave the string empty.
+    pub fn wipe(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl Drop for KeyString {
+    fn drop(&mut self) {
+        self.wipe();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wipe_zeroes_buffer() {
+        let mut key = KeyString::new(oboron::generate_key());
+        let (ptr, capacity) = (key.0.as_ptr(), key.0.capacity());
+        key.wipe();
+        assert_eq!(key.as_str(), "");
+        // The buffer is still allocated (wiping does not free it), so it
+        // can be inspected
+        assert_eq!(key.0.as_ptr(), ptr);
+        let buffer = unsafe { std::slice::from_raw_parts(ptr, capacity) };
+        assert!(buffer.iter().all(|&b| b == 0));
+    }
+}
@@ -321,6 +321,118 @@ fn test_enc_with_format_string() {
     cleanup_test_home(&test_home);
 }
 
+#[cfg(feature = "aasv")]
+#[test]
+fn test_enc_dec_with_positional_format() {
+    let test_home = test_home_dir();
+    let output = Command::cargo_bin("ob")
+        .unwrap()
+        .env("HOME", test_home.as_os_str())
+        .args(["enc", "-K", "aasv.b64", "secret"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let encd = String::from_utf8(output.stdout).unwrap().trim().to_string();
+
+    // Same obtext as with --format
+    Command::cargo_bin("ob")
+        .unwrap()
+        .env("HOME", test_home.as_os_str())
+        .args(["enc", "-K", "--format", "aasv.b64", "secret"])
+        .assert()
+        .success()
+        .stdout(format!("{}\n", encd));
+
+    Command::cargo_bin("ob")
+        .unwrap()
+        .env("HOME", test_home.as_os_str())
+        .args(["dec", "-K", "aasv.b64"])
+        .arg(&encd)
+        .assert()
+        .success()
+        .stdout("secret\n");
+
+    cleanup_test_home(&test_home);
+}
+
+#[cfg(feature = "aasv")]
+#[test]
+fn test_positional_format_conflicts() {
+    let test_home = test_home_dir();
+    for flags in [
+        &["--format", "aasv.c32"][..],
+        &["--aasv"][..],
+        &["--hex"][..],
+    ] {
+        Command::cargo_bin("ob")
+            .unwrap()
+            .env("HOME", test_home.as_os_str())
+            .args(["enc", "-K"])
+            .args(flags)
+            .args(["aasv.b64", "secret"])
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains(
+                "FORMAT argument cannot be combined",
+            ));
+    }
+
+    // A single positional is still the plaintext
+    Command::cargo_bin("ob")
+        .unwrap()
+        .env("HOME", test_home.as_os_str())
+        .args(["enc", "-K", "--aasv", "--b64", "aasv.b64"])
+        .assert()
+        .success();
+
+    cleanup_test_home(&test_home);
+}
+
+#[cfg(feature = "aasv")]
+#[cfg(feature = "apsv")]
+#[test]
+fn test_dec_auto_detects_scheme_and_encoding() {
+    // No `ob init`: there is no configured scheme or encoding to fall back on
+    let test_home = test_home_dir();
+    for format in ["apsv.b64", "aasv.hex", "aasv.c32"] {
+        let output = Command::cargo_bin("ob")
+            .unwrap()
+            .env("HOME", test_home.as_os_str())
+            .args(["enc", "-k", TEST_KEY_B64, format, "auto secret"])
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        let encd = String::from_utf8(output.stdout).unwrap().trim().to_string();
+
+        Command::cargo_bin("ob")
+            .unwrap()
+            .env("HOME", test_home.as_os_str())
+            // b64 obtext may start with `-`
+            .args(["dec", "--auto", "-k", TEST_KEY_B64, "--"])
+            .arg(&encd)
+            .assert()
+            .success()
+            .stdout("auto secret\n");
+    }
+
+    // --auto takes no format
+    Command::cargo_bin("ob")
+        .unwrap()
+        .env("HOME", test_home.as_os_str())
+        .args(["dec", "-K", "--auto", "--b64", "abc"])
+        .assert()
+        .failure();
+    Command::cargo_bin("ob")
+        .unwrap()
+        .env("HOME", test_home.as_os_str())
+        .args(["dec", "-K", "--auto", "aasv.b64", "abc"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be combined with --auto"));
+
+    cleanup_test_home(&test_home);
+}
+
 #[cfg(feature = "aasv")]
 #[test]
 fn test_enc_short_alias_b64() {
@@ -809,3 +921,910 @@ fn test_obz_env_secret_overridden_by_flag() {
 
     cleanup_test_home(&test_home);
 }
+
+#[test]
+fn test_dynamic_completion_lists_profiles() {
+    let test_home = test_home_dir();
+    let complete_profile = |expected: &str| {
+        // Simulate the bash completion callback for `ob enc --profile <TAB>`
+        let mut cmd = Command::cargo_bin("ob").unwrap();
+        cmd.env("HOME", test_home.as_os_str())
+            .env("COMPLETE", "bash")
+            .env("_CLAP_COMPLETE_INDEX", "3")
+            .env("_CLAP_COMPLETE_COMP_TYPE", "9")
+            .env("_CLAP_COMPLETE_SPACE", "true")
+            .env("_CLAP_IFS", "\n")
+            .args(["--", "ob", "enc", "--profile", ""])
+            .assert()
+            .success()
+            .stdout(predicate::str::diff(expected.to_string()));
+    };
+
+    let mut cmd = Command::cargo_bin("ob").unwrap();
+    cmd.env("HOME", test_home.as_os_str())
+        .args(["profile", "create", "work"])
+        .assert()
+        .success();
+    complete_profile("work");
+
+    // A profile created afterwards completes without regenerating anything
+    let mut cmd = Command::cargo_bin("ob").unwrap();
+    cmd.env("HOME", test_home.as_os_str())
+        .args(["profile", "create", "default"])
+        .assert()
+        .success();
+    complete_profile("default\nwork");
+
+    cleanup_test_home(&test_home);
+}
+
+#[test]
+fn test_completion_covers_every_subcommand() {
+    let output = Command::cargo_bin("ob")
+        .unwrap()
+        .args(["completion", "bash"])
+        .output()
+        .unwrap();
+    let script = String::from_utf8(output.stdout).unwrap();
+    for subcommand in ["transcode", "bench", "redact", "export", "import"] {
+        assert!(script.contains(subcommand), "{}", subcommand);
+    }
+}
+
+#[test]
+fn test_profile_export_import_roundtrip() {
+    let source_home = test_home_dir();
+    let mut cmd = Command::cargo_bin("ob").unwrap();
+    cmd.env("HOME", source_home.as_os_str())
+        .args(["profile", "create", "moving", "--key", TEST_KEY_B64_ALT])
+        .assert()
+        .success();
+
+    // Export to stdout
+    let output = Command::cargo_bin("ob")
+        .unwrap()
+        .env("HOME", source_home.as_os_str())
+        .args(["profile", "export", "moving"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let exported = String::from_utf8(output.stdout).unwrap();
+    assert!(exported.contains("\"moving\""));
+    assert!(exported.contains(TEST_KEY_B64_ALT));
+
+    // Export to file, import on a "new machine"
+    let export_file = source_home.join("moving.json");
+    Command::cargo_bin("ob")
+        .unwrap()
+        .env("HOME", source_home.as_os_str())
+        .args(["profile", "export", "moving", "-o"])
+        .arg(&export_file)
+        .assert()
+        .success();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = fs::metadata(&export_file).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    // An existing file is never overwritten
+    Command::cargo_bin("ob")
+        .unwrap()
+        .env("HOME", source_home.as_os_str())
+        .args(["profile", "export", "moving", "-o"])
+        .arg(&export_file)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("already exists"));
+    assert!(fs::read_to_string(&export_file)
+        .unwrap()
+        .contains(TEST_KEY_B64_ALT));
+
+    let target_home = test_home_dir();
+    Command::cargo_bin("ob")
+        .unwrap()
+        .env("HOME", target_home.as_os_str())
+        .arg("profile")
+        .arg("import")
+        .arg(&export_file)
+        .assert()
+        .success();
+
+    Command::cargo_bin("ob")
+        .unwrap()
+        .env("HOME", target_home.as_os_str())
+        .args(["key", "--profile", "moving"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(TEST_KEY_B64_ALT));
+
+    // Importing again must not overwrite the existing profile
+    Command::cargo_bin("ob")
+        .unwrap()
+        .env("HOME", target_home.as_os_str())
+        .arg("profile")
+        .arg("import")
+        .arg(&export_file)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("already exists"));
+
+    cleanup_test_home(&source_home);
+    cleanup_test_home(&target_home);
+}
+
+#[test]
+fn test_profile_import_rejects_invalid_key() {
+    let test_home = test_home_dir();
+    fs::create_dir_all(&test_home).unwrap();
+    let export_file = test_home.join("bad.json");
+    fs::write(&export_file, r#"{"name": "bad", "key": "not-a-key"}"#).unwrap();
+
+    Command::cargo_bin("ob")
+        .unwrap()
+        .env("HOME", test_home.as_os_str())
+        .arg("profile")
+        .arg("import")
+        .arg(&export_file)
+        .assert()
+        .failure();
+
+    assert!(!test_home.join(".ob/profiles/bad.json").exists());
+    cleanup_test_home(&test_home);
+}
+
+#[cfg(feature = "aasv")]
+#[test]
+fn test_enc_dec_with_scheme_and_encoding_options() {
+    let test_home = test_home_dir();
+    let output = Command::cargo_bin("ob")
+        .unwrap()
+        .env("HOME", test_home.as_os_str())
+        .args(["enc", "-K", "--scheme", "aasv", "--encoding", "c32"])
+        .arg("value_enum")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let encd = String::from_utf8(output.stdout).unwrap().trim().to_string();
+
+    // Same obtext as the equivalent boolean flags
+    Command::cargo_bin("ob")
+        .unwrap()
+        .env("HOME", test_home.as_os_str())
+        .args(["enc", "-K", "--aasv", "--c32", "value_enum"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(encd.as_str()));
+
+    Command::cargo_bin("ob")
+        .unwrap()
+        .env("HOME", test_home.as_os_str())
+        .args(["dec", "-K", "--scheme", "aasv", "--encoding", "c32"])
+        .arg(&encd)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("value_enum"));
+
+    cleanup_test_home(&test_home);
+}
+
+#[cfg(feature = "aasv")]
+#[test]
+fn test_scheme_and_encoding_aliases() {
+    let test_home = test_home_dir();
+    let output = Command::cargo_bin("ob")
+        .unwrap()
+        .env("HOME", test_home.as_os_str())
+        .args(["enc", "-K", "--aasv", "--b64", "aliased"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let encd = String::from_utf8(output.stdout).unwrap().trim().to_string();
+
+    // Old names and other tools' spellings parse like the canonical ones
+    for (scheme, encoding) in [("ob32", "b64url"), ("32", "base64url")] {
+        Command::cargo_bin("ob")
+            .unwrap()
+            .env("HOME", test_home.as_os_str())
+            .args(["enc", "-K", "--scheme", scheme, "--encoding", encoding])
+            .arg("aliased")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains(encd.as_str()));
+    }
+
+    Command::cargo_bin("ob")
+        .unwrap()
+        .env("HOME", test_home.as_os_str())
+        .args(["dec", "-K", "ob32.b64url"])
+        .arg(&encd)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("aliased"));
+
+    cleanup_test_home(&test_home);
+}
+
+#[cfg(feature = "aasv")]
+#[test]
+fn test_redact_encrypts_matches_only() {
+    let test_home = test_home_dir();
+    let log = "user=alice@example.com ip=10.0.0.1\nno address here\n";
+    let output = Command::cargo_bin("ob")
+        .unwrap()
+        .env("HOME", test_home.as_os_str())
+        .args(["redact", "-K", "--aasv", "--b64", r"[\w.]+@[\w.]+"])
+        .write_stdin(log)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let redacted = String::from_utf8(output.stdout).unwrap();
+
+    let mut lines = redacted.lines();
+    let ot = lines
+        .next()
+        .and_then(|line| line.strip_prefix("user="))
+        .and_then(|rest| rest.strip_suffix(" ip=10.0.0.1"))
+        .unwrap();
+    assert_eq!(lines.next(), Some("no address here"));
+    assert_eq!(lines.next(), None);
+
+    Command::cargo_bin("ob")
+        .unwrap()
+        .env("HOME", test_home.as_os_str())
+        .args(["dec", "-K", "--aasv", "--b64", ot])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("alice@example.com"));
+
+    // An invalid pattern is reported, not ignored
+    Command::cargo_bin("ob")
+        .unwrap()
+        .env("HOME", test_home.as_os_str())
+        .args(["redact", "-K", "--aasv", "--b64", "("])
+        .write_stdin(log)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid pattern"));
+
+    cleanup_test_home(&test_home);
+}
+
+#[cfg(feature = "aasv")]
+#[test]
+fn test_dec_glob_into_output_dir() {
+    let test_home = test_home_dir();
+    let tokens = test_home.join("tokens");
+    let out = test_home.join("out");
+    fs::create_dir_all(&tokens).unwrap();
+
+    let plaintexts = ["first token", "second token", "third token"];
+    for (i, pt) in plaintexts.iter().enumerate() {
+        let output = Command::cargo_bin("ob")
+            .unwrap()
+            .env("HOME", test_home.as_os_str())
+            .args(["enc", "-K", "--aasv", "--b64", pt])
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        fs::write(tokens.join(format!("{}.token", i)), output.stdout).unwrap();
+    }
+    fs::write(tokens.join("notes.txt"), "not a token").unwrap();
+
+    Command::cargo_bin("ob")
+        .unwrap()
+        .env("HOME", test_home.as_os_str())
+        .args(["dec", "-K", "--aasv", "--b64", "--glob"])
+        .arg(tokens.join("*.token"))
+        .arg("--output-dir")
+        .arg(&out)
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("3 decrypted, 0 failed"));
+
+    for (i, pt) in plaintexts.iter().enumerate() {
+        let decd = fs::read_to_string(out.join(format!("{}.token", i))).unwrap();
+        assert_eq!(&decd, pt);
+    }
+    assert!(!out.join("notes.txt").exists());
+
+    // A file that does not decrypt is reported, the rest still are, and
+    // the command fails
+    fs::write(tokens.join("bad.token"), "not-an-obtext").unwrap();
+    fs::remove_dir_all(&out).unwrap();
+    Command::cargo_bin("ob")
+        .unwrap()
+        .env("HOME", test_home.as_os_str())
+        .args(["dec", "-K", "--aasv", "--b64", "--glob"])
+        .arg(tokens.join("*.token"))
+        .arg("--output-dir")
+        .arg(&out)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("FAILED"))
+        .stderr(predicate::str::contains("bad.token"))
+        .stderr(predicate::str::contains("3 decrypted, 1 failed"));
+    assert_eq!(fs::read_dir(&out).unwrap().count(), 3);
+
+    cleanup_test_home(&test_home);
+}
+
+/// Run `ob transcode` with the given arguments and stdin, returning stdout
+fn transcode(test_home: &std::path::Path, args: &[&str], stdin: &str) -> String {
+    let output = Command::cargo_bin("ob")
+        .unwrap()
+        .env("HOME", test_home.as_os_str())
+        .arg("transcode")
+        .args(args)
+        .write_stdin(stdin)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{:?}", output);
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[cfg(feature = "aasv")]
+#[test]
+fn test_transcode_all_encoding_pairs() {
+    let test_home = test_home_dir();
+    let encodings = ["c32", "b32", "b64", "hex", "hexu"];
+
+    let output = Command::cargo_bin("ob")
+        .unwrap()
+        .env("HOME", test_home.as_os_str())
+        .args(["enc", "-k", TEST_KEY_B64, "aasv.hex", "transcoded"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let hex = String::from_utf8(output.stdout).unwrap();
+
+    // The same obtext in every encoding, each of which still decrypts
+    let obtexts: Vec<String> = encodings
+        .iter()
+        .map(|to| transcode(&test_home, &["--from", "hex", "--to", to], &hex))
+        .collect();
+    for (encoding, ot) in encodings.iter().zip(&obtexts) {
+        let format = format!("aasv.{}", encoding);
+        Command::cargo_bin("ob")
+            .unwrap()
+            .env("HOME", test_home.as_os_str())
+            .args(["dec", "-k", TEST_KEY_B64, &format, ot.trim()])
+            .assert()
+            .success()
+            .stdout("transcoded\n");
+    }
+
+    for (from, ot) in encodings.iter().zip(&obtexts) {
+        for (to, expected) in encodings.iter().zip(&obtexts) {
+            let args = ["--from", from, "--to", to];
+            let transcoded = transcode(&test_home, &args, ot);
+            assert_eq!(&transcoded, expected, "{from} -> {to}");
+        }
+    }
+
+    cleanup_test_home(&test_home);
+}
+
+#[cfg(feature = "aasv")]
+#[test]
+fn test_transcode_lines() {
+    let test_home = test_home_dir();
+    let hex = ["00ff106f626f726f6e", "", "6f626f726f6e"];
+    let c32 = transcode(
+        &test_home,
+        &["--from", "hex", "--to", "c32", "--lines"],
+        &hex.join("\n"),
+    );
+    assert_eq!(c32, "03zh0vv2dxs6yvg\n\ndxh6ywkfdr\n");
+    let back = transcode(&test_home, &["--from", "c32", "--to", "hex", "-l"], &c32);
+    assert_eq!(back, hex.join("\n") + "\n");
+
+    // Invalid input is a decode failure naming the line
+    Command::cargo_bin("ob")
+        .unwrap()
+        .env("HOME", test_home.as_os_str())
+        .args(["transcode", "--from", "hex", "--to", "c32", "--lines"])
+        .write_stdin("00ff\nnot hex\n")
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("line 2"));
+
+    cleanup_test_home(&test_home);
+}
+
+#[test]
+fn test_bench_prints_throughput() {
+    let test_home = test_home_dir();
+    let output = Command::cargo_bin("ob")
+        .unwrap()
+        .env("HOME", test_home.as_os_str())
+        .args(["bench", "--iters", "10", "--size", "64"])
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{:?}", output);
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let mut lines = stdout.lines();
+    assert!(lines.next().unwrap().contains("ops/s"));
+    let mut formats = 0;
+    for line in lines {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        assert_eq!(fields.len(), 5, "{}", line);
+        for figure in &fields[1..] {
+            assert!(figure.parse::<f64>().unwrap() > 0.0, "{}", line);
+        }
+        formats += 1;
+    }
+    assert!(formats >= 4);
+
+    cleanup_test_home(&test_home);
+}
+
+#[cfg(feature = "aasv")]
+#[test]
+fn test_dec_try_all_profiles() {
+    let test_home = test_home_dir();
+    // Profiles are tried in name order; only the second one holds the key
+    for (name, key) in [("alpha", TEST_KEY_B64), ("beta", TEST_KEY_B64_ALT)] {
+        Command::cargo_bin("ob")
+            .unwrap()
+            .env("HOME", test_home.as_os_str())
+            .args(["profile", "create", name, "--key", key])
+            .assert()
+            .success();
+    }
+
+    let output = Command::cargo_bin("ob")
+        .unwrap()
+        .env("HOME", test_home.as_os_str())
+        .args(["enc", "-k", TEST_KEY_B64_ALT, "-s", "-B", "multi-key"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let ot = String::from_utf8(output.stdout).unwrap();
+
+    Command::cargo_bin("ob")
+        .unwrap()
+        .env("HOME", test_home.as_os_str())
+        .args(["dec", "--try-all-profiles", "-a", ot.trim()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("multi-key"))
+        .stderr(predicate::str::contains("Decrypted with profile 'beta'"));
+
+    // No profile holds the key
+    let output = Command::cargo_bin("ob")
+        .unwrap()
+        .env("HOME", test_home.as_os_str())
+        .args(["enc", "-K", "--aasv", "--b64", "multi-key"])
+        .output()
+        .unwrap();
+    let ot = String::from_utf8(output.stdout).unwrap();
+    Command::cargo_bin("ob")
+        .unwrap()
+        .env("HOME", test_home.as_os_str())
+        .args(["dec", "--try-all-profiles", "-a", ot.trim()])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("No profile decrypts the obtext"));
+
+    cleanup_test_home(&test_home);
+}
+
+#[cfg(feature = "aasv")]
+#[test]
+fn test_enc_dec_with_hexu_encoding() {
+    let test_home = test_home_dir();
+    let output = Command::cargo_bin("ob")
+        .unwrap()
+        .env("HOME", test_home.as_os_str())
+        .args(["enc", "-K", "--aasv", "--encoding", "hexu", "upper"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let encd = String::from_utf8(output.stdout).unwrap().trim().to_string();
+    assert_eq!(encd, encd.to_uppercase());
+
+    Command::cargo_bin("ob")
+        .unwrap()
+        .env("HOME", test_home.as_os_str())
+        .args(["dec", "-K", "--format", "aasv.hexu"])
+        .arg(&encd)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("upper"));
+
+    cleanup_test_home(&test_home);
+}
+
+#[test]
+fn test_encoding_option_conflicts() {
+    let test_home = test_home_dir();
+    Command::cargo_bin("ob")
+        .unwrap()
+        .env("HOME", test_home.as_os_str())
+        .args(["enc", "-K", "--format", "aasv.b64"])
+        .args(["--encoding", "c32", "x"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+
+    Command::cargo_bin("ob")
+        .unwrap()
+        .env("HOME", test_home.as_os_str())
+        .args(["enc", "-K", "--aasv", "--encoding", "c32", "--hex", "x"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+
+    Command::cargo_bin("ob")
+        .unwrap()
+        .env("HOME", test_home.as_os_str())
+        .args(["enc", "-K", "--aasv", "--encoding", "base99", "x"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("possible values"));
+
+    cleanup_test_home(&test_home);
+}
+
+#[cfg(feature = "aasv")]
+#[test]
+fn test_enc_dec_large_file_framed() {
+    let test_home = test_home_dir();
+    fs::create_dir_all(&test_home).unwrap();
+    let input = test_home.join("large.txt");
+    let encrypted = test_home.join("large.ob");
+    let decrypted = test_home.join("large.out");
+
+    // ~10 MB of text, including multi-byte characters that straddle frames
+    let mut content = String::with_capacity(10 * 1024 * 1024 + 64);
+    let mut i = 0u64;
+    while content.len() < 10 * 1024 * 1024 {
+        content.push_str(&format!("line {} — ünïcödé ✓\n", i));
+        i += 1;
+    }
+    fs::write(&input, &content).unwrap();
+
+    Command::cargo_bin("ob")
+        .unwrap()
+        .env("HOME", test_home.as_os_str())
+        .args(["enc", "-K", "--aasv", "--b64", "--input-file"])
+        .arg(&input)
+        .arg("--output-file")
+        .arg(&encrypted)
+        .assert()
+        .success();
+
+    let header = fs::read_to_string(&encrypted).unwrap();
+    assert!(header.starts_with("OBFRAMES 1 aasv.b64 "));
+
+    Command::cargo_bin("ob")
+        .unwrap()
+        .env("HOME", test_home.as_os_str())
+        .args(["dec", "-K", "--aasv", "--b64", "--input-file"])
+        .arg(&encrypted)
+        .arg("--output-file")
+        .arg(&decrypted)
+        .assert()
+        .success();
+
+    assert!(fs::read(&decrypted).unwrap() == content.as_bytes());
+
+    cleanup_test_home(&test_home);
+}
+
+#[cfg(feature = "aasv")]
+#[test]
+fn test_dec_framed_rejects_tampering_without_partial_output() {
+    let test_home = test_home_dir();
+    fs::create_dir_all(&test_home).unwrap();
+    let input = test_home.join("large.txt");
+    let encrypted = test_home.join("large.ob");
+    let tampered = test_home.join("tampered.ob");
+    let decrypted = test_home.join("large.out");
+    fs::write(&input, "0123456789abcdef\n".repeat(200_000)).unwrap();
+
+    Command::cargo_bin("ob")
+        .unwrap()
+        .env("HOME", test_home.as_os_str())
+        .args(["enc", "-K", "--aasv", "--b64", "--input-file"])
+        .arg(&input)
+        .arg("--output-file")
+        .arg(&encrypted)
+        .assert()
+        .success();
+
+    // Cut off the final frame and fix up the trailer count
+    let container = fs::read_to_string(&encrypted).unwrap();
+    let mut lines: Vec<&str> = container.lines().collect();
+    let frames = lines.len() - 2;
+    lines.truncate(lines.len() - 2);
+    let trailer = format!("END {}", frames - 1);
+    lines.push(&trailer);
+    fs::write(&tampered, lines.join("\n")).unwrap();
+
+    Command::cargo_bin("ob")
+        .unwrap()
+        .env("HOME", test_home.as_os_str())
+        .args(["dec", "-K", "--aasv", "--b64", "--input-file"])
+        .arg(&tampered)
+        .arg("--output-file")
+        .arg(&decrypted)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("final frame missing"));
+    assert!(!decrypted.exists());
+
+    // A header format that differs from the requested one is an error
+    Command::cargo_bin("ob")
+        .unwrap()
+        .env("HOME", test_home.as_os_str())
+        .args(["dec", "-K", "--aasv", "--c32", "--input-file"])
+        .arg(&encrypted)
+        .arg("--output-file")
+        .arg(&decrypted)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("does not match"));
+    assert!(!decrypted.exists());
+
+    cleanup_test_home(&test_home);
+}
+
+#[cfg(feature = "aasv")]
+#[test]
+fn test_enc_dec_small_file_single_blob() {
+    let test_home = test_home_dir();
+    fs::create_dir_all(&test_home).unwrap();
+    let input = test_home.join("small.txt");
+    let encrypted = test_home.join("small.ob");
+    let decrypted = test_home.join("small.out");
+    fs::write(&input, "small file\nwith two lines\n").unwrap();
+
+    Command::cargo_bin("ob")
+        .unwrap()
+        .env("HOME", test_home.as_os_str())
+        .args(["enc", "-K", "--aasv", "--c32", "-i"])
+        .arg(&input)
+        .arg("-o")
+        .arg(&encrypted)
+        .assert()
+        .success();
+
+    let obtext = fs::read_to_string(&encrypted).unwrap();
+    assert!(!obtext.starts_with("OBFRAMES"));
+    assert_eq!(obtext.trim().lines().count(), 1);
+
+    Command::cargo_bin("ob")
+        .unwrap()
+        .env("HOME", test_home.as_os_str())
+        .args(["dec", "-K", "--aasv", "--c32", "-i"])
+        .arg(&encrypted)
+        .arg("-o")
+        .arg(&decrypted)
+        .assert()
+        .success();
+
+    assert_eq!(
+        fs::read_to_string(&decrypted).unwrap(),
+        "small file\nwith two lines\n"
+    );
+    // Decrypted output is readable by its owner only
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = fs::metadata(&decrypted).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    cleanup_test_home(&test_home);
+}
+
+#[cfg(feature = "aags")]
+#[test]
+fn test_config_set_format() {
+    let test_home = test_home_dir();
+
+    Command::cargo_bin("ob")
+        .unwrap()
+        .env("HOME", test_home.as_os_str())
+        .args(["profile", "create", "default", "--key", TEST_KEY_B64])
+        .assert()
+        .success();
+
+    Command::cargo_bin("ob")
+        .unwrap()
+        .env("HOME", test_home.as_os_str())
+        .args(["config", "set", "--format", "aags.b64"])
+        .assert()
+        .success();
+
+    Command::cargo_bin("ob")
+        .unwrap()
+        .env("HOME", test_home.as_os_str())
+        .args(["config", "show"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Scheme:   aags"))
+        .stdout(predicate::str::contains("Encoding: b64"));
+
+    // Conflicts with the individual flags, like `enc --format`
+    Command::cargo_bin("ob")
+        .unwrap()
+        .env("HOME", test_home.as_os_str())
+        .args(["config", "set", "--format", "aasv.c32", "--hex"])
+        .assert()
+        .failure();
+
+    Command::cargo_bin("ob")
+        .unwrap()
+        .env("HOME", test_home.as_os_str())
+        .args(["config", "set", "--format", "nope.c32"])
+        .assert()
+        .failure();
+
+    cleanup_test_home(&test_home);
+}
+
+#[cfg(all(feature = "aasv", feature = "aags"))]
+#[test]
+fn test_bare_scheme_format_keeps_configured_encoding() {
+    let test_home = test_home_dir();
+
+    Command::cargo_bin("ob")
+        .unwrap()
+        .env("HOME", test_home.as_os_str())
+        .args(["profile", "create", "default", "--key", TEST_KEY_B64])
+        .assert()
+        .success();
+
+    Command::cargo_bin("ob")
+        .unwrap()
+        .env("HOME", test_home.as_os_str())
+        .args(["config", "set", "--format", "aags.b64"])
+        .assert()
+        .success();
+
+    // Only the scheme changes
+    Command::cargo_bin("ob")
+        .unwrap()
+        .env("HOME", test_home.as_os_str())
+        .args(["config", "set", "--format", "aasv"])
+        .assert()
+        .success();
+
+    Command::cargo_bin("ob")
+        .unwrap()
+        .env("HOME", test_home.as_os_str())
+        .args(["config", "show"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Scheme:   aasv"))
+        .stdout(predicate::str::contains("Encoding: b64"));
+
+    // `enc --format <scheme>` uses the configured encoding too
+    let output = Command::cargo_bin("ob")
+        .unwrap()
+        .env("HOME", test_home.as_os_str())
+        .args(["enc", "--format", "aags", "hello"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let obtext = String::from_utf8(output.stdout).unwrap();
+
+    Command::cargo_bin("ob")
+        .unwrap()
+        .env("HOME", test_home.as_os_str())
+        .args(["dec", "--format", "aags.b64", obtext.trim()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("hello"));
+
+    cleanup_test_home(&test_home);
+}
+
+#[cfg(feature = "aasv")]
+#[test]
+fn test_exit_code_bad_key() {
+    let test_home = test_home_dir();
+    Command::cargo_bin("ob")
+        .unwrap()
+        .env("HOME", test_home.as_os_str())
+        .env_remove("OBORON_KEY")
+        .args(["enc", "--aasv", "--c32", "--key", "too-short", "x"])
+        .assert()
+        .code(3)
+        .stdout("");
+
+    // No key at all
+    Command::cargo_bin("ob")
+        .unwrap()
+        .env("HOME", test_home.as_os_str())
+        .env_remove("OBORON_KEY")
+        .args(["dec", "--aasv", "--c32", "x"])
+        .assert()
+        .code(3)
+        .stderr(predicate::str::contains("No key specified"));
+
+    cleanup_test_home(&test_home);
+}
+
+#[cfg(feature = "aasv")]
+#[test]
+fn test_exit_code_corrupt_obtext() {
+    let test_home = test_home_dir();
+    let output = Command::cargo_bin("ob")
+        .unwrap()
+        .env("HOME", test_home.as_os_str())
+        .args(["enc", "-K", "aasv.c32", "intact"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let mut encd = String::from_utf8(output.stdout).unwrap().trim().to_string();
+
+    // Swap one character for another valid c32 character
+    let c = if &encd[5..6] == "a" { "b" } else { "a" };
+    encd.replace_range(5..6, c);
+
+    // Explicit format, and autodetection with the configured default
+    Command::cargo_bin("ob")
+        .unwrap()
+        .env("HOME", test_home.as_os_str())
+        .args(["config", "set", "--format", "aasv.c32"])
+        .assert()
+        .success();
+    for args in [&["dec", "-K", "aasv.c32"][..], &["dec", "-K"][..]] {
+        Command::cargo_bin("ob")
+            .unwrap()
+            .env("HOME", test_home.as_os_str())
+            .args(args)
+            .arg(&encd)
+            .assert()
+            .code(2)
+            .stdout("");
+    }
+
+    // Usage errors are not decode failures
+    Command::cargo_bin("ob")
+        .unwrap()
+        .env("HOME", test_home.as_os_str())
+        .args(["dec", "--no-such-flag"])
+        .assert()
+        .code(1);
+
+    cleanup_test_home(&test_home);
+}
+
+#[test]
+fn test_quiet_suppresses_success_messages() {
+    let test_home = test_home_dir();
+
+    Command::cargo_bin("ob")
+        .unwrap()
+        .env("HOME", test_home.as_os_str())
+        .args(["init", "--quiet"])
+        .assert()
+        .success()
+        .stdout("");
+
+    Command::cargo_bin("ob")
+        .unwrap()
+        .env("HOME", test_home.as_os_str())
+        .args(["-q", "config", "set", "--format", "aags.b64"])
+        .assert()
+        .success()
+        .stdout("");
+
+    // Data output is unaffected
+    Command::cargo_bin("ob")
+        .unwrap()
+        .env("HOME", test_home.as_os_str())
+        .args(["config", "show", "-q"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Scheme:   aags"));
+
+    cleanup_test_home(&test_home);
+}
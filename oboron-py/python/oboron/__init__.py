@@ -112,6 +112,12 @@ OboronBase.register(_oboron.ApgsB32)
 OboronBase.register(_oboron.ApgsB64)
 OboronBase.register(_oboron.ApgsHex)
 
+# Apgc variants
+OboronBase.register(_oboron.ApgcC32)
+OboronBase.register(_oboron.ApgcB32)
+OboronBase.register(_oboron.ApgcB64)
+OboronBase.register(_oboron.ApgcHex)
+
 # Apsv variants
 OboronBase.register(_oboron.ApsvC32)
 OboronBase.register(_oboron.ApsvB32)
@@ -136,6 +142,18 @@ OboronBase.register(_oboron.Mock2B32)
 OboronBase.register(_oboron.Mock2B64)
 OboronBase.register(_oboron.Mock2Hex)
 
+# Mock3 variants (testing)
+OboronBase.register(_oboron.Mock3C32)
+OboronBase.register(_oboron.Mock3B32)
+OboronBase.register(_oboron.Mock3B64)
+OboronBase.register(_oboron.Mock3Hex)
+
+# Mock4 variants (demo)
+OboronBase.register(_oboron.Mock4C32)
+OboronBase.register(_oboron.Mock4B32)
+OboronBase.register(_oboron.Mock4B64)
+OboronBase.register(_oboron.Mock4Hex)
+
 # Flexible interfaces
 OboronBase.register(_oboron.Ob)
 
@@ -166,6 +184,12 @@ ApgsB32 = _oboron.ApgsB32
 ApgsB64 = _oboron.ApgsB64
 ApgsHex = _oboron.ApgsHex
 
+# Apgc variants
+ApgcC32 = _oboron.ApgcC32
+ApgcB32 = _oboron.ApgcB32
+ApgcB64 = _oboron.ApgcB64
+ApgcHex = _oboron.ApgcHex
+
 # Apsv variants
 ApsvC32 = _oboron.ApsvC32
 ApsvB32 = _oboron.ApsvB32
@@ -190,6 +214,18 @@ Mock2B32 = _oboron.Mock2B32
 Mock2B64 = _oboron.Mock2B64
 Mock2Hex = _oboron.Mock2Hex
 
+# Mock3 (testing)
+Mock3C32 = _oboron.Mock3C32
+Mock3B32 = _oboron.Mock3B32
+Mock3B64 = _oboron.Mock3B64
+Mock3Hex = _oboron.Mock3Hex
+
+# Mock4 (demo)
+Mock4C32 = _oboron.Mock4C32
+Mock4B32 = _oboron.Mock4B32
+Mock4B64 = _oboron.Mock4B64
+Mock4Hex = _oboron.Mock4Hex
+
 # Utility functions
 generate_key = _oboron.generate_key
 generate_key_hex = _oboron.generate_key_hex
@@ -206,6 +242,8 @@ autodec = _oboron.autodec
 enc_keyless = _oboron.enc_keyless
 dec_keyless = _oboron.dec_keyless
 autodec_keyless = _oboron.autodec_keyless
+transcode = _oboron.transcode
+detect = _oboron.detect
 
 # ============================================================================
 # __all__ export
@@ -238,6 +276,12 @@ __all__ = [
     'ApgsB64',
     'ApgsHex',
 
+    # Apgc
+    'ApgcC32',
+    'ApgcB32',
+    'ApgcB64',
+    'ApgcHex',
+
     # Apsv
     'ApsvC32',
     'ApsvB32',
@@ -262,6 +306,18 @@ __all__ = [
     'Mock2B64',
     'Mock2Hex',
 
+    # Mock3 (testing)
+    'Mock3C32',
+    'Mock3B32',
+    'Mock3B64',
+    'Mock3Hex',
+
+    # Mock4 (demo)
+    'Mock4C32',
+    'Mock4B32',
+    'Mock4B64',
+    'Mock4Hex',
+
     # Format constants module
     'formats',
 
@@ -281,4 +337,6 @@ __all__ = [
     'enc_keyless',
     'dec_keyless',
     'autodec_keyless',
+    'transcode',
+    'detect',
 ]
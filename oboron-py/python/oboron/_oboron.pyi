@@ -229,6 +229,78 @@ class ApgsHex:
     def key_bytes(self) -> bytes: ...
     def __repr__(self) -> str: ...
 
+class ApgcC32:
+    def __init__(self, key: Optional[str] = None, keyless: bool = False) -> None: ...
+    def enc(self, plaintext: str) -> str: ...
+    def dec(self, obtext: str) -> str: ...
+    @property
+    def format(self) -> str: ...
+    @property
+    def scheme(self) -> str: ...
+    @property
+    def encoding(self) -> str: ...
+    @property
+    def key(self) -> str: ...
+    @property
+    def key_hex(self) -> str: ...
+    @property
+    def key_bytes(self) -> bytes: ...
+    def __repr__(self) -> str: ...
+
+class ApgcB32:
+    def __init__(self, key: Optional[str] = None, keyless: bool = False) -> None: ...
+    def enc(self, plaintext: str) -> str: ...
+    def dec(self, obtext: str) -> str: ...
+    @property
+    def format(self) -> str: ...
+    @property
+    def scheme(self) -> str: ...
+    @property
+    def encoding(self) -> str: ...
+    @property
+    def key(self) -> str: ...
+    @property
+    def key_hex(self) -> str: ...
+    @property
+    def key_bytes(self) -> bytes: ...
+    def __repr__(self) -> str: ...
+
+class ApgcB64:
+    def __init__(self, key: Optional[str] = None, keyless: bool = False) -> None: ...
+    def enc(self, plaintext: str) -> str: ...
+    def dec(self, obtext: str) -> str: ...
+    @property
+    def format(self) -> str: ...
+    @property
+    def scheme(self) -> str: ...
+    @property
+    def encoding(self) -> str: ...
+    @property
+    def key(self) -> str: ...
+    @property
+    def key_hex(self) -> str: ...
+    @property
+    def key_bytes(self) -> bytes: ...
+    def __repr__(self) -> str: ...
+
+class ApgcHex:
+    def __init__(self, key: Optional[str] = None, keyless: bool = False) -> None: ...
+    def enc(self, plaintext: str) -> str: ...
+    def dec(self, obtext: str) -> str: ...
+    @property
+    def format(self) -> str: ...
+    @property
+    def scheme(self) -> str: ...
+    @property
+    def encoding(self) -> str: ...
+    @property
+    def key(self) -> str: ...
+    @property
+    def key_hex(self) -> str: ...
+    @property
+    def key_bytes(self) -> bytes: ...
+    def __repr__(self) -> str: ...
+
 class ApsvC32:
     def __init__(self, key: Optional[str] = None, keyless: bool = False) -> None: ...
     def enc(self, plaintext: str) -> str: ...
@@ -480,6 +552,8 @@ class Omnib:
     def enc(self, plaintext: str, format: str) -> str: ...
     def dec(self, obtext: str, format: str) -> str: ...
     def autodec(self, obtext: str) -> str: ...
+    def enc_many(self, plaintexts: list[str], format: str) -> list[str]: ...
+    def autodec_many(self, obtexts: list[str]) -> list[str]: ...
     @property
     def key(self) -> str: ...
     @property
@@ -671,6 +745,150 @@ class Mock2Hex:
     def key_bytes(self) -> bytes: ...
     def __repr__(self) -> str: ...
 
+class Mock3C32:
+    def __init__(self, key: Optional[str] = None, keyless: bool = False) -> None: ...
+    def enc(self, plaintext: str) -> str: ...
+    def dec(self, obtext: str) -> str: ...
+    @property
+    def format(self) -> str: ...
+    @property
+    def scheme(self) -> str: ...
+    @property
+    def encoding(self) -> str: ...
+    @property
+    def key(self) -> str: ...
+    @property
+    def key_hex(self) -> str: ...
+    @property
+    def key_bytes(self) -> bytes: ...
+    def __repr__(self) -> str: ...
+
+class Mock3B32:
+    def __init__(self, key: Optional[str] = None, keyless: bool = False) -> None: ...
+    def enc(self, plaintext: str) -> str: ...
+    def dec(self, obtext: str) -> str: ...
+    @property
+    def format(self) -> str: ...
+    @property
+    def scheme(self) -> str: ...
+    @property
+    def encoding(self) -> str: ...
+    @property
+    def key(self) -> str: ...
+    @property
+    def key_hex(self) -> str: ...
+    @property
+    def key_bytes(self) -> bytes: ...
+    def __repr__(self) -> str: ...
+
+class Mock3B64:
+    def __init__(self, key: Optional[str] = None, keyless: bool = False) -> None: ...
+    def enc(self, plaintext: str) -> str: ...
+    def dec(self, obtext: str) -> str: ...
+    @property
+    def format(self) -> str: ...
+    @property
+    def scheme(self) -> str: ...
+    @property
+    def encoding(self) -> str: ...
+    @property
+    def key(self) -> str: ...
+    @property
+    def key_hex(self) -> str: ...
+    @property
+    def key_bytes(self) -> bytes: ...
+    def __repr__(self) -> str: ...
+
+class Mock3Hex:
+    def __init__(self, key: Optional[str] = None, keyless: bool = False) -> None: ...
+    def enc(self, plaintext: str) -> str: ...
+    def dec(self, obtext: str) -> str: ...
+    @property
+    def format(self) -> str: ...
+    @property
+    def scheme(self) -> str: ...
+    @property
+    def encoding(self) -> str: ...
+    @property
+    def key(self) -> str: ...
+    @property
+    def key_hex(self) -> str: ...
+    @property
+    def key_bytes(self) -> bytes: ...
+    def __repr__(self) -> str: ...
+
+class Mock4C32:
+    def __init__(self, key: Optional[str] = None, keyless: bool = False) -> None: ...
+    def enc(self, plaintext: str) -> str: ...
+    def dec(self, obtext: str) -> str: ...
+    @property
+    def format(self) -> str: ...
+    @property
+    def scheme(self) -> str: ...
+    @property
+    def encoding(self) -> str: ...
+    @property
+    def key(self) -> str: ...
+    @property
+    def key_hex(self) -> str: ...
+    @property
+    def key_bytes(self) -> bytes: ...
+    def __repr__(self) -> str: ...
+
+class Mock4B32:
+    def __init__(self, key: Optional[str] = None, keyless: bool = False) -> None: ...
+    def enc(self, plaintext: str) -> str: ...
+    def dec(self, obtext: str) -> str: ...
+    @property
+    def format(self) -> str: ...
+    @property
+    def scheme(self) -> str: ...
+    @property
+    def encoding(self) -> str: ...
+    @property
+    def key(self) -> str: ...
+    @property
+    def key_hex(self) -> str: ...
+    @property
+    def key_bytes(self) -> bytes: ...
+    def __repr__(self) -> str: ...
+
+class Mock4B64:
+    def __init__(self, key: Optional[str] = None, keyless: bool = False) -> None: ...
+    def enc(self, plaintext: str) -> str: ...
+    def dec(self, obtext: str) -> str: ...
+    @property
+    def format(self) -> str: ...
+    @property
+    def scheme(self) -> str: ...
+    @property
+    def encoding(self) -> str: ...
+    @property
+    def key(self) -> str: ...
+    @property
+    def key_hex(self) -> str: ...
+    @property
+    def key_bytes(self) -> bytes: ...
+    def __repr__(self) -> str: ...
+
+class Mock4Hex:
+    def __init__(self, key: Optional[str] = None, keyless: bool = False) -> None: ...
+    def enc(self, plaintext: str) -> str: ...
+    def dec(self, obtext: str) -> str: ...
+    @property
+    def format(self) -> str: ...
+    @property
+    def scheme(self) -> str: ...
+    @property
+    def encoding(self) -> str: ...
+    @property
+    def key(self) -> str: ...
+    @property
+    def key_hex(self) -> str: ...
+    @property
+    def key_bytes(self) -> bytes: ...
+    def __repr__(self) -> str: ...
+
 # ============================================================================
 # Z-tier mock/testing codec classes
 # ============================================================================
@@ -790,3 +1008,5 @@ def dec(obtext: str, format: str, key: str) -> str: ...
 def dec_keyless(obtext: str, format: str) -> str: ...
 def autodec(obtext: str, key: str) -> str: ...
 def autodec_keyless(obtext: str) -> str: ...
+def transcode(obtext: str, from_enc: str, to_enc: str) -> str: ...
+def detect(obtext: str) -> Optional[str]: ...
@@ -1,7 +1,7 @@
 """Format string constants for Oboron. 
 
 All constants follow the pattern:  {SCHEME}_{ENCODING}
-- Schemes: AAGS, AASV, APGS, APSV, UPBC, ZRBCX, LEGACY, MOCK1, MOCK2
+- Schemes: AAGS, AASV, APGS, APGC, APSV, UPBC, ZRBCX, LEGACY, MOCK1, MOCK2
 - Encodings:
   - B32 (RFC 4648 base32),
   - B64 (RFC 4648 base64url),
@@ -34,6 +34,12 @@ APGS_B64: str = "apgs.b64"
 APGS_C32: str = "apgs.c32"
 APGS_HEX: str = "apgs.hex"
 
+# apgc - probabilistic AES-GCM (secure and authenticated; not nonce-misuse resistant)
+APGC_B32: str = "apgc.b32"
+APGC_B64: str = "apgc.b64"
+APGC_C32: str = "apgc.c32"
+APGC_HEX: str = "apgc.hex"
+
 # apsv - probabilistic AES-SIV (secure and authenticated)
 APSV_B32: str = "apsv.b32"
 APSV_B64: str = "apsv.b64"
@@ -73,6 +79,8 @@ __all__ = [
     "AASV_B32", "AASV_B64", "AASV_C32", "AASV_HEX",
     # apgs
     "APGS_B32", "APGS_B64", "APGS_C32", "APGS_HEX",
+    # apgc
+    "APGC_B32", "APGC_B64", "APGC_C32", "APGC_HEX",
     # apsv
     "APSV_B32", "APSV_B64", "APSV_C32", "APSV_HEX",
     # zrbcx
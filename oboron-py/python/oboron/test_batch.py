@@ -0,0 +1,63 @@
+"""Test Omnib batch operations."""
+
+import oboron
+
+
+def test_enc_many_roundtrip():
+    """Test a 1000-element batch round trip, in input order."""
+    omnib = oboron.Omnib(keyless=True)
+    plaintexts = [f"user-{i}" for i in range(1000)]
+
+    ots = omnib.enc_many(plaintexts, "aasv.b64")
+    assert len(ots) == 1000
+    # Same results as one call per item
+    assert ots[0] == omnib.enc(plaintexts[0], "aasv.b64")
+    assert ots[999] == omnib.enc(plaintexts[999], "aasv.b64")
+
+    assert omnib.autodec_many(ots) == plaintexts
+
+    # Mixed formats decode in one batch too
+    formats = ["apsv.c32", "aags.hex"] * 500
+    mixed = [omnib.enc(pt, fmt) for pt, fmt in zip(plaintexts, formats)]
+    assert omnib.autodec_many(mixed) == plaintexts
+    assert omnib.enc_many([], "aasv.b64") == []
+
+    print("✓ Batch round trip test passed!")
+
+
+def test_batch_errors():
+    """Test that the first failure raises ValueError naming its index."""
+    omnib = oboron.Omnib(keyless=True)
+    plaintexts = [f"user-{i}" for i in range(1000)]
+
+    try:
+        omnib.enc_many(plaintexts, "nope.b64")
+    except ValueError:
+        pass
+    else:
+        raise AssertionError("enc_many should reject an invalid format")
+
+    # Empty plaintext is rejected
+    try:
+        omnib.enc_many(plaintexts[:10] + [""] + plaintexts[10:], "aasv.b64")
+    except ValueError as e:
+        assert "index 10" in str(e)
+    else:
+        raise AssertionError("enc_many should raise ValueError")
+
+    ots = omnib.enc_many(plaintexts, "aasv.b64")
+    ots[500] = "not an obtext"
+    try:
+        omnib.autodec_many(ots)
+    except ValueError as e:
+        assert "index 500" in str(e)
+    else:
+        raise AssertionError("autodec_many should raise ValueError")
+
+    print("✓ Batch error test passed!")
+
+
+if __name__ == "__main__":
+    test_enc_many_roundtrip()
+    test_batch_errors()
+    print("\n✅ All tests passed!")
@@ -0,0 +1,72 @@
+"""Test module-level transcode and detect functions."""
+
+import oboron
+
+
+def test_transcode_c32_to_b64():
+    """Test that c32 obtext transcodes to b64 obtext of the same ciphertext."""
+    ot_c32 = oboron.enc_keyless("hello", "aasv.c32")
+    ot_b64 = oboron.transcode(ot_c32, "c32", "b64")
+
+    # Deterministic scheme: same as encoding directly to b64
+    assert ot_b64 == oboron.enc_keyless("hello", "aasv.b64")
+    assert oboron.dec_keyless(ot_b64, "aasv.b64") == "hello"
+
+    # Long encoding names are accepted too
+    assert oboron.transcode(ot_b64, "base64", "base32crockford") == ot_c32
+
+    print("✓ Transcode test passed!")
+
+
+def test_transcode_invalid():
+    """Test that invalid input raises ValueError."""
+    for args in [("not hex!", "hex", "b64"), ("abc", "nope", "b64")]:
+        try:
+            oboron.transcode(*args)
+        except ValueError:
+            continue
+        raise AssertionError(f"transcode{args} should raise ValueError")
+
+    print("✓ Transcode error test passed!")
+
+
+def test_detect_known_output():
+    """Test format detection on known output."""
+    key = oboron.generate_key_hex()
+    assert len(key) == 128
+
+    for format in ["aasv.c32", "aags.b64", "apsv.hex", "upbc.b32"]:
+        ot = oboron.enc_keyless("detect me", format)
+        assert oboron.detect(ot) == format
+
+    assert oboron.detect("not obtext!") is None
+
+    print("✓ Detect test passed!")
+
+
+def test_generate_key_encodings():
+    """Test that key generators return the documented encodings."""
+    key = oboron.generate_key()
+    assert len(key) == 86
+    assert all(c.isalnum() or c in "-_" for c in key)
+    codec = oboron.AasvC32(key)
+    assert codec.dec(codec.enc("roundtrip")) == "roundtrip"
+
+    key_hex = oboron.generate_key_hex()
+    assert len(key_hex) == 128
+    assert all(c in "0123456789abcdef" for c in key_hex)
+    assert len(oboron.generate_key_bytes()) == 64
+
+    assert len(oboron.generate_secret()) == 43
+    assert len(oboron.generate_secret_hex()) == 64
+    assert len(oboron.generate_secret_bytes()) == 32
+
+    print("✓ Key generation test passed!")
+
+
+if __name__ == "__main__":
+    test_transcode_c32_to_b64()
+    test_transcode_invalid()
+    test_detect_known_output()
+    test_generate_key_encodings()
+    print("\n✅ All tests passed!")
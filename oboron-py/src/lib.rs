@@ -338,6 +338,33 @@ impl_codec_class!(
     "Apgs codec (probabilistic AES-GCM-SIV) with Hex encoding"
 );
 
+// Apgc variants
+// --------------
+#[cfg(feature = "apgc")]
+impl_codec_class!(
+    ApgcB32,
+    ::oboron::ApgcB32,
+    "Apgc codec (probabilistic AES-GCM) with B32 encoding"
+);
+#[cfg(feature = "apgc")]
+impl_codec_class!(
+    ApgcB64,
+    ::oboron::ApgcB64,
+    "Apgc codec (probabilistic AES-GCM) with B64 encoding"
+);
+#[cfg(feature = "apgc")]
+impl_codec_class!(
+    ApgcC32,
+    ::oboron::ApgcC32,
+    "Apgc codec (probabilistic AES-GCM) with C32 encoding"
+);
+#[cfg(feature = "apgc")]
+impl_codec_class!(
+    ApgcHex,
+    ::oboron::ApgcHex,
+    "Apgc codec (probabilistic AES-GCM) with Hex encoding"
+);
+
 // Apsv variants
 // --------------
 #[cfg(feature = "apsv")]
@@ -467,6 +494,52 @@ impl_codec_class!(
     "Mock2 codec (reverse plaintext scheme, for testing) with Hex encoding"
 );
 
+// Mock3 variants
+// -------------
+impl_codec_class!(
+    Mock3B32,
+    ::oboron::Mock3B32,
+    "Mock3 codec (length-preserving keyed substitution, for testing) with B32 encoding"
+);
+impl_codec_class!(
+    Mock3B64,
+    ::oboron::Mock3B64,
+    "Mock3 codec (length-preserving keyed substitution, for testing) with B64 encoding"
+);
+impl_codec_class!(
+    Mock3C32,
+    ::oboron::Mock3C32,
+    "Mock3 codec (length-preserving keyed substitution, for testing) with C32 encoding"
+);
+impl_codec_class!(
+    Mock3Hex,
+    ::oboron::Mock3Hex,
+    "Mock3 codec (length-preserving keyed substitution, for testing) with Hex encoding"
+);
+
+// Mock4 variants
+// -------------
+impl_codec_class!(
+    Mock4B32,
+    ::oboron::Mock4B32,
+    "Mock4 codec (word-only substitution, for demos) with B32 encoding"
+);
+impl_codec_class!(
+    Mock4B64,
+    ::oboron::Mock4B64,
+    "Mock4 codec (word-only substitution, for demos) with B64 encoding"
+);
+impl_codec_class!(
+    Mock4C32,
+    ::oboron::Mock4C32,
+    "Mock4 codec (word-only substitution, for demos) with C32 encoding"
+);
+impl_codec_class!(
+    Mock4Hex,
+    ::oboron::Mock4Hex,
+    "Mock4 codec (word-only substitution, for demos) with Hex encoding"
+);
+
 // Zmock1 variants
 // -------------
 impl_zcodec_class!(
@@ -646,7 +719,8 @@ impl Ob {
     fn set_format(&mut self, format: &str) -> PyResult<()> {
         self.inner
             .set_format(format)
-            .map_err(|e| PyValueError::new_err(format!("Failed to set format: {}", e)))
+            .map_err(|e| PyValueError::new_err(format!("Failed to set format: {}", e)))?;
+        Ok(())
     }
 
     /// Change the scheme while keeping the current encoding.
@@ -661,14 +735,16 @@ impl Ob {
             .map_err(|e| PyValueError::new_err(format!("Invalid scheme: {}", e)))?;
         self.inner
             .set_scheme(scheme_enum)
-            .map_err(|e| PyValueError::new_err(format!("Failed to set scheme: {}", e)))
+            .map_err(|e| PyValueError::new_err(format!("Failed to set scheme: {}", e)))?;
+        Ok(())
     }
 
     /// Change the encoding while keeping the current scheme.
     ///
     /// Args:  
-    ///     encoding: Encoding name: "b32", "b64", "c32", "hex".
-    ///               Also accepts long forms: "base32rfc", "base64", "base32crockford", or "hex".
+    ///     encoding: Encoding name: "b32", "b64", "c32", "hex", "hexu" (uppercase hex).
+    ///               Also accepts long forms: "base32rfc", "base64", "base32crockford", "hex",
+    ///               or "hexupper".
     ///
     /// Raises:  
     ///     ValueError: If encoding is invalid.
@@ -677,7 +753,8 @@ impl Ob {
             .map_err(|e| PyValueError::new_err(format!("Invalid encoding: {}", e)))?;
         self.inner
             .set_encoding(encoding_enum)
-            .map_err(|e| PyValueError::new_err(format!("Failed to set encoding: {}", e)))
+            .map_err(|e| PyValueError::new_err(format!("Failed to set encoding: {}", e)))?;
+        Ok(())
     }
 
     fn __repr__(&self) -> String {
@@ -782,6 +859,62 @@ impl Omnib {
         result.map_err(|e| PyValueError::new_err(format!("Autodec operation failed: {}", e)))
     }
 
+    /// Encrypt+encode a list of plaintext strings with a specific format.
+    ///
+    /// The whole batch runs in one call with the GIL released, which avoids
+    /// the per-call overhead of many `enc()` calls.
+    ///
+    /// Args:
+    ///     plaintexts: The plaintext strings to encrypt+encode.
+    ///     format: Format string like "aags.b64", "apsv.hex", etc.
+    ///
+    /// Returns:
+    ///     The obtext strings, in input order.
+    ///
+    /// Raises:
+    ///     ValueError: If the format is invalid, or on the first plaintext
+    ///         that fails (e.g. an empty string), naming its index.
+    fn enc_many(&self, py: Python, plaintexts: Vec<String>, format: &str) -> PyResult<Vec<String>> {
+        let format = ::oboron::Format::from_str(format)
+            .map_err(|e| PyValueError::new_err(format!("Enc operation failed: {}", e)))?;
+        let result: Result<Vec<String>, _> = py.allow_threads(|| {
+            plaintexts
+                .iter()
+                .enumerate()
+                .map(|(i, plaintext)| self.inner.enc(plaintext, format).map_err(|e| (i, e)))
+                .collect()
+        });
+        result.map_err(|(i, e)| {
+            PyValueError::new_err(format!("Enc operation failed at index {}: {}", i, e))
+        })
+    }
+
+    /// Decode+decrypt a list of obtext strings with automatic scheme and
+    /// encoding detection, as with `autodec()`.
+    ///
+    /// The whole batch runs in one call with the GIL released.
+    ///
+    /// Args:
+    ///     obtexts: The encrypted+encoded strings to decode+decrypt.
+    ///
+    /// Returns:
+    ///     The plaintext strings, in input order.
+    ///
+    /// Raises:
+    ///     ValueError: On the first obtext that fails, naming its index.
+    fn autodec_many(&self, py: Python, obtexts: Vec<String>) -> PyResult<Vec<String>> {
+        let result: Result<Vec<String>, _> = py.allow_threads(|| {
+            obtexts
+                .iter()
+                .enumerate()
+                .map(|(i, obtext)| self.inner.autodec(obtext).map_err(|e| (i, e)))
+                .collect()
+        });
+        result.map_err(|(i, e)| {
+            PyValueError::new_err(format!("Autodec operation failed at index {}: {}", i, e))
+        })
+    }
+
     /// Get the key used by this instance (as base64 string).
     #[getter]
     fn key(&self) -> String {
@@ -947,7 +1080,8 @@ impl Obz {
     fn set_format(&mut self, format: &str) -> PyResult<()> {
         self.inner
             .set_format(format)
-            .map_err(|e| PyValueError::new_err(format!("Failed to set format: {}", e)))
+            .map_err(|e| PyValueError::new_err(format!("Failed to set format: {}", e)))?;
+        Ok(())
     }
 
     /// Change the scheme while keeping the current encoding.
@@ -962,14 +1096,16 @@ impl Obz {
             .map_err(|e| PyValueError::new_err(format!("Invalid scheme: {}", e)))?;
         self.inner
             .set_scheme(scheme_enum)
-            .map_err(|e| PyValueError::new_err(format!("Failed to set scheme: {}", e)))
+            .map_err(|e| PyValueError::new_err(format!("Failed to set scheme: {}", e)))?;
+        Ok(())
     }
 
     /// Change the encoding while keeping the current scheme.
     ///
     /// Args:  
-    ///     encoding: Encoding name: "b32", "b64", "c32", "hex".
-    ///               Also accepts long forms: "base32rfc", "base64", "base32crockford", or "hex".
+    ///     encoding: Encoding name: "b32", "b64", "c32", "hex", "hexu" (uppercase hex).
+    ///               Also accepts long forms: "base32rfc", "base64", "base32crockford", "hex",
+    ///               or "hexupper".
     ///
     /// Raises:  
     ///     ValueError: If encoding is invalid.
@@ -978,7 +1114,8 @@ impl Obz {
             .map_err(|e| PyValueError::new_err(format!("Invalid encoding: {}", e)))?;
         self.inner
             .set_encoding(encoding_enum)
-            .map_err(|e| PyValueError::new_err(format!("Failed to set encoding: {}", e)))
+            .map_err(|e| PyValueError::new_err(format!("Failed to set encoding: {}", e)))?;
+        Ok(())
     }
 
     fn __repr__(&self) -> String {
@@ -1104,8 +1241,12 @@ impl Omnibz {
 
 /// Generate a random 64-byte key as a base64 string.
 ///
+/// This is the key format accepted by the codec constructors; use
+/// `generate_key_hex` for the hex form.
+///
 /// Returns:
-///     A random 64-byte key as a 86-character base64 string.
+///     A random 64-byte key as an 86-character URL-safe base64 string
+///     (no padding).
 #[pyfunction]
 fn generate_key() -> PyResult<String> {
     Ok(::oboron::generate_key())
@@ -1133,7 +1274,7 @@ fn generate_key_bytes(py: Python) -> PyResult<Py<PyBytes>> {
 /// Generate a random 32-byte secret as a base64 string.
 ///
 /// Returns:
-///     A random 64-byte key as a 43-character base64 string.
+///     A random 32-byte secret as a 43-character base64 string.
 #[pyfunction]
 fn generate_secret() -> PyResult<String> {
     Ok(::oboron::generate_secret())
@@ -1142,7 +1283,7 @@ fn generate_secret() -> PyResult<String> {
 /// Generate a random 32-byte secret as a hex string.
 ///
 /// Returns:
-///     A random 32-byte key as a 64-character hex string.
+///     A random 32-byte secret as a 64-character hex string.
 #[pyfunction]
 fn generate_secret_hex() -> PyResult<String> {
     Ok(::oboron::generate_secret_hex())
@@ -1268,6 +1409,47 @@ fn autodec_keyless(obtext: &str) -> PyResult<String> {
         .map_err(|e| PyValueError::new_err(format!("Autodec operation failed: {}", e)))
 }
 
+/// Re-encode obtext from one encoding to another (no key needed).
+///
+/// The ciphertext is unchanged, so the result decodes under the same scheme
+/// in the new encoding.  Not applicable to legacy obtext.
+///
+/// Args:
+///     obtext:   The obtext string to re-encode.
+///     from_enc: Current encoding: "b32", "b64", "c32", "hex", or a long form
+///               ("base32rfc", "base64", "base32crockford").
+///     to_enc:   Target encoding, same accepted names as from_enc.
+///
+/// Returns:
+///     The obtext in the target encoding.
+///
+/// Raises:
+///     ValueError: If an encoding name is invalid or obtext is not valid in from_enc.
+#[pyfunction]
+fn transcode(obtext: &str, from_enc: &str, to_enc: &str) -> PyResult<String> {
+    let from = ::oboron::Encoding::from_str(from_enc)
+        .map_err(|e| PyValueError::new_err(format!("Invalid encoding: {}", e)))?;
+    let to = ::oboron::Encoding::from_str(to_enc)
+        .map_err(|e| PyValueError::new_err(format!("Invalid encoding: {}", e)))?;
+    ::oboron::encoding::transcode(obtext, from, to)
+        .map_err(|e| PyValueError::new_err(format!("Transcode operation failed: {}", e)))
+}
+
+/// Detect the format of an obtext from its scheme marker (no key needed).
+///
+/// A detected format is a strong hint, not a guarantee that the obtext
+/// decrypts.  Legacy obtext is never detected.
+///
+/// Args:
+///     obtext: The obtext string to inspect.
+///
+/// Returns:
+///     The format string (e.g. "aasv.b64"), or None if no enabled scheme matches.
+#[pyfunction]
+fn detect(obtext: &str) -> Option<String> {
+    ::oboron::detect_format(obtext).map(|format| format.to_string())
+}
+
 /// Python module for Oboron (internal Rust extension)
 #[pymodule]
 fn _oboron(m: &Bound<'_, PyModule>) -> PyResult<()> {
@@ -1298,6 +1480,15 @@ fn _oboron(m: &Bound<'_, PyModule>) -> PyResult<()> {
         m.add_class::<ApgsHex>()?;
     }
 
+    // Apgc variants
+    #[cfg(feature = "apgc")]
+    {
+        m.add_class::<ApgcC32>()?;
+        m.add_class::<ApgcB32>()?;
+        m.add_class::<ApgcB64>()?;
+        m.add_class::<ApgcHex>()?;
+    }
+
     // Aasv variants
     #[cfg(feature = "aasv")]
     {
@@ -1340,6 +1531,16 @@ fn _oboron(m: &Bound<'_, PyModule>) -> PyResult<()> {
         m.add_class::<Mock2B32>()?;
         m.add_class::<Mock2B64>()?;
         m.add_class::<Mock2Hex>()?;
+        // Mock3 variants
+        m.add_class::<Mock3C32>()?;
+        m.add_class::<Mock3B32>()?;
+        m.add_class::<Mock3B64>()?;
+        m.add_class::<Mock3Hex>()?;
+        // Mock4 variants
+        m.add_class::<Mock4C32>()?;
+        m.add_class::<Mock4B32>()?;
+        m.add_class::<Mock4B64>()?;
+        m.add_class::<Mock4Hex>()?;
     }
 
     // Z-TIER =========================
@@ -1395,6 +1596,8 @@ fn _oboron(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(autodec, m)?)?;
     #[cfg(feature = "keyless")]
     m.add_function(wrap_pyfunction!(autodec_keyless, m)?)?;
+    m.add_function(wrap_pyfunction!(transcode, m)?)?;
+    m.add_function(wrap_pyfunction!(detect, m)?)?;
 
     Ok(())
 }
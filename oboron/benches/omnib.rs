@@ -1,5 +1,5 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
-use oboron::Omnib;
+use oboron::{Format, Omnib};
 use serde::Deserialize;
 use std::fs;
 use std::path::PathBuf;
@@ -125,5 +125,30 @@ fn run_omnib_benchmarks(c: &mut Criterion) {
     eprintln!("Registered {} omnib benchmarks", bench_count);
 }
 
-criterion_group!(benches, run_omnib_benchmarks);
+/// Repeated `enc` with one format: a cached alias string, a format
+/// constant string, the alias parsed on every call, and a pre-parsed Format
+fn run_format_cache_benchmarks(c: &mut Criterion) {
+    let ob = Omnib::new_keyless().unwrap();
+    let plaintext = "hello world";
+    let alias = "ob32.base64";
+    let format = Format::from_str(alias).unwrap();
+
+    c.bench_function("Omnib/format_cache/enc/alias_str_cached", |b| {
+        b.iter(|| ob.enc(black_box(plaintext), black_box(alias)).unwrap());
+    });
+    c.bench_function("Omnib/format_cache/enc/constant_str", |b| {
+        b.iter(|| ob.enc(black_box(plaintext), black_box("aasv.b64")).unwrap());
+    });
+    c.bench_function("Omnib/format_cache/enc/alias_str_parsed", |b| {
+        b.iter(|| {
+            let format = Format::from_str(black_box(alias)).unwrap();
+            ob.enc(black_box(plaintext), format).unwrap()
+        });
+    });
+    c.bench_function("Omnib/format_cache/enc/format", |b| {
+        b.iter(|| ob.enc(black_box(plaintext), black_box(format)).unwrap());
+    });
+}
+
+criterion_group!(benches, run_omnib_benchmarks, run_format_cache_benchmarks);
 criterion_main!(benches);
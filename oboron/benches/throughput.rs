@@ -0,0 +1,125 @@
+//! Size-parametric throughput benchmarks.
+//!
+//! Unlike the spec-driven benches (which time short plaintexts listed in
+//! `benchmarks_*.jsonl`), this measures `enc`/`dec`/`autodec` throughput for
+//! every enabled secure scheme × encoding at 16 B, 1 KB and 1 MB, plus the
+//! cost of full format detection in `Omnib::autodec` and of passing
+//! `Omnib::enc`/`dec` a format string rather than a parsed `Format`.
+//!
+//! Run with `cargo bench --bench throughput`; filter with e.g.
+//! `cargo bench --bench throughput -- throughput/1KB/enc/aasv`.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use oboron::{Encoding, Format, Ob, Omnib, Scheme};
+
+const SIZES: [(&str, usize); 3] = [("16B", 16), ("1KB", 1024), ("1MB", 1024 * 1024)];
+
+const SCHEMES: &[Scheme] = &[
+    #[cfg(feature = "aags")]
+    Scheme::Aags,
+    #[cfg(feature = "apgs")]
+    Scheme::Apgs,
+    #[cfg(feature = "aasv")]
+    Scheme::Aasv,
+    #[cfg(feature = "apsv")]
+    Scheme::Apsv,
+    #[cfg(feature = "upbc")]
+    Scheme::Upbc,
+];
+
+/// Representative plaintext: printable ASCII, not a single repeated byte.
+fn plaintext(size: usize) -> String {
+    (0..size).map(|i| (b'!' + (i % 94) as u8) as char).collect()
+}
+
+fn formats() -> impl Iterator<Item = Format> {
+    SCHEMES
+        .iter()
+        .flat_map(|&scheme| Encoding::ALL.map(|encoding| Format::new(scheme, encoding)))
+}
+
+fn bench_ob(c: &mut Criterion) {
+    for (label, size) in SIZES {
+        let pt = plaintext(size);
+        let mut group = c.benchmark_group(format!("throughput/{}", label));
+        group.throughput(Throughput::Bytes(size as u64));
+        if size >= 1024 * 1024 {
+            group.sample_size(10);
+        }
+
+        for format in formats() {
+            // Create ob and obtext once, OUTSIDE the timed loop
+            let ob = Ob::new_keyless(format).unwrap();
+            let ot = ob.enc(&pt).unwrap();
+            let id = format.to_string();
+
+            group.bench_with_input(BenchmarkId::new("enc", &id), &pt, |b, pt| {
+                b.iter(|| ob.enc(black_box(pt)).unwrap());
+            });
+            group.bench_with_input(BenchmarkId::new("dec", &id), &ot, |b, ot| {
+                b.iter(|| ob.dec(black_box(ot)).unwrap());
+            });
+            group.bench_with_input(BenchmarkId::new("autodec", &id), &ot, |b, ot| {
+                b.iter(|| ob.autodec(black_box(ot)).unwrap());
+            });
+        }
+        group.finish();
+    }
+}
+
+/// `Omnib::autodec` detects both scheme and encoding.  Comparing against
+/// `Ob::dec` on the same obtext isolates the detection overhead.
+fn bench_omnib_detection(c: &mut Criterion) {
+    let omnib = Omnib::new_keyless().unwrap();
+    let pt = plaintext(16);
+
+    let mut group = c.benchmark_group("omnib_detection");
+    for format in formats() {
+        let ob = Ob::new_keyless(format).unwrap();
+        let ot = ob.enc(&pt).unwrap();
+        let id = format.to_string();
+
+        group.bench_with_input(BenchmarkId::new("omnib_autodec", &id), &ot, |b, ot| {
+            b.iter(|| omnib.autodec(black_box(ot)).unwrap());
+        });
+        group.bench_with_input(BenchmarkId::new("ob_dec", &id), &ot, |b, ot| {
+            b.iter(|| ob.dec(black_box(ot)).unwrap());
+        });
+    }
+    group.finish();
+}
+
+/// `Omnib::enc`/`dec` accept a format string or a `Format`; the string is
+/// parsed on every call, the `Format` is used as is.
+fn bench_omnib_format_arg(c: &mut Criterion) {
+    let omnib = Omnib::new_keyless().unwrap();
+    let pt = plaintext(16);
+
+    let mut group = c.benchmark_group("omnib_format_arg");
+    for format in formats() {
+        let name = format.to_string();
+        let ot = omnib.enc(&pt, format).unwrap();
+
+        group.bench_with_input(BenchmarkId::new("enc_str", &name), &pt, |b, pt| {
+            b.iter(|| omnib.enc(black_box(pt), black_box(name.as_str())).unwrap());
+        });
+        group.bench_with_input(BenchmarkId::new("enc_format", &name), &pt, |b, pt| {
+            b.iter(|| omnib.enc(black_box(pt), black_box(format)).unwrap());
+        });
+        group.bench_with_input(BenchmarkId::new("dec_str", &name), &ot, |b, ot| {
+            b.iter(|| omnib.dec(black_box(ot), black_box(name.as_str())).unwrap());
+        });
+        group.bench_with_input(BenchmarkId::new("dec_format", &name), &ot, |b, ot| {
+            b.iter(|| omnib.dec(black_box(ot), black_box(format)).unwrap());
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_ob,
+    bench_omnib_detection,
+    bench_omnib_format_arg
+);
+criterion_main!(benches);
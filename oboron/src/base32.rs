@@ -8,6 +8,11 @@ pub(crate) static BASE32_CROCKFORD: Lazy<Encoding> = Lazy::new(|| {
     let mut spec = Specification::new();
     // spec.symbols.push_str("abcdefghijklmnopqrstuvwxyz234567");
     spec.symbols.push_str("0123456789abcdefghjkmnpqrstvwxyz"); // <- Crockford's base32!
+
+    // Decoding is case-insensitive and reads i, l as 1 and o as 0, per
+    // Crockford's spec; encoding stays lowercase
+    spec.translate.from.push_str("ABCDEFGHJKMNPQRSTVWXYZIiLlOo");
+    spec.translate.to.push_str("abcdefghjkmnpqrstvwxyz111100");
     spec.padding = None;
     spec.encoding().unwrap()
 });
@@ -0,0 +1,96 @@
+use crate::codec::from_bytes_with_format_internal;
+use crate::{format::IntoFormat, Error, Format, MasterKey, ObAny};
+use std::collections::HashMap;
+
+/// A per-format cache of [`ObAny`] encoders sharing one key.
+///
+/// Like [`Omnib`](crate::Omnib), `ObCache` takes the format on each call,
+/// but the encoder for a format is constructed on first use and reused on
+/// every later call with the same format, so repeated calls skip format
+/// dispatch and key setup.
+///
+/// Only formats with a typed codec are supported: the `hexu` encoding and
+/// custom alphabets return [`Error::UnsupportedFormat`], and custom schemes
+/// [`Error::UnknownScheme`]; use `Omnib` for those.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> Result<(), oboron::Error> {
+/// # #[cfg(feature = "aasv")]
+/// # {
+/// use oboron::{ObCache, AASV_B64};
+///
+/// let mut cache = ObCache::new(&oboron::generate_key())?;
+/// let ot = cache.enc("hello", AASV_B64)?;
+/// assert_eq!(cache.dec(&ot, AASV_B64)?, "hello");
+/// assert_eq!(cache.len(), 1);
+/// # }
+/// # Ok(())
+/// # }
+/// ```
+pub struct ObCache {
+    masterkey: MasterKey,
+    encoders: HashMap<Format, ObAny>,
+}
+
+impl ObCache {
+    /// Create an empty cache with a base64 key.
+    pub fn new(key_b64: &str) -> Result<Self, Error> {
+        Ok(Self {
+            masterkey: MasterKey::from_base64(key_b64)?,
+            encoders: HashMap::new(),
+        })
+    }
+
+    /// Create an empty cache with hardcoded key (testing only).
+    #[cfg(feature = "keyless")]
+    pub fn new_keyless() -> Result<Self, Error> {
+        Self::from_bytes(crate::keyless::keyless_key())
+    }
+
+    /// Create an empty cache from raw bytes.
+    pub fn from_bytes(key_bytes: &[u8; 64]) -> Result<Self, Error> {
+        Ok(Self {
+            masterkey: MasterKey::from_bytes(key_bytes)?,
+            encoders: HashMap::new(),
+        })
+    }
+
+    /// Get the encoder for a format, constructing and caching it on first use.
+    ///
+    /// Accepts either a format string (`&str`) or a `Format` instance.
+    pub fn get(&mut self, format: impl IntoFormat) -> Result<&ObAny, Error> {
+        let format = format.into_format()?;
+        if !self.encoders.contains_key(&format) {
+            let encoder = from_bytes_with_format_internal(format, self.masterkey.key())?;
+            self.encoders.insert(format, encoder);
+        }
+        Ok(&self.encoders[&format])
+    }
+
+    /// Encrypt and encode plaintext with the cached encoder for `format`.
+    pub fn enc(&mut self, plaintext: &str, format: impl IntoFormat) -> Result<String, Error> {
+        self.get(format)?.enc(plaintext)
+    }
+
+    /// Decode and decrypt obtext with the cached encoder for `format`.
+    pub fn dec(&mut self, obtext: &str, format: impl IntoFormat) -> Result<String, Error> {
+        self.get(format)?.dec(obtext)
+    }
+
+    /// Number of cached encoders.
+    pub fn len(&self) -> usize {
+        self.encoders.len()
+    }
+
+    /// Whether no encoder has been cached yet.
+    pub fn is_empty(&self) -> bool {
+        self.encoders.is_empty()
+    }
+
+    /// Get the key used by this instance.
+    pub fn key(&self) -> String {
+        self.masterkey.key_base64()
+    }
+}
@@ -1,7 +1,27 @@
 //! Trait-based interface for scheme-specific ObtextCodec implementations.
-#[cfg(feature = "keyless")]
-use crate::constants::HARDCODED_KEY_BYTES;
-use crate::{error::Error, Encoding, Format, MasterKey, Scheme};
+use crate::{error::Error, Encoding, Format, MasterKey, Obtext, Plaintext, Scheme};
+use sha2::{Digest, Sha256};
+
+/// Domain-separation prefix hashed ahead of the payload for
+/// [`ObtextCodec::dedup_key`].
+const DEDUP_DOMAIN: &[u8] = b"oboron dedup v1";
+
+/// Plaintext round-tripped by [`ObtextCodec::self_test`].
+const SELF_TEST_SENTINEL: &str = "oboron self-test";
+
+/// [`ObtextCodec::key_id`] of a codec that does not know its key.  Not hex,
+/// so it never equals a real key fingerprint.
+pub const UNKNOWN_KEY_ID: &str = "unknown";
+
+/// Dedup key of a decoded obtext payload: domain-separated SHA-256, as 64
+/// lowercase hex characters.
+pub(crate) fn dedup_digest(payload: &[u8]) -> String {
+    let digest = Sha256::new()
+        .chain_update(DEDUP_DOMAIN)
+        .chain_update(payload)
+        .finalize();
+    hex::encode(digest)
+}
 
 /// Core trait for ObtextCodec encryption+encoding/decoding+decryption implementations.
 ///
@@ -10,11 +30,69 @@ use crate::{error::Error, Encoding, Format, MasterKey, Scheme};
 ///
 /// Note: Construction methods (`new`, `from_bytes`, `new_keyless`) are not part of
 /// this trait.     Each type provides its own constructor with an appropriate signature.
+///
+/// # Wrapping a codec
+///
+/// Domain-specific wrappers need no access to oboron internals: hold any
+/// `ObtextCodec`, and build it with the factory functions
+/// ([`new`](crate::new), [`new_with_format`](crate::new_with_format),
+/// [`from_bytes_with_format`](crate::from_bytes_with_format), ...), a typed
+/// codec such as [`AasvC32`](crate::AasvC32), or [`Ob`](crate::Ob):
+///
+/// ```rust
+/// # fn main() -> Result<(), oboron::Error> {
+/// # #[cfg(feature = "aasv")]
+/// # {
+/// use oboron::{Format, ObAny, ObtextCodec};
+///
+/// /// A user id, carried around as an opaque token
+/// struct UserToken<C = ObAny> {
+///     codec: C,
+/// }
+///
+/// impl UserToken {
+///     fn new(key: &str) -> Result<Self, oboron::Error> {
+///         let codec = oboron::new_with_format(Format::from_str("aasv.c32")?, key)?;
+///         Ok(Self { codec })
+///     }
+/// }
+///
+/// impl<C: ObtextCodec> UserToken<C> {
+///     fn issue(&self, user_id: u64) -> Result<String, oboron::Error> {
+///         self.codec.enc(&user_id.to_string())
+///     }
+///
+///     fn user_id(&self, token: &str) -> Option<u64> {
+///         self.codec.dec(token).ok()?.parse().ok()
+///     }
+/// }
+///
+/// let tokens = UserToken::new(&oboron::generate_key())?;
+/// let token = tokens.issue(42)?;
+/// assert_eq!(tokens.user_id(&token), Some(42));
+/// assert_eq!(tokens.user_id("not-a-token"), None);
+/// # }
+/// # Ok(())
+/// # }
+/// ```
 pub trait ObtextCodec {
     /// Encode a plaintext string.
     fn enc(&self, plaintext: &str) -> Result<String, Error>;
 
     /// Decode an encoded string back to plaintext
+    ///
+    /// The result must be used: discarding it would silently drop a
+    /// decryption error, so the compiler warns, and under
+    /// `#![deny(unused_must_use)]` rejects, code that ignores it:
+    ///
+    /// ```compile_fail
+    /// #![deny(unused_must_use)]
+    /// use oboron::ObtextCodec;
+    ///
+    /// fn check(ob: &impl ObtextCodec, ot: &str) {
+    ///     ob.dec(ot); // error: unused `Result` that must be used
+    /// }
+    /// ```
     fn dec(&self, obtext: &str) -> Result<String, Error>;
 
     /// Get the full format (encapsulating scheme + encoding) used by this instance
@@ -25,9 +103,225 @@ pub trait ObtextCodec {
 
     /// Get the encoding used by this instance.
     fn encoding(&self) -> Encoding;
+
+    /// Whether this instance uses `scheme`, i.e. `self.scheme() == scheme`.
+    fn scheme_is(&self, scheme: Scheme) -> bool {
+        self.scheme() == scheme
+    }
+
+    /// Whether this instance uses `encoding`, i.e. `self.encoding() == encoding`.
+    fn encoding_is(&self, encoding: Encoding) -> bool {
+        self.encoding() == encoding
+    }
+
+    /// Like [`dec`](Self::dec), but also return the scheme the obtext was
+    /// decrypted with, e.g. for an audit trail.
+    ///
+    /// `dec` never autodetects the scheme: obtext whose marker names another
+    /// scheme is rejected, so the returned scheme is always
+    /// [`scheme()`](Self::scheme).  For authenticated schemes it is also
+    /// cryptographically confirmed by the verified tag; for the others it is
+    /// only the configured scheme.
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), oboron::Error> {
+    /// # #[cfg(feature = "aasv")]
+    /// # {
+    /// use oboron::{Ob, ObtextCodec, Scheme};
+    /// # let key = oboron::generate_key();
+    /// let ob = Ob::new("aasv.b64", &key)?;
+    /// let ot = ob.enc("hello")?;
+    /// assert_eq!(ob.dec_strict_detail(&ot)?, ("hello".to_string(), Scheme::Aasv));
+    /// # }
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn dec_strict_detail(&self, obtext: &str) -> Result<(String, Scheme), Error> {
+        let plaintext = self.dec(obtext)?;
+        Ok((plaintext, self.scheme()))
+    }
+
+    /// Get a short, non-secret identifier of the key (8 lowercase hex
+    /// characters), e.g. to tell keys apart in logs.  Available in every
+    /// build; the raw key bytes stay behind the `bytes-keys` feature.
+    ///
+    /// For [`Ob`](crate::Ob) this is the [`fingerprint`](crate::Ob::fingerprint),
+    /// and every keyed codec in this crate returns its key's fingerprint.
+    /// The default, for implementations that do not override it, is
+    /// [`UNKNOWN_KEY_ID`], which no real key produces.
+    fn key_id(&self) -> String {
+        UNKNOWN_KEY_ID.to_string()
+    }
+
+    /// Get a deduplication key for `plaintext`: a hash of its ciphertext
+    /// (64 lowercase hex characters) that does not depend on the encoding,
+    /// so the c32 and b64 obtexts of a secret share one index key.
+    ///
+    /// Only meaningful for deterministic schemes, where a plaintext always
+    /// has the same ciphertext under a given key.  The key does not reveal
+    /// the plaintext, but equal dedup keys do reveal equal plaintexts.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ProbabilisticScheme`] for probabilistic schemes, and
+    /// any error [`enc`](Self::enc) would return.
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), oboron::Error> {
+    /// # #[cfg(feature = "aasv")]
+    /// # {
+    /// use oboron::{Ob, ObtextCodec};
+    /// # let key = oboron::generate_key();
+    /// let c32 = Ob::new("aasv.c32", &key)?;
+    /// let b64 = Ob::new("aasv.b64", &key)?;
+    /// assert_eq!(c32.dedup_key("secret")?, b64.dedup_key("secret")?);
+    /// # }
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn dedup_key(&self, plaintext: &str) -> Result<String, Error> {
+        let scheme = self.scheme();
+        if scheme.is_probabilistic() {
+            return Err(Error::ProbabilisticScheme {
+                scheme: scheme.as_str(),
+            });
+        }
+        let obtext = self.enc(plaintext)?;
+        let payload = crate::encoding::decode(&obtext, self.encoding())?;
+        Ok(dedup_digest(&payload))
+    }
+
+    /// Check that this instance is usable: encrypt a fixed sentinel, decrypt
+    /// the result and compare, e.g. from a service health check.  Works for
+    /// probabilistic schemes too, as the instance decrypts its own output.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error [`enc`](Self::enc) or [`dec`](Self::dec) would
+    /// return, e.g. [`Error::UnsupportedScheme`] for an [`Ob`](crate::Ob)
+    /// built with a z-tier or legacy format, and [`Error::SelfTestFailed`]
+    /// if the round trip does not give back the sentinel.
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), oboron::Error> {
+    /// # #[cfg(feature = "apsv")]
+    /// # {
+    /// use oboron::{Ob, ObtextCodec};
+    /// # let key = oboron::generate_key();
+    /// Ob::new("apsv.b64", &key)?.self_test()?;
+    /// # }
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn self_test(&self) -> Result<(), Error> {
+        let obtext = self.enc(SELF_TEST_SENTINEL)?;
+        if self.dec(&obtext)? == SELF_TEST_SENTINEL {
+            Ok(())
+        } else {
+            Err(Error::SelfTestFailed {
+                format: self.format(),
+            })
+        }
+    }
+
+    /// Encrypt `data` into a caller-owned buffer: `out` is cleared and then
+    /// holds the obtext, so one `String` can be reused across calls.
+    ///
+    /// Plaintexts are strings throughout this API, so `data` must be UTF-8.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidUtf8`] if `data` is not UTF-8, and any error
+    /// [`enc`](Self::enc) would return.  `out` is left empty on error.
+    fn enc_bytes_into(&self, data: &[u8], out: &mut String) -> Result<(), Error> {
+        out.clear();
+        let plaintext = std::str::from_utf8(data).map_err(|_| Error::InvalidUtf8)?;
+        out.push_str(&self.enc(plaintext)?);
+        Ok(())
+    }
+
+    /// Decrypt `obtext` into a caller-owned buffer: `out` is cleared and
+    /// then holds the plaintext bytes, so one `Vec<u8>` can be reused across
+    /// calls.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error [`dec`](Self::dec) would return.  `out` is left
+    /// empty on error.
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), oboron::Error> {
+    /// # #[cfg(feature = "aasv")]
+    /// # {
+    /// use oboron::{AasvB64, ObtextCodec};
+    /// # let key = oboron::generate_key();
+    /// let ob = AasvB64::new(&key)?;
+    /// let mut buf = Vec::new();
+    /// ob.dec_bytes_into(&ob.enc("hello")?, &mut buf)?;
+    /// assert_eq!(buf, b"hello");
+    /// # }
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn dec_bytes_into(&self, obtext: &str, out: &mut Vec<u8>) -> Result<(), Error> {
+        out.clear();
+        out.extend_from_slice(self.dec(obtext)?.as_bytes());
+        Ok(())
+    }
+
+    /// Typed variant of [`enc`](Self::enc): takes a [`Plaintext`] and
+    /// returns an [`Obtext`], so the two cannot be mixed up.
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), oboron::Error> {
+    /// # #[cfg(feature = "aasv")]
+    /// # {
+    /// use oboron::{AasvC32, ObtextCodec, Plaintext};
+    /// # let key = oboron::generate_key();
+    /// let ob = AasvC32::new(&key)?;
+    /// let ot = ob.enc_typed(Plaintext::from("hello"))?;
+    /// let pt = ob.dec_typed(&ot)?;
+    /// assert_eq!(&*pt, "hello");
+    /// # }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Passing an obtext where a plaintext is expected no longer compiles:
+    ///
+    /// ```compile_fail
+    /// # use oboron::{AasvC32, ObtextCodec, Plaintext};
+    /// # let key = oboron::generate_key();
+    /// let ob = AasvC32::new(&key).unwrap();
+    /// let ot = ob.enc_typed(Plaintext::from("hello")).unwrap();
+    /// let _ = ob.enc_typed(ot); // expected `Plaintext`, found `Obtext`
+    /// ```
+    #[inline]
+    fn enc_typed(&self, plaintext: Plaintext) -> Result<Obtext, Error> {
+        self.enc(&plaintext).map(Obtext::from)
+    }
+
+    /// Typed variant of [`dec`](Self::dec): takes an [`Obtext`] and returns
+    /// a [`Plaintext`].
+    ///
+    /// The decoded [`Plaintext`] is `#[must_use]` too, so unwrapping the
+    /// result and dropping the value is rejected the same way:
+    ///
+    /// ```compile_fail
+    /// #![deny(unused_must_use)]
+    /// use oboron::{Obtext, ObtextCodec};
+    ///
+    /// fn check(ob: &impl ObtextCodec, ot: &Obtext) {
+    ///     ob.dec_typed(ot).unwrap(); // error: unused `Plaintext` that must be used
+    /// }
+    /// ```
+    #[inline]
+    fn dec_typed(&self, obtext: &Obtext) -> Result<Plaintext, Error> {
+        self.dec(obtext).map(Plaintext::from)
+    }
 }
 
-/// Macro for 32-byte key schemes (aags, apgs, upbc, mock1, mock2)
+/// Macro for 32-byte key schemes (aags, apgs, apgc, upbc, mock1, mock2, mock3, mock4)
 ///
 /// This macro generates a complete ObtextCodec implementation with all overhead eliminated:
 /// - No runtime scheme matching
@@ -63,7 +357,6 @@ macro_rules! impl_codec_32 {
 
             /// Create a new instance from a 64-byte key.
             #[inline]
-            #[cfg(any(feature = "keyless", feature = "bytes-keys"))]
             fn from_bytes_internal(key_bytes: &[u8; 64]) -> Result<Self, Error> {
                 Ok(Self {
                     masterkey: MasterKey::from_bytes(key_bytes)?,
@@ -75,7 +368,7 @@ macro_rules! impl_codec_32 {
             #[cfg(feature = "keyless")]
             pub fn new_keyless() -> Result<Self, Error> {
                 Ok(Self {
-                    masterkey: MasterKey::from_bytes(&HARDCODED_KEY_BYTES)?,
+                    masterkey: MasterKey::from_bytes(crate::keyless::keyless_key())?,
                 })
             }
 
@@ -121,9 +414,7 @@ macro_rules! impl_codec_32 {
         impl ObtextCodec for $name {
             #[inline(always)]
             fn enc(&self, plaintext: &str) -> Result<String, Error> {
-                if plaintext.is_empty() {
-                    return Err(Error::EmptyPlaintext);
-                }
+                crate::error::check_plaintext_len(plaintext, $scheme)?;
 
                 let mut ciphertext = $encrypt_fn(self.masterkey.key(), plaintext.as_bytes())?;
 
@@ -142,8 +433,8 @@ macro_rules! impl_codec_32 {
                 // Decode
                 let mut buffer = decode_bytes(obtext, $encoding)?;
 
-                if buffer.len() < 2 {
-                    return Err(Error::PayloadTooShort);
+                if buffer.len() < crate::dec::MIN_PAYLOAD_LEN {
+                    return Err(Error::TruncatedObtext);
                 }
 
                 // XOR and extract marker
@@ -153,7 +444,7 @@ macro_rules! impl_codec_32 {
 
                 // Validate marker
                 if scheme_marker != $scheme.marker() {
-                    return Err(Error::SchemeMarkerMismatch);
+                    return Err(crate::dec::marker_mismatch($scheme, scheme_marker));
                 }
 
                 buffer.truncate(len - 2);
@@ -186,6 +477,10 @@ macro_rules! impl_codec_32 {
             fn encoding(&self) -> Encoding {
                 $encoding
             }
+
+            fn key_id(&self) -> String {
+                self.masterkey.fingerprint()
+            }
         }
 
         // Add inherent methods that delegate to trait methods
@@ -252,7 +547,6 @@ macro_rules! impl_codec_64 {
 
             /// Create a new instance from a 64-byte key.
             #[inline]
-            #[cfg(any(feature = "keyless", feature = "bytes-keys"))]
             fn from_bytes_internal(key_bytes: &[u8; 64]) -> Result<Self, Error> {
                 Ok(Self {
                     masterkey: MasterKey::from_bytes(key_bytes)?,
@@ -264,7 +558,7 @@ macro_rules! impl_codec_64 {
             #[cfg(feature = "keyless")]
             pub fn new_keyless() -> Result<Self, Error> {
                 Ok(Self {
-                    masterkey: MasterKey::from_bytes(&HARDCODED_KEY_BYTES)?,
+                    masterkey: MasterKey::from_bytes(crate::keyless::keyless_key())?,
                 })
             }
 
@@ -310,9 +604,7 @@ macro_rules! impl_codec_64 {
         impl ObtextCodec for $name {
             #[inline(always)]
             fn enc(&self, plaintext: &str) -> Result<String, Error> {
-                if plaintext.is_empty() {
-                    return Err(Error::EmptyPlaintext);
-                }
+                crate::error::check_plaintext_len(plaintext, $scheme)?;
 
                 let mut ciphertext = $encrypt_fn(self.masterkey.key(), plaintext.as_bytes())?;
 
@@ -331,8 +623,8 @@ macro_rules! impl_codec_64 {
                 // Decode
                 let mut buffer = decode_bytes(obtext, $encoding)?;
 
-                if buffer.len() < 2 {
-                    return Err(Error::PayloadTooShort);
+                if buffer.len() < crate::dec::MIN_PAYLOAD_LEN {
+                    return Err(Error::TruncatedObtext);
                 }
 
                 // XOR and extract marker
@@ -342,7 +634,7 @@ macro_rules! impl_codec_64 {
 
                 // Validate marker
                 if scheme_marker != $scheme.marker() {
-                    return Err(Error::SchemeMarkerMismatch);
+                    return Err(crate::dec::marker_mismatch($scheme, scheme_marker));
                 }
 
                 buffer.truncate(len - 2);
@@ -375,6 +667,10 @@ macro_rules! impl_codec_64 {
             fn encoding(&self) -> Encoding {
                 $encoding
             }
+
+            fn key_id(&self) -> String {
+                self.masterkey.fingerprint()
+            }
         }
 
         impl $name {
@@ -411,33 +707,88 @@ macro_rules! impl_codec_64 {
     };
 }
 
+/// Macro adding `enc_with_nonce` to the typed codecs of a probabilistic
+/// scheme, with `$reuse` documenting the cost of reusing a nonce under it.
+#[cfg(any(feature = "apgs", feature = "apgc", feature = "apsv"))]
+macro_rules! impl_enc_with_nonce {
+    ($encrypt_fn:path, $nonce_size:literal, $reuse:literal, $($name:ident),+) => {
+        $(
+            impl $name {
+                #[doc = concat!(
+                    "Encrypt and encode plaintext with a caller-supplied ",
+                    stringify!($nonce_size),
+                    "-byte nonce instead of a random one, e.g. a counter managed by another \
+                     system.  The output decrypts with [`dec`](Self::dec) like any other.\n\n",
+                    "Use [`enc`](Self::enc) unless the nonce must come from outside: the \
+                     caller becomes responsible for never repeating a nonce under this key.  ",
+                    $reuse,
+                    "\n\n# Errors\n\nReturns [`Error::InvalidNonceLength`] if `nonce` is not ",
+                    stringify!($nonce_size),
+                    " bytes, and any error [`enc`](Self::enc) would return."
+                )]
+                pub fn enc_with_nonce(&self, plaintext: &str, nonce: &[u8]) -> Result<String, Error> {
+                    crate::error::check_plaintext_len(plaintext, self.scheme())?;
+
+                    let mut ciphertext =
+                        $encrypt_fn(self.masterkey.key(), plaintext.as_bytes(), nonce)?;
+
+                    // Append marker and XOR
+                    let marker = self.scheme().marker();
+                    let first_byte = ciphertext[0];
+                    ciphertext.push(marker[0] ^ first_byte);
+                    ciphertext.push(marker[1] ^ first_byte);
+
+                    Ok(encode_bytes(&ciphertext, self.encoding()))
+                }
+            }
+        )+
+    };
+}
+
+#[cfg(feature = "apgs")]
+impl_enc_with_nonce!(
+    crate::encrypt_apgs_with_nonce,
+    12,
+    "AES-GCM-SIV is nonce-misuse resistant: a repeated nonce only reveals \
+     whether two plaintexts are equal, as with the deterministic `aags`.",
+    ApgsC32,
+    ApgsB32,
+    ApgsB64,
+    ApgsHex
+);
+#[cfg(feature = "apgc")]
+impl_enc_with_nonce!(
+    crate::encrypt_apgc_with_nonce,
+    12,
+    "**AES-GCM is not nonce-misuse resistant: a single repeated nonce reveals \
+     the XOR of the two plaintexts and the authentication key, letting an \
+     attacker forge obtexts under this key.**",
+    ApgcC32,
+    ApgcB32,
+    ApgcB64,
+    ApgcHex
+);
+#[cfg(feature = "apsv")]
+impl_enc_with_nonce!(
+    crate::encrypt_apsv_with_nonce,
+    16,
+    "AES-SIV is nonce-misuse resistant: a repeated nonce only reveals \
+     whether two plaintexts are equal, as with the deterministic `aasv`.",
+    ApsvC32,
+    ApsvB32,
+    ApsvB64,
+    ApsvHex
+);
+
 // Helper functions for encoding/decoding with compile-time dispatch
 #[inline(always)]
 fn encode_bytes(bytes: &[u8], encoding: Encoding) -> String {
-    match encoding {
-        Encoding::C32 => crate::base32::BASE32_CROCKFORD.encode(bytes),
-        Encoding::B32 => crate::base32::BASE32_RFC.encode(bytes),
-        Encoding::B64 => data_encoding::BASE64URL_NOPAD.encode(bytes),
-        Encoding::Hex => data_encoding::HEXLOWER.encode(bytes),
-    }
+    crate::encoding::encode(bytes, encoding)
 }
 
 #[inline(always)]
 fn decode_bytes(text: &str, encoding: Encoding) -> Result<Vec<u8>, Error> {
-    match encoding {
-        Encoding::C32 => crate::base32::BASE32_CROCKFORD
-            .decode(text.as_bytes())
-            .map_err(|_| Error::InvalidC32),
-        Encoding::B32 => crate::base32::BASE32_RFC
-            .decode(text.as_bytes())
-            .map_err(|_| Error::InvalidB32),
-        Encoding::B64 => data_encoding::BASE64URL_NOPAD
-            .decode(text.as_bytes())
-            .map_err(|_| Error::InvalidB64),
-        Encoding::Hex => data_encoding::HEXLOWER
-            .decode(text.as_bytes())
-            .map_err(|_| Error::InvalidHex),
-    }
+    crate::encoding::decode(text, encoding)
 }
 
 // Generate all scheme+encoding combinations
@@ -568,6 +919,48 @@ impl_codec_32!(
     apgs
 );
 
+// apgc variants (32-byte key)
+#[cfg(feature = "apgc")]
+impl_codec_32!(
+    ApgcC32,
+    Scheme::Apgc,
+    Encoding::C32,
+    "apgc.c32",
+    crate::encrypt_apgc,
+    crate::decrypt_apgc,
+    apgc
+);
+#[cfg(feature = "apgc")]
+impl_codec_32!(
+    ApgcB32,
+    Scheme::Apgc,
+    Encoding::B32,
+    "apgc.b32",
+    crate::encrypt_apgc,
+    crate::decrypt_apgc,
+    apgc
+);
+#[cfg(feature = "apgc")]
+impl_codec_32!(
+    ApgcB64,
+    Scheme::Apgc,
+    Encoding::B64,
+    "apgc.b64",
+    crate::encrypt_apgc,
+    crate::decrypt_apgc,
+    apgc
+);
+#[cfg(feature = "apgc")]
+impl_codec_32!(
+    ApgcHex,
+    Scheme::Apgc,
+    Encoding::Hex,
+    "apgc.hex",
+    crate::encrypt_apgc,
+    crate::decrypt_apgc,
+    apgc
+);
+
 // apsv variants (64-byte key)
 #[cfg(feature = "apsv")]
 impl_codec_64!(
@@ -736,6 +1129,90 @@ impl_codec_32!(
     mock2
 );
 
+// mock3 variants (32-byte key)
+#[cfg(feature = "mock")]
+impl_codec_32!(
+    Mock3C32,
+    Scheme::Mock3,
+    Encoding::C32,
+    "mock3.c32",
+    crate::encrypt_mock3,
+    crate::decrypt_mock3,
+    mock3
+);
+#[cfg(feature = "mock")]
+impl_codec_32!(
+    Mock3B32,
+    Scheme::Mock3,
+    Encoding::B32,
+    "mock3.b32",
+    crate::encrypt_mock3,
+    crate::decrypt_mock3,
+    mock3
+);
+#[cfg(feature = "mock")]
+impl_codec_32!(
+    Mock3B64,
+    Scheme::Mock3,
+    Encoding::B64,
+    "mock3.b64",
+    crate::encrypt_mock3,
+    crate::decrypt_mock3,
+    mock3
+);
+#[cfg(feature = "mock")]
+impl_codec_32!(
+    Mock3Hex,
+    Scheme::Mock3,
+    Encoding::Hex,
+    "mock3.hex",
+    crate::encrypt_mock3,
+    crate::decrypt_mock3,
+    mock3
+);
+
+// mock4 variants (32-byte key)
+#[cfg(feature = "mock")]
+impl_codec_32!(
+    Mock4C32,
+    Scheme::Mock4,
+    Encoding::C32,
+    "mock4.c32",
+    crate::encrypt_mock4,
+    crate::decrypt_mock4,
+    mock4
+);
+#[cfg(feature = "mock")]
+impl_codec_32!(
+    Mock4B32,
+    Scheme::Mock4,
+    Encoding::B32,
+    "mock4.b32",
+    crate::encrypt_mock4,
+    crate::decrypt_mock4,
+    mock4
+);
+#[cfg(feature = "mock")]
+impl_codec_32!(
+    Mock4B64,
+    Scheme::Mock4,
+    Encoding::B64,
+    "mock4.b64",
+    crate::encrypt_mock4,
+    crate::decrypt_mock4,
+    mock4
+);
+#[cfg(feature = "mock")]
+impl_codec_32!(
+    Mock4Hex,
+    Scheme::Mock4,
+    Encoding::Hex,
+    "mock4.hex",
+    crate::encrypt_mock4,
+    crate::decrypt_mock4,
+    mock4
+);
+
 /// Type-erased ObtextCodec encoder that can hold any scheme+encoding combination.
 ///
 /// This enum allows for runtime scheme selection without heap allocation.
@@ -758,6 +1235,14 @@ pub enum ObAny {
     ApgsB64(ApgsB64),
     #[cfg(feature = "apgs")]
     ApgsHex(ApgsHex),
+    #[cfg(feature = "apgc")]
+    ApgcC32(ApgcC32),
+    #[cfg(feature = "apgc")]
+    ApgcB32(ApgcB32),
+    #[cfg(feature = "apgc")]
+    ApgcB64(ApgcB64),
+    #[cfg(feature = "apgc")]
+    ApgcHex(ApgcHex),
     #[cfg(feature = "aasv")]
     AasvC32(AasvC32),
     #[cfg(feature = "aasv")]
@@ -788,17 +1273,33 @@ pub enum ObAny {
     #[cfg(feature = "mock")]
     Mock1B32(Mock1B32),
     #[cfg(feature = "mock")]
-    Mock1Hex(Mock1Hex),
-    #[cfg(feature = "mock")]
     Mock1B64(Mock1B64),
     #[cfg(feature = "mock")]
+    Mock1Hex(Mock1Hex),
+    #[cfg(feature = "mock")]
     Mock2C32(Mock2C32),
     #[cfg(feature = "mock")]
     Mock2B32(Mock2B32),
     #[cfg(feature = "mock")]
+    Mock2B64(Mock2B64),
+    #[cfg(feature = "mock")]
     Mock2Hex(Mock2Hex),
     #[cfg(feature = "mock")]
-    Mock2B64(Mock2B64),
+    Mock3C32(Mock3C32),
+    #[cfg(feature = "mock")]
+    Mock3B32(Mock3B32),
+    #[cfg(feature = "mock")]
+    Mock3B64(Mock3B64),
+    #[cfg(feature = "mock")]
+    Mock3Hex(Mock3Hex),
+    #[cfg(feature = "mock")]
+    Mock4C32(Mock4C32),
+    #[cfg(feature = "mock")]
+    Mock4B32(Mock4B32),
+    #[cfg(feature = "mock")]
+    Mock4B64(Mock4B64),
+    #[cfg(feature = "mock")]
+    Mock4Hex(Mock4Hex),
 }
 
 // Macro to delegate ObtextCodec methods to the inner type
@@ -822,6 +1323,14 @@ macro_rules! delegate_to_inner {
                 ObAny::ApgsB64(ob) => ob.$method($($arg),*),
                 #[cfg(feature = "apgs")]
                 ObAny::ApgsHex(ob) => ob.$method($($arg),*),
+                #[cfg(feature = "apgc")]
+                ObAny::ApgcC32(ob) => ob.$method($($arg),*),
+                #[cfg(feature = "apgc")]
+                ObAny::ApgcB32(ob) => ob.$method($($arg),*),
+                #[cfg(feature = "apgc")]
+                ObAny::ApgcB64(ob) => ob.$method($($arg),*),
+                #[cfg(feature = "apgc")]
+                ObAny::ApgcHex(ob) => ob.$method($($arg),*),
                 #[cfg(feature = "aasv")]
                 ObAny::AasvC32(ob) => ob.$method($($arg),*),
                 #[cfg(feature = "aasv")]
@@ -863,6 +1372,22 @@ macro_rules! delegate_to_inner {
                 ObAny::Mock2B64(ob) => ob.$method($($arg),*),
                 #[cfg(feature = "mock")]
                 ObAny::Mock2Hex(ob) => ob.$method($($arg),*),
+                #[cfg(feature = "mock")]
+                ObAny::Mock3C32(ob) => ob.$method($($arg),*),
+                #[cfg(feature = "mock")]
+                ObAny::Mock3B32(ob) => ob.$method($($arg),*),
+                #[cfg(feature = "mock")]
+                ObAny::Mock3B64(ob) => ob.$method($($arg),*),
+                #[cfg(feature = "mock")]
+                ObAny::Mock3Hex(ob) => ob.$method($($arg),*),
+                #[cfg(feature = "mock")]
+                ObAny::Mock4C32(ob) => ob.$method($($arg),*),
+                #[cfg(feature = "mock")]
+                ObAny::Mock4B32(ob) => ob.$method($($arg),*),
+                #[cfg(feature = "mock")]
+                ObAny::Mock4B64(ob) => ob.$method($($arg),*),
+                #[cfg(feature = "mock")]
+                ObAny::Mock4Hex(ob) => ob.$method($($arg),*),
             }
         }
     };
@@ -874,6 +1399,7 @@ impl ObtextCodec for ObAny {
     delegate_to_inner!(fn format(&self) -> Format);
     delegate_to_inner!(fn scheme(&self) -> Scheme);
     delegate_to_inner!(fn encoding(&self) -> Encoding);
+    delegate_to_inner!(fn key_id(&self) -> String);
 }
 
 // Inherent constructors for ObAny
@@ -905,6 +1431,7 @@ impl ObAny {
             feature = "aasv"
         )))]
         return Ok(ObAny::ApsvC32(ApsvC32::new(key)?));
+        #[cfg(feature = "apgc")]
         #[cfg(not(any(
             feature = "mock",
             feature = "upbc",
@@ -913,6 +1440,16 @@ impl ObAny {
             feature = "aasv",
             feature = "apsv",
         )))]
+        return Ok(ObAny::ApgcC32(ApgcC32::new(key)?));
+        #[cfg(not(any(
+            feature = "mock",
+            feature = "upbc",
+            feature = "aags",
+            feature = "apgs",
+            feature = "aasv",
+            feature = "apsv",
+            feature = "apgc",
+        )))]
         compile_error!("At least one oboron scheme must be enabled");
     }
 
@@ -957,6 +1494,18 @@ impl ObAny {
         return Ok(ObAny::ApsvC32(ApsvC32 {
             masterkey: MasterKey::from_bytes(key_bytes)?,
         }));
+        #[cfg(feature = "apgc")]
+        #[cfg(not(any(
+            feature = "aags",
+            feature = "aasv",
+            feature = "apgs",
+            feature = "apsv",
+            feature = "mock",
+            feature = "upbc",
+        )))]
+        return Ok(ObAny::ApgcC32(ApgcC32 {
+            masterkey: MasterKey::from_bytes(key_bytes)?,
+        }));
         #[cfg(not(any(
             feature = "aags",
             feature = "aasv",
@@ -964,6 +1513,7 @@ impl ObAny {
             feature = "apsv",
             feature = "mock",
             feature = "upbc",
+            feature = "apgc",
         )))]
         compile_error!("At least one oboron scheme must be enabled");
     }
@@ -993,6 +1543,16 @@ impl ObAny {
             feature = "aasv"
         )))]
         return Ok(ObAny::ApsvC32(ApsvC32::from_hex_key(key_hex)?));
+        #[cfg(feature = "apgc")]
+        #[cfg(not(any(
+            feature = "mock",
+            feature = "upbc",
+            feature = "aags",
+            feature = "apgs",
+            feature = "aasv",
+            feature = "apsv",
+        )))]
+        return Ok(ObAny::ApgcC32(ApgcC32::from_hex_key(key_hex)?));
         #[cfg(not(any(
             feature = "mock",
             feature = "upbc",
@@ -1000,6 +1560,7 @@ impl ObAny {
             feature = "apgs",
             feature = "aasv",
             feature = "apsv",
+            feature = "apgc",
         )))]
         compile_error!("At least one oboron scheme must be enabled");
     }
@@ -1011,27 +1572,27 @@ impl ObAny {
     pub fn new_keyless() -> Result<Self, Error> {
         #[cfg(feature = "mock")]
         return Ok(ObAny::Mock1C32(Mock1C32 {
-            masterkey: MasterKey::from_bytes(&HARDCODED_KEY_BYTES)?,
+            masterkey: MasterKey::from_bytes(crate::keyless::keyless_key())?,
         }));
         #[cfg(feature = "upbc")]
         #[cfg(not(any(feature = "mock")))]
         return Ok(ObAny::UpbcC32(UpbcC32 {
-            masterkey: MasterKey::from_bytes(&HARDCODED_KEY_BYTES)?,
+            masterkey: MasterKey::from_bytes(crate::keyless::keyless_key())?,
         }));
         #[cfg(feature = "aags")]
         #[cfg(not(any(feature = "mock", feature = "upbc")))]
         return Ok(ObAny::AagsC32(AagsC32 {
-            masterkey: MasterKey::from_bytes(&HARDCODED_KEY_BYTES)?,
+            masterkey: MasterKey::from_bytes(crate::keyless::keyless_key())?,
         }));
         #[cfg(feature = "apgs")]
         #[cfg(not(any(feature = "mock", feature = "upbc", feature = "aags")))]
         return Ok(ObAny::ApgsC32(ApgsC32 {
-            masterkey: MasterKey::from_bytes(&HARDCODED_KEY_BYTES)?,
+            masterkey: MasterKey::from_bytes(crate::keyless::keyless_key())?,
         }));
         #[cfg(feature = "aasv")]
         #[cfg(not(any(feature = "mock", feature = "upbc", feature = "aags", feature = "apgs")))]
         return Ok(ObAny::AasvC32(AasvC32 {
-            masterkey: MasterKey::from_bytes(&HARDCODED_KEY_BYTES)?,
+            masterkey: MasterKey::from_bytes(crate::keyless::keyless_key())?,
         }));
         #[cfg(feature = "apsv")]
         #[cfg(not(any(
@@ -1042,8 +1603,9 @@ impl ObAny {
             feature = "aasv"
         )))]
         return Ok(ObAny::ApsvC32(ApsvC32 {
-            masterkey: MasterKey::from_bytes(&HARDCODED_KEY_BYTES)?,
+            masterkey: MasterKey::from_bytes(crate::keyless::keyless_key())?,
         }));
+        #[cfg(feature = "apgc")]
         #[cfg(not(any(
             feature = "mock",
             feature = "upbc",
@@ -1052,6 +1614,18 @@ impl ObAny {
             feature = "aasv",
             feature = "apsv",
         )))]
+        return Ok(ObAny::ApgcC32(ApgcC32 {
+            masterkey: MasterKey::from_bytes(crate::keyless::keyless_key())?,
+        }));
+        #[cfg(not(any(
+            feature = "mock",
+            feature = "upbc",
+            feature = "aags",
+            feature = "apgs",
+            feature = "aasv",
+            feature = "apsv",
+            feature = "apgc",
+        )))]
         compile_error!("At least one oboron scheme must be enabled");
     }
 }
@@ -1122,6 +1696,14 @@ pub fn new_with_format(format: Format, key: &str) -> Result<ObAny, Error> {
         (Scheme::Apgs, Encoding::B64) => Ok(ObAny::ApgsB64(ApgsB64::new(key)?)),
         #[cfg(feature = "apgs")]
         (Scheme::Apgs, Encoding::Hex) => Ok(ObAny::ApgsHex(ApgsHex::new(key)?)),
+        #[cfg(feature = "apgc")]
+        (Scheme::Apgc, Encoding::C32) => Ok(ObAny::ApgcC32(ApgcC32::new(key)?)),
+        #[cfg(feature = "apgc")]
+        (Scheme::Apgc, Encoding::B32) => Ok(ObAny::ApgcB32(ApgcB32::new(key)?)),
+        #[cfg(feature = "apgc")]
+        (Scheme::Apgc, Encoding::B64) => Ok(ObAny::ApgcB64(ApgcB64::new(key)?)),
+        #[cfg(feature = "apgc")]
+        (Scheme::Apgc, Encoding::Hex) => Ok(ObAny::ApgcHex(ApgcHex::new(key)?)),
         #[cfg(feature = "aasv")]
         (Scheme::Aasv, Encoding::C32) => Ok(ObAny::AasvC32(AasvC32::new(key)?)),
         #[cfg(feature = "aasv")]
@@ -1155,13 +1737,31 @@ pub fn new_with_format(format: Format, key: &str) -> Result<ObAny, Error> {
         (Scheme::Mock2, Encoding::B64) => Ok(ObAny::Mock2B64(Mock2B64::new(key)?)),
         #[cfg(feature = "mock")]
         (Scheme::Mock2, Encoding::Hex) => Ok(ObAny::Mock2Hex(Mock2Hex::new(key)?)),
+        #[cfg(feature = "mock")]
+        (Scheme::Mock3, Encoding::C32) => Ok(ObAny::Mock3C32(Mock3C32::new(key)?)),
+        #[cfg(feature = "mock")]
+        (Scheme::Mock3, Encoding::B32) => Ok(ObAny::Mock3B32(Mock3B32::new(key)?)),
+        #[cfg(feature = "mock")]
+        (Scheme::Mock3, Encoding::B64) => Ok(ObAny::Mock3B64(Mock3B64::new(key)?)),
+        #[cfg(feature = "mock")]
+        (Scheme::Mock3, Encoding::Hex) => Ok(ObAny::Mock3Hex(Mock3Hex::new(key)?)),
+        #[cfg(feature = "mock")]
+        (Scheme::Mock4, Encoding::C32) => Ok(ObAny::Mock4C32(Mock4C32::new(key)?)),
+        #[cfg(feature = "mock")]
+        (Scheme::Mock4, Encoding::B32) => Ok(ObAny::Mock4B32(Mock4B32::new(key)?)),
+        #[cfg(feature = "mock")]
+        (Scheme::Mock4, Encoding::B64) => Ok(ObAny::Mock4B64(Mock4B64::new(key)?)),
+        #[cfg(feature = "mock")]
+        (Scheme::Mock4, Encoding::Hex) => Ok(ObAny::Mock4Hex(Mock4Hex::new(key)?)),
         #[allow(unreachable_patterns)]
         _ => Err(Error::UnknownScheme),
     }
 }
 
-#[cfg(any(feature = "keyless", feature = "bytes-keys", feature = "hex-keys"))]
-fn from_bytes_with_format_internal(format: Format, key_bytes: &[u8; 64]) -> Result<ObAny, Error> {
+pub(crate) fn from_bytes_with_format_internal(
+    format: Format,
+    key_bytes: &[u8; 64],
+) -> Result<ObAny, Error> {
     match (format.scheme(), format.encoding()) {
         #[cfg(feature = "upbc")]
         (Scheme::Upbc, Encoding::C32) => {
@@ -1211,6 +1811,22 @@ fn from_bytes_with_format_internal(format: Format, key_bytes: &[u8; 64]) -> Resu
         (Scheme::Apgs, Encoding::Hex) => {
             Ok(ObAny::ApgsHex(ApgsHex::from_bytes_internal(key_bytes)?))
         }
+        #[cfg(feature = "apgc")]
+        (Scheme::Apgc, Encoding::C32) => {
+            Ok(ObAny::ApgcC32(ApgcC32::from_bytes_internal(key_bytes)?))
+        }
+        #[cfg(feature = "apgc")]
+        (Scheme::Apgc, Encoding::B32) => {
+            Ok(ObAny::ApgcB32(ApgcB32::from_bytes_internal(key_bytes)?))
+        }
+        #[cfg(feature = "apgc")]
+        (Scheme::Apgc, Encoding::B64) => {
+            Ok(ObAny::ApgcB64(ApgcB64::from_bytes_internal(key_bytes)?))
+        }
+        #[cfg(feature = "apgc")]
+        (Scheme::Apgc, Encoding::Hex) => {
+            Ok(ObAny::ApgcHex(ApgcHex::from_bytes_internal(key_bytes)?))
+        }
         #[cfg(feature = "aasv")]
         (Scheme::Aasv, Encoding::C32) => {
             Ok(ObAny::AasvC32(AasvC32::from_bytes_internal(key_bytes)?))
@@ -1276,16 +1892,51 @@ fn from_bytes_with_format_internal(format: Format, key_bytes: &[u8; 64]) -> Resu
         (Scheme::Mock2, Encoding::Hex) => {
             Ok(ObAny::Mock2Hex(Mock2Hex::from_bytes_internal(key_bytes)?))
         }
+        #[cfg(feature = "mock")]
+        (Scheme::Mock3, Encoding::C32) => {
+            Ok(ObAny::Mock3C32(Mock3C32::from_bytes_internal(key_bytes)?))
+        }
+        #[cfg(feature = "mock")]
+        (Scheme::Mock3, Encoding::B32) => {
+            Ok(ObAny::Mock3B32(Mock3B32::from_bytes_internal(key_bytes)?))
+        }
+        #[cfg(feature = "mock")]
+        (Scheme::Mock3, Encoding::B64) => {
+            Ok(ObAny::Mock3B64(Mock3B64::from_bytes_internal(key_bytes)?))
+        }
+        #[cfg(feature = "mock")]
+        (Scheme::Mock3, Encoding::Hex) => {
+            Ok(ObAny::Mock3Hex(Mock3Hex::from_bytes_internal(key_bytes)?))
+        }
+        #[cfg(feature = "mock")]
+        (Scheme::Mock4, Encoding::C32) => {
+            Ok(ObAny::Mock4C32(Mock4C32::from_bytes_internal(key_bytes)?))
+        }
+        #[cfg(feature = "mock")]
+        (Scheme::Mock4, Encoding::B32) => {
+            Ok(ObAny::Mock4B32(Mock4B32::from_bytes_internal(key_bytes)?))
+        }
+        #[cfg(feature = "mock")]
+        (Scheme::Mock4, Encoding::B64) => {
+            Ok(ObAny::Mock4B64(Mock4B64::from_bytes_internal(key_bytes)?))
+        }
+        #[cfg(feature = "mock")]
+        (Scheme::Mock4, Encoding::Hex) => {
+            Ok(ObAny::Mock4Hex(Mock4Hex::from_bytes_internal(key_bytes)?))
+        }
         #[allow(unreachable_patterns)]
-        _ => Err(Error::UnknownScheme),
+        (_, Encoding::C32 | Encoding::B32 | Encoding::B64 | Encoding::Hex) => {
+            Err(Error::UnknownScheme)
+        }
+        // hexu and custom alphabets have no typed codecs
+        _ => Err(Error::UnsupportedFormat { format }),
     }
 }
 
 #[cfg(feature = "hex-keys")]
 fn from_hex_key_with_format_internal(format: Format, key_hex: &str) -> Result<ObAny, Error> {
-    let key_vec = hex::decode(key_hex)?;
-    let key_arr: [u8; 64] = key_vec.try_into().map_err(|_| Error::InvalidKeyLength)?;
-    from_bytes_with_format_internal(format, &key_arr)
+    let masterkey = MasterKey::from_hex(key_hex)?;
+    from_bytes_with_format_internal(format, masterkey.key())
 }
 
 /// Create an encoder from a format string and raw bytes.
@@ -1318,13 +1969,13 @@ pub fn from_bytes_with_format(format: Format, key_bytes: &[u8; 64]) -> Result<Ob
 #[cfg(feature = "keyless")]
 pub fn new_keyless(fmt: &str) -> Result<ObAny, Error> {
     let format = Format::from_str(fmt)?;
-    from_bytes_with_format_internal(format, &HARDCODED_KEY_BYTES)
+    from_bytes_with_format_internal(format, crate::keyless::keyless_key())
 }
 
 /// Create an encoder from a pre-parsed Format using the hardcoded key (testing only).
 #[cfg(feature = "keyless")]
 pub fn new_keyless_with_format(format: Format) -> Result<ObAny, Error> {
-    from_bytes_with_format_internal(format, &HARDCODED_KEY_BYTES)
+    from_bytes_with_format_internal(format, crate::keyless::keyless_key())
 }
 
 #[cfg(test)]
@@ -1344,6 +1995,8 @@ mod tests {
             Scheme::Aags,
             #[cfg(feature = "apgs")]
             Scheme::Apgs,
+            #[cfg(feature = "apgc")]
+            Scheme::Apgc,
             #[cfg(feature = "aasv")]
             Scheme::Aasv,
             #[cfg(feature = "apsv")]
@@ -1353,6 +2006,10 @@ mod tests {
             Scheme::Mock1,
             #[cfg(feature = "mock")]
             Scheme::Mock2,
+            #[cfg(feature = "mock")]
+            Scheme::Mock3,
+            #[cfg(feature = "mock")]
+            Scheme::Mock4,
         ];
 
         // Define all encodings
@@ -1395,6 +2052,8 @@ mod tests {
 
         // Define all schemes
         let schemes = vec![
+            Scheme::Mock4,
+            Scheme::Mock3,
             Scheme::Mock2,
             Scheme::Mock1,
             #[cfg(feature = "upbc")]
@@ -1403,6 +2062,8 @@ mod tests {
             Scheme::Aags,
             #[cfg(feature = "apgs")]
             Scheme::Apgs,
+            #[cfg(feature = "apgc")]
+            Scheme::Apgc,
             #[cfg(feature = "aasv")]
             Scheme::Aasv,
             #[cfg(feature = "apsv")]
@@ -1447,6 +2108,8 @@ mod tests {
 
         // Define all schemes
         let schemes = vec![
+            Scheme::Mock4,
+            Scheme::Mock3,
             Scheme::Mock2,
             Scheme::Mock1,
             #[cfg(feature = "aags")]
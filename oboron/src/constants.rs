@@ -18,6 +18,12 @@ pub const HARDCODED_SECRET_BYTES: [u8; 32] = [
     0x31, 0x00, 0x60, 0x46, 0x8e, 0x8b, 0x46, 0x54, 0x55, 0xa4, 0x15, 0x17, 0x4e, 0xa6, 0xe9, 0x66,
 ];
 
+/// Environment variable holding the base64 key for the `from_env`
+/// constructors, as for the `ob` CLI.
+pub const KEY_ENV_VAR: &str = "OBORON_KEY";
+/// Environment variable holding the format for [`Ob::from_env`](crate::Ob::from_env).
+pub const FORMAT_ENV_VAR: &str = "OBORON_FORMAT";
+
 // Scheme marker size (2 bytes)
 pub const SCHEME_MARKER_SIZE: usize = 2;
 
@@ -45,6 +51,7 @@ pub const SCHEME_MARKER_SIZE: usize = 2;
 //   - 0001 (1): CBC
 //   - 0010 (2): GCM-SIV
 //   - 0011 (3): SIV
+//   - 0100 (4): GCM
 
 // Helper function to construct scheme marker
 const fn make_marker(tier: u8, properties: u8, algorithm: u8) -> [u8; 2] {
@@ -62,6 +69,9 @@ pub const AAGS_MARKER: [u8; 2] = make_marker(1, 1, 2);
 // apgs: tier=001, properties=0000 (probabilistic), algorithm=0010 (GCM-SIV)
 #[cfg(feature = "apgs")]
 pub const APGS_MARKER: [u8; 2] = make_marker(1, 0, 2);
+// apgc: tier=001, properties=0000 (probabilistic), algorithm=0100 (GCM)
+#[cfg(feature = "apgc")]
+pub const APGC_MARKER: [u8; 2] = make_marker(1, 0, 4);
 
 // aasv: tier=001, properties=0001 (det/avalanche), algorithm=0011 (SIV)
 #[cfg(feature = "aasv")]
@@ -93,6 +103,14 @@ pub const MOCK1_MARKER: [u8; 2] = make_marker(0, 4, 15);
 #[cfg(feature = "mock")]
 pub const MOCK2_MARKER: [u8; 2] = make_marker(0, 4, 14);
 
+// mock3: tier=000, properties=0100 (det/non-ref), algorithm=1101 (keyed substitution)
+#[cfg(feature = "mock")]
+pub const MOCK3_MARKER: [u8; 2] = make_marker(0, 4, 13);
+
+// mock4: tier=000, properties=0100 (det/non-ref), algorithm=1100 (word-only substitution)
+#[cfg(feature = "mock")]
+pub const MOCK4_MARKER: [u8; 2] = make_marker(0, 4, 12);
+
 // Tier zmock - Z-tier Testing (non-encrypted)
 // -------------------------------------------
 
@@ -100,6 +118,31 @@ pub const MOCK2_MARKER: [u8; 2] = make_marker(0, 4, 14);
 #[cfg(feature = "zmock")]
 pub const ZMOCK1_MARKER: [u8; 2] = make_marker(7, 4, 15);
 
+// Test double - reported by `NullOb`, never produced by any codec
+// ----------------------------------------------------------------
+// null: tier=000, properties=0000, algorithm=0000 (none)
+#[cfg(feature = "testing")]
+pub const NULL_MARKER: [u8; 2] = make_marker(0, 0, 0);
+
+// Markers reserved by built-in schemes, whether or not their feature is
+// enabled.  Custom schemes may not reuse them; tiers 3-5 are unassigned.
+#[cfg(feature = "custom-schemes")]
+pub(crate) const BUILTIN_MARKERS: &[[u8; 2]] = &[
+    make_marker(1, 1, 2),  // aags
+    make_marker(1, 0, 2),  // apgs
+    make_marker(1, 0, 4),  // apgc
+    make_marker(1, 1, 3),  // aasv
+    make_marker(1, 0, 3),  // apsv
+    make_marker(2, 0, 1),  // upbc
+    make_marker(6, 2, 1),  // zrbcx
+    make_marker(0, 4, 15), // mock1
+    make_marker(0, 4, 14), // mock2
+    make_marker(0, 4, 13), // mock3
+    make_marker(0, 4, 12), // mock4
+    make_marker(7, 4, 15), // zmock1
+    make_marker(0, 0, 0),  // null (NullOb)
+];
+
 // Format identifiers
 //
 #[cfg(feature = "aags")]
@@ -117,6 +160,13 @@ pub(crate) mod apgs_constants {
     pub const APGS_B64_STR: &str = "apgs.b64";
     pub const APGS_HEX_STR: &str = "apgs.hex";
 }
+#[cfg(feature = "apgc")]
+pub(crate) mod apgc_constants {
+    pub const APGC_C32_STR: &str = "apgc.c32";
+    pub const APGC_B32_STR: &str = "apgc.b32";
+    pub const APGC_B64_STR: &str = "apgc.b64";
+    pub const APGC_HEX_STR: &str = "apgc.hex";
+}
 
 #[cfg(feature = "aasv")]
 pub(crate) mod aasv_constants {
@@ -160,6 +210,14 @@ pub(crate) mod mock_constants {
     pub const MOCK2_B64_STR: &str = "mock2.b64";
     pub const MOCK2_C32_STR: &str = "mock2.c32";
     pub const MOCK2_HEX_STR: &str = "mock2.hex";
+    pub const MOCK3_B32_STR: &str = "mock3.b32";
+    pub const MOCK3_B64_STR: &str = "mock3.b64";
+    pub const MOCK3_C32_STR: &str = "mock3.c32";
+    pub const MOCK3_HEX_STR: &str = "mock3.hex";
+    pub const MOCK4_B32_STR: &str = "mock4.b32";
+    pub const MOCK4_B64_STR: &str = "mock4.b64";
+    pub const MOCK4_C32_STR: &str = "mock4.c32";
+    pub const MOCK4_HEX_STR: &str = "mock4.hex";
 }
 
 #[cfg(feature = "zmock")]
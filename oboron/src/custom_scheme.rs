@@ -0,0 +1,233 @@
+//! Application-defined schemes plugged into oboron's encoding and detection.
+//!
+//! An application implements [`SchemeImpl`] for its cipher and registers it
+//! once at startup with [`register_scheme`].  The scheme is then usable
+//! anywhere a built-in scheme is: in format strings (`"<name>.<encoding>"`),
+//! with [`Ob`](crate::Ob) and [`Omnib`](crate::Omnib), and in `autodec`,
+//! which dispatches on the scheme's 2-byte marker after the built-ins.
+//!
+//! Registrations are process-wide and permanent.  Built-in schemes are
+//! unaffected: a custom scheme can never shadow a built-in name or marker.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use oboron::{register_scheme, Error, Omnib, SchemeImpl};
+//!
+//! /// Toy scheme: XOR with the first key byte (NOT secure).
+//! struct Xor;
+//!
+//! impl SchemeImpl for Xor {
+//!     fn name(&self) -> &'static str {
+//!         "docxor"
+//!     }
+//!     fn marker(&self) -> [u8; 2] {
+//!         [0x03, 0x01]
+//!     }
+//!     fn is_deterministic(&self) -> bool {
+//!         true
+//!     }
+//!     fn encrypt(&self, key: &[u8; 64], plaintext: &[u8]) -> Result<Vec<u8>, Error> {
+//!         Ok(plaintext.iter().map(|b| b ^ key[0]).collect())
+//!     }
+//!     fn decrypt(&self, key: &[u8; 64], data: &[u8]) -> Result<Vec<u8>, Error> {
+//!         Ok(data.iter().map(|b| b ^ key[0]).collect())
+//!     }
+//! }
+//!
+//! register_scheme(Box::new(Xor))?;
+//!
+//! let omb = Omnib::new(&oboron::generate_key())?;
+//! let ot = omb.enc("hello", "docxor.c32")?;
+//! assert_eq!(omb.autodec(&ot)?, "hello");
+//! # Ok::<(), oboron::Error>(())
+//! ```
+
+#![cfg(feature = "custom-schemes")]
+
+use crate::{constants::BUILTIN_MARKERS, scheme, Error, Scheme};
+use once_cell::sync::Lazy;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::sync::RwLock;
+
+/// An application-defined encryption scheme.
+///
+/// Implementations receive the full 64-byte master key, like the built-in
+/// schemes, and are responsible for deriving whatever subkeys they need.
+/// Obtext encoding and scheme-marker handling are done by oboron.
+pub trait SchemeImpl: Send + Sync + 'static {
+    /// Scheme name used in format strings, e.g. `"xor"` for `"xor.c32"`.
+    /// Must be non-empty lowercase ASCII alphanumerics.
+    fn name(&self) -> &'static str;
+
+    /// 2-byte scheme marker appended to the ciphertext.  Must not collide
+    /// with any built-in or previously registered marker; using a tier
+    /// (low 3 bits of the first byte) of 3, 4 or 5 avoids built-ins.
+    fn marker(&self) -> [u8; 2];
+
+    /// Whether the same plaintext always produces the same ciphertext.
+    fn is_deterministic(&self) -> bool;
+
+    /// Bytes of per-call nonce or IV carried in the ciphertext, reported by
+    /// [`Scheme::nonce_len`].  Defaults to 0.
+    fn nonce_len(&self) -> usize {
+        0
+    }
+
+    /// Encrypt plaintext bytes.  The result must be non-empty.
+    fn encrypt(&self, key: &[u8; 64], plaintext: &[u8]) -> Result<Vec<u8>, Error>;
+
+    /// Decrypt ciphertext bytes produced by [`encrypt`](Self::encrypt).
+    fn decrypt(&self, key: &[u8; 64], data: &[u8]) -> Result<Vec<u8>, Error>;
+}
+
+/// Handle to a registered [`SchemeImpl`], carried by [`Scheme::Custom`].
+#[derive(Clone, Copy)]
+pub struct CustomScheme(&'static dyn SchemeImpl);
+
+impl CustomScheme {
+    #[inline]
+    pub(crate) fn imp(&self) -> &'static dyn SchemeImpl {
+        self.0
+    }
+}
+
+impl PartialEq for CustomScheme {
+    fn eq(&self, other: &Self) -> bool {
+        // Markers are unique among registered schemes
+        self.0.marker() == other.0.marker()
+    }
+}
+
+impl Eq for CustomScheme {}
+
+impl Hash for CustomScheme {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.marker().hash(state);
+    }
+}
+
+impl fmt::Debug for CustomScheme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CustomScheme")
+            .field("name", &self.0.name())
+            .field("marker", &self.0.marker())
+            .finish()
+    }
+}
+
+static REGISTRY: Lazy<RwLock<Vec<CustomScheme>>> = Lazy::new(|| RwLock::new(Vec::new()));
+
+/// Register an application-defined scheme, returning its [`Scheme`].
+///
+/// # Errors
+///
+/// Returns [`Error::InvalidScheme`] if the name is empty or not lowercase
+/// alphanumeric, and [`Error::SchemeConflict`] if the name or marker is
+/// already used by a built-in (whether or not its feature is enabled) or a
+/// registered scheme.
+pub fn register_scheme(imp: Box<dyn SchemeImpl>) -> Result<Scheme, Error> {
+    let name = imp.name();
+    let marker = imp.marker();
+    let valid_name = !name.is_empty()
+        && name
+            .bytes()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit());
+    if !valid_name {
+        return Err(Error::InvalidScheme);
+    }
+
+    let mut registry = REGISTRY.write().unwrap_or_else(|e| e.into_inner());
+    let conflict = scheme::is_builtin_name(name)
+        || BUILTIN_MARKERS.contains(&marker)
+        || registry
+            .iter()
+            .any(|c| c.0.name() == name || c.0.marker() == marker);
+    if conflict {
+        return Err(Error::SchemeConflict { scheme: name });
+    }
+
+    let custom = CustomScheme(Box::leak(imp));
+    registry.push(custom);
+    Ok(Scheme::Custom(custom))
+}
+
+/// Look up a registered scheme by name.
+pub(crate) fn by_name(name: &str) -> Option<Scheme> {
+    let registry = REGISTRY.read().unwrap_or_else(|e| e.into_inner());
+    registry
+        .iter()
+        .find(|c| c.0.name() == name)
+        .map(|&c| Scheme::Custom(c))
+}
+
+/// Look up a registered scheme by marker.
+pub(crate) fn by_marker(marker: [u8; 2]) -> Option<CustomScheme> {
+    let registry = REGISTRY.read().unwrap_or_else(|e| e.into_inner());
+    registry.iter().find(|c| c.0.marker() == marker).copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Named(&'static str, [u8; 2]);
+
+    impl SchemeImpl for Named {
+        fn name(&self) -> &'static str {
+            self.0
+        }
+        fn marker(&self) -> [u8; 2] {
+            self.1
+        }
+        fn is_deterministic(&self) -> bool {
+            true
+        }
+        fn encrypt(&self, _key: &[u8; 64], plaintext: &[u8]) -> Result<Vec<u8>, Error> {
+            Ok(plaintext.to_vec())
+        }
+        fn decrypt(&self, _key: &[u8; 64], data: &[u8]) -> Result<Vec<u8>, Error> {
+            Ok(data.to_vec())
+        }
+    }
+
+    #[test]
+    fn test_register_rejects_conflicts() {
+        // Built-in names and markers, even for disabled features
+        let err = register_scheme(Box::new(Named("aasv", [0x05, 0x01])));
+        assert_eq!(err, Err(Error::SchemeConflict { scheme: "aasv" }));
+        let err = register_scheme(Box::new(Named("unittest1", BUILTIN_MARKERS[0])));
+        assert_eq!(
+            err,
+            Err(Error::SchemeConflict {
+                scheme: "unittest1"
+            })
+        );
+
+        // Invalid names
+        for name in ["", "Upper", "dot.ted"] {
+            let err = register_scheme(Box::new(Named(name, [0x05, 0x02])));
+            assert_eq!(err, Err(Error::InvalidScheme));
+        }
+
+        // Previously registered name or marker
+        let scheme = register_scheme(Box::new(Named("unittest2", [0x05, 0x03]))).unwrap();
+        assert_eq!(scheme.as_str(), "unittest2");
+        assert_eq!(by_name("unittest2"), Some(scheme));
+        let err = register_scheme(Box::new(Named("unittest2", [0x05, 0x04])));
+        assert_eq!(
+            err,
+            Err(Error::SchemeConflict {
+                scheme: "unittest2"
+            })
+        );
+        let err = register_scheme(Box::new(Named("unittest3", [0x05, 0x03])));
+        assert_eq!(
+            err,
+            Err(Error::SchemeConflict {
+                scheme: "unittest3"
+            })
+        );
+    }
+}
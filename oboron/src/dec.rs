@@ -1,16 +1,12 @@
-use crate::{
-    base32::{BASE32_CROCKFORD, BASE32_RFC},
-    constants::SCHEME_MARKER_SIZE,
-    error::Error,
-    Encoding, Format, Scheme,
-};
-use data_encoding::{BASE64URL_NOPAD, HEXLOWER};
+use crate::{constants::SCHEME_MARKER_SIZE, error::Error, Encoding, Format, Scheme};
 
 // Conditionally import decrypt functions
 #[cfg(feature = "aags")]
 use crate::decrypt_aags;
 #[cfg(feature = "aasv")]
 use crate::decrypt_aasv;
+#[cfg(feature = "apgc")]
+use crate::decrypt_apgc;
 #[cfg(feature = "apgs")]
 use crate::decrypt_apgs;
 #[cfg(feature = "apsv")]
@@ -19,9 +15,39 @@ use crate::decrypt_apsv;
 use crate::decrypt_mock1;
 #[cfg(feature = "mock")]
 use crate::decrypt_mock2;
+#[cfg(feature = "mock")]
+use crate::decrypt_mock3;
+#[cfg(feature = "mock")]
+use crate::decrypt_mock4;
 #[cfg(feature = "upbc")]
 use crate::decrypt_upbc;
 
+/// Shortest decoded payload that can be an obtext of any enabled scheme: the
+/// shortest ciphertext plus the scheme marker.  Anything shorter is rejected
+/// with [`Error::TruncatedObtext`] before the scheme marker is examined.
+pub(crate) const MIN_PAYLOAD_LEN: usize = {
+    let min_ciphertext_len = crate::obcrypt::MIN_CIPHERTEXT_LEN;
+    // Application-defined schemes may produce a single ciphertext byte
+    #[cfg(feature = "custom-schemes")]
+    let min_ciphertext_len = if min_ciphertext_len > 1 {
+        1
+    } else {
+        min_ciphertext_len
+    };
+    min_ciphertext_len.saturating_add(SCHEME_MARKER_SIZE)
+};
+
+/// Error for an obtext whose scheme marker is not `expected`'s: names the
+/// scheme that produced it if the marker is recognized, so that callers can
+/// tell a wrong-scheme obtext apart from one that fails authentication.
+#[cold]
+pub(crate) fn marker_mismatch(expected: Scheme, marker: [u8; 2]) -> Error {
+    match crate::dec_auto::scheme_for_marker(marker) {
+        Some(found) => Error::SchemeMismatch { expected, found },
+        None => Error::SchemeMarkerMismatch,
+    }
+}
+
 /// Generic decoding pipeline - takes full 64-byte key, obcrypt functions extract what they need
 ///
 /// Steps:
@@ -36,11 +62,53 @@ pub(crate) fn dec_from_format(
     format: Format,
     master_key: &[u8; 64],
 ) -> Result<String, Error> {
+    let buffer = obtext_to_ciphertext(obtext, format)?;
+    decrypt_ciphertext(format.scheme(), master_key, buffer)
+}
+
+/// Like [`dec_from_format`], but an obtext rejected before decryption (bad
+/// encoding, truncated payload, wrong scheme marker) still runs the scheme's
+/// decryption over a dummy ciphertext of the size the obtext would decode
+/// to, so that a reject costs about as much wherever it fails.
+pub(crate) fn dec_from_format_uniform(
+    obtext: &str,
+    format: Format,
+    master_key: &[u8; 64],
+) -> Result<String, Error> {
+    match obtext_to_ciphertext(obtext, format) {
+        Ok(buffer) => decrypt_ciphertext(format.scheme(), master_key, buffer),
+        Err(e) => {
+            dummy_decrypt(obtext, format, master_key);
+            Err(e)
+        }
+    }
+}
+
+/// Run `format`'s decryption over a zero ciphertext of the size `obtext`
+/// would decode to, discarding the result.  Z-tier and legacy formats are
+/// not decrypted on this path, so there is nothing to pad.
+pub(crate) fn dummy_decrypt(obtext: &str, format: Format, master_key: &[u8; 64]) {
+    if format.scheme().is_ztier_or_legacy() {
+        return;
+    }
+    let len = format
+        .encoding()
+        .decoded_len(obtext.len())
+        .max(MIN_PAYLOAD_LEN)
+        .saturating_sub(SCHEME_MARKER_SIZE);
+    let result = decrypt_ciphertext(format.scheme(), master_key, vec![0u8; len]);
+    std::hint::black_box(result).ok();
+}
+
+/// Steps 1-3 of [`dec_from_format`]: decode the obtext, check and strip the
+/// scheme marker, and check the length of the remaining ciphertext
+#[inline(always)]
+fn obtext_to_ciphertext(obtext: &str, format: Format) -> Result<Vec<u8>, Error> {
     // Step 1: Decode obtext
     let mut buffer = decode_obtext_to_payload(obtext, format.encoding())?;
 
-    if buffer.len() < SCHEME_MARKER_SIZE {
-        return Err(Error::PayloadTooShort);
+    if buffer.len() < MIN_PAYLOAD_LEN {
+        return Err(Error::TruncatedObtext);
     }
 
     // Step 2 & 3: XOR and extract marker in optimized way
@@ -50,18 +118,31 @@ pub(crate) fn dec_from_format(
 
     // Validate scheme marker
     if scheme_marker != format.scheme().marker() {
-        return Err(Error::SchemeMarkerMismatch);
+        return Err(marker_mismatch(format.scheme(), scheme_marker));
     }
 
     // Truncate to remove marker
     buffer.truncate(len - SCHEME_MARKER_SIZE);
+    crate::obcrypt::check_ciphertext_len(format.scheme(), buffer.len())?;
+    Ok(buffer)
+}
 
+/// Steps 4-5 of [`dec_from_format`]: decrypt and convert to a string
+#[inline(always)]
+#[allow(unused_mut, unused_variables)]
+fn decrypt_ciphertext(
+    scheme: Scheme,
+    master_key: &[u8; 64],
+    mut buffer: Vec<u8>,
+) -> Result<String, Error> {
     // Step 4: Decrypt using scheme-specific function
-    let plaintext_bytes = match format.scheme() {
+    let plaintext_bytes = match scheme {
         #[cfg(feature = "aags")]
         Scheme::Aags => decrypt_aags(master_key, &buffer)?,
         #[cfg(feature = "apgs")]
         Scheme::Apgs => decrypt_apgs(master_key, &buffer)?,
+        #[cfg(feature = "apgc")]
+        Scheme::Apgc => decrypt_apgc(master_key, &buffer)?,
         #[cfg(feature = "aasv")]
         Scheme::Aasv => decrypt_aasv(master_key, &buffer)?,
         #[cfg(feature = "apsv")]
@@ -72,13 +153,23 @@ pub(crate) fn dec_from_format(
         Scheme::Mock1 => decrypt_mock1(master_key, &buffer)?,
         #[cfg(feature = "mock")]
         Scheme::Mock2 => decrypt_mock2(master_key, &buffer)?,
+        #[cfg(feature = "mock")]
+        Scheme::Mock3 => decrypt_mock3(master_key, &buffer)?,
+        #[cfg(feature = "mock")]
+        Scheme::Mock4 => decrypt_mock4(master_key, &buffer)?,
         // Z-tier
         #[cfg(feature = "zrbcx")]
-        Scheme::Zrbcx => unreachable!("ztier uses separate path"),
+        Scheme::Zrbcx => return Err(Error::UnsupportedScheme { scheme }),
         #[cfg(feature = "zmock")]
-        Scheme::Zmock1 => unreachable!("ztier uses separate path"),
+        Scheme::Zmock1 => return Err(Error::UnsupportedScheme { scheme }),
         #[cfg(feature = "legacy")]
-        Scheme::Legacy => unreachable!("legacy uses separate path"),
+        Scheme::Legacy => return Err(Error::UnsupportedScheme { scheme }),
+        // Application-defined
+        #[cfg(feature = "custom-schemes")]
+        Scheme::Custom(c) => c.imp().decrypt(master_key, &buffer)?,
+        // Only `NullOb` reports it, and no codec produces its obtext
+        #[cfg(feature = "testing")]
+        Scheme::Null => return Err(Error::UnknownScheme),
     };
 
     // Step 5: Convert to string
@@ -97,18 +188,54 @@ pub(crate) fn dec_from_format(
 /// Decode text encoding to raw bytes.
 #[inline]
 pub(crate) fn decode_obtext_to_payload(obtext: &str, encoding: Encoding) -> Result<Vec<u8>, Error> {
-    match encoding {
-        Encoding::B32 => BASE32_RFC
-            .decode(obtext.as_bytes())
-            .map_err(|_| Error::InvalidB32),
-        Encoding::C32 => BASE32_CROCKFORD
-            .decode(obtext.as_bytes())
-            .map_err(|_| Error::InvalidC32),
-        Encoding::B64 => BASE64URL_NOPAD
-            .decode(obtext.as_bytes())
-            .map_err(|_| Error::InvalidB64),
-        Encoding::Hex => HEXLOWER
-            .decode(obtext.as_bytes())
-            .map_err(|_| Error::InvalidHex),
+    crate::encoding::decode(obtext, encoding)
+}
+
+/// Check whether two obtexts, possibly in different encodings, carry the
+/// same ciphertext.
+///
+/// No key is needed: both obtexts are decoded to their payload bytes, which
+/// are compared in constant time.  Useful for deduplicating obtexts of a
+/// deterministic scheme that were rendered in different encodings.
+///
+/// # Errors
+///
+/// Returns the decoding error (e.g. [`Error::InvalidC32`]) if either obtext
+/// is not valid in its encoding.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> Result<(), oboron::Error> {
+/// # #[cfg(feature = "aasv")]
+/// # {
+/// use oboron::{same_ciphertext, Encoding, Omnib};
+///
+/// let omb = Omnib::new(&oboron::generate_key())?;
+/// let c32 = omb.enc("user@example.com", "aasv.c32")?;
+/// let b64 = omb.enc("user@example.com", "aasv.b64")?;
+/// assert!(same_ciphertext(&c32, Encoding::C32, &b64, Encoding::B64)?);
+/// # }
+/// # Ok(())
+/// # }
+/// ```
+pub fn same_ciphertext(
+    a: &str,
+    a_encoding: Encoding,
+    b: &str,
+    b_encoding: Encoding,
+) -> Result<bool, Error> {
+    let a = decode_obtext_to_payload(a, a_encoding)?;
+    let b = decode_obtext_to_payload(b, b_encoding)?;
+    Ok(constant_time_eq(&a, &b))
+}
+
+/// Compare byte slices without an early exit on the first difference.
+/// Lengths are not secret and are compared directly.
+pub(crate) fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
     }
+    let diff = a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y));
+    std::hint::black_box(diff) == 0
 }
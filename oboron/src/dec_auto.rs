@@ -1,9 +1,11 @@
-use crate::{constants::SCHEME_MARKER_SIZE, error::Error, Encoding, MasterKey};
+use crate::{constants::SCHEME_MARKER_SIZE, error::Error, Encoding, Format, MasterKey, Scheme};
 
 #[cfg(feature = "aags")]
 use crate::{constants::AAGS_MARKER, decrypt_aags};
 #[cfg(feature = "aasv")]
 use crate::{constants::AASV_MARKER, decrypt_aasv};
+#[cfg(feature = "apgc")]
+use crate::{constants::APGC_MARKER, decrypt_apgc};
 #[cfg(feature = "apgs")]
 use crate::{constants::APGS_MARKER, decrypt_apgs};
 #[cfg(feature = "apsv")]
@@ -15,6 +17,11 @@ use crate::{constants::UPBC_MARKER, decrypt_upbc};
 use crate::{constants::MOCK1_MARKER, decrypt_mock1};
 #[cfg(feature = "mock")]
 use crate::{constants::MOCK2_MARKER, decrypt_mock2};
+#[cfg(feature = "mock")]
+use crate::{constants::MOCK3_MARKER, decrypt_mock3};
+#[cfg(feature = "mock")]
+use crate::{constants::MOCK4_MARKER, decrypt_mock4};
+use std::num::NonZeroUsize;
 
 /// Decode the given encoding, then decrypt autodetecting the scheme (SECURE SCHEMES ONLY)
 pub fn dec_any_scheme(
@@ -22,21 +29,38 @@ pub fn dec_any_scheme(
     encoding: Encoding,
     obtext: &str,
 ) -> Result<String, Error> {
+    dec_any_scheme_authenticated(masterkey, encoding, obtext).map(|(plaintext, _)| plaintext)
+}
+
+/// Like [`dec_any_scheme`], also returning whether the scheme that decrypted
+/// the payload verified an authentication tag (the AEAD schemes).  Only then
+/// does a successful decryption prove that `encoding` was the right one.
+fn dec_any_scheme_authenticated(
+    masterkey: &MasterKey,
+    encoding: Encoding,
+    obtext: &str,
+) -> Result<(String, bool), Error> {
     // Step 1: Decode obtext using encoding
     let mut buffer = crate::dec::decode_obtext_to_payload(obtext, encoding)?;
 
-    if buffer.len() < SCHEME_MARKER_SIZE {
-        return Err(Error::PayloadTooShort);
+    // A valid payload holds at least the shortest ciphertext besides the
+    // marker, so the marker can never overlap the first byte
+    if buffer.len() < crate::dec::MIN_PAYLOAD_LEN {
+        return Err(Error::TruncatedObtext);
     }
 
-    // Step 2: XOR the last two bytes with the first two to undo mixing
+    // Step 2 & 3: XOR the last two bytes with the first to undo mixing, and
+    // extract the 2-byte scheme marker.  `buffer[0]` is read once up front:
+    // XORing in place would clobber it when the marker overlaps the first byte.
     let len = buffer.len();
-    buffer[len - 1] ^= buffer[0];
-    buffer[len - 2] ^= buffer[0];
-
-    // Step 3: Extract 2-byte scheme marker from end
-    let scheme_marker = [buffer[len - 2], buffer[len - 1]];
+    let first_byte = buffer[0];
+    let scheme_marker = [buffer[len - 2] ^ first_byte, buffer[len - 1] ^ first_byte];
     buffer.truncate(len - SCHEME_MARKER_SIZE);
+    let scheme = scheme_for_marker(scheme_marker);
+    if let Some(scheme) = scheme {
+        crate::obcrypt::check_ciphertext_len(scheme, buffer.len())?;
+    }
+    let authenticated = scheme.is_some_and(|s| s.is_authenticated());
 
     // Step 4: Match scheme marker and decrypt with available SECURE schemes only
     let plaintext_bytes = match scheme_marker {
@@ -46,6 +70,8 @@ pub fn dec_any_scheme(
         AAGS_MARKER => decrypt_aags(masterkey.key(), &buffer)?,
         #[cfg(feature = "apgs")]
         APGS_MARKER => decrypt_apgs(masterkey.key(), &buffer)?,
+        #[cfg(feature = "apgc")]
+        APGC_MARKER => decrypt_apgc(masterkey.key(), &buffer)?,
         #[cfg(feature = "aasv")]
         AASV_MARKER => decrypt_aasv(masterkey.key(), &buffer)?,
         #[cfg(feature = "apsv")]
@@ -55,6 +81,17 @@ pub fn dec_any_scheme(
         MOCK1_MARKER => decrypt_mock1(masterkey.key(), &buffer)?,
         #[cfg(feature = "mock")]
         MOCK2_MARKER => decrypt_mock2(masterkey.key(), &buffer)?,
+        #[cfg(feature = "mock")]
+        MOCK3_MARKER => decrypt_mock3(masterkey.key(), &buffer)?,
+        #[cfg(feature = "mock")]
+        MOCK4_MARKER => decrypt_mock4(masterkey.key(), &buffer)?,
+        // Application-defined schemes are consulted after the built-ins
+        #[cfg(feature = "custom-schemes")]
+        _ => match crate::custom_scheme::by_marker(scheme_marker) {
+            Some(custom) => custom.imp().decrypt(masterkey.key(), &buffer)?,
+            None => return Err(Error::UnknownScheme),
+        },
+        #[cfg(not(feature = "custom-schemes"))]
         _ => {
             // Unknown scheme marker - no fallback for secure schemes
             return Err(Error::UnknownScheme);
@@ -65,34 +102,12 @@ pub fn dec_any_scheme(
 
     // Unchecked (Assuming plaintext was originally valid UTF-8, and correct key is used)
     #[cfg(feature = "unchecked-utf8")]
-    {
-        Ok(unsafe { String::from_utf8_unchecked(plaintext_bytes) })
-    }
+    let plaintext = unsafe { String::from_utf8_unchecked(plaintext_bytes) };
 
     #[cfg(not(feature = "unchecked-utf8"))]
-    {
-        String::from_utf8(plaintext_bytes).map_err(|_| Error::InvalidUtf8)
-    }
-}
+    let plaintext = String::from_utf8(plaintext_bytes).map_err(|_| Error::InvalidUtf8)?;
 
-/// Decode c32, autodetect the scheme and decrypt accordingly
-pub(crate) fn dec_any_scheme_c32(masterkey: &MasterKey, obtext: &str) -> Result<String, Error> {
-    dec_any_scheme(masterkey, Encoding::C32, obtext)
-}
-
-/// Decode b32, autodetect the scheme and decrypt accordingly
-pub(crate) fn dec_any_scheme_b32(masterkey: &MasterKey, obtext: &str) -> Result<String, Error> {
-    dec_any_scheme(masterkey, Encoding::B32, obtext)
-}
-
-/// Decode b64, autodetect the scheme and decrypt accordingly
-pub(crate) fn dec_any_scheme_b64(masterkey: &MasterKey, obtext: &str) -> Result<String, Error> {
-    dec_any_scheme(masterkey, Encoding::B64, obtext)
-}
-
-/// Decode hex, autodetect the scheme and decrypt accordingly
-pub(crate) fn dec_any_scheme_hex(masterkey: &MasterKey, obtext: &str) -> Result<String, Error> {
-    dec_any_scheme(masterkey, Encoding::Hex, obtext)
+    Ok((plaintext, authenticated))
 }
 
 /// Autodetect both the encoding and scheme, then decode accordingly (SECURE SCHEMES ONLY).
@@ -102,66 +117,672 @@ pub(crate) fn dec_any_scheme_hex(masterkey: &MasterKey, obtext: &str) -> Result<
 /// If the most likely encoding fails, it falls back to trying other encodings.
 ///
 /// Detection logic:
-/// 1. If text contains '-', '_', or uppercase letters -> B64 (definitive)
-/// 2. Else if text contains non-hex lowercase letters (g-z) -> Try Base32, fallback to B64
-/// 3. Else -> Try Hex, fallback to Base32, then B64
+/// 1. If text contains '-', '_', or mixed-case letters -> B64 (definitive)
+/// 2. Else if text contains uppercase letters -> Try uppercase Hex if they are
+///    all A-F, then B32, fallback to B64
+/// 3. Else if text contains non-hex lowercase letters (g-z) -> Try C32, fallback to B64
+/// 4. Else -> Try Hex, fallback to C32, then B64
+///
+/// A decode through an AEAD scheme is accepted at once: its verified tag
+/// proves the encoding right.  A decode through an unauthenticated scheme
+/// (upbc, the mocks) may be a payload misread under the wrong encoding that
+/// happens to carry such a scheme's marker, so it is only kept as a
+/// fallback while the remaining encodings are tried; an AEAD decode among
+/// them wins.
+///
+/// Each attempt decodes the obtext once and decrypts it with at most one
+/// scheme, the one its marker names, and no encoding is tried twice, so an
+/// obtext costs at most five attempts (one per [`Encoding`]).
 pub fn dec_any_format(masterkey: &MasterKey, obtext: &str) -> Result<String, Error> {
-    // Single-pass classification
-    let mut has_dash = false;
-    let mut has_underscore = false;
-    let mut has_upper = false;
-    let mut has_lower = false;
-    let mut has_non_hex_lower = false;
-
-    for b in obtext.bytes() {
-        match b {
-            b'-' => has_dash = true,
-            b'_' => has_underscore = true,
-            b'A'..=b'Z' => has_upper = true,
-            b'a'..=b'f' => has_lower = true,
-            b'g'..=b'z' => {
-                has_lower = true;
-                has_non_hex_lower = true;
+    dec_any_format_bounded(masterkey, obtext, None, None, false)
+}
+
+/// Like [`dec_any_format`], but tries the `preferred` encoding first and
+/// gives up with [`Error::AutodetectExhausted`], carrying the last
+/// attempt's error, once `max_attempts` candidates have been tried without
+/// a match.  With `legacy`, legacy-shaped obtext is also tried as legacy
+/// (see [`candidate_order`]).
+pub(crate) fn dec_any_format_bounded(
+    masterkey: &MasterKey,
+    obtext: &str,
+    max_attempts: Option<NonZeroUsize>,
+    preferred: Option<Encoding>,
+    legacy: bool,
+) -> Result<String, Error> {
+    let order = candidate_order(obtext, preferred, legacy);
+    let attempt = |candidate| try_candidate(masterkey, candidate, obtext);
+    match max_attempts.map(NonZeroUsize::get) {
+        Some(max) if max < order.len() => {
+            first_authenticated(&order[..max], attempt).map_err(|e| Error::AutodetectExhausted {
+                attempts: max,
+                last_error: Box::new(e),
+            })
+        }
+        _ => first_authenticated(&order, attempt),
+    }
+}
+
+/// Try `attempt` on each candidate in order, returning the first
+/// authenticated plaintext, else the first unauthenticated one, else the
+/// last error.
+fn first_authenticated<C: Copy>(
+    order: &[C],
+    mut attempt: impl FnMut(C) -> Result<(String, bool), Error>,
+) -> Result<String, Error> {
+    let mut fallback = None;
+    // `order` is never empty; this only seeds the loop
+    let mut last_err = Error::DecryptionFailed;
+    for &candidate in order {
+        match attempt(candidate) {
+            Ok((plaintext, true)) => return Ok(plaintext),
+            Ok((plaintext, false)) => {
+                fallback.get_or_insert(plaintext);
             }
-            _ => {}
+            Err(e) => last_err = e,
         }
     }
+    fallback.ok_or(last_err)
+}
 
-    // Check for B64 indicators: '-', '_', or mixed case letters (definitive)
-    if has_dash || has_underscore || (has_lower && has_upper) {
-        if let Ok(result) = dec_any_scheme_b64(masterkey, obtext) {
-            return Ok(result);
+/// One decode attempt made by autodetection, as recorded in an [`AutodecReport`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AutodecAttempt {
+    /// Encoding the obtext was decoded as
+    pub encoding: Encoding,
+    /// Scheme identified by the payload's marker, if any
+    pub scheme: Option<Scheme>,
+    /// Why the attempt failed
+    pub error: Error,
+}
+
+/// Diagnostic report returned by [`Omnib::autodec_verbose`](crate::Omnib::autodec_verbose)
+/// when no encoding/scheme combination decodes an obtext.
+///
+/// Attempts are listed in the order autodetection tries them.  The report
+/// converts into the [`Error`] that [`autodec`](crate::Omnib::autodec)
+/// would have returned.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AutodecReport {
+    error: Error,
+    attempts: Vec<AutodecAttempt>,
+}
+
+impl AutodecReport {
+    /// The error [`autodec`](crate::Omnib::autodec) returns for this obtext.
+    pub fn error(&self) -> &Error {
+        &self.error
+    }
+
+    /// Every attempt made, in order.  Empty if the obtext was rejected
+    /// before any decoding, e.g. for exceeding the input size limit.
+    pub fn attempts(&self) -> &[AutodecAttempt] {
+        &self.attempts
+    }
+
+    /// A report for an obtext rejected before any decode attempt.
+    pub(crate) fn rejected(error: Error) -> Self {
+        Self {
+            error,
+            attempts: Vec::new(),
         }
     }
 
-    // Check for uppercase letters, indicating B32
-    if has_upper {
-        // Try B32 first, fallback to B64 (no point trying hex)
-        if let Ok(result) = dec_any_scheme_b32(masterkey, obtext) {
-            return Ok(result);
+    /// Replay the attempts of [`dec_any_format`] on an obtext it failed to
+    /// decode, recording each one.  Only called on failure, so the happy
+    /// path never allocates a report.
+    #[cold]
+    pub(crate) fn replay(
+        masterkey: &MasterKey,
+        obtext: &str,
+        error: Error,
+        max_attempts: Option<NonZeroUsize>,
+        preferred: Option<Encoding>,
+        legacy: bool,
+    ) -> Self {
+        let mut attempts = Vec::new();
+        let order = candidate_order(obtext, preferred, legacy);
+        let max = max_attempts.map_or(order.len(), NonZeroUsize::get);
+        for candidate in order.into_iter().take(max) {
+            let scheme = match candidate {
+                Candidate::Encoding(encoding) => {
+                    crate::dec::decode_obtext_to_payload(obtext, encoding)
+                        .ok()
+                        .filter(|buffer| buffer.len() >= crate::dec::MIN_PAYLOAD_LEN)
+                        .and_then(|buffer| {
+                            let len = buffer.len();
+                            scheme_for_marker([
+                                buffer[len - 2] ^ buffer[0],
+                                buffer[len - 1] ^ buffer[0],
+                            ])
+                        })
+                }
+                #[cfg(feature = "legacy")]
+                Candidate::Legacy => Some(Scheme::Legacy),
+            };
+            if let Err(error) = try_candidate(masterkey, candidate, obtext) {
+                attempts.push(AutodecAttempt {
+                    encoding: candidate.encoding(),
+                    scheme,
+                    error,
+                });
+            }
+        }
+        Self { error, attempts }
+    }
+}
+
+impl std::fmt::Display for AutodecReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "autodetection failed: {}", self.error)?;
+        for attempt in &self.attempts {
+            match attempt.scheme {
+                Some(scheme) => {
+                    write!(f, "\n  {}.{}: {}", scheme, attempt.encoding, attempt.error)?
+                }
+                None => write!(f, "\n  {}: {}", attempt.encoding, attempt.error)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for AutodecReport {}
+
+impl From<AutodecReport> for Error {
+    fn from(report: AutodecReport) -> Self {
+        report.error
+    }
+}
+
+/// Encodings [`dec_any_format_bounded`] tries on `obtext`, in order,
+/// without repeats.
+pub(crate) fn attempted_encodings(
+    obtext: &str,
+    max_attempts: Option<NonZeroUsize>,
+    preferred: Option<Encoding>,
+    legacy: bool,
+) -> Vec<Encoding> {
+    let mut order = candidate_order(obtext, preferred, legacy);
+    if let Some(max) = max_attempts {
+        order.truncate(max.get());
+    }
+    let mut encodings = Vec::with_capacity(order.len());
+    for candidate in order {
+        if !encodings.contains(&candidate.encoding()) {
+            encodings.push(candidate.encoding());
         }
-        if let Ok(result) = dec_any_scheme_b64(masterkey, obtext) {
-            return Ok(result);
+    }
+    encodings
+}
+
+/// One thing autodetection tries: decoding an encoding, whose payload's
+/// marker then names the scheme, or decoding as legacy, which has no
+/// marker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Candidate {
+    Encoding(Encoding),
+    #[cfg(feature = "legacy")]
+    Legacy,
+}
+
+impl Candidate {
+    /// The encoding an attempt on this candidate decodes
+    fn encoding(self) -> Encoding {
+        match self {
+            Candidate::Encoding(encoding) => encoding,
+            #[cfg(feature = "legacy")]
+            Candidate::Legacy => Encoding::B32,
         }
     }
+}
+
+/// Candidates tried on `obtext`, in order: the [`attempt_order`]
+/// encodings, and with `legacy` (and the `legacy` feature) a legacy
+/// attempt for obtext of its shape.
+///
+/// The legacy attempt goes right after the `preferred` encoding: the shape
+/// is a stronger hint than the character classes the heuristic order goes
+/// by.  Its success is unauthenticated, so an AEAD decode under a later
+/// encoding still wins.
+#[cfg_attr(not(feature = "legacy"), allow(unused_variables))]
+fn candidate_order(obtext: &str, preferred: Option<Encoding>, legacy: bool) -> Vec<Candidate> {
+    #[allow(unused_mut)] // only legacy builds insert a candidate
+    let mut order: Vec<Candidate> = attempt_order(obtext, preferred)
+        .into_iter()
+        .map(Candidate::Encoding)
+        .collect();
+    #[cfg(feature = "legacy")]
+    if legacy && has_legacy_shape(obtext) {
+        order.insert(usize::from(preferred.is_some()), Candidate::Legacy);
+    }
+    order
+}
 
-    // Check for non-hex lowercase letters (g-z), indicating C32
+/// Decode+decrypt `obtext` as `candidate`, also returning whether the
+/// scheme verified an authentication tag
+fn try_candidate(
+    masterkey: &MasterKey,
+    candidate: Candidate,
+    obtext: &str,
+) -> Result<(String, bool), Error> {
+    match candidate {
+        Candidate::Encoding(encoding) => dec_any_scheme_authenticated(masterkey, encoding, obtext),
+        #[cfg(feature = "legacy")]
+        Candidate::Legacy => {
+            crate::ztier::dec_legacy_strict(legacy_secret(masterkey), obtext).map(|pt| (pt, false))
+        }
+    }
+}
+
+/// The legacy secret of a master key: its first 32 bytes, as for
+/// [`Obz`](crate::ztier::Obz) built from a 64-byte key.
+#[cfg(feature = "legacy")]
+pub(crate) fn legacy_secret(masterkey: &MasterKey) -> &[u8; 32] {
+    masterkey.key()[..32].try_into().unwrap()
+}
+
+/// Encodings tried by [`dec_any_format`], in order, without repeats.
+/// The `preferred` encoding, if any, comes first.
+fn attempt_order(obtext: &str, preferred: Option<Encoding>) -> Vec<Encoding> {
+    let has_upper = obtext.bytes().any(|b| b.is_ascii_uppercase());
+    let has_non_hex_upper = obtext.bytes().any(|b| matches!(b, b'G'..=b'Z'));
+    let has_lower = obtext.bytes().any(|b| b.is_ascii_lowercase());
+    let has_non_hex_lower = obtext.bytes().any(|b| matches!(b, b'g'..=b'z'));
+
+    let mut order = Vec::with_capacity(5);
+    let mut push = |encoding: Encoding| {
+        if !order.contains(&encoding) {
+            order.push(encoding);
+        }
+    };
+    if let Some(encoding) = preferred {
+        push(encoding);
+    }
+    if obtext.contains(['-', '_']) || (has_lower && has_upper) {
+        push(Encoding::B64);
+    }
+    if has_upper {
+        if !has_non_hex_upper {
+            push(Encoding::HexUpper);
+        }
+        push(Encoding::B32);
+        push(Encoding::B64);
+    }
     if has_non_hex_lower {
-        // Try C32 first, fallback to B64 (no point trying hex)
-        if let Ok(result) = dec_any_scheme_c32(masterkey, obtext) {
-            return Ok(result);
+        push(Encoding::C32);
+        push(Encoding::B64);
+    }
+    push(Encoding::Hex);
+    push(Encoding::C32);
+    push(Encoding::B64);
+    order
+}
+
+/// Detect the format of an obtext from its scheme marker, without a key.
+///
+/// Encodings are tried in the same order as [`dec_any_format`]; the first
+/// one whose payload carries the marker of an enabled scheme (z-tier and
+/// registered custom schemes included) wins.  Legacy obtext has no marker
+/// and is never detected.
+///
+/// A detected format is a strong hint, not a proof: the obtext may still
+/// fail to decrypt, and about one random string in 65536 carries a
+/// plausible marker.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> Result<(), oboron::Error> {
+/// # #[cfg(all(feature = "aasv", feature = "keyless"))]
+/// # {
+/// let ot = oboron::enc_keyless("hello", "aasv.b64")?;
+/// assert_eq!(oboron::detect_format(&ot), Some(oboron::AASV_B64));
+/// assert_eq!(oboron::detect_format("not obtext!"), None);
+/// # }
+/// # Ok(())
+/// # }
+/// ```
+pub fn detect_format(obtext: &str) -> Option<Format> {
+    let has_upper = obtext.bytes().any(|b| b.is_ascii_uppercase());
+    let has_non_hex_upper = obtext.bytes().any(|b| matches!(b, b'G'..=b'Z'));
+    let has_lower = obtext.bytes().any(|b| b.is_ascii_lowercase());
+    let has_non_hex_lower = obtext.bytes().any(|b| matches!(b, b'g'..=b'z'));
+    let b64_only = obtext.contains(['-', '_']) || (has_lower && has_upper);
+
+    let candidates: &[Encoding] = if b64_only {
+        &[Encoding::B64]
+    } else if has_upper && !has_non_hex_upper {
+        &[Encoding::HexUpper, Encoding::B32, Encoding::B64]
+    } else if has_upper {
+        &[Encoding::B32, Encoding::B64]
+    } else if has_non_hex_lower {
+        &[Encoding::C32, Encoding::B64]
+    } else {
+        &[Encoding::Hex, Encoding::C32, Encoding::B64]
+    };
+
+    candidates.iter().find_map(|&encoding| {
+        let buffer = crate::dec::decode_obtext_to_payload(obtext, encoding).ok()?;
+        if buffer.len() <= SCHEME_MARKER_SIZE {
+            return None;
+        }
+        let len = buffer.len();
+        let marker = [buffer[len - 2] ^ buffer[0], buffer[len - 1] ^ buffer[0]];
+        scheme_for_marker(marker).map(|scheme| Format::new(scheme, encoding))
+    })
+}
+
+/// Check whether `key` decrypts `obtext`, e.g. to confirm that a new
+/// service's key matches existing data before going live.
+///
+/// The format is autodetected as with [`Omnib::autodec`](crate::Omnib::autodec).
+/// Returns `Ok(true)` if the obtext decrypts under `key`, and `Ok(false)`
+/// if it carries the marker of an enabled scheme (see [`detect_format`])
+/// but does not decrypt.
+///
+/// For the AEAD schemes (`aags`, `apgs`, `apgc`, `aasv`, `apsv`) the answer
+/// is conclusive: `true` means the authentication tag verified under `key`.
+/// The other schemes (`upbc`, the mocks, custom schemes) have no tag, so
+/// `true` only means the payload decrypted to valid UTF-8 (with valid
+/// padding, for `upbc`), which a wrong key also achieves now and then;
+/// `false` is still conclusive.  The identity and reversal mocks (`mock1`,
+/// `mock2`, `zmock1`) ignore the key altogether, so their obtext cannot
+/// confirm one either way (see [`Scheme::is_keyed`]).
+///
+/// # Errors
+///
+/// Returns the key's error if `key` is invalid,
+/// [`Error::UnkeyedScheme`] if the obtext carries the marker of a scheme
+/// that does not use a key, and the
+/// [`autodec`](crate::Omnib::autodec) error if the obtext does not decode
+/// to a known scheme in any encoding, e.g. for garbage or truncated input.
+/// Legacy obtext carries no marker, so it too is an error unless it
+/// decrypts.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> Result<(), oboron::Error> {
+/// # #[cfg(feature = "aasv")]
+/// # {
+/// let key = oboron::generate_key();
+/// let ot = oboron::Omnib::new(&key)?.enc("hello", "aasv.c32")?;
+/// assert!(oboron::verify_key(&ot, &key)?);
+/// assert!(!oboron::verify_key(&ot, &oboron::generate_key())?);
+/// assert!(oboron::verify_key("not obtext!", &key).is_err());
+/// # }
+/// # Ok(())
+/// # }
+/// ```
+pub fn verify_key(obtext: &str, key: &str) -> Result<bool, Error> {
+    let omb = crate::Omnib::new(key)?;
+    let detected = detect_format(obtext);
+    if let Some(format) = detected.filter(|f| !f.scheme().is_keyed()) {
+        return Err(Error::UnkeyedScheme {
+            scheme: format.scheme(),
+        });
+    }
+    match omb.autodec(obtext) {
+        Ok(_) => Ok(true),
+        Err(_) if detected.is_some() => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+/// Whether `obtext` has the shape of legacy obtext: unpadded lowercase
+/// RFC 4648 base32 (reversed) of a whole number of AES blocks.  Legacy
+/// obtext carries no marker, so this is a hint only.
+pub(crate) fn has_legacy_shape(obtext: &str) -> bool {
+    let bytes = obtext.len() * 5 / 8;
+    obtext
+        .bytes()
+        .all(|b| b.is_ascii_lowercase() || (b'2'..=b'7').contains(&b))
+        && bytes > 0
+        && bytes % 16 == 0
+        && (bytes * 8).div_ceil(5) == obtext.len()
+}
+
+/// Map a scheme marker to an enabled scheme, built-ins first.
+pub(crate) fn scheme_for_marker(marker: [u8; 2]) -> Option<Scheme> {
+    let builtin = crate::supported_schemes()
+        .iter()
+        .copied()
+        .filter(|_scheme| {
+            // Legacy has no marker
+            #[cfg(feature = "legacy")]
+            if *_scheme == Scheme::Legacy {
+                return false;
+            }
+            true
+        })
+        .find(|scheme| scheme.marker() == marker);
+    #[cfg(feature = "custom-schemes")]
+    let builtin = builtin.or_else(|| crate::custom_scheme::by_marker(marker).map(Scheme::Custom));
+    builtin
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::encode;
+    use data_encoding::HEXLOWER;
+
+    /// Schemes handled by `enc_to_format`/`dec_from_format` (not z-tier or legacy)
+    fn is_secure_path(scheme: crate::Scheme) -> bool {
+        !scheme.is_ztier_or_legacy()
+    }
+
+    #[test]
+    fn test_has_legacy_shape() {
+        // 16 and 32 bytes encode to 26 and 52 base32 characters
+        assert!(has_legacy_shape("yveb6bzj6daq6zmquekykwr65g"));
+        assert!(has_legacy_shape(&"a".repeat(52)));
+        assert!(!has_legacy_shape(""));
+        assert!(!has_legacy_shape(&"a".repeat(25)));
+        assert!(!has_legacy_shape(&"a".repeat(27)));
+        assert!(!has_legacy_shape("yveb6bzj6daq6zmquekykwr650")); // '0' not in the alphabet
+        assert!(!has_legacy_shape("YVEB6BZJ6DAQ6ZMQUEKYKWR65G"));
+    }
+
+    #[test]
+    fn test_degenerate_payloads_error_without_panic() {
+        let masterkey = MasterKey::from_bytes(&[0u8; 64]).unwrap();
+        for encoding in Encoding::ALL {
+            for len in 0..=SCHEME_MARKER_SIZE {
+                for byte in [0x00, 0x01, 0x7f, 0xff] {
+                    let obtext = encode(&vec![byte; len], encoding);
+                    assert!(
+                        dec_any_scheme(&masterkey, encoding, &obtext).is_err(),
+                        "{}-byte payload {:?} in {:?} should fail",
+                        len,
+                        obtext,
+                        encoding
+                    );
+                    assert!(dec_any_format(&masterkey, &obtext).is_err());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_marker_only_payload_is_truncated() {
+        // Two bytes that would unmix to a valid marker if the marker were
+        // allowed to overlap the first byte.
+        let masterkey = MasterKey::from_bytes(&[0u8; 64]).unwrap();
+        let obtext = encode(&[0x12, 0x34], Encoding::Hex);
+        assert_eq!(
+            dec_any_scheme(&masterkey, Encoding::Hex, &obtext),
+            Err(Error::TruncatedObtext)
+        );
+    }
+
+    #[test]
+    fn test_short_payloads_are_truncated_per_encoding() {
+        let masterkey = MasterKey::from_bytes(&[0u8; 64]).unwrap();
+        for encoding in Encoding::ALL {
+            for len in 0..crate::dec::MIN_PAYLOAD_LEN {
+                let obtext = encode(&vec![0xa5; len], encoding);
+                assert_eq!(
+                    dec_any_scheme(&masterkey, encoding, &obtext),
+                    Err(Error::TruncatedObtext),
+                    "{}-byte payload in {:?}",
+                    len,
+                    encoding
+                );
+                for &scheme in crate::supported_schemes() {
+                    if !is_secure_path(scheme) {
+                        continue;
+                    }
+                    let format = crate::Format::new(scheme, encoding);
+                    assert_eq!(
+                        crate::dec::dec_from_format(&obtext, format, masterkey.key()),
+                        Err(Error::TruncatedObtext)
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_min_payload_len_is_attainable() {
+        // The shortest real obtext of each enabled scheme is at least
+        // MIN_PAYLOAD_LEN bytes, so the check never rejects valid input.
+        let masterkey = MasterKey::from_bytes(&[0u8; 64]).unwrap();
+        for &scheme in crate::supported_schemes() {
+            if !is_secure_path(scheme) {
+                continue;
+            }
+            let format = crate::Format::new(scheme, Encoding::Hex);
+            let obtext = crate::enc::enc_to_format("a", format, masterkey.key()).unwrap();
+            let payload = HEXLOWER.decode(obtext.as_bytes()).unwrap();
+            assert!(
+                payload.len() >= crate::dec::MIN_PAYLOAD_LEN,
+                "{} produced a {}-byte payload",
+                scheme,
+                payload.len()
+            );
         }
-        if let Ok(result) = dec_any_scheme_b64(masterkey, obtext) {
-            return Ok(result);
+    }
+
+    #[test]
+    fn test_minimal_obtext_fixtures_per_scheme() {
+        // The shortest obtext of each scheme (a 1-byte plaintext) unmixes to
+        // its own marker in every encoding.  Prefixes too short to hold a
+        // marker apart from the first byte are truncated, and no prefix
+        // decrypts to the original plaintext.
+        let masterkey = MasterKey::from_bytes(&[7u8; 64]).unwrap();
+        for &scheme in crate::supported_schemes() {
+            if !is_secure_path(scheme) {
+                continue;
+            }
+            let payload = crate::enc::enc_to_payload("a", scheme, masterkey.key()).unwrap();
+            let ciphertext_len = payload.len() - SCHEME_MARKER_SIZE;
+            assert_eq!(
+                crate::obcrypt::check_ciphertext_len(scheme, ciphertext_len),
+                Ok(()),
+                "{}",
+                scheme
+            );
+            if ciphertext_len > 1 && !scheme.is_testing() {
+                // Nothing shorter is a valid ciphertext of this scheme
+                assert!(
+                    crate::obcrypt::check_ciphertext_len(scheme, ciphertext_len - 1).is_err(),
+                    "{}",
+                    scheme
+                );
+            }
+
+            for encoding in Encoding::ALL {
+                let obtext = encode(&payload, encoding);
+                assert_eq!(
+                    dec_any_scheme(&masterkey, encoding, &obtext),
+                    Ok("a".to_string()),
+                    "{} {:?}",
+                    scheme,
+                    encoding
+                );
+
+                for len in 0..payload.len() {
+                    let prefix = encode(&payload[..len], encoding);
+                    let result = dec_any_scheme(&masterkey, encoding, &prefix);
+                    if len < crate::dec::MIN_PAYLOAD_LEN {
+                        assert_eq!(result, Err(Error::TruncatedObtext));
+                    } else {
+                        assert_ne!(result, Ok("a".to_string()), "{} {:?}", scheme, encoding);
+                    }
+                }
+            }
         }
     }
 
-    // Likely hex - try Hex, then Base32, then B64
-    if let Ok(result) = dec_any_scheme_hex(masterkey, obtext) {
-        return Ok(result);
+    #[test]
+    fn test_unauthenticated_decode_does_not_stop_search() {
+        // A wrong encoding that decodes to a payload carrying an
+        // unauthenticated scheme's marker, followed by the right one, whose
+        // AEAD tag verifies
+        let order = [Encoding::Hex, Encoding::C32, Encoding::B64];
+        let mut tried = Vec::new();
+        let result = first_authenticated(&order, |encoding| {
+            tried.push(encoding);
+            match encoding {
+                Encoding::Hex => Ok(("garbage".to_string(), false)),
+                Encoding::C32 => Ok(("secret".to_string(), true)),
+                _ => unreachable!("search continues past an authenticated decode"),
+            }
+        });
+        assert_eq!(result, Ok("secret".to_string()));
+        assert_eq!(tried, [Encoding::Hex, Encoding::C32]);
+
+        // A failed tag is not accepted either
+        let result = first_authenticated(&order, |encoding| match encoding {
+            Encoding::Hex => Err(Error::DecryptionFailed),
+            _ => Ok((encoding.to_string(), true)),
+        });
+        assert_eq!(result, Ok("c32".to_string()));
     }
-    if let Ok(result) = dec_any_scheme_c32(masterkey, obtext) {
-        return Ok(result);
+
+    #[test]
+    fn test_unauthenticated_decode_is_kept_as_fallback() {
+        let order = [Encoding::Hex, Encoding::C32, Encoding::B64];
+        let mut tried = 0;
+        let result = first_authenticated(&order, |encoding| {
+            tried += 1;
+            match encoding {
+                Encoding::C32 => Ok(("upbc plaintext".to_string(), false)),
+                Encoding::B64 => Ok(("later".to_string(), false)),
+                _ => Err(Error::InvalidHex),
+            }
+        });
+        assert_eq!(result, Ok("upbc plaintext".to_string()));
+        assert_eq!(tried, 3);
+
+        // With no decode at all, the last error is returned
+        let result = first_authenticated(&order, |encoding| match encoding {
+            Encoding::B64 => Err(Error::InvalidB64),
+            _ => Err(Error::DecryptionFailed),
+        });
+        assert_eq!(result, Err(Error::InvalidB64));
+    }
+
+    #[test]
+    fn test_dec_any_format_reports_authentication() {
+        let masterkey = MasterKey::from_bytes(&[7u8; 64]).unwrap();
+        for &scheme in crate::supported_schemes() {
+            if !is_secure_path(scheme) {
+                continue;
+            }
+            let format = crate::Format::new(scheme, Encoding::C32);
+            let obtext = crate::enc::enc_to_format("tagged", format, masterkey.key()).unwrap();
+            let (plaintext, authenticated) =
+                dec_any_scheme_authenticated(&masterkey, Encoding::C32, &obtext).unwrap();
+            assert_eq!(plaintext, "tagged");
+            assert_eq!(
+                authenticated,
+                matches!(scheme.as_str(), "aags" | "apgs" | "apgc" | "aasv" | "apsv"),
+                "{}",
+                scheme
+            );
+            assert_eq!(dec_any_format(&masterkey, &obtext).unwrap(), "tagged");
+        }
     }
-    dec_any_scheme_b64(masterkey, obtext)
 }
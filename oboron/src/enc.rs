@@ -1,15 +1,12 @@
-use crate::{
-    base32::{BASE32_CROCKFORD, BASE32_RFC},
-    error::Error,
-    Encoding, Format, Scheme,
-};
-use data_encoding::{BASE64URL_NOPAD, HEXLOWER};
+use crate::{error::Error, Format, Scheme};
 
 // Conditionally import encrypt functions
 #[cfg(feature = "aags")]
 use crate::encrypt_aags;
 #[cfg(feature = "aasv")]
 use crate::encrypt_aasv;
+#[cfg(feature = "apgc")]
+use crate::encrypt_apgc;
 #[cfg(feature = "apgs")]
 use crate::encrypt_apgs;
 #[cfg(feature = "apsv")]
@@ -18,6 +15,10 @@ use crate::encrypt_apsv;
 use crate::encrypt_mock1;
 #[cfg(feature = "mock")]
 use crate::encrypt_mock2;
+#[cfg(feature = "mock")]
+use crate::encrypt_mock3;
+#[cfg(feature = "mock")]
+use crate::encrypt_mock4;
 #[cfg(feature = "upbc")]
 use crate::encrypt_upbc;
 
@@ -34,16 +35,27 @@ pub(crate) fn enc_to_format(
     format: Format,
     master_key: &[u8; 64],
 ) -> Result<String, Error> {
-    if plaintext.is_empty() {
-        return Err(Error::EmptyPlaintext);
-    }
+    let payload = enc_to_payload(plaintext, format.scheme(), master_key)?;
+    Ok(crate::encoding::encode(&payload, format.encoding()))
+}
+
+/// Steps 1-3 of [`enc_to_format`]: the marked payload, before encoding.
+#[inline(always)]
+pub(crate) fn enc_to_payload(
+    plaintext: &str,
+    scheme: Scheme,
+    master_key: &[u8; 64],
+) -> Result<Vec<u8>, Error> {
+    crate::error::check_plaintext_len(plaintext, scheme)?;
 
     // Encrypt using scheme-specific function (they extract the key portion they need)
-    let mut ciphertext: Vec<u8> = match format.scheme() {
+    let mut ciphertext: Vec<u8> = match scheme {
         #[cfg(feature = "aags")]
         Scheme::Aags => encrypt_aags(master_key, plaintext.as_bytes())?,
         #[cfg(feature = "apgs")]
         Scheme::Apgs => encrypt_apgs(master_key, plaintext.as_bytes())?,
+        #[cfg(feature = "apgc")]
+        Scheme::Apgc => encrypt_apgc(master_key, plaintext.as_bytes())?,
         #[cfg(feature = "aasv")]
         Scheme::Aasv => encrypt_aasv(master_key, plaintext.as_bytes())?,
         #[cfg(feature = "apsv")]
@@ -54,26 +66,32 @@ pub(crate) fn enc_to_format(
         Scheme::Mock1 => encrypt_mock1(master_key, plaintext.as_bytes())?,
         #[cfg(feature = "mock")]
         Scheme::Mock2 => encrypt_mock2(master_key, plaintext.as_bytes())?,
+        #[cfg(feature = "mock")]
+        Scheme::Mock3 => encrypt_mock3(master_key, plaintext.as_bytes())?,
+        #[cfg(feature = "mock")]
+        Scheme::Mock4 => encrypt_mock4(master_key, plaintext.as_bytes())?,
         // Z-tier
         #[cfg(feature = "zrbcx")]
-        Scheme::Zrbcx => unreachable!("ztier uses separate path"),
+        Scheme::Zrbcx => return Err(Error::UnsupportedScheme { scheme }),
         #[cfg(feature = "zmock")]
-        Scheme::Zmock1 => unreachable!("ztier uses separate path"),
+        Scheme::Zmock1 => return Err(Error::UnsupportedScheme { scheme }),
         #[cfg(feature = "legacy")]
-        Scheme::Legacy => unreachable!("legacy uses separate path"),
+        Scheme::Legacy => return Err(Error::UnsupportedScheme { scheme }),
+        // Application-defined
+        #[cfg(feature = "custom-schemes")]
+        Scheme::Custom(c) => match c.imp().encrypt(master_key, plaintext.as_bytes())? {
+            ciphertext if ciphertext.is_empty() => return Err(Error::EncryptionFailed),
+            ciphertext => ciphertext,
+        },
+        // Only `NullOb` reports it, and no codec produces its obtext
+        #[cfg(feature = "testing")]
+        Scheme::Null => return Err(Error::UnknownScheme),
     };
 
     // Append marker and XOR
-    let marker = format.scheme().marker();
+    let marker = scheme.marker();
     let first_byte = ciphertext[0];
     ciphertext.push(marker[0] ^ first_byte);
     ciphertext.push(marker[1] ^ first_byte);
-
-    // Encode
-    Ok(match format.encoding() {
-        Encoding::C32 => BASE32_CROCKFORD.encode(&ciphertext),
-        Encoding::B32 => BASE32_RFC.encode(&ciphertext),
-        Encoding::B64 => BASE64URL_NOPAD.encode(&ciphertext),
-        Encoding::Hex => HEXLOWER.encode(&ciphertext),
-    })
+    Ok(ciphertext)
 }
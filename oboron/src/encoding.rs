@@ -1,34 +1,357 @@
-//! Text encoding identifiers for oboron output.
+//! Text encodings for oboron output.
+//!
+//! Besides the [`Encoding`] identifier, this module exposes the byte-level
+//! [`encode`] and [`decode`] functions used internally to turn payload
+//! bytes (ciphertext plus scheme marker) into obtext and back.  They apply
+//! no encryption, marker handling or reversal; the exact alphabets are:
+//!
+//! - `c32`: lowercase Crockford base32, no padding (decoding also accepts
+//!   uppercase, and `i`/`l`/`o` for `1`/`1`/`0`)
+//! - `b32`: uppercase RFC 4648 base32, no padding
+//! - `b64`: URL-safe RFC 4648 base64, no padding
+//! - `hex`: lowercase hex
+//! - `hexu`: uppercase hex
+//! - `custom:<symbols>`: a caller-supplied [`Alphabet`] of 2, 4, 8, 16, 32
+//!   or 64 symbols, each carrying a fixed number of bits, no padding
+//!
+//! Decoding is strict: it accepts only what [`encode`] produces, except
+//! that `b32` and `b64` text may carry trailing `=` padding, as emitted by
+//! many other tools.  Encoding never pads.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use oboron::encoding::{decode, encode};
+//! use oboron::Encoding;
+//!
+//! let text = encode(b"\x00\xffhi", Encoding::C32);
+//! assert_eq!(decode(&text, Encoding::C32)?, b"\x00\xffhi");
+//! # Ok::<(), oboron::Error>(())
+//! ```
 
+use crate::base32::{BASE32_CROCKFORD, BASE32_RFC};
 use crate::error::Error;
+use data_encoding::{Specification, BASE64URL_NOPAD, HEXLOWER, HEXUPPER};
+use once_cell::sync::Lazy;
+use std::sync::RwLock;
 
 /// Encoding identifier for text representation.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+///
+/// New encodings may be added in minor releases, so matches on `Encoding`
+/// outside this crate need a wildcard arm.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
 pub enum Encoding {
     B32,
     C32,
     B64,
     Hex,
+    /// Uppercase hex (`hexu`), for systems that emit and expect it.
+    ///
+    /// Encoding names parse case-insensitively, so `"HEX"` is lowercase
+    /// [`Hex`](Self::Hex); use `"hexu"` or `"hexupper"` for this variant.
+    ///
+    /// Runtime interfaces ([`Ob`](crate::Ob), [`Omnib`](crate::Omnib),
+    /// [`Obz`](crate::ztier::Obz)) support it; there are no fixed-format
+    /// codec types or format constants for it.
+    HexUpper,
+    /// A caller-supplied alphabet, e.g. one without vowels for short codes
+    /// that never spell words.
+    ///
+    /// Named `custom:<symbols>` in format strings, e.g.
+    /// `aasv.custom:0123456789bcdfghjkmnpqrstvwxyzBC`, which parses back to
+    /// the same alphabet once it has been created with [`Alphabet::new`]:
+    /// parsing never creates alphabets.  Obtext in a custom alphabet is never
+    /// autodetected, so it must be decoded with its format, e.g. by an
+    /// instance made with
+    /// [`Ob::new_custom_alphabet`](crate::Ob::new_custom_alphabet).
+    Custom(Alphabet),
+}
+
+/// Symbols of an [`Encoding::Custom`] encoding.
+///
+/// The radix must be a power of two from 2 to 64, so that each symbol
+/// carries a whole number of bits, as in base32 and base64.  Symbols are
+/// ASCII letters, digits, `-` and `_`, which keeps obtext URL-safe.
+///
+/// Alphabets are registered: [`new`](Self::new) validates each distinct
+/// alphabet and builds its codec once, then keeps it for the life of the
+/// process, so that `Alphabet` (and [`Encoding`]) stay `Copy`.  Create
+/// them from a bounded set such as configuration, not from untrusted
+/// input.  Parsing a `custom:<symbols>` encoding name only looks up
+/// alphabets already created this way and fails with
+/// [`Error::UnknownEncoding`] for any other.
+///
+/// # Examples
+///
+/// ```rust
+/// use oboron::encoding::{decode, encode, Alphabet};
+/// use oboron::Encoding;
+///
+/// // 32 symbols without vowels
+/// let alphabet = Alphabet::new("0123456789bcdfghjkmnpqrstvwxyzBC")?;
+/// let encoding = Encoding::Custom(alphabet);
+/// let text = encode(b"hi", encoding);
+/// assert!(!text.contains(['a', 'e', 'i', 'o', 'u']));
+/// assert_eq!(decode(&text, encoding)?, b"hi");
+/// # Ok::<(), oboron::Error>(())
+/// ```
+#[derive(Clone, Copy)]
+pub struct Alphabet(&'static AlphabetSpec);
+
+/// Interned data of an [`Alphabet`]
+struct AlphabetSpec {
+    /// `custom:<symbols>`, the encoding's name in format strings
+    name: String,
+    bits: usize,
+    codec: data_encoding::Encoding,
+}
+
+/// Prefix of a custom alphabet's encoding name
+const CUSTOM_PREFIX: &str = "custom:";
+
+static ALPHABETS: Lazy<RwLock<Vec<&'static AlphabetSpec>>> = Lazy::new(|| RwLock::new(Vec::new()));
+
+impl Alphabet {
+    /// Validate `symbols` as an alphabet and register it, or return the
+    /// alphabet already registered with these symbols.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidAlphabet`] if the number of symbols is not a
+    /// power of two from 2 to 64, a symbol is not an ASCII letter, digit,
+    /// `-` or `_`, or a symbol repeats.
+    pub fn new(symbols: &str) -> Result<Self, Error> {
+        Self::validate(symbols)?;
+        if let Some(alphabet) = Self::registered(symbols) {
+            return Ok(alphabet);
+        }
+
+        let mut alphabets = ALPHABETS.write().unwrap_or_else(|e| e.into_inner());
+        // Registered by another thread since the lookup above
+        if let Some(&spec) = alphabets.iter().find(|spec| spec.symbols() == symbols) {
+            return Ok(Self(spec));
+        }
+        let mut codec = Specification::new();
+        codec.symbols.push_str(symbols);
+        let spec: &'static AlphabetSpec = Box::leak(Box::new(AlphabetSpec {
+            name: format!("{}{}", CUSTOM_PREFIX, symbols),
+            bits: symbols.len().trailing_zeros() as usize,
+            codec: codec.encoding().expect("alphabet validated above"),
+        }));
+        alphabets.push(spec);
+        Ok(Self(spec))
+    }
+
+    /// Look up an alphabet already created by [`new`](Self::new).
+    fn registered(symbols: &str) -> Option<Self> {
+        let alphabets = ALPHABETS.read().unwrap_or_else(|e| e.into_inner());
+        alphabets
+            .iter()
+            .find(|spec| spec.symbols() == symbols)
+            .map(|&spec| Self(spec))
+    }
+
+    /// Check the rules of [`new`](Self::new) without registering anything.
+    fn validate(symbols: &str) -> Result<(), Error> {
+        let len = symbols.len();
+        if !(2..=64).contains(&len) || !len.is_power_of_two() {
+            return Err(Error::InvalidAlphabet {
+                reason: "must have 2, 4, 8, 16, 32 or 64 symbols",
+            });
+        }
+        let bytes = symbols.as_bytes();
+        if !bytes
+            .iter()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_'))
+        {
+            return Err(Error::InvalidAlphabet {
+                reason: "symbols must be ASCII letters, digits, '-' or '_'",
+            });
+        }
+        if (1..len).any(|i| bytes[..i].contains(&bytes[i])) {
+            return Err(Error::InvalidAlphabet {
+                reason: "symbols must be distinct",
+            });
+        }
+        Ok(())
+    }
+
+    /// The symbols, in digit order.
+    pub fn as_str(&self) -> &'static str {
+        self.0.symbols()
+    }
+
+    /// Number of symbols.
+    pub const fn radix(&self) -> usize {
+        1 << self.0.bits
+    }
+
+    /// Bits carried by each symbol.
+    const fn bits(&self) -> usize {
+        self.0.bits
+    }
+
+    /// The `data_encoding` codec for this alphabet, built by [`new`](Self::new).
+    fn codec(&self) -> &'static data_encoding::Encoding {
+        &self.0.codec
+    }
+}
+
+impl AlphabetSpec {
+    fn symbols(&self) -> &str {
+        &self.name[CUSTOM_PREFIX.len()..]
+    }
+}
+
+impl PartialEq for Alphabet {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Eq for Alphabet {}
+
+impl std::hash::Hash for Alphabet {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state);
+    }
+}
+
+impl PartialOrd for Alphabet {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Alphabet {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+impl std::fmt::Debug for Alphabet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Alphabet").field(&self.as_str()).finish()
+    }
 }
 
 impl Encoding {
-    /// Convert encoding to string representation.
+    /// The canonical encoding: Crockford base32 (`c32`), the first encoding
+    /// in [`Ord`] order.  See [`canonicalize`].
+    pub const CANONICAL: Encoding = Encoding::C32;
+
+    /// Every built-in encoding, in canonical order.  Custom alphabets are
+    /// not included.
+    pub const ALL: [Encoding; 5] = [
+        Encoding::C32,
+        Encoding::B32,
+        Encoding::B64,
+        Encoding::Hex,
+        Encoding::HexUpper,
+    ];
+
+    /// Position in canonical order: `c32`, `b32`, `b64`, `hex`, `hexu`,
+    /// then custom alphabets.
+    const fn rank(self) -> u8 {
+        match self {
+            Encoding::C32 => 0,
+            Encoding::B32 => 1,
+            Encoding::B64 => 2,
+            Encoding::Hex => 3,
+            Encoding::HexUpper => 4,
+            Encoding::Custom(_) => 5,
+        }
+    }
+
+    /// Convert encoding to long string representation (e.g. `base32crockford`).
+    ///
+    /// Accepted by [`Encoding::from_str`], like the abbreviated form.
     pub fn as_long_str(&self) -> &'static str {
         match self {
             Encoding::C32 => "base32crockford",
             Encoding::B32 => "base32rfc",
             Encoding::B64 => "base64",
             Encoding::Hex => "hex",
+            Encoding::HexUpper => "hexupper",
+            Encoding::Custom(alphabet) => &alphabet.0.name,
         }
     }
 
+    /// Alternative names accepted by [`Encoding::from_str`] for this
+    /// encoding, including the long name, e.g. `base64` and `b64url` for
+    /// `b64`.  Output always uses [`as_str`](Self::as_str).
+    pub fn aliases(&self) -> impl Iterator<Item = &'static str> {
+        let encoding = *self;
+        ENCODING_ALIASES
+            .iter()
+            .filter(move |(_, e)| *e == encoding)
+            .map(|(alias, _)| *alias)
+    }
+
     /// Convert encoding to abbreviated string representation (for format strings).
+    ///
+    /// A custom alphabet is named `custom:<symbols>`, so every encoding's
+    /// name parses back with [`Encoding::from_str`].
     pub fn as_str(&self) -> &'static str {
         match self {
             Encoding::C32 => "c32",
             Encoding::B32 => "b32",
             Encoding::B64 => "b64",
             Encoding::Hex => "hex",
+            Encoding::HexUpper => "hexu",
+            Encoding::Custom(alphabet) => &alphabet.0.name,
+        }
+    }
+
+    /// Human-readable name for display, e.g. `"Base32 (Crockford)"`.
+    ///
+    /// Not accepted by [`Encoding::from_str`]; use [`as_str`](Self::as_str)
+    /// or [`as_long_str`](Self::as_long_str) for values that are parsed back.
+    pub fn description(&self) -> &'static str {
+        match self {
+            Encoding::C32 => "Base32 (Crockford)",
+            Encoding::B32 => "Base32 (RFC 4648)",
+            Encoding::B64 => "Base64 (URL-safe)",
+            Encoding::Hex => "Hex",
+            Encoding::HexUpper => "Hex (uppercase)",
+            Encoding::Custom(_) => "Custom alphabet",
+        }
+    }
+
+    /// Length of the text [`encode`] produces for `raw_bytes` bytes, e.g.
+    /// to size a `String::with_capacity` up front.
+    ///
+    /// ```rust
+    /// # use oboron::Encoding;
+    /// assert_eq!(Encoding::C32.encoded_len(16), 26);
+    /// assert_eq!(Encoding::B64.encoded_len(16), 22);
+    /// assert_eq!(Encoding::Hex.encoded_len(16), 32);
+    /// ```
+    pub const fn encoded_len(&self, raw_bytes: usize) -> usize {
+        match self {
+            Encoding::C32 | Encoding::B32 => (raw_bytes * 8).div_ceil(5),
+            Encoding::B64 => (raw_bytes * 4).div_ceil(3),
+            Encoding::Hex | Encoding::HexUpper => raw_bytes * 2,
+            Encoding::Custom(alphabet) => (raw_bytes * 8).div_ceil(alphabet.bits()),
+        }
+    }
+
+    /// Upper bound on the bytes [`decode`] produces from `encoded_chars`
+    /// characters, e.g. to size a `Vec::with_capacity` up front.  Exact for
+    /// unpadded text; `=` padding only makes it an overestimate.
+    ///
+    /// ```rust
+    /// # use oboron::Encoding;
+    /// assert_eq!(Encoding::C32.decoded_len(26), 16);
+    /// assert_eq!(Encoding::B64.decoded_len(24), 18); // at most 18 bytes
+    /// ```
+    pub const fn decoded_len(&self, encoded_chars: usize) -> usize {
+        match self {
+            Encoding::C32 | Encoding::B32 => encoded_chars * 5 / 8,
+            Encoding::B64 => encoded_chars * 3 / 4,
+            Encoding::Hex | Encoding::HexUpper => encoded_chars / 2,
+            Encoding::Custom(alphabet) => encoded_chars * alphabet.bits() / 8,
         }
     }
 
@@ -42,22 +365,289 @@ impl std::str::FromStr for Encoding {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Symbols are case-sensitive, so only the prefix is matched loosely.
+        // Only registered alphabets resolve: parsing must not register one
+        // for every string it is given.
+        if let Some(prefix) = s.get(..CUSTOM_PREFIX.len()) {
+            if prefix.eq_ignore_ascii_case(CUSTOM_PREFIX) {
+                let symbols = &s[CUSTOM_PREFIX.len()..];
+                Alphabet::validate(symbols)?;
+                return Alphabet::registered(symbols)
+                    .map(Encoding::Custom)
+                    .ok_or(Error::UnknownEncoding);
+            }
+        }
         match s.to_lowercase().as_str() {
             "b32" => Ok(Encoding::B32),
             "c32" => Ok(Encoding::C32),
             "b64" => Ok(Encoding::B64),
             "hex" => Ok(Encoding::Hex),
-            // Long names
-            "base32crockford" => Ok(Encoding::C32),
-            "base32rfc" => Ok(Encoding::B32),
-            "base64" => Ok(Encoding::B64),
-            _ => Err(Error::UnknownEncoding),
+            "hexu" => Ok(Encoding::HexUpper),
+            other => ENCODING_ALIASES
+                .iter()
+                .find(|(alias, _)| *alias == other)
+                .map(|&(_, encoding)| encoding)
+                .ok_or(Error::UnknownEncoding),
+        }
+    }
+}
+
+/// Alternative encoding names: the long names, then common spellings used
+/// by other tools.
+const ENCODING_ALIASES: &[(&str, Encoding)] = &[
+    ("base32crockford", Encoding::C32),
+    ("base32rfc", Encoding::B32),
+    ("base64", Encoding::B64),
+    ("hexupper", Encoding::HexUpper),
+    ("crockford", Encoding::C32),
+    ("base32", Encoding::B32),
+    ("b64url", Encoding::B64),
+    ("base64url", Encoding::B64),
+    ("base16", Encoding::Hex),
+];
+
+/// Encodings sort in canonical order, so the minimum of a set of encodings
+/// is the preferred one: `c32`, `b32`, `b64`, `hex`, `hexu`, then custom
+/// alphabets by their symbols.
+impl Ord for Encoding {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        match (self, other) {
+            (Encoding::Custom(a), Encoding::Custom(b)) => a.cmp(b),
+            _ => self.rank().cmp(&other.rank()),
         }
     }
 }
 
+impl PartialOrd for Encoding {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 impl std::fmt::Display for Encoding {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.as_str())
     }
 }
+
+/// Encode raw bytes as text in the given encoding.
+#[inline]
+pub fn encode(bytes: &[u8], encoding: Encoding) -> String {
+    match encoding {
+        Encoding::C32 => BASE32_CROCKFORD.encode(bytes),
+        Encoding::B32 => BASE32_RFC.encode(bytes),
+        Encoding::B64 => BASE64URL_NOPAD.encode(bytes),
+        Encoding::Hex => HEXLOWER.encode(bytes),
+        Encoding::HexUpper => HEXUPPER.encode(bytes),
+        Encoding::Custom(alphabet) => alphabet.codec().encode(bytes),
+    }
+}
+
+/// Decode text in the given encoding back to raw bytes.
+///
+/// # Errors
+///
+/// Returns the encoding's error variant ([`Error::InvalidC32`],
+/// [`Error::InvalidB32`], [`Error::InvalidB64`], [`Error::InvalidHex`] or
+/// [`Error::InvalidCustom`]) if `text` is not valid in that encoding.
+#[inline]
+pub fn decode(text: &str, encoding: Encoding) -> Result<Vec<u8>, Error> {
+    match encoding {
+        Encoding::C32 => BASE32_CROCKFORD
+            .decode(text.as_bytes())
+            .map_err(|_| Error::InvalidC32),
+        Encoding::B32 => BASE32_RFC
+            .decode(strip_padding(text, 6).as_bytes())
+            .map_err(|_| Error::InvalidB32),
+        Encoding::B64 => BASE64URL_NOPAD
+            .decode(strip_padding(text, 2).as_bytes())
+            .map_err(|_| Error::InvalidB64),
+        Encoding::Hex => HEXLOWER
+            .decode(text.as_bytes())
+            .map_err(|_| Error::InvalidHex),
+        Encoding::HexUpper => HEXUPPER
+            .decode(text.as_bytes())
+            .map_err(|_| Error::InvalidHex),
+        Encoding::Custom(alphabet) => alphabet
+            .codec()
+            .decode(text.as_bytes())
+            .map_err(|_| Error::InvalidCustom),
+    }
+}
+
+/// Whether `text` is c32 as a person might retype it: all uppercase or all
+/// lowercase, with the Crockford substitutes `i`, `l` and `o` allowed.
+/// Mixed case is left to b64.
+fn fits_c32_any_case(text: &str) -> bool {
+    let upper = text.bytes().any(|b| b.is_ascii_uppercase());
+    let lower = text.bytes().any(|b| b.is_ascii_lowercase());
+    !(upper && lower)
+        && text
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() && !matches!(b, b'u' | b'U'))
+}
+
+/// Whether every character of `text` is in the alphabet of `encoding`,
+/// counting the `=` padding that [`decode`] tolerates.
+fn fits_alphabet(text: &str, encoding: Encoding) -> bool {
+    text.bytes().all(|b| match encoding {
+        Encoding::C32 => matches!(b, b'0'..=b'9' | b'a'..=b'z') && !b"ilou".contains(&b),
+        Encoding::B32 => matches!(b, b'A'..=b'Z' | b'2'..=b'7' | b'='),
+        Encoding::B64 => b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'='),
+        Encoding::Hex => matches!(b, b'0'..=b'9' | b'a'..=b'f'),
+        Encoding::HexUpper => matches!(b, b'0'..=b'9' | b'A'..=b'F'),
+        Encoding::Custom(alphabet) => alphabet.as_str().as_bytes().contains(&b),
+    })
+}
+
+/// View `bytes` as obtext, checking each byte against the union of the
+/// encoding alphabets (all ASCII) rather than validating UTF-8.
+pub(crate) fn obtext_from_bytes(bytes: &[u8]) -> Result<&str, Error> {
+    let invalid = |b: &u8| !(b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'='));
+    if let Some(offset) = bytes.iter().position(invalid) {
+        return Err(Error::InvalidObtextByte {
+            byte: bytes[offset],
+            offset,
+        });
+    }
+    // SAFETY: every byte was checked to be ASCII above
+    Ok(unsafe { std::str::from_utf8_unchecked(bytes) })
+}
+
+/// Reject `text` with [`Error::EncodingMismatch`] if its characters rule
+/// out `requested` but fit another encoding.
+///
+/// Text that fits no alphabet passes, leaving [`decode`] to report it.
+pub(crate) fn check_encoding(text: &str, requested: Encoding) -> Result<(), Error> {
+    if fits_alphabet(text, requested) || (requested == Encoding::C32 && fits_c32_any_case(text)) {
+        return Ok(());
+    }
+    // Narrowest alphabets first
+    let candidates = [
+        Encoding::Hex,
+        Encoding::HexUpper,
+        Encoding::C32,
+        Encoding::B32,
+        Encoding::B64,
+    ];
+    match candidates.into_iter().find(|&e| fits_alphabet(text, e)) {
+        Some(detected) => Err(Error::EncodingMismatch {
+            requested,
+            detected,
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Strip up to `max` trailing `=` padding characters.  Longer runs are
+/// left in place so that decoding rejects them.
+#[inline]
+fn strip_padding(text: &str, max: usize) -> &str {
+    let stripped = text.trim_end_matches('=');
+    if text.len() - stripped.len() <= max {
+        stripped
+    } else {
+        text
+    }
+}
+
+/// Re-encode obtext from one encoding to another, without a key.
+///
+/// The payload bytes are carried over unchanged, so the result decodes
+/// under the same scheme in the new encoding (e.g. `aasv.c32` obtext
+/// becomes `aasv.b64` obtext).  Legacy obtext is not a plain encoding of
+/// its payload and cannot be transcoded.
+///
+/// # Errors
+///
+/// Returns the decoding error for `from` if `text` is not valid in it.
+#[inline]
+pub fn transcode(text: &str, from: Encoding, to: Encoding) -> Result<String, Error> {
+    decode(text, from).map(|bytes| encode(&bytes, to))
+}
+
+/// Re-encode obtext into the canonical encoding ([`Encoding::CANONICAL`],
+/// `c32`), without a key.
+///
+/// All renderings of the same ciphertext canonicalize to the same string,
+/// giving storage and deduplication layers a single form to compare.  As
+/// with [`transcode`], legacy obtext cannot be canonicalized.
+///
+/// # Errors
+///
+/// Returns the decoding error for `from` if `text` is not valid in it.
+#[inline]
+pub fn canonicalize(text: &str, from: Encoding) -> Result<String, Error> {
+    transcode(text, from, Encoding::CANONICAL)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_short_and_long_names_roundtrip() {
+        for encoding in Encoding::ALL {
+            assert_eq!(Encoding::from_str(encoding.as_str()), Ok(encoding));
+            assert_eq!(Encoding::from_str(encoding.as_long_str()), Ok(encoding));
+            assert_eq!(
+                Encoding::from_str(&encoding.as_long_str().to_uppercase()),
+                Ok(encoding)
+            );
+        }
+    }
+
+    #[test]
+    fn test_aliases_roundtrip() {
+        for encoding in Encoding::ALL {
+            for alias in encoding.aliases() {
+                assert_eq!(Encoding::from_str(alias), Ok(encoding));
+                assert_ne!(alias, encoding.as_str());
+            }
+        }
+        assert_eq!(Encoding::from_str("B64URL"), Ok(Encoding::B64));
+        assert!(Encoding::B64.aliases().any(|a| a == "b64url"));
+        assert_eq!(Encoding::from_str("b64u"), Err(Error::UnknownEncoding));
+    }
+
+    #[test]
+    fn test_padded_input_decodes_like_unpadded() {
+        for len in 1..=12 {
+            let bytes: Vec<u8> = (0..len).map(|i| (i as u8).wrapping_mul(37)).collect();
+            for (encoding, block, pad) in [(Encoding::B32, 8, 6), (Encoding::B64, 4, 2)] {
+                let text = encode(&bytes, encoding);
+                let padding = "=".repeat((block - text.len() % block) % block);
+                assert_eq!(
+                    decode(&(text.clone() + &padding), encoding),
+                    Ok(bytes.clone())
+                );
+                let overpadded = text + &"=".repeat(pad + 1);
+                assert!(decode(&overpadded, encoding).is_err());
+            }
+        }
+        // Other encodings stay strict
+        assert!(decode("00ff=", Encoding::Hex).is_err());
+        assert!(decode("0000=", Encoding::C32).is_err());
+    }
+
+    #[test]
+    fn test_canonical_order() {
+        let mut sorted = Encoding::ALL;
+        sorted.reverse();
+        sorted.sort();
+        assert_eq!(sorted, Encoding::ALL);
+        assert_eq!(Encoding::ALL.iter().min(), Some(&Encoding::CANONICAL));
+    }
+
+    #[test]
+    fn test_descriptions_are_distinct() {
+        for a in Encoding::ALL {
+            assert!(!a.description().is_empty());
+            for b in Encoding::ALL {
+                if a != b {
+                    assert_ne!(a.description(), b.description());
+                }
+            }
+        }
+    }
+}
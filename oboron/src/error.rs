@@ -8,19 +8,45 @@ pub enum Error {
     // ----------
     #[error("key must be 64 bytes")]
     InvalidKeyLength,
+    #[error("key must be {expected} base64 characters (without padding), got {got}")]
+    KeyWrongLength { expected: usize, got: usize },
+    #[error("key is not valid base64: expected URL-safe A-Za-z0-9-_ or standard A-Za-z0-9+/")]
+    KeyInvalidEncoding,
+    #[error("environment variable {name} is not set or not valid unicode")]
+    MissingEnvVar { name: &'static str },
+    #[error("keyless key already in use; set_test_key must precede any keyless use")]
+    KeylessKeyInUse,
 
     // Encoding errors
     // ---------------
-    #[error("invalid hex encoding")]
+    #[error("invalid hex encoding: expected 0-9a-f (0-9A-F for hexu)")]
     InvalidHex,
-    #[error("invalid base64 encoding")]
+    #[error("invalid base64 encoding: expected URL-safe A-Za-z0-9-_, optionally padded with '='")]
     InvalidB64,
-    #[error("invalid base32rfc encoding")]
+    #[error(
+        "invalid base32rfc encoding: expected uppercase A-Z and 2-7, optionally padded with '='"
+    )]
     InvalidB32,
-    #[error("invalid base32crockford encoding")]
+    #[error(
+        "invalid base32crockford encoding: expected lowercase 0-9a-z excluding i, l, o, u \
+         (uppercase, and i, l, o for 1, 1, 0, are also accepted)"
+    )]
     InvalidC32,
+    #[error("invalid encoding: expected symbols of the custom alphabet")]
+    InvalidCustom,
+    #[error("invalid custom alphabet: {reason}")]
+    InvalidAlphabet { reason: &'static str },
+    #[error(
+        "obtext looks {detected}-encoded, not {requested}; use autodec to detect the encoding"
+    )]
+    EncodingMismatch {
+        requested: crate::Encoding,
+        detected: crate::Encoding,
+    },
     #[error("invalid UTF-8")]
     InvalidUtf8,
+    #[error("byte 0x{byte:02x} at offset {offset} is not in any obtext alphabet")]
+    InvalidObtextByte { byte: u8, offset: usize },
 
     // Format/scheme errors
     // --------------------
@@ -32,6 +58,30 @@ pub enum Error {
     UnknownScheme,
     #[error("unknown encoding")]
     UnknownEncoding,
+    #[error("scheme '{scheme}' is not enabled in this build (enable the `{feature}` feature)")]
+    SchemeNotEnabled {
+        scheme: &'static str,
+        feature: &'static str,
+    },
+    #[error("obtext looks like legacy data, but the `legacy` scheme is not enabled in this build (enable the `legacy` feature)")]
+    LegacySchemeNotEnabled,
+    #[error("scheme '{scheme}' conflicts with an existing scheme name or marker")]
+    SchemeConflict { scheme: &'static str },
+    #[error("scheme '{scheme}' is probabilistic; a deterministic scheme is required")]
+    ProbabilisticScheme { scheme: &'static str },
+    #[error("scheme '{scheme}' is not supported here; z-tier and legacy schemes go through Obz or Legacy")]
+    UnsupportedScheme { scheme: crate::Scheme },
+    #[error("format {format} has no typed codec; use Ob or Omnib")]
+    UnsupportedFormat { format: crate::Format },
+    #[error("scheme '{scheme}' does not use a key, so its obtext cannot verify one")]
+    UnkeyedScheme { scheme: crate::Scheme },
+
+    // Keyring errors
+    // --------------
+    #[error("unknown key id '{key_id}'")]
+    UnknownKeyId { key_id: String },
+    #[error("duplicate key id '{key_id}'")]
+    DuplicateKeyId { key_id: String },
 
     // Encryption errors
     // -----------------
@@ -39,10 +89,25 @@ pub enum Error {
     EncryptionFailed,
     #[error("enc failed: empty plaintext")]
     EmptyPlaintext,
+    #[error("enc failed: plaintext of {len} bytes is shorter than the minimum of {min}")]
+    PlaintextTooShort { len: usize, min: usize },
+    #[error("nonce must be {expected} bytes, got {actual}")]
+    InvalidNonceLength { expected: usize, actual: usize },
     #[error("dec failed: empty payload")]
     EmptyPayload,
     #[error("dec failed: payload too short")]
     PayloadTooShort,
+    #[error("dec failed: obtext too short for any enabled scheme (truncated or not an obtext)")]
+    TruncatedObtext,
+    #[error("input of {len} bytes exceeds the configured maximum of {max}")]
+    InputTooLarge { len: usize, max: usize },
+    #[error("autodetection gave up after {attempts} attempts: {last_error}")]
+    AutodetectExhausted {
+        attempts: usize,
+        last_error: Box<Error>,
+    },
+    #[error("{0}")]
+    DecodeFailed(DecodeFailure),
 
     // Decryption errors
     // -----------------
@@ -50,13 +115,170 @@ pub enum Error {
     DecryptionFailed,
     #[error("invalid block length")]
     InvalidBlockLength,
+    #[error("dec failed: a {len}-byte ciphertext cannot be {scheme} (corrupt or not an obtext)")]
+    MalformedCiphertext { scheme: crate::Scheme, len: usize },
     #[error("decoding failed: scheme marker mismatch")]
     SchemeMarkerMismatch,
+    #[error("decoding failed: expected {expected} obtext, found {found}")]
+    SchemeMismatch {
+        expected: crate::Scheme,
+        found: crate::Scheme,
+    },
+    #[error("expected {expected} obtext, found {found}")]
+    FormatMismatch {
+        expected: crate::Format,
+        found: crate::Format,
+    },
+    #[error("not a data URL of the form data:<mime>;ob=<format>,<obtext>")]
+    InvalidDataUrl,
+    #[error("token has {count} segments, none at index {index}")]
+    MissingSegment { index: usize, count: usize },
+    #[error("self-test failed: {format} round trip did not return the sentinel")]
+    SelfTestFailed { format: crate::Format },
+    #[error("format {format} is not allowed by this instance")]
+    FormatNotAllowed { format: crate::Format },
     #[cfg(feature = "legacy")]
     #[error("legacy fallback produced invalid output (likely encoding mismatch)")]
     InvalidLegacyOutput,
 }
 
+impl Error {
+    /// The underlying error: the cause of a [`DecodeFailed`](Self::DecodeFailed),
+    /// the last attempt's error of an
+    /// [`AutodetectExhausted`](Self::AutodetectExhausted), else this error
+    /// itself.
+    pub fn root_cause(&self) -> &Error {
+        match self {
+            Error::DecodeFailed(failure) => &failure.cause,
+            Error::AutodetectExhausted { last_error, .. } => last_error,
+            _ => self,
+        }
+    }
+}
+
+/// The step of dec at which a [`DecodeFailure`] happened.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DecodeStage {
+    /// The obtext could not be decoded from its text encoding.
+    Decode,
+    /// The obtext decoded, but its payload did not decrypt.
+    Decrypt,
+}
+
+impl DecodeStage {
+    /// Classify a dec error, or `None` if it is not a decode or decrypt
+    /// failure (e.g. an oversized input or an invalid format).
+    fn of(error: &Error) -> Option<Self> {
+        match error {
+            Error::InvalidHex
+            | Error::InvalidB64
+            | Error::InvalidB32
+            | Error::InvalidC32
+            | Error::InvalidCustom
+            | Error::EncodingMismatch { .. }
+            | Error::InvalidObtextByte { .. } => Some(Self::Decode),
+            Error::EmptyPayload
+            | Error::PayloadTooShort
+            | Error::TruncatedObtext
+            | Error::DecryptionFailed
+            | Error::InvalidBlockLength
+            | Error::MalformedCiphertext { .. }
+            | Error::SchemeMarkerMismatch
+            | Error::SchemeMismatch { .. }
+            | Error::UnknownScheme
+            | Error::InvalidUtf8 => Some(Self::Decrypt),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for DecodeStage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Decode => "decode",
+            Self::Decrypt => "decrypt",
+        })
+    }
+}
+
+/// Structured report of a failed dec, carried by [`Error::DecodeFailed`],
+/// e.g. to aggregate failure reasons in logs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodeFailure {
+    /// Length of the obtext in bytes
+    pub input_len: usize,
+    /// Encodings the obtext was decoded as, in order
+    pub encodings: Vec<crate::Encoding>,
+    /// Whether the last attempt failed to decode or to decrypt
+    pub stage: DecodeStage,
+    /// The error of the last attempt
+    pub cause: Box<Error>,
+}
+
+impl DecodeFailure {
+    /// Wrap `error` from a dec of `obtext` in [`Error::DecodeFailed`], or
+    /// return it unchanged if it is not a decode or decrypt failure.
+    pub(crate) fn wrap(error: Error, obtext: &str, encodings: Vec<crate::Encoding>) -> Error {
+        match DecodeStage::of(&error) {
+            Some(stage) => Error::DecodeFailed(Self {
+                input_len: obtext.len(),
+                encodings,
+                stage,
+                cause: Box::new(error),
+            }),
+            None => error,
+        }
+    }
+}
+
+impl std::fmt::Display for DecodeFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} failed on {}-byte obtext (tried ",
+            self.stage, self.input_len
+        )?;
+        for (i, encoding) in self.encodings.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{}", encoding)?;
+        }
+        write!(f, "): {}", self.cause)
+    }
+}
+
+/// Reject `plaintext` if it is shorter than `scheme` accepts.
+#[inline]
+pub(crate) fn check_plaintext_len(plaintext: &str, scheme: crate::Scheme) -> Result<(), Error> {
+    let min = scheme.min_plaintext_len();
+    if plaintext.len() < min {
+        return Err(Error::PlaintextTooShort {
+            len: plaintext.len(),
+            min,
+        });
+    }
+    Ok(())
+}
+
+/// Reject `input` if it is longer than `max_len` bytes (`None` = unlimited).
+#[inline]
+pub(crate) fn check_input_len(input: &str, max_len: Option<usize>) -> Result<(), Error> {
+    match max_len {
+        Some(max) if input.len() > max => Err(Error::InputTooLarge {
+            len: input.len(),
+            max,
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// Read the environment variable `name` for a `from_env` constructor.
+#[cfg(feature = "std")]
+pub(crate) fn env_var(name: &'static str) -> Result<String, Error> {
+    std::env::var(name).map_err(|_| Error::MissingEnvVar { name })
+}
+
 impl From<hex::FromHexError> for Error {
     fn from(_: hex::FromHexError) -> Self {
         Error::InvalidHex
@@ -1,9 +1,11 @@
 //! Format combines a scheme (encryption method) with an encoding (text representation).  
 
 use crate::{Encoding, Error, Scheme};
+use std::collections::HashMap;
+use std::sync::RwLock;
 
 /// Format combines a scheme (encryption method) with an encoding (text representation).
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Format {
     scheme: Scheme,
     encoding: Encoding,
@@ -11,19 +13,104 @@ pub struct Format {
 
 impl Format {
     /// Create a new format with the specified scheme and encoding.
+    ///
+    /// Like [`with_scheme`](Self::with_scheme) and the accessors, this is a
+    /// `const fn`, so formats can be module-level constants:
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "aasv")]
+    /// # {
+    /// use oboron::{Encoding, Format, Scheme};
+    ///
+    /// const TOKEN_FORMAT: Format = Format::new(Scheme::Aasv, Encoding::B64);
+    /// const TOKEN_ENCODING: Encoding = TOKEN_FORMAT.encoding();
+    /// assert_eq!(TOKEN_ENCODING, Encoding::B64);
+    /// # }
+    /// ```
     pub const fn new(scheme: Scheme, encoding: Encoding) -> Self {
         Self { scheme, encoding }
     }
 
+    /// Create a format with the given scheme and its default encoding,
+    /// [`canonical_encoding`](Self::canonical_encoding) (`c32` except for
+    /// legacy).
+    pub const fn with_scheme(scheme: Scheme) -> Self {
+        let format = Self::new(scheme, Encoding::CANONICAL);
+        Self::new(scheme, format.canonical_encoding())
+    }
+
     /// Get the scheme.
-    pub fn scheme(&self) -> Scheme {
+    pub const fn scheme(&self) -> Scheme {
         self.scheme
     }
 
     /// Get the encoding.
-    pub fn encoding(&self) -> Encoding {
+    pub const fn encoding(&self) -> Encoding {
         self.encoding
     }
+
+    /// Split into scheme and encoding; the inverse of `From<(Scheme, Encoding)>`.
+    pub const fn into_parts(self) -> (Scheme, Encoding) {
+        (self.scheme, self.encoding)
+    }
+
+    /// The encoding to store obtext of this format's scheme in, when a
+    /// single canonical rendering is wanted (e.g. for deduplication).
+    ///
+    /// This is [`Encoding::CANONICAL`] (`c32`) for every scheme except
+    /// legacy, whose only encoding is `b32`.  Obtext in any other encoding
+    /// converts to it without the key via
+    /// [`canonicalize`](crate::encoding::canonicalize).
+    pub const fn canonical_encoding(&self) -> Encoding {
+        #[cfg(feature = "legacy")]
+        if matches!(self.scheme, Scheme::Legacy) {
+            return Encoding::B32;
+        }
+        Encoding::CANONICAL
+    }
+
+    /// Whether every obtext of a given plaintext is the same, so storage
+    /// sized from one encryption fits all later ones, e.g. a fixed-width
+    /// column keyed on the plaintext.
+    ///
+    /// True for deterministic schemes.  Probabilistic schemes draw a fresh
+    /// [`nonce_len`](Scheme::nonce_len)-byte nonce per call, so their
+    /// obtext varies; its length is bounded by the plaintext length plus
+    /// the nonce, tag and marker.
+    pub fn output_is_fixed_length(&self) -> bool {
+        self.scheme.is_deterministic()
+    }
+
+    /// Whether both formats use the same encoding.
+    pub fn shares_encoding(&self, other: &Format) -> bool {
+        self.encoding == other.encoding
+    }
+
+    /// Whether a decoder set up for this format can autodetect obtext of
+    /// `other`, e.g. `Ob::autodec` with `aasv.c32` decoding `mock1.c32`.
+    ///
+    /// Autodetection across schemes works only with a shared encoding, and
+    /// only within a tier: the secure schemes are detected by `Ob`/`Omnib`,
+    /// the z-tier schemes by `Obz`/`Omnibz`.  Legacy has no scheme marker,
+    /// so it is only compatible with itself.
+    pub fn autodetect_compatible(&self, other: &Format) -> bool {
+        if self == other {
+            return true;
+        }
+        #[cfg(feature = "legacy")]
+        if self.scheme == Scheme::Legacy || other.scheme == Scheme::Legacy {
+            return false;
+        }
+        // Z-tier schemes are autodetected by the z-tier decoders, the rest by
+        // the secure ones
+        self.shares_encoding(other) && self.scheme.is_ztier() == other.scheme.is_ztier()
+    }
+}
+
+impl From<(Scheme, Encoding)> for Format {
+    fn from((scheme, encoding): (Scheme, Encoding)) -> Self {
+        Self::new(scheme, encoding)
+    }
 }
 
 #[cfg(feature = "zrbcx")]
@@ -61,6 +148,14 @@ pub(crate) mod apgs_formats {
     pub const APGS_B64: Format = Format::new(Scheme::Apgs, Encoding::B64);
     pub const APGS_HEX: Format = Format::new(Scheme::Apgs, Encoding::Hex);
 }
+#[cfg(feature = "apgc")]
+pub(crate) mod apgc_formats {
+    use super::{Encoding, Format, Scheme};
+    pub const APGC_C32: Format = Format::new(Scheme::Apgc, Encoding::C32);
+    pub const APGC_B32: Format = Format::new(Scheme::Apgc, Encoding::B32);
+    pub const APGC_B64: Format = Format::new(Scheme::Apgc, Encoding::B64);
+    pub const APGC_HEX: Format = Format::new(Scheme::Apgc, Encoding::Hex);
+}
 
 #[cfg(feature = "aasv")]
 pub(crate) mod aasv_formats {
@@ -97,6 +192,14 @@ pub(crate) mod mock_formats {
     pub const MOCK2_B32: Format = Format::new(Scheme::Mock2, Encoding::B32);
     pub const MOCK2_B64: Format = Format::new(Scheme::Mock2, Encoding::B64);
     pub const MOCK2_HEX: Format = Format::new(Scheme::Mock2, Encoding::Hex);
+    pub const MOCK3_C32: Format = Format::new(Scheme::Mock3, Encoding::C32);
+    pub const MOCK3_B32: Format = Format::new(Scheme::Mock3, Encoding::B32);
+    pub const MOCK3_B64: Format = Format::new(Scheme::Mock3, Encoding::B64);
+    pub const MOCK3_HEX: Format = Format::new(Scheme::Mock3, Encoding::Hex);
+    pub const MOCK4_C32: Format = Format::new(Scheme::Mock4, Encoding::C32);
+    pub const MOCK4_B32: Format = Format::new(Scheme::Mock4, Encoding::B32);
+    pub const MOCK4_B64: Format = Format::new(Scheme::Mock4, Encoding::B64);
+    pub const MOCK4_HEX: Format = Format::new(Scheme::Mock4, Encoding::Hex);
 }
 #[cfg(feature = "zmock")]
 pub(crate) mod zmock_formats {
@@ -110,9 +213,22 @@ pub(crate) mod zmock_formats {
 impl Format {
     /// Parse format from compact string representation (e.g., "zrbcx.c32", "aags.b64")
     ///
+    /// A bare scheme name (e.g. "aasv" or its alias "ob32") stands for the
+    /// scheme's default format, [`Format::with_scheme`].  That includes the
+    /// numeric aliases, so a bare number such as "32" parses too (as
+    /// `aasv.c32`); check for a `.` first where that is unwanted.
+    ///
     /// This uses fast match-based parsing for maximum performance.
     pub fn from_str(s: &str) -> Result<Self, Error> {
-        Ok(match s {
+        match Self::from_constant_str(s) {
+            Some(format) => Ok(format),
+            None => Self::from_other_str(s),
+        }
+    }
+
+    /// Match `s` against the string forms of the format constants.
+    fn from_constant_str(s: &str) -> Option<Self> {
+        Some(match s {
             #[cfg(feature = "zrbcx")]
             crate::ZRBCX_C32_STR => zrbcx_formats::ZRBCX_C32,
             #[cfg(feature = "zrbcx")]
@@ -148,6 +264,14 @@ impl Format {
             crate::APGS_B64_STR => apgs_formats::APGS_B64,
             #[cfg(feature = "apgs")]
             crate::APGS_HEX_STR => apgs_formats::APGS_HEX,
+            #[cfg(feature = "apgc")]
+            crate::APGC_C32_STR => apgc_formats::APGC_C32,
+            #[cfg(feature = "apgc")]
+            crate::APGC_B32_STR => apgc_formats::APGC_B32,
+            #[cfg(feature = "apgc")]
+            crate::APGC_B64_STR => apgc_formats::APGC_B64,
+            #[cfg(feature = "apgc")]
+            crate::APGC_HEX_STR => apgc_formats::APGC_HEX,
 
             #[cfg(feature = "aasv")]
             crate::AASV_C32_STR => aasv_formats::AASV_C32,
@@ -189,6 +313,26 @@ impl Format {
             #[cfg(feature = "mock")]
             crate::MOCK2_HEX_STR => mock_formats::MOCK2_HEX,
 
+            // mock3 variants
+            #[cfg(feature = "mock")]
+            crate::MOCK3_C32_STR => mock_formats::MOCK3_C32,
+            #[cfg(feature = "mock")]
+            crate::MOCK3_B32_STR => mock_formats::MOCK3_B32,
+            #[cfg(feature = "mock")]
+            crate::MOCK3_B64_STR => mock_formats::MOCK3_B64,
+            #[cfg(feature = "mock")]
+            crate::MOCK3_HEX_STR => mock_formats::MOCK3_HEX,
+
+            // mock4 variants
+            #[cfg(feature = "mock")]
+            crate::MOCK4_C32_STR => mock_formats::MOCK4_C32,
+            #[cfg(feature = "mock")]
+            crate::MOCK4_B32_STR => mock_formats::MOCK4_B32,
+            #[cfg(feature = "mock")]
+            crate::MOCK4_B64_STR => mock_formats::MOCK4_B64,
+            #[cfg(feature = "mock")]
+            crate::MOCK4_HEX_STR => mock_formats::MOCK4_HEX,
+
             // zmock1 variants
             #[cfg(feature = "zmock")]
             crate::ZMOCK1_C32_STR => zmock_formats::ZMOCK1_C32,
@@ -203,9 +347,39 @@ impl Format {
             #[cfg(feature = "legacy")]
             crate::LEGACY_STR => legacy_formats::LEGACY,
 
-            _ => return Err(Error::InvalidFormat),
+            _ => return None,
         })
     }
+
+    /// Parse a format string with no format constant: uppercase hex,
+    /// aliases (e.g. `ob32.b64url`), bare scheme names and custom schemes.
+    fn from_other_str(s: &str) -> Result<Self, Error> {
+        if !s.contains('.') {
+            if let Ok(scheme) = s.parse::<Scheme>() {
+                return Ok(Format::with_scheme(scheme));
+            }
+            #[cfg(feature = "custom-schemes")]
+            if let Some(scheme) = crate::custom_scheme::by_name(s) {
+                return Ok(Format::with_scheme(scheme));
+            }
+        }
+        if let Some((scheme, encoding)) = s.split_once('.') {
+            match (scheme.parse::<Scheme>(), encoding.parse::<Encoding>()) {
+                #[cfg(feature = "legacy")]
+                (Ok(Scheme::Legacy), _) => return Err(Error::InvalidFormat),
+                (Ok(scheme), Ok(encoding)) => return Ok(Format::new(scheme, encoding)),
+                _ => {}
+            }
+        }
+        #[cfg(feature = "custom-schemes")]
+        if let Some((scheme, encoding)) = s.split_once('.') {
+            if let Some(scheme) = crate::custom_scheme::by_name(scheme) {
+                return Ok(Format::new(scheme, encoding.parse()?));
+            }
+        }
+        let scheme = s.split('.').next().unwrap_or_default();
+        Err(crate::scheme::disabled_scheme_error(scheme).unwrap_or(Error::InvalidFormat))
+    }
 }
 
 impl std::str::FromStr for Format {
@@ -227,12 +401,42 @@ impl std::fmt::Display for Format {
     }
 }
 
+impl From<Format> for String {
+    fn from(format: Format) -> Self {
+        format.to_string()
+    }
+}
+
+impl TryFrom<&str> for Format {
+    type Error = Error;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        Format::from_str(s)
+    }
+}
+
+impl TryFrom<String> for Format {
+    type Error = Error;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        Format::from_str(&s)
+    }
+}
+
 /// Trait for types that can be converted into a Format.
 ///
-/// This trait is sealed and only implemented for `&str`, `Format`, and `&Format`.
+/// This trait is sealed and only implemented for `&str`, `String`, `&String`,
+/// `Format`, `&Format` and `(Scheme, Encoding)`.
 pub trait IntoFormat: private::Sealed {
     /// Convert into a Format, possibly returning an error.
     fn into_format(self) -> Result<Format, Error>;
+
+    /// The format string, for types that still need parsing, so that
+    /// callers can look it up in a cache instead.
+    #[doc(hidden)]
+    fn as_format_str(&self) -> Option<&str> {
+        None
+    }
 }
 
 impl IntoFormat for Format {
@@ -251,18 +455,91 @@ impl IntoFormat for &str {
     fn into_format(self) -> Result<Format, Error> {
         Format::from_str(self)
     }
+
+    fn as_format_str(&self) -> Option<&str> {
+        Some(self)
+    }
 }
 
 impl IntoFormat for String {
     fn into_format(self) -> Result<Format, Error> {
         Format::from_str(&self)
     }
+
+    fn as_format_str(&self) -> Option<&str> {
+        Some(self)
+    }
 }
 
 impl IntoFormat for &String {
     fn into_format(self) -> Result<Format, Error> {
         Format::from_str(self)
     }
+
+    fn as_format_str(&self) -> Option<&str> {
+        Some(self)
+    }
+}
+
+impl IntoFormat for (Scheme, Encoding) {
+    fn into_format(self) -> Result<Format, Error> {
+        Ok(self.into())
+    }
+}
+
+/// Parsed format strings, so that a string passed repeatedly to
+/// [`Omnib`](crate::Omnib) is parsed once.
+///
+/// Strings of the format constants (e.g. `"aasv.b64"`) are matched
+/// directly, which is cheaper than a lookup; only the others (aliases,
+/// uppercase, custom schemes), which need splitting and lowercasing, are
+/// cached.  Holds at most [`CAPACITY`](Self::CAPACITY) strings; others are
+/// parsed on every use rather than evicting, so arbitrary format strings
+/// cannot grow it without bound.  Failed parses are never cached.
+#[derive(Debug, Default)]
+pub(crate) struct FormatCache {
+    formats: RwLock<HashMap<String, Format>>,
+}
+
+impl FormatCache {
+    /// Most format strings cached
+    pub(crate) const CAPACITY: usize = 64;
+
+    /// Parse `s`, or return the format it parsed to before.
+    pub(crate) fn get_or_parse(&self, s: &str) -> Result<Format, Error> {
+        if let Some(format) = Format::from_constant_str(s) {
+            return Ok(format);
+        }
+        let cached = self
+            .formats
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(s)
+            .copied();
+        if let Some(format) = cached {
+            return Ok(format);
+        }
+        let format = Format::from_other_str(s)?;
+        let mut formats = self.formats.write().unwrap_or_else(|e| e.into_inner());
+        if formats.len() < Self::CAPACITY {
+            formats.insert(s.to_owned(), format);
+        }
+        Ok(format)
+    }
+
+    /// Resolve `format`, parsing strings through the cache.
+    pub(crate) fn resolve(&self, format: impl IntoFormat) -> Result<Format, Error> {
+        match format.as_format_str() {
+            Some(s) => self.get_or_parse(s),
+            None => format.into_format(),
+        }
+    }
+
+    /// Number of format strings cached
+    #[cfg(test)]
+    fn len(&self) -> usize {
+        self.formats.read().unwrap().len()
+    }
 }
 
 // Seal the trait to prevent external implementations
@@ -273,12 +550,56 @@ mod private {
     impl Sealed for &String {}
     impl Sealed for super::Format {}
     impl Sealed for &super::Format {}
+    impl Sealed for (crate::Scheme, crate::Encoding) {}
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[cfg(feature = "aasv")]
+    #[test]
+    fn test_format_cache_parses_once() {
+        let cache = FormatCache::default();
+        let format = Format::new(Scheme::Aasv, Encoding::B64);
+
+        // Format constant strings are matched, not cached
+        assert_eq!(cache.get_or_parse("aasv.b64").unwrap(), format);
+        assert_eq!(cache.len(), 0);
+
+        for s in ["AASV.B64", "ob32.base64"] {
+            assert_eq!(cache.get_or_parse(s).unwrap(), format);
+            assert_eq!(cache.resolve(s).unwrap(), format);
+            assert_eq!(cache.resolve(String::from(s)).unwrap(), format);
+        }
+        assert_eq!(cache.len(), 2);
+
+        // Formats need no parsing, and errors are not cached
+        assert_eq!(cache.resolve(format).unwrap(), format);
+        assert_eq!(cache.resolve("nope.b64"), Err(Error::InvalidFormat));
+        assert_eq!(cache.len(), 2);
+
+        // Past capacity, strings are still parsed, just not kept
+        for mask in 0u32..128 {
+            let s: String = "aasv.hex"
+                .chars()
+                .enumerate()
+                .map(|(i, c)| {
+                    if mask & (1 << i) != 0 {
+                        c.to_ascii_uppercase()
+                    } else {
+                        c
+                    }
+                })
+                .collect();
+            assert_eq!(
+                cache.get_or_parse(&s).unwrap(),
+                Format::new(Scheme::Aasv, Encoding::Hex)
+            );
+        }
+        assert_eq!(cache.len(), FormatCache::CAPACITY);
+    }
+
     #[test]
     fn test_format_from_str_all_combinations() {
         // Define all schemes
@@ -291,6 +612,8 @@ mod tests {
             Scheme::Aags,
             #[cfg(feature = "apgs")]
             Scheme::Apgs,
+            #[cfg(feature = "apgc")]
+            Scheme::Apgc,
             #[cfg(feature = "aasv")]
             Scheme::Aasv,
             #[cfg(feature = "apsv")]
@@ -300,6 +623,10 @@ mod tests {
             Scheme::Mock1,
             #[cfg(feature = "mock")]
             Scheme::Mock2,
+            #[cfg(feature = "mock")]
+            Scheme::Mock3,
+            #[cfg(feature = "mock")]
+            Scheme::Mock4,
             // Legacy
             #[cfg(feature = "legacy")]
             Scheme::Legacy,
@@ -340,12 +667,24 @@ mod tests {
     fn test_format_from_str_invalid() {
         // Test invalid format strings
         assert!(Format::from_str("invalid").is_err());
-        assert!(Format::from_str("zrbcx").is_err());
         assert!(Format::from_str("zrbcx.").is_err());
         assert!(Format::from_str(".b64").is_err());
         assert!(Format::from_str("mock1:invalid").is_err());
     }
 
+    #[test]
+    fn test_format_from_bare_scheme() {
+        for &scheme in crate::supported_schemes() {
+            assert_eq!(
+                Format::from_str(scheme.as_str()),
+                Ok(Format::with_scheme(scheme)),
+                "{}",
+                scheme.as_str()
+            );
+        }
+        assert!(Format::from_str("").is_err());
+    }
+
     #[test]
     fn test_format_to_string_roundtrip() {
         // Define test cases: (scheme, encoding, expected_string)
@@ -355,6 +694,14 @@ mod tests {
             (Scheme::Mock2, Encoding::B32, "mock2.b32"),
             (Scheme::Mock2, Encoding::B64, "mock2.b64"),
             (Scheme::Mock2, Encoding::Hex, "mock2.hex"),
+            (Scheme::Mock3, Encoding::C32, "mock3.c32"),
+            (Scheme::Mock3, Encoding::B32, "mock3.b32"),
+            (Scheme::Mock3, Encoding::B64, "mock3.b64"),
+            (Scheme::Mock3, Encoding::Hex, "mock3.hex"),
+            (Scheme::Mock4, Encoding::C32, "mock4.c32"),
+            (Scheme::Mock4, Encoding::B32, "mock4.b32"),
+            (Scheme::Mock4, Encoding::B64, "mock4.b64"),
+            (Scheme::Mock4, Encoding::Hex, "mock4.hex"),
             (Scheme::Mock1, Encoding::C32, "mock1.c32"),
             (Scheme::Mock1, Encoding::B32, "mock1.b32"),
             (Scheme::Mock1, Encoding::B64, "mock1.b64"),
@@ -362,9 +709,7 @@ mod tests {
         ];
 
         #[cfg(feature = "legacy")]
-        test_cases.extend(vec![
-            (Scheme::Legacy, Encoding::B32, "legacy"),
-        ]);
+        test_cases.extend(vec![(Scheme::Legacy, Encoding::B32, "legacy")]);
 
         #[cfg(feature = "zrbcx")]
         test_cases.extend(vec![
@@ -397,6 +742,13 @@ mod tests {
             (Scheme::Apgs, Encoding::B64, "apgs.b64"),
             (Scheme::Apgs, Encoding::Hex, "apgs.hex"),
         ]);
+        #[cfg(feature = "apgc")]
+        test_cases.extend(vec![
+            (Scheme::Apgc, Encoding::C32, "apgc.c32"),
+            (Scheme::Apgc, Encoding::B32, "apgc.b32"),
+            (Scheme::Apgc, Encoding::B64, "apgc.b64"),
+            (Scheme::Apgc, Encoding::Hex, "apgc.hex"),
+        ]);
 
         #[cfg(feature = "aasv")]
         test_cases.extend(vec![
@@ -456,7 +808,8 @@ mod tests {
     fn test_all_schemes_support_both_base32_variants() {
         // All schemes should support both RFC 4648 base32 (b32) and Crockford base32 (c32)
         let schemes = vec![
-            "zrbcx", "upbc", "aags", "apgs", "aasv", "apsv", "mock1", "mock2",
+            "zrbcx", "upbc", "aags", "apgs", "apgc", "aasv", "apsv", "mock1", "mock2", "mock3",
+            "mock4",
         ];
 
         for scheme_str in schemes {
@@ -487,4 +840,62 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_unknown_format_is_invalid_format() {
+        assert_eq!(Format::from_str("nope.c32"), Err(Error::InvalidFormat));
+        assert_eq!(Format::from_str(""), Err(Error::InvalidFormat));
+    }
+
+    #[cfg(all(feature = "aasv", feature = "aags"))]
+    #[test]
+    fn test_const_formats_in_match() {
+        const TOKEN: Format = Format::new(Scheme::Aasv, Encoding::B64);
+        const SESSION: Format = Format::with_scheme(Scheme::Aags);
+        const SESSION_ENCODING: Encoding = SESSION.encoding();
+        assert_eq!(SESSION_ENCODING, Encoding::C32);
+        assert_eq!(TOKEN.scheme(), Scheme::Aasv);
+
+        let kind = |format: Format| match format {
+            TOKEN => "token",
+            SESSION => "session",
+            _ => "other",
+        };
+        assert_eq!(kind(Format::from_str("aasv.b64").unwrap()), "token");
+        assert_eq!(kind(Format::from_str("aags.c32").unwrap()), "session");
+        assert_eq!(kind(Format::from_str("aags.b64").unwrap()), "other");
+    }
+
+    #[cfg(feature = "aasv")]
+    #[test]
+    fn test_format_tuple_roundtrip() {
+        let format: Format = (Scheme::Aasv, Encoding::B64).into();
+        assert_eq!(format, Format::new(Scheme::Aasv, Encoding::B64));
+        assert_eq!(format.into_parts(), (Scheme::Aasv, Encoding::B64));
+
+        let (scheme, encoding) = Format::from_str("aasv.hex").unwrap().into_parts();
+        assert_eq!(Format::from((scheme, encoding)).to_string(), "aasv.hex");
+    }
+
+    #[cfg(feature = "aasv")]
+    #[test]
+    fn test_format_string_conversions() {
+        for encoding in Encoding::ALL {
+            let format = Format::new(Scheme::Aasv, encoding);
+            let s = String::from(format);
+            assert_eq!(s, format.to_string());
+            assert_eq!(Format::try_from(s.as_str()), Ok(format));
+            assert_eq!(Format::try_from(s), Ok(format));
+        }
+
+        fn scheme_of(s: &str) -> Result<Scheme, Error> {
+            let format: Format = s.try_into()?;
+            Ok(format.scheme())
+        }
+        assert_eq!(scheme_of("ob32.b64"), Ok(Scheme::Aasv));
+        assert_eq!(
+            Format::try_from("nope.c32".to_string()),
+            Err(Error::InvalidFormat)
+        );
+    }
 }
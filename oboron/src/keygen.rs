@@ -18,9 +18,31 @@ use rand::RngCore;
 /// ```
 #[must_use]
 pub fn generate_key() -> String {
+    generate_key_with_rng(&mut rand::thread_rng())
+}
+
+/// Like [`generate_key`], but draws the key from `rng` instead of the
+/// operating system's generator.
+///
+/// Use a cryptographically secure generator (e.g. one backed by an HSM) for
+/// real keys.  A seeded generator yields the same key for the same seed,
+/// which is useful for stable test fixtures.
+///
+/// # Examples
+///
+/// ```
+/// use oboron::generate_key_with_rng;
+/// use rand::{rngs::StdRng, SeedableRng};
+///
+/// let key = generate_key_with_rng(&mut StdRng::seed_from_u64(7));
+/// assert_eq!(key, generate_key_with_rng(&mut StdRng::seed_from_u64(7)));
+/// assert_eq!(key.len(), 86);
+/// ```
+#[must_use]
+pub fn generate_key_with_rng(rng: &mut impl RngCore) -> String {
     loop {
         let mut key_bytes = [0u8; 64];
-        rand::thread_rng().fill_bytes(&mut key_bytes);
+        rng.fill_bytes(&mut key_bytes);
         let key_base64 = BASE64URL_NOPAD.encode(&key_bytes);
         if !key_base64.contains('-') && !key_base64.contains('_') {
             return key_base64;
@@ -44,8 +66,16 @@ pub fn generate_key() -> String {
 #[must_use]
 #[cfg(feature = "bytes-keys")]
 pub fn generate_key_bytes() -> [u8; 64] {
+    generate_key_bytes_with_rng(&mut rand::thread_rng())
+}
+
+/// Like [`generate_key_bytes`], but draws the key from `rng`; see
+/// [`generate_key_with_rng`].
+#[must_use]
+#[cfg(feature = "bytes-keys")]
+pub fn generate_key_bytes_with_rng(rng: &mut impl RngCore) -> [u8; 64] {
     let decoded = BASE64URL_NOPAD
-        .decode(generate_key().as_bytes())
+        .decode(generate_key_with_rng(rng).as_bytes())
         .expect("Failed to decode base64");
     decoded.try_into().expect("Decoded key is not 64 bytes")
 }
@@ -0,0 +1,50 @@
+//! Runtime override of the key used by the `*_keyless` constructors.
+//!
+//! **TESTING ONLY.  This is not a security feature.**  Keyless mode exists
+//! for tests and demos; overriding its key merely lets a CI environment use
+//! a reproducible key other than the public hardcoded one.  Anything
+//! encrypted in keyless mode must be treated as unencrypted.
+
+#![cfg(feature = "keyless")]
+
+use crate::{constants::HARDCODED_KEY_BYTES, Error};
+use std::sync::OnceLock;
+
+static KEYLESS_KEY: OnceLock<[u8; 64]> = OnceLock::new();
+
+/// Replace the hardcoded key used by every 64-byte-key `*_keyless`
+/// constructor and convenience function (`Ob::new_keyless`,
+/// `Omnib::new_keyless`, `enc_keyless`, ...) for the rest of the process.
+///
+/// **TESTING ONLY - NOT A SECURITY FEATURE.**  The key is process-global
+/// and can only be set once, before any keyless instance is created; the
+/// z-tier keyless secret is unaffected.
+///
+/// # Errors
+///
+/// Returns [`Error::KeylessKeyInUse`] if a test key was already set or a
+/// keyless instance was already created with the default key.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> Result<(), oboron::Error> {
+/// # #[cfg(feature = "aasv")]
+/// # {
+/// use oboron::Ob;
+///
+/// oboron::set_test_key(&[7u8; 64])?;
+/// let ob = Ob::new_keyless("aasv.b64")?;
+/// # }
+/// # Ok(())
+/// # }
+/// ```
+pub fn set_test_key(key: &[u8; 64]) -> Result<(), Error> {
+    KEYLESS_KEY.set(*key).map_err(|_| Error::KeylessKeyInUse)
+}
+
+/// The key used by keyless constructors: the test key if one was set,
+/// otherwise the hardcoded key (which then can no longer be overridden).
+pub(crate) fn keyless_key() -> &'static [u8; 64] {
+    KEYLESS_KEY.get_or_init(|| HARDCODED_KEY_BYTES)
+}
@@ -0,0 +1,199 @@
+use crate::masterkey::FINGERPRINT_LEN;
+use crate::{Error, Ob};
+use std::collections::HashMap;
+
+/// Separator between the key-ID prefix and the obtext.
+pub(crate) const KEY_ID_SEPARATOR: char = '.';
+
+/// A set of [`Ob`] instances indexed by key fingerprint.
+///
+/// Obtexts produced by [`Ob::enc_with_key_id`] carry the key fingerprint as a
+/// prefix (`<fingerprint>.<obtext>`); [`dec`](Self::dec) uses it to select the
+/// matching key with a single map lookup, rather than trying every key.
+/// Unprefixed obtexts fall back to trying each key in insertion order.
+///
+/// Each key is stored together with its format, and a prefixed obtext is
+/// decoded with exactly that format.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> Result<(), oboron::Error> {
+/// # #[cfg(feature = "aasv")]
+/// # {
+/// use oboron::{Keyring, Ob};
+///
+/// let tenant_a = Ob::new("aasv.b64", &oboron::generate_key())?;
+/// let tenant_b = Ob::new("aasv.b64", &oboron::generate_key())?;
+/// let ot = tenant_b.enc_with_key_id("hello")?;
+///
+/// let mut keyring = Keyring::new();
+/// keyring.insert(tenant_a)?;
+/// keyring.insert(tenant_b)?;
+///
+/// assert_eq!(keyring.dec(&ot)?, "hello");
+/// # }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Default)]
+pub struct Keyring {
+    obs: Vec<Ob>,
+    index: HashMap<String, usize>,
+}
+
+impl Keyring {
+    /// Create an empty keyring.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an `Ob` to the keyring, returning its key fingerprint.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::DuplicateKeyId`] if a key with the same fingerprint
+    /// is already present.
+    pub fn insert(&mut self, ob: Ob) -> Result<String, Error> {
+        let key_id = ob.fingerprint();
+        if self.index.contains_key(&key_id) {
+            return Err(Error::DuplicateKeyId { key_id });
+        }
+        self.index.insert(key_id.clone(), self.obs.len());
+        self.obs.push(ob);
+        Ok(key_id)
+    }
+
+    /// Look up an `Ob` by key fingerprint.
+    pub fn get(&self, key_id: &str) -> Option<&Ob> {
+        self.index.get(key_id).map(|&i| &self.obs[i])
+    }
+
+    /// Number of keys in the keyring.
+    pub fn len(&self) -> usize {
+        self.obs.len()
+    }
+
+    /// Whether the keyring is empty.
+    pub fn is_empty(&self) -> bool {
+        self.obs.is_empty()
+    }
+
+    /// Decode and decrypt obtext, selecting the key by its key-ID prefix.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::UnknownKeyId`] if the obtext has a key-ID prefix that
+    /// matches no key in the keyring.  Unprefixed obtexts are tried against
+    /// each key in turn; if none succeeds, the last error is returned
+    /// ([`Error::DecryptionFailed`] for an empty keyring).
+    pub fn dec(&self, obtext: &str) -> Result<String, Error> {
+        if let Some((key_id, rest)) = split_key_id(obtext) {
+            return match self.get(key_id) {
+                Some(ob) => ob.dec(rest),
+                None => Err(Error::UnknownKeyId {
+                    key_id: key_id.to_string(),
+                }),
+            };
+        }
+
+        let mut last_err = Error::DecryptionFailed;
+        for ob in &self.obs {
+            match ob.dec(obtext) {
+                Ok(plaintext) => return Ok(plaintext),
+                Err(e) => last_err = e,
+            }
+        }
+        Err(last_err)
+    }
+}
+
+/// Split `<fingerprint>.<obtext>` into its parts, or `None` if `obtext` has
+/// no well-formed key-ID prefix.
+fn split_key_id(obtext: &str) -> Option<(&str, &str)> {
+    let (key_id, rest) = obtext.split_once(KEY_ID_SEPARATOR)?;
+    let well_formed = key_id.len() == 2 * FINGERPRINT_LEN
+        && key_id
+            .bytes()
+            .all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b));
+    well_formed.then_some((key_id, rest))
+}
+
+#[cfg(all(test, feature = "aasv", feature = "bytes-keys"))]
+mod tests {
+    use super::*;
+
+    fn ob_with_key(byte: u8, format: &str) -> Ob {
+        Ob::from_bytes(format, &[byte; 64]).unwrap()
+    }
+
+    fn three_key_ring() -> Keyring {
+        let mut keyring = Keyring::new();
+        for (byte, format) in [(1, "aasv.b64"), (2, "aasv.c32"), (3, "aasv.hex")] {
+            keyring.insert(ob_with_key(byte, format)).unwrap();
+        }
+        keyring
+    }
+
+    #[test]
+    fn test_dec_selects_key_by_prefix() {
+        let keyring = three_key_ring();
+        assert_eq!(keyring.len(), 3);
+
+        for (byte, format) in [(1, "aasv.b64"), (2, "aasv.c32"), (3, "aasv.hex")] {
+            let ob = ob_with_key(byte, format);
+            let ot = ob.enc_with_key_id("tenant data").unwrap();
+            assert!(ot.starts_with(&format!("{}.", ob.fingerprint())));
+            assert_eq!(keyring.dec(&ot).unwrap(), "tenant data");
+            assert_eq!(
+                keyring.get(&ob.fingerprint()).unwrap().format(),
+                ob.format()
+            );
+        }
+    }
+
+    #[test]
+    fn test_dec_uses_only_the_prefixed_key() {
+        let keyring = three_key_ring();
+        let key1 = ob_with_key(1, "aasv.b64");
+        let key2 = ob_with_key(2, "aasv.c32");
+
+        // Obtext from key 2, relabelled with key 1's id: no other key is tried
+        let ot = key2.enc("tenant data").unwrap();
+        let relabelled = format!("{}.{}", key1.fingerprint(), ot);
+        assert!(keyring.dec(&relabelled).is_err());
+    }
+
+    #[test]
+    fn test_unknown_key_id() {
+        let keyring = three_key_ring();
+        let ot = ob_with_key(4, "aasv.b64").enc_with_key_id("x").unwrap();
+        let key_id = ob_with_key(4, "aasv.b64").fingerprint();
+        assert_eq!(keyring.dec(&ot), Err(Error::UnknownKeyId { key_id }));
+    }
+
+    #[test]
+    fn test_unprefixed_falls_back_to_each_key() {
+        let keyring = three_key_ring();
+        let ot = ob_with_key(3, "aasv.hex").enc("legacy").unwrap();
+        assert_eq!(keyring.dec(&ot).unwrap(), "legacy");
+
+        assert_eq!(Keyring::new().dec(&ot), Err(Error::DecryptionFailed));
+    }
+
+    #[test]
+    fn test_duplicate_key_id() {
+        let mut keyring = three_key_ring();
+        let err = keyring.insert(ob_with_key(1, "aasv.c32")).unwrap_err();
+        assert!(matches!(err, Error::DuplicateKeyId { .. }));
+        assert_eq!(keyring.len(), 3);
+    }
+
+    #[test]
+    fn test_split_key_id() {
+        assert_eq!(split_key_id("0123abcd.xyz"), Some(("0123abcd", "xyz")));
+        assert_eq!(split_key_id("0123ABCD.xyz"), None);
+        assert_eq!(split_key_id("0123abc.xyz"), None);
+        assert_eq!(split_key_id("0123abcdxyz"), None);
+    }
+}
@@ -176,6 +176,7 @@
 //!   - `Aags`: deterministic AES-GCM-SIV
 //!   - `Aasv`: deterministic AES-SIV (nonce-misuse resistant)
 //!   - `Apgs`: probabilistic AES-GCM-SIV
+//!   - `Apgc`: probabilistic AES-GCM (standard, for interop)
 //!   - `Apsv`: probabilistic AES-SIV
 //! - Un-authenticated:
 //!   - `Upbc`: probabilistic AES-CBC
@@ -185,6 +186,8 @@
 //! Testing/Demo only schemes using no encryption (`mock` feature group):
 //! - `Mock1`: Identity
 //! - `Mock2`: Reverse plaintext
+//! - `Mock3`: Keyed byte substitution, output as long as the plaintext
+//! - `Mock4`: Word characters substituted, punctuation and spaces kept
 //!
 //! Each scheme supports four string encodings:
 //! - B64 - URL-safe base64 (RFC 4648 base64url standard)
@@ -219,26 +222,42 @@
 //! The `ObtextCodec` trait is automatically imported via the prelude.
 
 mod base32;
+mod cache;
 mod codec;
 mod constants;
+#[cfg(feature = "custom-schemes")]
+mod custom_scheme;
 mod dec;
 mod dec_auto;
 mod enc;
-mod encoding;
+pub mod encoding;
 mod error;
 mod format;
 mod keygen;
+#[cfg(feature = "keyless")]
+mod keyless;
+mod keyring;
 mod masterkey;
+#[cfg(feature = "metrics")]
+mod metrics;
 mod ob;
 mod obcrypt;
 mod omnib;
 mod scheme;
+#[cfg(any(feature = "sqlx", feature = "diesel"))]
+pub mod sql;
+#[cfg(feature = "testing")]
+mod testing;
+mod text;
 #[cfg(feature = "ztier")]
 pub mod ztier;
 
 // Re-export public types and constants
-pub use constants::{HARDCODED_KEY_BASE64, HARDCODED_KEY_BYTES};
-pub use error::Error;
+pub use constants::{FORMAT_ENV_VAR, HARDCODED_KEY_BASE64, HARDCODED_KEY_BYTES, KEY_ENV_VAR};
+pub use error::{DecodeFailure, DecodeStage, Error};
+// Lets callers name `SecretString` without a direct dependency
+#[cfg(feature = "secrecy")]
+pub use secrecy;
 
 pub(crate) use masterkey::MasterKey;
 
@@ -248,10 +267,12 @@ pub(crate) use masterkey::MasterKey;
 pub(crate) use obcrypt::{decrypt_aags, encrypt_aags};
 #[cfg(feature = "aasv")]
 pub(crate) use obcrypt::{decrypt_aasv, encrypt_aasv};
+#[cfg(feature = "apgc")]
+pub(crate) use obcrypt::{decrypt_apgc, encrypt_apgc, encrypt_apgc_with_nonce};
 #[cfg(feature = "apgs")]
-pub(crate) use obcrypt::{decrypt_apgs, encrypt_apgs};
+pub(crate) use obcrypt::{decrypt_apgs, encrypt_apgs, encrypt_apgs_with_nonce};
 #[cfg(feature = "apsv")]
-pub(crate) use obcrypt::{decrypt_apsv, encrypt_apsv};
+pub(crate) use obcrypt::{decrypt_apsv, encrypt_apsv, encrypt_apsv_with_nonce};
 #[cfg(feature = "upbc")]
 pub(crate) use obcrypt::{decrypt_upbc, encrypt_upbc};
 #[cfg(feature = "zrbcx")]
@@ -262,12 +283,13 @@ pub(crate) use ztier::{decrypt_zrbcx, encrypt_zrbcx};
 pub(crate) use obcrypt::{decrypt_mock1, encrypt_mock1};
 #[cfg(feature = "mock")]
 pub(crate) use obcrypt::{decrypt_mock2, encrypt_mock2};
+#[cfg(feature = "mock")]
+pub(crate) use obcrypt::{decrypt_mock3, encrypt_mock3};
+#[cfg(feature = "mock")]
+pub(crate) use obcrypt::{decrypt_mock4, encrypt_mock4};
 #[cfg(feature = "zmock")]
 pub(crate) use ztier::{decrypt_zmock1, encrypt_zmock1};
 
-pub use keygen::generate_key;
-#[cfg(feature = "bytes-keys")]
-pub use keygen::generate_key_bytes;
 #[cfg(feature = "hex-keys")]
 pub use keygen::generate_key_hex;
 pub use keygen::generate_secret;
@@ -275,21 +297,42 @@ pub use keygen::generate_secret;
 pub use keygen::generate_secret_bytes;
 #[cfg(feature = "hex-keys")]
 pub use keygen::generate_secret_hex;
+pub use keygen::{generate_key, generate_key_with_rng};
+#[cfg(feature = "bytes-keys")]
+pub use keygen::{generate_key_bytes, generate_key_bytes_with_rng};
+#[cfg(feature = "keyless")]
+pub use keyless::set_test_key;
 
 // Re-export core types
+#[cfg(feature = "custom-schemes")]
+pub use custom_scheme::{register_scheme, CustomScheme, SchemeImpl};
+pub use dec::same_ciphertext;
+pub use dec_auto::{detect_format, verify_key, AutodecAttempt, AutodecReport};
 pub use encoding::Encoding;
 pub use format::Format;
-pub use scheme::Scheme;
+pub use scheme::{supported_schemes, Scheme};
+
+pub use text::{Obtext, Plaintext};
+
+#[cfg(feature = "testing")]
+pub use testing::NullOb;
+
+#[cfg(feature = "metrics")]
+pub use metrics::{ObObserver, ObOperation};
 
 // Re-export Ob
 pub use ob::Ob;
 
+pub use keyring::Keyring;
+
+pub use cache::ObCache;
+
 // Factory functions
 #[cfg(feature = "bytes-keys")]
 pub use codec::{from_bytes, from_bytes_with_format};
 #[cfg(feature = "hex-keys")]
 pub use codec::{from_hex_key, from_hex_key_with_format};
-pub use codec::{new, new_with_format, ObAny, ObtextCodec};
+pub use codec::{new, new_with_format, ObAny, ObtextCodec, UNKNOWN_KEY_ID};
 #[cfg(feature = "keyless")]
 pub use codec::{new_keyless, new_keyless_with_format};
 
@@ -298,6 +341,8 @@ pub use codec::{new_keyless, new_keyless_with_format};
 pub use constants::aags_constants::*;
 #[cfg(feature = "aasv")]
 pub use constants::aasv_constants::*;
+#[cfg(feature = "apgc")]
+pub use constants::apgc_constants::*;
 #[cfg(feature = "apgs")]
 pub use constants::apgs_constants::*;
 #[cfg(feature = "apsv")]
@@ -319,6 +364,8 @@ pub use constants::zmock_constants::*;
 pub use format::aags_formats::*;
 #[cfg(feature = "aasv")]
 pub use format::aasv_formats::*;
+#[cfg(feature = "apgc")]
+pub use format::apgc_formats::*;
 #[cfg(feature = "apgs")]
 pub use format::apgs_formats::*;
 #[cfg(feature = "apsv")]
@@ -341,6 +388,8 @@ pub use format::zmock_formats::*;
 pub use codec::{AagsB32, AagsB64, AagsC32, AagsHex};
 #[cfg(feature = "aasv")]
 pub use codec::{AasvB32, AasvB64, AasvC32, AasvHex};
+#[cfg(feature = "apgc")]
+pub use codec::{ApgcB32, ApgcB64, ApgcC32, ApgcHex};
 #[cfg(feature = "apgs")]
 pub use codec::{ApgsB32, ApgsB64, ApgsC32, ApgsHex};
 #[cfg(feature = "apsv")]
@@ -352,9 +401,13 @@ pub use codec::{UpbcB32, UpbcB64, UpbcC32, UpbcHex};
 pub use codec::{Mock1B32, Mock1B64, Mock1C32, Mock1Hex};
 #[cfg(feature = "mock")]
 pub use codec::{Mock2B32, Mock2B64, Mock2C32, Mock2Hex};
+#[cfg(feature = "mock")]
+pub use codec::{Mock3B32, Mock3B64, Mock3C32, Mock3Hex};
+#[cfg(feature = "mock")]
+pub use codec::{Mock4B32, Mock4B64, Mock4C32, Mock4Hex};
 
 // Re-export multi-format Oboron implementation
-pub use omnib::Omnib;
+pub use omnib::{EncodingsOf, Omnib};
 
 /// Convenience prelude for common imports.
 ///
@@ -367,12 +420,15 @@ pub mod prelude {
     pub use crate::{AagsB32, AagsB64, AagsC32, AagsHex};
     #[cfg(feature = "aasv")]
     pub use crate::{AasvB32, AasvB64, AasvC32, AasvHex};
+    #[cfg(feature = "apgc")]
+    pub use crate::{ApgcB32, ApgcB64, ApgcC32, ApgcHex};
     #[cfg(feature = "apgs")]
     pub use crate::{ApgsB32, ApgsB64, ApgsC32, ApgsHex};
     #[cfg(feature = "apsv")]
     pub use crate::{ApsvB32, ApsvB64, ApsvC32, ApsvHex};
     pub use crate::{Encoding, Error, Format, ObtextCodec, Scheme};
     pub use crate::{Ob, Omnib};
+    pub use crate::{Obtext, Plaintext};
 }
 
 // ============================================================================
@@ -1,7 +1,23 @@
 use crate::Error;
-use data_encoding::BASE64URL_NOPAD;
+use data_encoding::{BASE64URL_NOPAD, BASE64_NOPAD};
+#[cfg(feature = "seed-keys")]
+use hkdf::Hkdf;
+use sha2::{Digest, Sha256};
 use zeroize::{Zeroize, ZeroizeOnDrop};
 
+/// HKDF `info` label used when expanding a 32-byte seed into a master key.
+#[cfg(feature = "seed-keys")]
+const SEED_EXPAND_INFO: &[u8] = b"oboron masterkey v1";
+
+/// Domain-separation prefix hashed ahead of the key for [`MasterKey::fingerprint`].
+const FINGERPRINT_DOMAIN: &[u8] = b"oboron fingerprint v1";
+
+/// Length of a base64 key without padding.
+const KEY_BASE64_LEN: usize = 86;
+
+/// Length in bytes of a key fingerprint (8 hex characters).
+pub(crate) const FINGERPRINT_LEN: usize = 4;
+
 #[derive(Zeroize, ZeroizeOnDrop)]
 pub struct MasterKey {
     key: [u8; 64],
@@ -14,22 +30,72 @@ impl MasterKey {
         Ok(MasterKey { key: *key_bytes })
     }
 
-    /// Create a new MasterKey from a 86-character base64 string key.
+    /// Create a new MasterKey by HKDF-SHA256 expanding a 32-byte seed.
+    ///
+    /// The expansion is deterministic, but the resulting key material is
+    /// distinct from a native 64-byte key: `from_bytes_32(seed)` is never
+    /// equivalent to `from_bytes` with the same bytes zero-padded.
+    #[cfg(feature = "seed-keys")]
+    pub fn from_bytes_32(seed: &[u8; 32]) -> Result<Self, Error> {
+        let mut key = [0u8; 64];
+        Hkdf::<Sha256>::new(None, seed)
+            .expand(SEED_EXPAND_INFO, &mut key)
+            .map_err(|_| Error::InvalidKeyLength)?;
+        let masterkey = Self::from_bytes(&key);
+        key.zeroize();
+        masterkey
+    }
+
+    /// Create a new MasterKey from a base64-encoded 32-byte seed
+    /// (43 chars unpadded). See [`from_bytes_32`](Self::from_bytes_32).
+    #[cfg(feature = "seed-keys")]
+    pub fn from_base64_32(seed_base64: &str) -> Result<Self, Error> {
+        let mut seed: [u8; 32] = decode_base64_lenient(seed_base64)?
+            .try_into()
+            .map_err(|_| Error::InvalidKeyLength)?;
+        let masterkey = Self::from_bytes_32(&seed);
+        seed.zeroize();
+        masterkey
+    }
+
+    /// Create a new MasterKey from a base64 string key.
+    ///
+    /// The canonical form is 86 characters of unpadded URL-safe base64, but
+    /// standard base64 (`+`/`/`) and `=`-padded input are accepted as well,
+    /// as long as the key decodes to exactly 64 bytes.
+    ///
+    /// A key of the wrong length, e.g. with a character dropped or added
+    /// when pasting, fails with [`Error::KeyWrongLength`] before decoding;
+    /// one of the right length with characters outside the base64
+    /// alphabets fails with [`Error::KeyInvalidEncoding`].
     #[inline]
     pub fn from_base64(key_base64: &str) -> Result<Self, Error> {
-        let key: [u8; 64] = BASE64URL_NOPAD
-            .decode(key_base64.as_bytes())
-            .map_err(|_| Error::InvalidB64)?
+        let len = key_base64.trim_end_matches('=').len();
+        if len != KEY_BASE64_LEN {
+            return Err(Error::KeyWrongLength {
+                expected: KEY_BASE64_LEN,
+                got: len,
+            });
+        }
+        let key: [u8; 64] = decode_base64_lenient(key_base64)
+            .map_err(|_| Error::KeyInvalidEncoding)?
             .try_into()
-            .map_err(|_| Error::InvalidKeyLength)?;
+            .expect("86 base64 characters decode to 64 bytes");
 
         Self::from_bytes(&key)
     }
 
     /// Create a new MasterKey from a 128-character hex string.
+    ///
+    /// Hex digits may be upper- or lower-case, and an optional `0x` prefix
+    /// is ignored.
     #[cfg(feature = "hex-keys")]
     #[inline]
     pub fn from_hex(key_hex: &str) -> Result<Self, Error> {
+        let key_hex = key_hex
+            .strip_prefix("0x")
+            .or_else(|| key_hex.strip_prefix("0X"))
+            .unwrap_or(key_hex);
         let key_bytes: [u8; 64] = hex::decode(key_hex)?
             .try_into()
             .map_err(|_| Error::InvalidKeyLength)?;
@@ -58,4 +124,249 @@ impl MasterKey {
     pub(crate) fn key(&self) -> &[u8; 64] {
         &self.key
     }
+
+    /// Short, non-secret key identifier: the first 4 bytes of a
+    /// domain-separated SHA-256 of the key, as 8 lowercase hex characters.
+    pub fn fingerprint(&self) -> String {
+        fingerprint(&self.key)
+    }
+}
+
+/// Equality compares the key bytes in constant time, without exiting early
+/// at the first difference, so comparing against a secret key does not leak
+/// how much of it matched.
+impl PartialEq for MasterKey {
+    fn eq(&self, other: &Self) -> bool {
+        crate::dec::constant_time_eq(&self.key, &other.key)
+    }
+}
+
+impl Eq for MasterKey {}
+
+/// Fingerprint of raw key material; shared with z-tier secrets.
+pub(crate) fn fingerprint(key: &[u8]) -> String {
+    let digest = Sha256::new()
+        .chain_update(FINGERPRINT_DOMAIN)
+        .chain_update(key)
+        .finalize();
+    hex::encode(&digest[..FINGERPRINT_LEN])
+}
+
+/// Decode base64 in either the URL-safe or the standard alphabet, with or
+/// without trailing `=` padding.
+fn decode_base64_lenient(input: &str) -> Result<Vec<u8>, Error> {
+    let unpadded = input.trim_end_matches('=');
+    if input.len() - unpadded.len() > 2 {
+        return Err(Error::InvalidB64);
+    }
+    let encoding = if unpadded.contains(['+', '/']) {
+        &BASE64_NOPAD
+    } else {
+        &BASE64URL_NOPAD
+    };
+    encoding
+        .decode(unpadded.as_bytes())
+        .map_err(|_| Error::InvalidB64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use data_encoding::BASE64;
+
+    fn sample_key() -> [u8; 64] {
+        // Byte values chosen so that both alphabets differ ('-'/'_' vs '+'/'/').
+        let mut key = [0u8; 64];
+        for (i, b) in key.iter_mut().enumerate() {
+            *b = (i as u8).wrapping_mul(37).wrapping_add(0xf8);
+        }
+        key
+    }
+
+    #[test]
+    fn test_eq_compares_key_bytes() {
+        let key = sample_key();
+        let a = MasterKey::from_bytes(&key).unwrap();
+        assert!(a == MasterKey::from_base64(&BASE64URL_NOPAD.encode(&key)).unwrap());
+
+        // A difference in the last byte only
+        let mut other = key;
+        other[63] ^= 1;
+        assert!(a != MasterKey::from_bytes(&other).unwrap());
+        #[cfg(feature = "seed-keys")]
+        assert!(a != MasterKey::from_bytes_32(&[0u8; 32]).unwrap());
+    }
+
+    #[test]
+    fn test_from_base64_url_safe_unpadded() {
+        let key = sample_key();
+        let mk = MasterKey::from_base64(&BASE64URL_NOPAD.encode(&key)).unwrap();
+        assert_eq!(mk.key(), &key);
+    }
+
+    #[test]
+    fn test_from_base64_url_safe_padded() {
+        let key = sample_key();
+        let padded = format!("{}==", BASE64URL_NOPAD.encode(&key));
+        let mk = MasterKey::from_base64(&padded).unwrap();
+        assert_eq!(mk.key(), &key);
+    }
+
+    #[test]
+    fn test_from_base64_standard_padded() {
+        let key = sample_key();
+        let encoded = BASE64.encode(&key);
+        assert!(encoded.ends_with("=="));
+        assert!(encoded.contains(['+', '/']));
+        let mk = MasterKey::from_base64(&encoded).unwrap();
+        assert_eq!(mk.key(), &key);
+    }
+
+    #[test]
+    fn test_from_base64_standard_unpadded() {
+        let key = sample_key();
+        let mk = MasterKey::from_base64(&BASE64_NOPAD.encode(&key)).unwrap();
+        assert_eq!(mk.key(), &key);
+    }
+
+    #[test]
+    fn test_from_base64_normalizes_to_canonical() {
+        let key = sample_key();
+        let mk = MasterKey::from_base64(&BASE64.encode(&key)).unwrap();
+        assert_eq!(mk.key_base64(), BASE64URL_NOPAD.encode(&key));
+    }
+
+    #[test]
+    fn test_from_base64_invalid() {
+        let key = sample_key();
+        // Mixed alphabets
+        let url_safe = BASE64URL_NOPAD.encode(&key);
+        assert!(url_safe.contains(['-', '_']));
+        let mixed = format!("+{}", &url_safe[1..]);
+        assert_eq!(
+            MasterKey::from_base64(&mixed).err(),
+            Some(Error::KeyInvalidEncoding)
+        );
+        // Too much padding
+        let over_padded = format!("{}===", BASE64URL_NOPAD.encode(&key));
+        assert_eq!(
+            MasterKey::from_base64(&over_padded).err(),
+            Some(Error::KeyInvalidEncoding)
+        );
+        // Wrong length
+        assert_eq!(
+            MasterKey::from_base64(&BASE64URL_NOPAD.encode(&key[..32])).err(),
+            Some(Error::KeyWrongLength {
+                expected: 86,
+                got: 43
+            })
+        );
+        // Not base64 at all
+        assert!(MasterKey::from_base64("not a key!").is_err());
+    }
+
+    #[test]
+    fn test_from_base64_length_errors() {
+        let url_safe = BASE64URL_NOPAD.encode(&sample_key());
+        assert_eq!(url_safe.len(), KEY_BASE64_LEN);
+
+        // A character dropped or added when pasting
+        assert_eq!(
+            MasterKey::from_base64(&url_safe[1..]).err(),
+            Some(Error::KeyWrongLength {
+                expected: 86,
+                got: 85
+            })
+        );
+        assert_eq!(
+            MasterKey::from_base64(&format!("{}A", url_safe)).err(),
+            Some(Error::KeyWrongLength {
+                expected: 86,
+                got: 87
+            })
+        );
+        // Right length, not base64
+        assert_eq!(
+            MasterKey::from_base64(&format!("{}!", &url_safe[1..])).err(),
+            Some(Error::KeyInvalidEncoding)
+        );
+    }
+
+    #[cfg(feature = "seed-keys")]
+    #[test]
+    fn test_from_bytes_32_deterministic() {
+        let seed = [7u8; 32];
+        let mk1 = MasterKey::from_bytes_32(&seed).unwrap();
+        let mk2 = MasterKey::from_bytes_32(&seed).unwrap();
+        assert_eq!(mk1.key(), mk2.key());
+
+        let other = MasterKey::from_bytes_32(&[8u8; 32]).unwrap();
+        assert_ne!(mk1.key(), other.key());
+
+        // Distinct from using the seed as native key material
+        let mut padded = [0u8; 64];
+        padded[..32].copy_from_slice(&seed);
+        assert_ne!(mk1.key(), &padded);
+    }
+
+    #[cfg(feature = "seed-keys")]
+    #[test]
+    fn test_from_base64_32() {
+        let seed = [0xa5u8; 32];
+        let encoded = BASE64URL_NOPAD.encode(&seed);
+        assert_eq!(encoded.len(), 43);
+        let mk = MasterKey::from_base64_32(&encoded).unwrap();
+        assert_eq!(mk.key(), MasterKey::from_bytes_32(&seed).unwrap().key());
+
+        // A full 64-byte key is not a valid seed
+        let full = BASE64URL_NOPAD.encode(&sample_key());
+        assert_eq!(
+            MasterKey::from_base64_32(&full).err(),
+            Some(Error::InvalidKeyLength)
+        );
+    }
+
+    #[cfg(feature = "hex-keys")]
+    #[test]
+    fn test_from_hex_case_and_prefix() {
+        let key = sample_key();
+        let lower = hex::encode(key);
+        let upper = lower.to_uppercase();
+        for input in [
+            lower.clone(),
+            upper.clone(),
+            format!("0x{}", lower),
+            format!("0X{}", upper),
+        ] {
+            let mk = MasterKey::from_hex(&input).unwrap();
+            assert_eq!(mk.key(), &key, "input: {}", input);
+        }
+    }
+
+    #[cfg(feature = "hex-keys")]
+    #[test]
+    fn test_from_hex_invalid() {
+        let key = sample_key();
+        assert!(MasterKey::from_hex(&"zz".repeat(64)).is_err());
+        assert!(MasterKey::from_hex(&format!("0x0x{}", hex::encode(key))).is_err());
+        assert_eq!(
+            MasterKey::from_hex(&hex::encode(&key[..32])).err(),
+            Some(Error::InvalidKeyLength)
+        );
+    }
+
+    #[test]
+    fn test_fingerprint() {
+        let mk = MasterKey::from_bytes(&sample_key()).unwrap();
+        let fp = mk.fingerprint();
+        assert_eq!(fp.len(), 2 * FINGERPRINT_LEN);
+        assert!(fp
+            .bytes()
+            .all(|b| b.is_ascii_hexdigit() && !b.is_ascii_uppercase()));
+        assert_eq!(
+            fp,
+            MasterKey::from_bytes(&sample_key()).unwrap().fingerprint()
+        );
+        assert_ne!(fp, MasterKey::from_bytes(&[0u8; 64]).unwrap().fingerprint());
+    }
 }
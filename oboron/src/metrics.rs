@@ -0,0 +1,154 @@
+//! Instrumentation hooks for `enc`/`dec` metrics.
+//!
+//! An [`ObObserver`] attached to an [`Ob`](crate::Ob) or
+//! [`Omnib`](crate::Omnib) is told about every `enc`, `dec` and `autodec`
+//! call: the format, the input and output lengths and how long it took, or
+//! the error.  That is enough to feed counters and latency histograms
+//! (e.g. Prometheus) without wrapping every call site.
+//!
+//! Exactly one report is made per call of:
+//!
+//! - `Ob`: `enc`, `dec`, `autodec` and `dec_autoscheme`, and the methods
+//!   built on them (`enc_with_key_id`, `enc_labeled`, `enc_data_url`,
+//!   `dec_labeled`, `dec_data_url`, `autodec_bytes`, the `ObtextCodec`
+//!   methods)
+//! - `Omnib`: `enc`, `dec`, `autodec`, `autodec_within` and
+//!   `autodec_verbose`, and the methods built on them (`dec_known`,
+//!   `dec_report`, `autodec_report`, `autodec_labeled`, `autodec_bytes`,
+//!   `dec_segment`)
+//!
+//! `Omnib::encodings_of` and `Omnib::candidate_formats` are not reported:
+//! they produce or test several renderings rather than one enc or dec.
+//!
+//! Instances without an observer only pay for one `Option` check per call,
+//! and builds without the `metrics` feature carry no observer at all.
+
+#![cfg(feature = "metrics")]
+
+use crate::{Error, Format};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// The operation reported to [`ObObserver::on_error`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ObOperation {
+    /// `enc`, and the methods built on it
+    Enc,
+    /// `dec` with a known format
+    Dec,
+    /// `autodec`, and the other methods that detect the scheme or format
+    Autodec,
+}
+
+/// Receives the outcome of each `enc`/`dec`/`autodec` call of the
+/// [`Ob`](crate::Ob) or [`Omnib`](crate::Omnib) it is attached to with
+/// `with_observer`.
+///
+/// Every method has an empty default, so an observer implements only the
+/// hooks it needs.  Hooks run synchronously on the calling thread, after
+/// the operation, and should be cheap (e.g. bump an atomic counter).
+/// Lengths are in bytes; plaintext and obtext themselves are never passed,
+/// so observers cannot leak them.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> Result<(), oboron::Error> {
+/// # #[cfg(feature = "aasv")]
+/// # {
+/// use oboron::{Format, Ob, ObObserver};
+/// use std::sync::atomic::{AtomicU64, Ordering};
+/// use std::sync::Arc;
+/// use std::time::Duration;
+///
+/// #[derive(Default)]
+/// struct EncCounter(AtomicU64);
+///
+/// impl ObObserver for EncCounter {
+///     fn on_enc(&self, _format: Format, _in_len: usize, _out_len: usize, _elapsed: Duration) {
+///         self.0.fetch_add(1, Ordering::Relaxed);
+///     }
+/// }
+///
+/// # let key = oboron::generate_key();
+/// let counter = Arc::new(EncCounter::default());
+/// let ob = Ob::new("aasv.b64", &key)?.with_observer(counter.clone());
+/// ob.enc("hello")?;
+/// assert_eq!(counter.0.load(Ordering::Relaxed), 1);
+/// # }
+/// # Ok(())
+/// # }
+/// ```
+pub trait ObObserver: Send + Sync {
+    /// A successful encryption of `in_len` plaintext bytes into `out_len`
+    /// obtext bytes.
+    fn on_enc(&self, format: Format, in_len: usize, out_len: usize, elapsed: Duration) {
+        let _ = (format, in_len, out_len, elapsed);
+    }
+
+    /// A successful decryption of `in_len` obtext bytes into `out_len`
+    /// plaintext bytes.  `format` is `None` for `autodec` and the other
+    /// detecting methods, which do not report the format they detected.
+    fn on_dec(&self, format: Option<Format>, in_len: usize, out_len: usize, elapsed: Duration) {
+        let _ = (format, in_len, out_len, elapsed);
+    }
+
+    /// A failed `operation` on `in_len` input bytes.  `format` is `None`
+    /// for `autodec` and the other detecting methods, and for format
+    /// strings that do not parse.
+    fn on_error(
+        &self,
+        operation: ObOperation,
+        format: Option<Format>,
+        in_len: usize,
+        error: &Error,
+        elapsed: Duration,
+    ) {
+        let _ = (operation, format, in_len, error, elapsed);
+    }
+}
+
+/// Run enc `op` in `format` on `input`, reporting its outcome to
+/// `observer`.  A format that failed to resolve is reported as an error
+/// without a format, and `op` is not run.
+pub(crate) fn observe_enc(
+    observer: &Arc<dyn ObObserver>,
+    format: Result<Format, Error>,
+    input: &str,
+    op: impl FnOnce(Format) -> Result<String, Error>,
+) -> Result<String, Error> {
+    let start = Instant::now();
+    let format = match format {
+        Ok(format) => format,
+        Err(e) => {
+            observer.on_error(ObOperation::Enc, None, input.len(), &e, start.elapsed());
+            return Err(e);
+        }
+    };
+    let result = op(format);
+    let elapsed = start.elapsed();
+    match &result {
+        Ok(out) => observer.on_enc(format, input.len(), out.len(), elapsed),
+        Err(e) => observer.on_error(ObOperation::Enc, Some(format), input.len(), e, elapsed),
+    }
+    result
+}
+
+/// Run dec or autodec `op` on `input`, reporting its outcome to `observer`
+pub(crate) fn observe_dec(
+    observer: &Arc<dyn ObObserver>,
+    operation: ObOperation,
+    format: Option<Format>,
+    input: &str,
+    op: impl FnOnce() -> Result<String, Error>,
+) -> Result<String, Error> {
+    debug_assert_ne!(operation, ObOperation::Enc);
+    let start = Instant::now();
+    let result = op();
+    let elapsed = start.elapsed();
+    match &result {
+        Ok(out) => observer.on_dec(format, input.len(), out.len(), elapsed),
+        Err(e) => observer.on_error(operation, format, input.len(), e, elapsed),
+    }
+    result
+}
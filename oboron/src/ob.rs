@@ -1,6 +1,27 @@
-#[cfg(feature = "keyless")]
-use crate::constants::HARDCODED_KEY_BYTES;
 use crate::{format::IntoFormat, Encoding, Error, Format, MasterKey, ObtextCodec, Scheme};
+use std::sync::Arc;
+
+/// Separator between obtext and the format label of [`Ob::enc_labeled`].
+pub(crate) const LABEL_SEPARATOR: char = '#';
+
+/// Prefix of the URLs made by [`Ob::enc_data_url`].
+const DATA_URL_PREFIX: &str = "data:";
+
+/// Media type parameter of [`Ob::enc_data_url`] naming the obtext format.
+const DATA_URL_FORMAT_PARAM: &str = "ob=";
+
+/// Strip a trailing `#<label>`, if any, leaving the bare obtext.
+#[inline]
+pub(crate) fn strip_label(text: &str) -> &str {
+    text.split_once(LABEL_SEPARATOR)
+        .map_or(text, |(obtext, _)| obtext)
+}
+
+// `Ob::shared` hands out `Arc<Ob>` for use across threads
+const _: fn() = || {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<Ob>();
+};
 
 /// A flexible ObtextCodec implementation with runtime format selection.
 ///
@@ -50,6 +71,37 @@ use crate::{format::IntoFormat, Encoding, Error, Format, MasterKey, ObtextCodec,
 /// // Set entire format at once
 /// ob.set_format("aasv.hex")?; // now aasv.hex
 /// ob.set_format(AASV_B64)?;   // now aasv.b64 (using constant)
+///
+/// // Setters return `&mut Ob`, so they chain
+/// ob.set_scheme(Scheme::Mock1)?.set_encoding(Encoding::Hex)?; // now mock1.hex
+/// assert_eq!(ob.format(), Format::new(Scheme::Mock1, Encoding::Hex));
+/// # }
+/// # Ok(())
+/// # }
+/// ```
+///
+/// ## Sharing across threads
+///
+/// `Ob` is `Send + Sync`; [`Ob::shared`] returns an `Arc<Ob>` to clone into
+/// worker threads.
+///
+/// ```rust
+/// # fn main() -> Result<(), oboron::Error> {
+/// # #[cfg(feature = "aasv")]
+/// # {
+/// # use oboron::Ob;
+/// # let key = oboron::generate_key();
+/// let ob = Ob::shared("aasv.b64", &key)?;
+/// let workers: Vec<_> = (0..4)
+///     .map(|i| {
+///         let ob = ob.clone();
+///         std::thread::spawn(move || ob.enc(&format!("job {}", i)))
+///     })
+///     .collect();
+/// for worker in workers {
+///     let ot = worker.join().unwrap()?;
+///     assert!(ob.dec(&ot)?.starts_with("job "));
+/// }
 /// # }
 /// # Ok(())
 /// # }
@@ -57,6 +109,12 @@ use crate::{format::IntoFormat, Encoding, Error, Format, MasterKey, ObtextCodec,
 pub struct Ob {
     masterkey: MasterKey,
     format: Format,
+    max_input_len: Option<usize>,
+    constant_time_dec: bool,
+    #[cfg(feature = "debug-history")]
+    history: Vec<Format>,
+    #[cfg(feature = "metrics")]
+    observer: Option<Arc<dyn crate::ObObserver>>,
 }
 
 impl Ob {
@@ -87,9 +145,93 @@ impl Ob {
         Ok(Self {
             masterkey: MasterKey::from_base64(key)?,
             format,
+            max_input_len: None,
+            constant_time_dec: false,
+            #[cfg(feature = "debug-history")]
+            history: Vec::new(),
+            #[cfg(feature = "metrics")]
+            observer: None,
         })
     }
 
+    /// Create a new Ob from the specified format and a base64 key wrapped in
+    /// a [`SecretString`](secrecy::SecretString).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), oboron::Error> {
+    /// # #[cfg(all(feature = "aasv", feature = "secrecy"))]
+    /// # {
+    /// # use oboron::{Ob, ObtextCodec};
+    /// use oboron::secrecy::SecretString;
+    /// let key = SecretString::from(oboron::generate_key());
+    /// let ob = Ob::new_secret("aasv.b64", &key)?;
+    /// assert_eq!(ob.dec(&ob.enc("hello")?)?, "hello");
+    /// # }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "secrecy")]
+    pub fn new_secret(format: impl IntoFormat, key: &secrecy::SecretString) -> Result<Self, Error> {
+        use secrecy::ExposeSecret;
+        Self::new(format, key.expose_secret())
+    }
+
+    /// Create a new Ob that encodes obtext in a caller-supplied alphabet,
+    /// e.g. short codes without vowels for a URL shortener.
+    ///
+    /// Such obtext can only be decoded by an instance with the same
+    /// alphabet: [`dec`](Self::dec) and [`autodec`](Self::autodec) on this
+    /// instance work, but full format autodetection (e.g. in
+    /// [`Omnib::autodec`](crate::Omnib::autodec)) never tries custom
+    /// alphabets.  See [`Alphabet::new`](crate::encoding::Alphabet::new) for
+    /// the rules `alphabet` must follow.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), oboron::Error> {
+    /// # #[cfg(feature = "aasv")]
+    /// # {
+    /// # use oboron::{Ob, Scheme};
+    /// # let key = oboron::generate_key();
+    /// let ob = Ob::new_custom_alphabet(Scheme::Aasv, "0123456789bcdfghjkmnpqrstvwxyzBC", &key)?;
+    /// let code = ob.enc("https://example.com/some/long/path")?;
+    /// assert!(!code.contains(['a', 'e', 'i', 'o', 'u']));
+    /// assert_eq!(ob.dec(&code)?, "https://example.com/some/long/path");
+    /// # }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new_custom_alphabet(scheme: Scheme, alphabet: &str, key: &str) -> Result<Self, Error> {
+        let alphabet = crate::encoding::Alphabet::new(alphabet)?;
+        Self::new(Format::new(scheme, Encoding::Custom(alphabet)), key)
+    }
+
+    /// Create a new Ob from the environment, e.g. in a twelve-factor
+    /// deployment: the format from `OBORON_FORMAT` and the base64 key from
+    /// `OBORON_KEY` (the [`FORMAT_ENV_VAR`](crate::FORMAT_ENV_VAR) and
+    /// [`KEY_ENV_VAR`](crate::KEY_ENV_VAR) constants).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::MissingEnvVar`] naming the first variable that is
+    /// not set, and the errors of [`new`](Self::new) for invalid values.
+    #[cfg(feature = "std")]
+    pub fn from_env() -> Result<Self, Error> {
+        let format = crate::error::env_var(crate::FORMAT_ENV_VAR)?;
+        let key = crate::error::env_var(crate::KEY_ENV_VAR)?;
+        Self::new(format.as_str(), &key)
+    }
+
+    /// Like [`from_env`](Self::from_env), but with the hardcoded key
+    /// (testing only): only `OBORON_FORMAT` is read.
+    #[cfg(all(feature = "std", feature = "keyless"))]
+    pub fn from_env_keyless() -> Result<Self, Error> {
+        Self::new_keyless(crate::error::env_var(crate::FORMAT_ENV_VAR)?.as_str())
+    }
+
     /// Set the format to a new value.
     ///
     /// Accepts either a format string (`&str`) or a `Format` instance.
@@ -109,9 +251,15 @@ impl Ob {
     /// # Ok(())
     /// # }
     /// ```
-    pub fn set_format(&mut self, format: impl IntoFormat) -> Result<(), Error> {
-        self.format = format.into_format()?;
-        Ok(())
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::SchemeNotEnabled`] if the format names a scheme that
+    /// exists but whose feature is not compiled in, and [`Error::InvalidFormat`]
+    /// for any other unrecognized format string.
+    pub fn set_format(&mut self, format: impl IntoFormat) -> Result<&mut Self, Error> {
+        self.switch_format(format.into_format()?);
+        Ok(self)
     }
 
     /// Set the scheme while keeping the current encoding.
@@ -130,9 +278,9 @@ impl Ob {
     /// # Ok(())
     /// # }
     /// ```
-    pub fn set_scheme(&mut self, scheme: Scheme) -> Result<(), Error> {
-        self.format = Format::new(scheme, self.format.encoding());
-        Ok(())
+    pub fn set_scheme(&mut self, scheme: Scheme) -> Result<&mut Self, Error> {
+        self.switch_format(Format::new(scheme, self.format.encoding()));
+        Ok(self)
     }
 
     /// Set the encoding while keeping the current scheme.
@@ -151,9 +299,124 @@ impl Ob {
     /// # Ok(())
     /// # }
     /// ```
-    pub fn set_encoding(&mut self, encoding: Encoding) -> Result<(), Error> {
-        self.format = Format::new(self.format.scheme(), encoding);
-        Ok(())
+    pub fn set_encoding(&mut self, encoding: Encoding) -> Result<&mut Self, Error> {
+        self.switch_format(Format::new(self.format.scheme(), encoding));
+        Ok(self)
+    }
+
+    /// Number of formats kept by [`format_history`](Self::format_history).
+    #[cfg(feature = "debug-history")]
+    pub const FORMAT_HISTORY_LEN: usize = 16;
+
+    /// The last formats set with `set_format`, `set_scheme` or
+    /// `set_encoding`, oldest first, up to
+    /// [`FORMAT_HISTORY_LEN`](Self::FORMAT_HISTORY_LEN) entries.  The format
+    /// given at construction is not included.
+    ///
+    /// A debugging aid (feature `debug-history`), e.g. to see which format
+    /// changes led up to a failure in a fuzzing harness.
+    #[cfg(feature = "debug-history")]
+    pub fn format_history(&self) -> &[Format] {
+        &self.history
+    }
+
+    fn switch_format(&mut self, format: Format) {
+        self.format = format;
+        #[cfg(feature = "debug-history")]
+        {
+            if self.history.len() == Self::FORMAT_HISTORY_LEN {
+                self.history.remove(0);
+            }
+            self.history.push(format);
+        }
+    }
+
+    /// Limit the length in bytes of plaintext and obtext accepted by `enc`,
+    /// `dec` and `autodec` (unlimited by default).
+    ///
+    /// Longer inputs are rejected with [`Error::InputTooLarge`] before any
+    /// decoding or encryption work, bounding the cost of untrusted input.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), oboron::Error> {
+    /// # #[cfg(feature = "aasv")]
+    /// # {
+    /// # use oboron::{Error, Ob};
+    /// # let key = oboron::generate_key();
+    /// let ob = Ob::new("aasv.b64", &key)?.with_max_input_len(64);
+    /// assert!(ob.enc("short").is_ok());
+    /// assert_eq!(
+    ///     ob.dec(&"A".repeat(65)),
+    ///     Err(Error::InputTooLarge { len: 65, max: 64 })
+    /// );
+    /// # }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_max_input_len(mut self, max_len: usize) -> Self {
+        self.max_input_len = Some(max_len);
+        self
+    }
+
+    /// Get the input length limit set by
+    /// [`with_max_input_len`](Self::with_max_input_len), if any.
+    pub fn max_input_len(&self) -> Option<usize> {
+        self.max_input_len
+    }
+
+    /// Make `dec` reject invalid obtext in roughly uniform time.
+    ///
+    /// By default `dec` returns as soon as a check fails, so an obtext with
+    /// a bad encoding or the wrong scheme marker is rejected faster than
+    /// one that fails authentication, and the timing difference tells an
+    /// attacker how far a forged token got.  With this option the scheme's
+    /// decryption runs on a dummy ciphertext after an early failure, so
+    /// every reject pays for a decryption: rejects become slower, successful
+    /// decodes cost the same.  The error returned is unchanged.
+    ///
+    /// This is best effort: decoding itself is not constant time, and
+    /// `autodec` is not covered, since it tries several formats.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), oboron::Error> {
+    /// # #[cfg(feature = "aasv")]
+    /// # {
+    /// # use oboron::{Error, Ob, ObtextCodec};
+    /// # let key = oboron::generate_key();
+    /// let ob = Ob::new("aasv.b64", &key)?.with_constant_time_dec();
+    /// let ot = ob.enc("token")?;
+    /// assert_eq!(ob.dec(&ot)?, "token");
+    /// assert!(matches!(ob.dec("!!"), Err(Error::InvalidB64)));
+    /// # }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_constant_time_dec(mut self) -> Self {
+        self.constant_time_dec = true;
+        self
+    }
+
+    /// Whether [`with_constant_time_dec`](Self::with_constant_time_dec) is set.
+    pub fn constant_time_dec(&self) -> bool {
+        self.constant_time_dec
+    }
+
+    /// Report every `enc`, `dec` and `autodec` call to `observer`, e.g. to
+    /// collect metrics; see [`ObObserver`](crate::ObObserver).
+    #[cfg(feature = "metrics")]
+    pub fn with_observer(mut self, observer: Arc<dyn crate::ObObserver>) -> Self {
+        self.observer = Some(observer);
+        self
+    }
+
+    /// Get the observer set by [`with_observer`](Self::with_observer), if any.
+    #[cfg(feature = "metrics")]
+    pub fn observer(&self) -> Option<&Arc<dyn crate::ObObserver>> {
+        self.observer.as_ref()
     }
 
     /// Decode and decrypt obtext with automatic format detection.
@@ -187,15 +450,77 @@ impl Ob {
     /// ```
     #[inline]
     pub fn autodec(&self, obtext: &str) -> Result<String, Error> {
-        // Fast path: try current encoding first
-        if let Ok(result) =
-            crate::dec_auto::dec_any_scheme(&self.masterkey, self.format.encoding(), obtext)
-        {
-            return Ok(result);
+        let autodec = || {
+            crate::error::check_input_len(obtext, self.max_input_len)?;
+
+            // Fast path: try current encoding first
+            if let Ok(result) = self.dec_any_scheme(obtext) {
+                return Ok(result);
+            }
+
+            // Fallback:  full format autodetection (encoding + scheme)
+            crate::dec_auto::dec_any_format(&self.masterkey, obtext)
+        };
+        #[cfg(feature = "metrics")]
+        if let Some(observer) = &self.observer {
+            let operation = crate::ObOperation::Autodec;
+            return crate::metrics::observe_dec(observer, operation, None, obtext, autodec);
+        }
+        autodec()
+    }
+
+    /// Decode obtext with this instance's encoding, autodetecting only the
+    /// scheme.
+    ///
+    /// This is the fast path of [`autodec`](Self::autodec) on its own,
+    /// under a name that says what it accepts: obtext of any enabled scheme
+    /// in [`encoding()`](Self::encoding), and nothing in other encodings.
+    /// [`dec`](Self::dec) is the strict counterpart and rejects obtext whose
+    /// marker names another scheme.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), oboron::Error> {
+    /// # #[cfg(all(feature = "aasv", feature = "apsv"))]
+    /// # {
+    /// # use oboron::Ob;
+    /// # let key = oboron::generate_key();
+    /// let ot = Ob::new("apsv.b64", &key)?.enc("test")?;
+    ///
+    /// let ob = Ob::new("aasv.b64", &key)?;
+    /// assert_eq!(ob.dec_autoscheme(&ot)?, "test");
+    /// assert!(ob.dec(&ot).is_err());
+    /// # }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn dec_autoscheme(&self, obtext: &str) -> Result<String, Error> {
+        #[cfg(feature = "metrics")]
+        if let Some(observer) = &self.observer {
+            let dec = || self.dec_any_scheme(obtext);
+            let operation = crate::ObOperation::Autodec;
+            return crate::metrics::observe_dec(observer, operation, None, obtext, dec);
         }
+        self.dec_any_scheme(obtext)
+    }
 
-        // Fallback:  full format autodetection (encoding + scheme)
-        crate::dec_auto::dec_any_format(&self.masterkey, obtext)
+    /// [`dec_autoscheme`](Self::dec_autoscheme) without reporting to the
+    /// observer, for use inside methods that report themselves.
+    fn dec_any_scheme(&self, obtext: &str) -> Result<String, Error> {
+        crate::error::check_input_len(obtext, self.max_input_len)?;
+        crate::dec_auto::dec_any_scheme(&self.masterkey, self.format.encoding(), obtext)
+    }
+
+    /// Like [`autodec`](Self::autodec), but takes the obtext as raw bytes.
+    ///
+    /// The bytes are checked against the obtext alphabets instead of being
+    /// validated as UTF-8; any other byte returns
+    /// [`Error::InvalidObtextByte`].
+    #[inline]
+    pub fn autodec_bytes(&self, obtext: &[u8]) -> Result<String, Error> {
+        self.autodec(crate::encoding::obtext_from_bytes(obtext)?)
     }
 
     // Alt constructors ================================================
@@ -225,11 +550,49 @@ impl Ob {
     pub fn new_keyless(format: impl IntoFormat) -> Result<Self, Error> {
         let format = format.into_format()?;
         Ok(Self {
-            masterkey: MasterKey::from_bytes(&HARDCODED_KEY_BYTES)?,
+            masterkey: MasterKey::from_bytes(crate::keyless::keyless_key())?,
             format,
+            max_input_len: None,
+            constant_time_dec: false,
+            #[cfg(feature = "debug-history")]
+            history: Vec::new(),
+            #[cfg(feature = "metrics")]
+            observer: None,
         })
     }
 
+    /// Create a new Ob for `scheme` with its default encoding
+    /// ([`Format::with_scheme`]) and a base64 key.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), oboron::Error> {
+    /// # #[cfg(feature = "aasv")]
+    /// # {
+    /// # use oboron::{Ob, Scheme};
+    /// # let key = oboron::generate_key();
+    /// let ob = Ob::from_scheme(Scheme::Aasv, &key)?;
+    /// assert_eq!(ob.format(), Ob::new("aasv.c32", &key)?.format());
+    /// # }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_scheme(scheme: Scheme, key: &str) -> Result<Self, Error> {
+        Self::new(Format::with_scheme(scheme), key)
+    }
+
+    /// Like [`Ob::new`], but returns an `Arc<Ob>` ready to share across threads.
+    pub fn shared(format: impl IntoFormat, key: &str) -> Result<Arc<Self>, Error> {
+        Self::new(format, key).map(Arc::new)
+    }
+
+    /// Like [`Ob::new_keyless`], but returns an `Arc<Ob>` (testing only).
+    #[cfg(feature = "keyless")]
+    pub fn shared_keyless(format: impl IntoFormat) -> Result<Arc<Self>, Error> {
+        Self::new_keyless(format).map(Arc::new)
+    }
+
     /// Create a new Ob with the specified format and hex key.
     ///
     /// Accepts either a format string (`&str`) or a `Format` instance.
@@ -258,6 +621,12 @@ impl Ob {
         Ok(Self {
             masterkey: MasterKey::from_hex(key_hex)?,
             format,
+            max_input_len: None,
+            constant_time_dec: false,
+            #[cfg(feature = "debug-history")]
+            history: Vec::new(),
+            #[cfg(feature = "metrics")]
+            observer: None,
         })
     }
 
@@ -286,6 +655,80 @@ impl Ob {
         Ok(Self {
             masterkey: MasterKey::from_bytes(key)?,
             format,
+            max_input_len: None,
+            constant_time_dec: false,
+            #[cfg(feature = "debug-history")]
+            history: Vec::new(),
+            #[cfg(feature = "metrics")]
+            observer: None,
+        })
+    }
+
+    /// Create a new Ob from the specified format and a 32-byte key seed.
+    ///
+    /// The seed is expanded with HKDF-SHA256 into the 64-byte key material
+    /// used by all schemes, so this is distinct key material from a native
+    /// 64-byte key: [`key()`](Self::key) returns the expanded key, not the seed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), oboron::Error> {
+    /// # #[cfg(all(feature = "aasv", feature = "bytes-keys", feature = "seed-keys"))]
+    /// # {
+    /// # use oboron::{Ob, ObtextCodec};
+    /// let seed = [42u8; 32];
+    /// let ob = Ob::from_bytes_32("aasv.b64", &seed)?;
+    /// let ot = ob.enc("hello")?;
+    /// assert_eq!(Ob::from_bytes_32("aasv.b64", &seed)?.dec(&ot)?, "hello");
+    /// # }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(all(feature = "bytes-keys", feature = "seed-keys"))]
+    pub fn from_bytes_32(format: impl IntoFormat, seed: &[u8; 32]) -> Result<Self, Error> {
+        let format = format.into_format()?;
+        Ok(Self {
+            masterkey: MasterKey::from_bytes_32(seed)?,
+            format,
+            max_input_len: None,
+            constant_time_dec: false,
+            #[cfg(feature = "debug-history")]
+            history: Vec::new(),
+            #[cfg(feature = "metrics")]
+            observer: None,
+        })
+    }
+
+    /// Create a new Ob from the specified format and a base64 32-byte key
+    /// seed (43 chars). See [`from_bytes_32`](Self::from_bytes_32).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), oboron::Error> {
+    /// # #[cfg(all(feature = "aasv", feature = "seed-keys"))]
+    /// # {
+    /// # use oboron::{Ob, ObtextCodec};
+    /// let seed = "KioqKioqKioqKioqKioqKioqKioqKioqKioqKioqKio";
+    /// let ob = Ob::from_base64_32("aasv.b64", seed)?;
+    /// assert_eq!(ob.key().len(), 86); // expanded to the full 64-byte key
+    /// # }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "seed-keys")]
+    pub fn from_base64_32(format: impl IntoFormat, seed: &str) -> Result<Self, Error> {
+        let format = format.into_format()?;
+        Ok(Self {
+            masterkey: MasterKey::from_base64_32(seed)?,
+            format,
+            max_input_len: None,
+            constant_time_dec: false,
+            #[cfg(feature = "debug-history")]
+            history: Vec::new(),
+            #[cfg(feature = "metrics")]
+            observer: None,
         })
     }
 
@@ -312,6 +755,30 @@ impl Ob {
         self.masterkey.key_base64()
     }
 
+    /// Get the base64 key wrapped in a [`SecretString`](secrecy::SecretString),
+    /// so that it is redacted from `Debug` output.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), oboron::Error> {
+    /// # #[cfg(all(feature = "aasv", feature = "secrecy"))]
+    /// # {
+    /// # use oboron::Ob;
+    /// use oboron::secrecy::{ExposeSecret, SecretString};
+    /// let key = SecretString::from(oboron::generate_key());
+    /// let ob = Ob::new_secret("aasv.b64", &key)?;
+    /// assert_eq!(ob.key_secret().expose_secret(), key.expose_secret());
+    /// # }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "secrecy")]
+    #[inline]
+    pub fn key_secret(&self) -> secrecy::SecretString {
+        self.masterkey.key_base64().into()
+    }
+
     /// Get the key as a hex string.
     ///
     /// # Examples
@@ -357,15 +824,94 @@ impl Ob {
     pub fn key_bytes(&self) -> &[u8; 64] {
         self.masterkey.key_bytes()
     }
+
+    /// Get the key fingerprint: a short, non-secret key identifier
+    /// (8 lowercase hex characters) derived from the key by SHA-256.
+    ///
+    /// The same key always has the same fingerprint, regardless of format.
+    /// Used as the key-ID prefix by [`enc_with_key_id`](Self::enc_with_key_id)
+    /// and for key lookup by [`Keyring`](crate::Keyring).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), oboron::Error> {
+    /// # #[cfg(feature = "aasv")]
+    /// # {
+    /// # use oboron::Ob;
+    /// # let key = oboron::generate_key();
+    /// let ob = Ob::new("aasv.b64", &key)?;
+    /// assert_eq!(ob.fingerprint().len(), 8);
+    /// assert_eq!(ob.fingerprint(), Ob::new("aasv.c32", &key)?.fingerprint());
+    /// # }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn fingerprint(&self) -> String {
+        self.masterkey.fingerprint()
+    }
+
+    /// Whether `other` uses the same key, whatever its format, e.g. to
+    /// validate configuration.
+    ///
+    /// The keys are compared in constant time, without exiting at the
+    /// first difference, so comparing against a secret key does not leak
+    /// how much of it matched.  Unlike comparing [`key`](Self::key) strings,
+    /// no copy of either key is made.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), oboron::Error> {
+    /// # #[cfg(feature = "aasv")]
+    /// # {
+    /// # use oboron::Ob;
+    /// # let key = oboron::generate_key();
+    /// let ob = Ob::new("aasv.b64", &key)?;
+    /// assert!(ob.same_key(&Ob::new("aasv.c32", &key)?));
+    /// assert!(!ob.same_key(&Ob::new("aasv.b64", &oboron::generate_key())?));
+    /// # }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn same_key(&self, other: &Ob) -> bool {
+        self.masterkey == other.masterkey
+    }
 }
 
 impl ObtextCodec for Ob {
     fn enc(&self, plaintext: &str) -> Result<String, Error> {
-        crate::enc::enc_to_format(plaintext, self.format, self.masterkey.key())
+        let enc = || {
+            crate::error::check_input_len(plaintext, self.max_input_len)?;
+            crate::enc::enc_to_format(plaintext, self.format, self.masterkey.key())
+        };
+        #[cfg(feature = "metrics")]
+        if let Some(observer) = &self.observer {
+            return crate::metrics::observe_enc(observer, Ok(self.format), plaintext, |_| enc());
+        }
+        enc()
     }
 
     fn dec(&self, obtext: &str) -> Result<String, Error> {
-        crate::dec::dec_from_format(obtext, self.format, self.masterkey.key())
+        let dec = || {
+            crate::error::check_input_len(obtext, self.max_input_len)?;
+            if self.constant_time_dec {
+                return crate::dec::dec_from_format_uniform(
+                    obtext,
+                    self.format,
+                    self.masterkey.key(),
+                );
+            }
+            crate::dec::dec_from_format(obtext, self.format, self.masterkey.key())
+        };
+        #[cfg(feature = "metrics")]
+        if let Some(observer) = &self.observer {
+            let (operation, format) = (crate::ObOperation::Dec, Some(self.format));
+            return crate::metrics::observe_dec(observer, operation, format, obtext, dec);
+        }
+        dec()
     }
 
     fn format(&self) -> Format {
@@ -379,6 +925,17 @@ impl ObtextCodec for Ob {
     fn encoding(&self) -> Encoding {
         self.format.encoding()
     }
+
+    fn key_id(&self) -> String {
+        self.fingerprint()
+    }
+}
+
+/// Displays the format string (e.g. `aasv.b64`); the key is never printed.
+impl std::fmt::Display for Ob {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.format.fmt(f)
+    }
 }
 
 // Add inherent methods that delegate to trait methods
@@ -410,6 +967,13 @@ impl Ob {
     /// Uses the instance's configured format for decoding.  Does not perform
     /// scheme autodetection - use [`autodec`](Self::autodec) for that.
     ///
+    /// # Errors
+    ///
+    /// Returns [`Error::SchemeMismatch`] if the obtext was produced by another
+    /// enabled scheme (checked before decrypting, so no key is involved), and
+    /// [`Error::DecryptionFailed`] if the scheme matches but authentication
+    /// fails - the obtext is corrupt or was encrypted under another key.
+    ///
     /// # Examples
     ///
     /// ```rust
@@ -431,6 +995,167 @@ impl Ob {
         <Self as ObtextCodec>::dec(self, obtext)
     }
 
+    /// Encrypt and encode plaintext, prefixed with the key fingerprint.
+    ///
+    /// The result has the form `<fingerprint>.<obtext>`, which lets a
+    /// [`Keyring`](crate::Keyring) select the decryption key directly instead
+    /// of trying each key in turn.  `.` never occurs in obtext, so the prefix
+    /// is unambiguous.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), oboron::Error> {
+    /// # #[cfg(feature = "aasv")]
+    /// # {
+    /// # use oboron::Ob;
+    /// # let key = oboron::generate_key();
+    /// let ob = Ob::new("aasv.b64", &key)?;
+    /// let ot = ob.enc_with_key_id("secret data")?;
+    /// let (key_id, obtext) = ot.split_once('.').unwrap();
+    /// assert_eq!(key_id, ob.fingerprint());
+    /// assert_eq!(ob.dec(obtext)?, "secret data");
+    /// # }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn enc_with_key_id(&self, plaintext: &str) -> Result<String, Error> {
+        Ok(format!(
+            "{}{}{}",
+            self.fingerprint(),
+            crate::keyring::KEY_ID_SEPARATOR,
+            self.enc(plaintext)?
+        ))
+    }
+
+    /// Encrypt and encode plaintext, followed by a human-readable format
+    /// label for tooling: `<obtext>#<format>`, e.g. `...#aasv.b64`.
+    ///
+    /// The label is a comment: it is not part of the payload and is ignored
+    /// by [`dec_labeled`](Self::dec_labeled), so it cannot change how the
+    /// obtext decodes.  `#` never occurs in obtext.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), oboron::Error> {
+    /// # #[cfg(feature = "aasv")]
+    /// # {
+    /// # use oboron::Ob;
+    /// # let key = oboron::generate_key();
+    /// let ob = Ob::new("aasv.b64", &key)?;
+    /// let labeled = ob.enc_labeled("secret data")?;
+    /// assert!(labeled.ends_with("#aasv.b64"));
+    /// assert_eq!(ob.dec_labeled(&labeled)?, "secret data");
+    /// # }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn enc_labeled(&self, plaintext: &str) -> Result<String, Error> {
+        Ok(format!(
+            "{}{}{}",
+            self.enc(plaintext)?,
+            LABEL_SEPARATOR,
+            self.format
+        ))
+    }
+
+    /// Like [`dec`](Self::dec), but first strips a trailing `#<label>` as
+    /// added by [`enc_labeled`](Self::enc_labeled).  Bare obtext is decoded
+    /// as is.
+    #[inline]
+    pub fn dec_labeled(&self, obtext: &str) -> Result<String, Error> {
+        self.dec(strip_label(obtext))
+    }
+
+    /// Encrypt and encode plaintext as a `data:` URL for embedding in web
+    /// pages: `data:<mime>;ob=<format>,<obtext>`.
+    ///
+    /// The obtext is the URL's data as is; every encoding is URL-safe, so it
+    /// needs no percent-encoding.  `mime` may carry parameters of its own
+    /// (e.g. `text/plain;charset=utf-8`), but not a `,`, which ends the
+    /// header, nor an `ob=` parameter, which would shadow the format.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidDataUrl`] for such a `mime`, and for a format
+    /// whose name does not parse back to the same format, so that
+    /// [`dec_data_url`](Self::dec_data_url) could not read the URL.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), oboron::Error> {
+    /// # #[cfg(feature = "aasv")]
+    /// # {
+    /// # use oboron::Ob;
+    /// # let key = oboron::generate_key();
+    /// let ob = Ob::new("aasv.b64", &key)?;
+    /// let url = ob.enc_data_url("secret data", "application/octet-stream")?;
+    /// assert!(url.starts_with("data:application/octet-stream;ob=aasv.b64,"));
+    /// assert_eq!(ob.dec_data_url(&url)?, "secret data");
+    /// # }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn enc_data_url(&self, plaintext: &str, mime: &str) -> Result<String, Error> {
+        let shadows_format = mime
+            .split(';')
+            .skip(1)
+            .any(|param| param.trim_start().starts_with(DATA_URL_FORMAT_PARAM));
+        if mime.contains(',') || shadows_format {
+            return Err(Error::InvalidDataUrl);
+        }
+        // The format is read back by dec_data_url, up to the first `,`
+        let format = self.format.to_string();
+        if format.contains([',', ';']) || Format::from_str(&format) != Ok(self.format) {
+            return Err(Error::InvalidDataUrl);
+        }
+        Ok(format!(
+            "{}{};{}{},{}",
+            DATA_URL_PREFIX,
+            mime,
+            DATA_URL_FORMAT_PARAM,
+            format,
+            self.enc(plaintext)?
+        ))
+    }
+
+    /// Decode+decrypt a `data:` URL made by
+    /// [`enc_data_url`](Self::enc_data_url).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidDataUrl`] if `url` is not a `data:` URL with
+    /// an `ob=<format>` parameter naming the format as `enc_data_url` writes
+    /// it, the error of [`Format::from_str`](crate::Format::from_str) for an
+    /// invalid format, and [`Error::FormatMismatch`] if the format is not
+    /// this instance's.
+    pub fn dec_data_url(&self, url: &str) -> Result<String, Error> {
+        let (header, obtext) = url
+            .strip_prefix(DATA_URL_PREFIX)
+            .and_then(|rest| rest.split_once(','))
+            .ok_or(Error::InvalidDataUrl)?;
+        let format = header
+            .split(';')
+            .skip(1)
+            .find_map(|param| param.strip_prefix(DATA_URL_FORMAT_PARAM))
+            .ok_or(Error::InvalidDataUrl)?;
+        // enc_data_url writes the format's canonical name; the untrusted
+        // parameter is only parsed to report what it names instead
+        if format != self.format.to_string() {
+            let found = Format::from_str(format)?;
+            if found == self.format {
+                return Err(Error::InvalidDataUrl);
+            }
+            return Err(Error::FormatMismatch {
+                expected: self.format,
+                found,
+            });
+        }
+        self.dec(obtext)
+    }
+
     /// Get the current format (scheme + encoding).
     ///
     /// # Examples
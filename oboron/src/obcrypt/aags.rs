@@ -8,7 +8,7 @@ use aes_gcm_siv::{
 const KEY_OFFSET: usize = 32;
 const KEY_LEN: usize = 32;
 const NONCE_SIZE: usize = 12;
-const MIN_DATA_LEN: usize = 17;
+pub(crate) const MIN_DATA_LEN: usize = 17;
 
 /// Encrypt plaintext bytes using deterministic AES-GCM-SIV (aags scheme).
 /// Takes the full 64-byte key and extracts the second 32 bytes internally.
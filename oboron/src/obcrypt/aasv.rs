@@ -2,7 +2,7 @@
 use crate::Error;
 use aes_siv::{aead::KeyInit, siv::Aes256Siv};
 
-const MIN_DATA_LEN: usize = 17;
+pub(crate) const MIN_DATA_LEN: usize = 17;
 
 /// Encrypt plaintext bytes using deterministic AES-SIV (aasv scheme).
 /// Takes the full 64-byte key directly.
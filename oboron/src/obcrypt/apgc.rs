@@ -0,0 +1,134 @@
+//! Standard AES-256-GCM with a random 96-bit nonce.
+//!
+//! Provided for interoperability with systems that speak plain AES-GCM.
+//! Payload layout is `nonce (12) || ciphertext || tag (16)`.
+//!
+//! Every byte range of the master key is already an AES key of another
+//! scheme (bytes 0..32 are the first `aasv`/`apsv` SIV key, 32..64 the
+//! `aags`/`apgs` key), so the AES key is derived instead: HKDF-SHA256 of the
+//! whole master key, no salt, info `oboron apgc v1`.  A peer holding the
+//! master key derives the same 32 bytes with any HKDF implementation.
+//!
+//! Unlike the SIV schemes (`apgs`, `apsv`), AES-GCM is not nonce-misuse
+//! resistant: if a nonce is ever repeated under the same key, both
+//! confidentiality and authenticity are lost for the affected messages.
+//! Nonces are drawn at random, so keep the number of messages per key well
+//! below 2^32 to make a collision negligible.  Prefer `apgs` or `apsv` unless
+//! GCM is required.
+
+#![cfg(feature = "apgc")]
+use crate::Error;
+use aes_gcm::{
+    aead::{Aead, KeyInit},
+    Aes256Gcm, Nonce,
+};
+use hkdf::Hkdf;
+use rand::RngCore;
+use sha2::Sha256;
+use zeroize::Zeroizing;
+
+/// HKDF `info` label of the apgc AES key.
+const KEY_INFO: &[u8] = b"oboron apgc v1";
+const KEY_LEN: usize = 32;
+pub(crate) const NONCE_SIZE: usize = 12;
+const TAG_SIZE: usize = 16;
+pub(crate) const MIN_PAYLOAD_LEN: usize = NONCE_SIZE + 1 + TAG_SIZE;
+
+/// Derive the AES-GCM key from the master key.
+#[inline]
+fn derive_key(master_key: &[u8; 64]) -> Zeroizing<[u8; KEY_LEN]> {
+    let mut key = Zeroizing::new([0u8; KEY_LEN]);
+    Hkdf::<Sha256>::new(None, master_key)
+        .expand(KEY_INFO, key.as_mut())
+        .expect("32 bytes is a valid HKDF-SHA256 output length");
+    key
+}
+
+#[inline]
+pub fn encrypt(master_key: &[u8; 64], plaintext_bytes: &[u8]) -> Result<Vec<u8>, Error> {
+    let mut nonce = [0u8; NONCE_SIZE];
+    rand::thread_rng().fill_bytes(&mut nonce);
+    encrypt_with_nonce(master_key, plaintext_bytes, &nonce)
+}
+
+/// Encrypt with a caller-supplied nonce, which must be `NONCE_SIZE` bytes.
+#[inline]
+pub fn encrypt_with_nonce(
+    master_key: &[u8; 64],
+    plaintext_bytes: &[u8],
+    nonce: &[u8],
+) -> Result<Vec<u8>, Error> {
+    if nonce.len() != NONCE_SIZE {
+        return Err(Error::InvalidNonceLength {
+            expected: NONCE_SIZE,
+            actual: nonce.len(),
+        });
+    }
+    if plaintext_bytes.is_empty() {
+        return Err(Error::EmptyPlaintext);
+    }
+
+    let key = derive_key(master_key);
+
+    let ciphertext_len = plaintext_bytes.len() + TAG_SIZE;
+    let mut buffer = Vec::with_capacity(NONCE_SIZE + ciphertext_len);
+    buffer.extend_from_slice(nonce);
+
+    let cipher = Aes256Gcm::new(key.as_ref().into());
+    let nonce = Nonce::from(*<&[u8; NONCE_SIZE]>::try_from(&buffer[..NONCE_SIZE]).unwrap());
+
+    let ciphertext_with_tag = cipher
+        .encrypt(&nonce, plaintext_bytes)
+        .map_err(|_| Error::EncryptionFailed)?;
+
+    buffer.extend_from_slice(&ciphertext_with_tag);
+    Ok(buffer)
+}
+
+#[inline]
+pub fn decrypt(master_key: &[u8; 64], data: &[u8]) -> Result<Vec<u8>, Error> {
+    if data.len() < MIN_PAYLOAD_LEN {
+        return Err(Error::PayloadTooShort);
+    }
+
+    let key = derive_key(master_key);
+
+    let nonce_bytes = &data[..NONCE_SIZE];
+    let ciphertext_with_tag = &data[NONCE_SIZE..];
+
+    let cipher = Aes256Gcm::new(key.as_ref().into());
+    let nonce = Nonce::from(*<&[u8; NONCE_SIZE]>::try_from(nonce_bytes).unwrap());
+
+    let plaintext = cipher
+        .decrypt(&nonce, ciphertext_with_tag)
+        .map_err(|_| Error::DecryptionFailed)?;
+
+    Ok(plaintext)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_is_not_a_window_of_the_master_key() {
+        let master_key: [u8; 64] = core::array::from_fn(|i| i as u8);
+        let key = derive_key(&master_key);
+        // No 32-byte window, in particular not the SIV K1 (0..32) shared
+        // with aasv/apsv and legacy, nor the GCM-SIV key (32..64)
+        for window in master_key.windows(KEY_LEN) {
+            assert_ne!(window, key.as_slice());
+        }
+    }
+
+    #[test]
+    fn test_first_half_of_master_key_does_not_decrypt() {
+        let master_key = [5u8; 64];
+        let payload = encrypt(&master_key, b"separate keys").unwrap();
+        let siv_k1: &[u8; 32] = master_key[..32].try_into().unwrap();
+        let cipher = Aes256Gcm::new(siv_k1.into());
+        let nonce = Nonce::from(*<&[u8; NONCE_SIZE]>::try_from(&payload[..NONCE_SIZE]).unwrap());
+        assert!(cipher.decrypt(&nonce, &payload[NONCE_SIZE..]).is_err());
+        assert_eq!(decrypt(&master_key, &payload).unwrap(), b"separate keys");
+    }
+}
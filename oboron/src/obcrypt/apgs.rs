@@ -8,12 +8,30 @@ use rand::RngCore;
 
 const KEY_OFFSET: usize = 32;
 const KEY_LEN: usize = 32;
-const NONCE_SIZE: usize = 12;
+pub(crate) const NONCE_SIZE: usize = 12;
 const TAG_SIZE: usize = 16;
-const MIN_PAYLOAD_LEN: usize = NONCE_SIZE + 1 + TAG_SIZE;
+pub(crate) const MIN_PAYLOAD_LEN: usize = NONCE_SIZE + 1 + TAG_SIZE;
 
 #[inline]
 pub fn encrypt(master_key: &[u8; 64], plaintext_bytes: &[u8]) -> Result<Vec<u8>, Error> {
+    let mut nonce = [0u8; NONCE_SIZE];
+    rand::thread_rng().fill_bytes(&mut nonce);
+    encrypt_with_nonce(master_key, plaintext_bytes, &nonce)
+}
+
+/// Encrypt with a caller-supplied nonce, which must be `NONCE_SIZE` bytes.
+#[inline]
+pub fn encrypt_with_nonce(
+    master_key: &[u8; 64],
+    plaintext_bytes: &[u8],
+    nonce: &[u8],
+) -> Result<Vec<u8>, Error> {
+    if nonce.len() != NONCE_SIZE {
+        return Err(Error::InvalidNonceLength {
+            expected: NONCE_SIZE,
+            actual: nonce.len(),
+        });
+    }
     if plaintext_bytes.is_empty() {
         return Err(Error::EmptyPlaintext);
     }
@@ -23,8 +41,7 @@ pub fn encrypt(master_key: &[u8; 64], plaintext_bytes: &[u8]) -> Result<Vec<u8>,
 
     let ciphertext_len = plaintext_bytes.len() + TAG_SIZE;
     let mut buffer = Vec::with_capacity(NONCE_SIZE + ciphertext_len);
-    buffer.resize(NONCE_SIZE, 0);
-    rand::thread_rng().fill_bytes(&mut buffer[..NONCE_SIZE]);
+    buffer.extend_from_slice(nonce);
 
     let cipher = Aes256GcmSiv::new(key.into());
     let nonce = Nonce::from(*<&[u8; NONCE_SIZE]>::try_from(&buffer[..NONCE_SIZE]).unwrap());
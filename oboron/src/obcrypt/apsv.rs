@@ -3,19 +3,37 @@ use crate::Error;
 use aes_siv::{aead::KeyInit, siv::Aes256Siv};
 use rand::RngCore;
 
-const NONCE_SIZE: usize = 16;
+pub(crate) const NONCE_SIZE: usize = 16;
 const TAG_SIZE: usize = 16;
+pub(crate) const MIN_DATA_LEN: usize = NONCE_SIZE + 1 + TAG_SIZE;
 
 #[inline]
 pub fn encrypt(key: &[u8; 64], plaintext_bytes: &[u8]) -> Result<Vec<u8>, Error> {
+    let mut nonce = [0u8; NONCE_SIZE];
+    rand::thread_rng().fill_bytes(&mut nonce);
+    encrypt_with_nonce(key, plaintext_bytes, &nonce)
+}
+
+/// Encrypt with a caller-supplied nonce, which must be `NONCE_SIZE` bytes.
+#[inline]
+pub fn encrypt_with_nonce(
+    key: &[u8; 64],
+    plaintext_bytes: &[u8],
+    nonce: &[u8],
+) -> Result<Vec<u8>, Error> {
+    if nonce.len() != NONCE_SIZE {
+        return Err(Error::InvalidNonceLength {
+            expected: NONCE_SIZE,
+            actual: nonce.len(),
+        });
+    }
     if plaintext_bytes.is_empty() {
         return Err(Error::EmptyPlaintext);
     }
 
     let ciphertext_len = plaintext_bytes.len() + TAG_SIZE;
     let mut buffer = Vec::with_capacity(NONCE_SIZE + ciphertext_len);
-    buffer.resize(NONCE_SIZE, 0);
-    rand::thread_rng().fill_bytes(&mut buffer[..NONCE_SIZE]);
+    buffer.extend_from_slice(nonce);
 
     let mut cipher = Aes256Siv::new(key.into());
 
@@ -29,7 +47,7 @@ pub fn encrypt(key: &[u8; 64], plaintext_bytes: &[u8]) -> Result<Vec<u8>, Error>
 
 #[inline]
 pub fn decrypt(key: &[u8; 64], data: &[u8]) -> Result<Vec<u8>, Error> {
-    if data.len() < 33 {
+    if data.len() < MIN_DATA_LEN {
         return Err(Error::PayloadTooShort);
     }
 
@@ -0,0 +1,127 @@
+#![cfg(feature = "mock")]
+//! mock3 - Keyed byte substitution (length-preserving, obfuscation only)
+//!
+//! Each byte is XORed with a key byte chosen by its position, then mapped
+//! through a 256-entry substitution table shuffled from the key.  The
+//! ciphertext is exactly as long as the plaintext: there is no nonce, tag or
+//! padding.  This is NOT encryption: identical bytes at the same position
+//! always map to the same output and the table is trivially recoverable from
+//! known plaintext.  Use it only for demos and tests where output length must
+//! equal input length.
+
+use crate::Error;
+
+/// Build the substitution table from the first half of the key.
+///
+/// Fisher-Yates shuffle driven by a xorshift64 generator seeded from the key.
+fn sbox(key: &[u8; 64]) -> [u8; 256] {
+    let mut state = key[..32]
+        .chunks_exact(8)
+        .fold(0x9e37_79b9_7f4a_7c15_u64, |acc, chunk| {
+            acc.rotate_left(17) ^ u64::from_le_bytes(chunk.try_into().unwrap())
+        })
+        | 1;
+    let mut table = [0u8; 256];
+    for (i, b) in table.iter_mut().enumerate() {
+        *b = i as u8;
+    }
+    for i in (1..256).rev() {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        table.swap(i, (state % (i as u64 + 1)) as usize);
+    }
+    table
+}
+
+/// Position-dependent whitening byte from the second half of the key.
+#[inline]
+fn whitening(key: &[u8; 64], position: usize) -> u8 {
+    key[32 + position % 32]
+}
+
+/// "Encrypt" plaintext bytes using keyed substitution (mock3).
+/// The output has the same length as the input.
+#[inline]
+pub fn encrypt(key: &[u8; 64], plaintext_bytes: &[u8]) -> Result<Vec<u8>, Error> {
+    if plaintext_bytes.is_empty() {
+        return Err(Error::EmptyPlaintext);
+    }
+
+    let table = sbox(key);
+    Ok(plaintext_bytes
+        .iter()
+        .enumerate()
+        .map(|(i, &b)| table[(b ^ whitening(key, i)) as usize])
+        .collect())
+}
+
+/// "Decrypt" ciphertext bytes using keyed substitution (mock3).
+/// Inverts the substitution table and removes the whitening.
+#[inline]
+pub fn decrypt(key: &[u8; 64], data: &[u8]) -> Result<Vec<u8>, Error> {
+    if data.is_empty() {
+        return Err(Error::EmptyPayload);
+    }
+
+    let table = sbox(key);
+    let mut inverse = [0u8; 256];
+    for (i, &b) in table.iter().enumerate() {
+        inverse[b as usize] = i as u8;
+    }
+    Ok(data
+        .iter()
+        .enumerate()
+        .map(|(i, &b)| inverse[b as usize] ^ whitening(key, i))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_key(seed: u8) -> [u8; 64] {
+        let mut key = [0u8; 64];
+        for (i, b) in key.iter_mut().enumerate() {
+            *b = seed.wrapping_mul(31).wrapping_add(i as u8);
+        }
+        key
+    }
+
+    #[test]
+    fn test_mock3_roundtrip_preserves_length() {
+        let key = test_key(1);
+
+        for plaintext in ["a", "hello world", "Hello 世界", &"x".repeat(1000)] {
+            let ciphertext = encrypt(&key, plaintext.as_bytes()).unwrap();
+            assert_eq!(ciphertext.len(), plaintext.len());
+            assert_eq!(decrypt(&key, &ciphertext).unwrap(), plaintext.as_bytes());
+        }
+    }
+
+    #[test]
+    fn test_mock3_is_key_dependent() {
+        let plaintext = b"hello world";
+        let a = encrypt(&test_key(1), plaintext).unwrap();
+        let b = encrypt(&test_key(2), plaintext).unwrap();
+
+        assert_ne!(a, plaintext);
+        assert_ne!(a, b);
+        assert_eq!(a, encrypt(&test_key(1), plaintext).unwrap());
+    }
+
+    #[test]
+    fn test_mock3_sbox_is_permutation() {
+        let mut table = sbox(&test_key(3));
+        table.sort_unstable();
+        assert!(table.iter().enumerate().all(|(i, &b)| i == b as usize));
+    }
+
+    #[test]
+    fn test_mock3_empty() {
+        let key = test_key(1);
+
+        assert!(encrypt(&key, b"").is_err());
+        assert!(decrypt(&key, b"").is_err());
+    }
+}
@@ -0,0 +1,110 @@
+#![cfg(feature = "mock")]
+//! mock4 - Word-only substitution (delimiter- and case-preserving, demo only)
+//!
+//! Each ASCII letter or digit is rotated within its own class (lowercase,
+//! uppercase, digits) by a key byte chosen by its position; every other
+//! byte, such as spaces, punctuation and non-ASCII UTF-8, passes through
+//! unchanged.  The ciphertext is as long as the plaintext and keeps its
+//! word boundaries and letter case, which makes it a teaching aid for what
+//! encryption hides and what it must not leak.  This is NOT encryption:
+//! the structure of the plaintext is fully visible and the per-position
+//! shifts are trivially recovered.  Use it only for demos.
+
+use crate::Error;
+
+/// Rotate `byte` within its class by `shift` positions (`forward` for
+/// encryption), leaving bytes outside every class unchanged.
+#[inline]
+fn rotate(byte: u8, shift: u8, forward: bool) -> u8 {
+    let (base, len) = match byte {
+        b'a'..=b'z' => (b'a', 26),
+        b'A'..=b'Z' => (b'A', 26),
+        b'0'..=b'9' => (b'0', 10),
+        _ => return byte,
+    };
+    let shift = shift % len;
+    let offset = if forward {
+        (byte - base + shift) % len
+    } else {
+        (byte - base + len - shift) % len
+    };
+    base + offset
+}
+
+/// "Encrypt" plaintext bytes by rotating word characters (mock4).
+/// The output has the same length as the input.
+#[inline]
+pub fn encrypt(key: &[u8; 64], plaintext_bytes: &[u8]) -> Result<Vec<u8>, Error> {
+    if plaintext_bytes.is_empty() {
+        return Err(Error::EmptyPlaintext);
+    }
+
+    Ok(plaintext_bytes
+        .iter()
+        .enumerate()
+        .map(|(i, &b)| rotate(b, key[i % 64], true))
+        .collect())
+}
+
+/// "Decrypt" ciphertext bytes by rotating word characters back (mock4).
+#[inline]
+pub fn decrypt(key: &[u8; 64], data: &[u8]) -> Result<Vec<u8>, Error> {
+    if data.is_empty() {
+        return Err(Error::EmptyPayload);
+    }
+
+    Ok(data
+        .iter()
+        .enumerate()
+        .map(|(i, &b)| rotate(b, key[i % 64], false))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_key(seed: u8) -> [u8; 64] {
+        let mut key = [0u8; 64];
+        for (i, b) in key.iter_mut().enumerate() {
+            *b = seed.wrapping_mul(31).wrapping_add(i as u8).wrapping_add(1);
+        }
+        key
+    }
+
+    #[test]
+    fn test_mock4_keeps_delimiters_and_case() {
+        let key = test_key(1);
+        let plaintext = "Hello, World! 42 times-over; ünïcode.";
+        let ciphertext = encrypt(&key, plaintext.as_bytes()).unwrap();
+
+        assert_eq!(ciphertext.len(), plaintext.len());
+        assert_ne!(ciphertext, plaintext.as_bytes());
+        for (&p, &c) in plaintext.as_bytes().iter().zip(&ciphertext) {
+            if p.is_ascii_alphanumeric() {
+                assert_eq!(p.is_ascii_lowercase(), c.is_ascii_lowercase());
+                assert_eq!(p.is_ascii_uppercase(), c.is_ascii_uppercase());
+                assert_eq!(p.is_ascii_digit(), c.is_ascii_digit());
+            } else {
+                assert_eq!(p, c);
+            }
+        }
+        assert_eq!(decrypt(&key, &ciphertext).unwrap(), plaintext.as_bytes());
+    }
+
+    #[test]
+    fn test_mock4_roundtrip_every_class_and_shift() {
+        let key = test_key(2);
+        let plaintext: Vec<u8> = (0u8..=127).cycle().take(640).collect();
+        let ciphertext = encrypt(&key, &plaintext).unwrap();
+        assert_eq!(decrypt(&key, &ciphertext).unwrap(), plaintext);
+    }
+
+    #[test]
+    fn test_mock4_empty() {
+        let key = test_key(1);
+
+        assert!(encrypt(&key, b"").is_err());
+        assert!(decrypt(&key, b"").is_err());
+    }
+}
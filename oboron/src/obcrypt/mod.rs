@@ -1,11 +1,15 @@
 //! This library provides cryptographic library wrappings for oboron
 
+use crate::{Error, Scheme};
+
 mod constants;
 
 #[cfg(feature = "aags")]
 mod aags; //  AES-GCM-SIV (deterministic)
 #[cfg(feature = "aasv")]
 mod aasv; //  AES-SIV (deterministic)
+#[cfg(feature = "apgc")]
+mod apgc; // AES-GCM (probabilistic)
 #[cfg(feature = "apgs")]
 mod apgs; // AES-GCM-SIV (probabilistic)
 #[cfg(feature = "apsv")]
@@ -18,20 +22,115 @@ mod upbc; // AES-CBC (probabilistic)
 mod mock1;
 #[cfg(feature = "mock")]
 mod mock2; // Identity // String reversal
+#[cfg(feature = "mock")]
+mod mock3; // Keyed byte substitution
+#[cfg(feature = "mock")]
+mod mock4; // Word-only substitution
 
 #[cfg(feature = "aags")]
 pub use aags::{decrypt as decrypt_aags, encrypt as encrypt_aags};
 #[cfg(feature = "aasv")]
 pub use aasv::{decrypt as decrypt_aasv, encrypt as encrypt_aasv};
+#[cfg(feature = "apgc")]
+pub use apgc::{
+    decrypt as decrypt_apgc, encrypt as encrypt_apgc, encrypt_with_nonce as encrypt_apgc_with_nonce,
+};
 #[cfg(feature = "apgs")]
-pub use apgs::{decrypt as decrypt_apgs, encrypt as encrypt_apgs};
+pub use apgs::{
+    decrypt as decrypt_apgs, encrypt as encrypt_apgs, encrypt_with_nonce as encrypt_apgs_with_nonce,
+};
 #[cfg(feature = "apsv")]
-pub use apsv::{decrypt as decrypt_apsv, encrypt as encrypt_apsv};
+pub use apsv::{
+    decrypt as decrypt_apsv, encrypt as encrypt_apsv, encrypt_with_nonce as encrypt_apsv_with_nonce,
+};
 #[cfg(feature = "upbc")]
 pub use upbc::{decrypt as decrypt_upbc, encrypt as encrypt_upbc};
 
+// Per-call nonce/IV sizes, for `Scheme::nonce_len`
+#[cfg(feature = "apgc")]
+pub(crate) use apgc::NONCE_SIZE as APGC_NONCE_SIZE;
+#[cfg(feature = "apgs")]
+pub(crate) use apgs::NONCE_SIZE as APGS_NONCE_SIZE;
+#[cfg(feature = "apsv")]
+pub(crate) use apsv::NONCE_SIZE as APSV_NONCE_SIZE;
+#[cfg(feature = "upbc")]
+pub(crate) use upbc::IV_SIZE as UPBC_IV_SIZE;
+
 // Testing
 #[cfg(feature = "mock")]
 pub use mock1::{decrypt as decrypt_mock1, encrypt as encrypt_mock1};
 #[cfg(feature = "mock")]
 pub use mock2::{decrypt as decrypt_mock2, encrypt as encrypt_mock2};
+#[cfg(feature = "mock")]
+pub use mock3::{decrypt as decrypt_mock3, encrypt as encrypt_mock3};
+#[cfg(feature = "mock")]
+pub use mock4::{decrypt as decrypt_mock4, encrypt as encrypt_mock4};
+
+/// Reject a ciphertext (scheme marker removed) of a length no obtext of
+/// `scheme` can have, before any decryption is attempted: shorter than the
+/// nonce/IV and tag around a 1-byte plaintext, or, for upbc, not whole AES
+/// blocks after the IV.  The mocks and custom schemes accept any length.
+pub(crate) fn check_ciphertext_len(scheme: Scheme, len: usize) -> Result<(), Error> {
+    let valid = match scheme {
+        #[cfg(feature = "aags")]
+        Scheme::Aags => len >= aags::MIN_DATA_LEN,
+        #[cfg(feature = "apgs")]
+        Scheme::Apgs => len >= apgs::MIN_PAYLOAD_LEN,
+        #[cfg(feature = "apgc")]
+        Scheme::Apgc => len >= apgc::MIN_PAYLOAD_LEN,
+        #[cfg(feature = "aasv")]
+        Scheme::Aasv => len >= aasv::MIN_DATA_LEN,
+        #[cfg(feature = "apsv")]
+        Scheme::Apsv => len >= apsv::MIN_DATA_LEN,
+        #[cfg(feature = "upbc")]
+        Scheme::Upbc => {
+            len >= upbc::MIN_DATA_LEN && (len - upbc::IV_SIZE) % constants::AES_BLOCK_SIZE == 0
+        }
+        #[allow(unreachable_patterns)]
+        _ => true,
+    };
+    if valid {
+        Ok(())
+    } else {
+        Err(Error::MalformedCiphertext { scheme, len })
+    }
+}
+
+/// Shortest ciphertext (excluding the scheme marker) produced by any enabled
+/// scheme, i.e. a 1-byte plaintext plus that scheme's nonce/IV and tag.
+///
+/// `usize::MAX` when no scheme is enabled.
+pub(crate) const MIN_CIPHERTEXT_LEN: usize = {
+    #[allow(unused_mut)] // z-tier-only builds enable no scheme here
+    let mut min = usize::MAX;
+    #[cfg(feature = "aags")]
+    if aags::MIN_DATA_LEN < min {
+        min = aags::MIN_DATA_LEN;
+    }
+    #[cfg(feature = "aasv")]
+    if aasv::MIN_DATA_LEN < min {
+        min = aasv::MIN_DATA_LEN;
+    }
+    #[cfg(feature = "apgs")]
+    if apgs::MIN_PAYLOAD_LEN < min {
+        min = apgs::MIN_PAYLOAD_LEN;
+    }
+    #[cfg(feature = "apgc")]
+    if apgc::MIN_PAYLOAD_LEN < min {
+        min = apgc::MIN_PAYLOAD_LEN;
+    }
+    #[cfg(feature = "apsv")]
+    if apsv::MIN_DATA_LEN < min {
+        min = apsv::MIN_DATA_LEN;
+    }
+    #[cfg(feature = "upbc")]
+    if upbc::MIN_DATA_LEN < min {
+        min = upbc::MIN_DATA_LEN;
+    }
+    // mock1/mock2/mock3/mock4 preserve the plaintext length
+    #[cfg(feature = "mock")]
+    if 1 < min {
+        min = 1;
+    }
+    min
+};
@@ -11,7 +11,9 @@ type Aes256CbcDec = Decryptor<Aes256>;
 
 const KEY_OFFSET: usize = 8;
 const KEY_LEN: usize = 32;
-const IV_SIZE: usize = 16;
+pub(crate) const IV_SIZE: usize = 16;
+/// IV plus one padded block
+pub(crate) const MIN_DATA_LEN: usize = IV_SIZE + AES_BLOCK_SIZE;
 
 #[inline]
 pub fn encrypt(master_key: &[u8; 64], plaintext_bytes: &[u8]) -> Result<Vec<u8>, Error> {
@@ -43,7 +45,7 @@ pub fn encrypt(master_key: &[u8; 64], plaintext_bytes: &[u8]) -> Result<Vec<u8>,
 
 #[inline]
 pub fn decrypt(master_key: &[u8; 64], data: &mut [u8]) -> Result<Vec<u8>, Error> {
-    if data.len() < 32 {
+    if data.len() < MIN_DATA_LEN {
         return Err(Error::PayloadTooShort);
     }
 
@@ -1,6 +1,13 @@
-#[cfg(feature = "keyless")]
-use crate::constants::HARDCODED_KEY_BYTES;
-use crate::{format::IntoFormat, Error, MasterKey};
+use crate::format::{FormatCache, IntoFormat};
+use crate::{DecodeFailure, Encoding, Error, Format, MasterKey, Scheme};
+use std::num::NonZeroUsize;
+use std::sync::Arc;
+
+// `Omnib::shared` hands out `Arc<Omnib>` for use across threads
+const _: fn() = || {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<Omnib>();
+};
 
 /// An ObtextCodec implementation that takes format on enc operation and autodetects on dec operation.
 /// Unlike all other implementations (Ob, ZrbcxC32, .. .) it does not have
@@ -33,8 +40,41 @@ use crate::{format::IntoFormat, Error, MasterKey};
 /// # Ok(())
 /// # }
 /// ```
+///
+/// `Omnib` is `Send + Sync`.  [`Omnib::shared`] returns an `Arc<Omnib>` to
+/// clone into worker threads:
+///
+/// ```rust
+/// # fn main() -> Result<(), oboron::Error> {
+/// # #[cfg(feature = "aasv")]
+/// # {
+/// # use oboron::Omnib;
+/// # let key = oboron::generate_key();
+/// let omb = Omnib::shared(&key)?;
+/// let workers: Vec<_> = (0..4)
+///     .map(|i| {
+///         let omb = omb.clone();
+///         std::thread::spawn(move || omb.enc(&format!("job {}", i), "aasv.c32"))
+///     })
+///     .collect();
+/// for worker in workers {
+///     let ot = worker.join().unwrap()?;
+///     assert!(omb.autodec(&ot)?.starts_with("job "));
+/// }
+/// # }
+/// # Ok(())
+/// # }
+/// ```
 pub struct Omnib {
     masterkey: MasterKey,
+    max_input_len: Option<usize>,
+    max_autodec_attempts: Option<NonZeroUsize>,
+    constant_time_dec: bool,
+    allowed_formats: Option<Vec<Format>>,
+    preferred_encoding: Option<Encoding>,
+    format_cache: FormatCache,
+    #[cfg(feature = "metrics")]
+    observer: Option<Arc<dyn crate::ObObserver>>,
 }
 
 impl Omnib {
@@ -42,18 +82,236 @@ impl Omnib {
     pub fn new(key_b64: &str) -> Result<Self, Error> {
         Ok(Self {
             masterkey: MasterKey::from_base64(key_b64)?,
+            max_input_len: None,
+            max_autodec_attempts: None,
+            constant_time_dec: false,
+            allowed_formats: None,
+            preferred_encoding: None,
+            format_cache: FormatCache::default(),
+            #[cfg(feature = "metrics")]
+            observer: None,
         })
     }
 
+    /// Create a new Omnib instance with a base64 key that only accepts the
+    /// `allowed` formats.
+    ///
+    /// Every format is parsed up front, so a typo or a format whose scheme
+    /// is not enabled in this build fails here rather than on first use.
+    /// Afterwards [`enc`](Self::enc), [`dec`](Self::dec) and
+    /// [`autodec`](Self::autodec), and the methods built on them, reject any
+    /// other format with [`Error::FormatNotAllowed`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), oboron::Error> {
+    /// # #[cfg(all(feature = "aasv", feature = "aags"))]
+    /// # {
+    /// # use oboron::{Error, Omnib};
+    /// # let key = oboron::generate_key();
+    /// let omb = Omnib::new_restricted(&key, &["aasv.c32", "aasv.b64"])?;
+    /// let ot = omb.enc("hello", "aasv.b64")?;
+    /// assert_eq!(omb.autodec(&ot)?, "hello");
+    /// assert!(matches!(
+    ///     omb.enc("hello", "aags.c32"),
+    ///     Err(Error::FormatNotAllowed { .. })
+    /// ));
+    /// # }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new_restricted(key_b64: &str, allowed: &[&str]) -> Result<Self, Error> {
+        let allowed = allowed
+            .iter()
+            .map(|format| Format::from_str(format))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut omb = Self::new(key_b64)?;
+        omb.allowed_formats = Some(allowed);
+        Ok(omb)
+    }
+
+    /// Get the formats this instance is restricted to by
+    /// [`new_restricted`](Self::new_restricted) (`None` = any format).
+    pub fn allowed_formats(&self) -> Option<&[Format]> {
+        self.allowed_formats.as_deref()
+    }
+
+    /// Reject `format` unless this instance accepts it.
+    fn check_allowed(&self, format: Format) -> Result<(), Error> {
+        match &self.allowed_formats {
+            Some(allowed) if !allowed.contains(&format) => Err(Error::FormatNotAllowed { format }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Create a new Omnib instance with a base64 key wrapped in a
+    /// [`SecretString`](secrecy::SecretString).
+    #[cfg(feature = "secrecy")]
+    pub fn new_secret(key: &secrecy::SecretString) -> Result<Self, Error> {
+        use secrecy::ExposeSecret;
+        Self::new(key.expose_secret())
+    }
+
+    /// Create a new Omnib instance with the base64 key in the `OBORON_KEY`
+    /// environment variable ([`KEY_ENV_VAR`](crate::KEY_ENV_VAR)).
+    ///
+    /// Returns [`Error::MissingEnvVar`] if it is not set.
+    #[cfg(feature = "std")]
+    pub fn from_env() -> Result<Self, Error> {
+        Self::new(&crate::error::env_var(crate::KEY_ENV_VAR)?)
+    }
+
     /// Create a new Omnib instance with hardcoded key (testing only).
     #[cfg(feature = "keyless")]
     pub fn new_keyless() -> Result<Self, Error> {
-        Self::from_bytes(&HARDCODED_KEY_BYTES)
+        Self::from_bytes(crate::keyless::keyless_key())
+    }
+
+    /// Like [`Omnib::new`], but returns an `Arc<Omnib>` ready to share across
+    /// threads.
+    pub fn shared(key_b64: &str) -> Result<Arc<Self>, Error> {
+        Self::new(key_b64).map(Arc::new)
+    }
+
+    /// Like [`Omnib::new_keyless`], but returns an `Arc<Omnib>` (testing only).
+    #[cfg(feature = "keyless")]
+    pub fn shared_keyless() -> Result<Arc<Self>, Error> {
+        Self::new_keyless().map(Arc::new)
+    }
+
+    /// Limit the length in bytes of plaintext and obtext accepted by `enc`,
+    /// `dec`, `autodec` and `autodec_within` (unlimited by default).
+    ///
+    /// Longer inputs are rejected with [`Error::InputTooLarge`] before any
+    /// decoding or encryption work, bounding the cost of untrusted input.
+    pub fn with_max_input_len(mut self, max_len: usize) -> Self {
+        self.max_input_len = Some(max_len);
+        self
+    }
+
+    /// Get the input length limit set by
+    /// [`with_max_input_len`](Self::with_max_input_len), if any.
+    pub fn max_input_len(&self) -> Option<usize> {
+        self.max_input_len
+    }
+
+    /// Limit the number of candidates `autodec`, `autodec_verbose` and
+    /// `autodec_bytes` try before giving up with
+    /// [`Error::AutodetectExhausted`], which carries the last attempt's
+    /// error (by default all candidates are tried).
+    ///
+    /// Each attempt decodes the obtext and decrypts it with at most one
+    /// scheme, so the limit bounds the worst-case cost of untrusted input;
+    /// without it an obtext costs at most five attempts, plus one legacy
+    /// attempt with the `legacy` feature.  Encodings are tried most likely
+    /// first, so a limit of 2 or 3 rarely rejects valid obtext.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), oboron::Error> {
+    /// # #[cfg(feature = "aasv")]
+    /// # {
+    /// # use oboron::{Error, Omnib};
+    /// # use std::num::NonZeroUsize;
+    /// # let key = oboron::generate_key();
+    /// let omb = Omnib::new(&key)?.with_max_autodec_attempts(NonZeroUsize::MIN);
+    /// let ot = omb.enc("hello", "aasv.b64")?;
+    /// assert_eq!(omb.autodec(&ot)?, "hello");
+    /// assert!(matches!(
+    ///     omb.autodec("NotAnObtext"),
+    ///     Err(Error::AutodetectExhausted { attempts: 1, .. })
+    /// ));
+    /// # }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_max_autodec_attempts(mut self, max_attempts: NonZeroUsize) -> Self {
+        self.max_autodec_attempts = Some(max_attempts);
+        self
+    }
+
+    /// Get the attempt limit set by
+    /// [`with_max_autodec_attempts`](Self::with_max_autodec_attempts), if any.
+    pub fn max_autodec_attempts(&self) -> Option<NonZeroUsize> {
+        self.max_autodec_attempts
+    }
+
+    /// Try `encoding` first in `autodec`, `autodec_verbose` and
+    /// `autodec_bytes`, before the heuristic order.
+    ///
+    /// Useful when most obtext in a deployment shares one encoding: it then
+    /// decodes on the first attempt, also under
+    /// [`with_max_autodec_attempts`](Self::with_max_autodec_attempts).
+    /// Obtext in other encodings is still detected, at the cost of one
+    /// extra attempt.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), oboron::Error> {
+    /// # #[cfg(feature = "aasv")]
+    /// # {
+    /// # use oboron::{Encoding, Omnib};
+    /// # use std::num::NonZeroUsize;
+    /// # let key = oboron::generate_key();
+    /// let omb = Omnib::new(&key)?
+    ///     .with_preferred_encoding(Encoding::Hex)
+    ///     .with_max_autodec_attempts(NonZeroUsize::MIN);
+    /// let ot = omb.enc("hello", "aasv.hex")?;
+    /// assert_eq!(omb.autodec(&ot)?, "hello");
+    /// # }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_preferred_encoding(mut self, encoding: Encoding) -> Self {
+        self.preferred_encoding = Some(encoding);
+        self
+    }
+
+    /// Get the encoding set by
+    /// [`with_preferred_encoding`](Self::with_preferred_encoding), if any.
+    pub fn preferred_encoding(&self) -> Option<Encoding> {
+        self.preferred_encoding
+    }
+
+    /// Make `dec` reject invalid obtext in roughly uniform time, at the cost
+    /// of slower rejects; see [`Ob::with_constant_time_dec`](crate::Ob::with_constant_time_dec).
+    /// Each candidate of [`autodec_within`](Self::autodec_within), and of
+    /// `autodec` on a [restricted](Self::new_restricted) instance, is
+    /// decoded the same way; unrestricted `autodec` is not covered.
+    pub fn with_constant_time_dec(mut self) -> Self {
+        self.constant_time_dec = true;
+        self
+    }
+
+    /// Whether [`with_constant_time_dec`](Self::with_constant_time_dec) is set.
+    pub fn constant_time_dec(&self) -> bool {
+        self.constant_time_dec
+    }
+
+    /// Report every `enc`, `dec` and `autodec` call to `observer`, e.g. to
+    /// collect metrics; see [`ObObserver`](crate::ObObserver).
+    #[cfg(feature = "metrics")]
+    pub fn with_observer(mut self, observer: Arc<dyn crate::ObObserver>) -> Self {
+        self.observer = Some(observer);
+        self
+    }
+
+    /// Get the observer set by [`with_observer`](Self::with_observer), if any.
+    #[cfg(feature = "metrics")]
+    pub fn observer(&self) -> Option<&Arc<dyn crate::ObObserver>> {
+        self.observer.as_ref()
     }
 
     /// Encrypt and encode plaintext with the specified format.
     ///
-    /// Accepts either a format string (`&str`) or a `Format` instance.
+    /// Accepts either a format string (`&str`) or a `Format` instance.  The
+    /// strings of the format constants are matched directly, and others
+    /// (aliases, uppercase) are parsed once and cached by this instance;
+    /// passing a `Format` (e.g. a constant like `AASV_B64`, or one parsed
+    /// once up front) skips even the lookup.
     ///
     /// # Examples
     ///
@@ -79,13 +337,77 @@ impl Omnib {
     /// ```
     #[inline]
     pub fn enc(&self, plaintext: &str, format: impl IntoFormat) -> Result<String, Error> {
-        let format = format.into_format()?;
-        crate::enc::enc_to_format(plaintext, format, self.masterkey.key())
+        let format = self.format_cache.resolve(format);
+        let enc = |format| {
+            crate::error::check_input_len(plaintext, self.max_input_len)?;
+            self.check_allowed(format)?;
+            crate::enc::enc_to_format(plaintext, format, self.masterkey.key())
+        };
+        #[cfg(feature = "metrics")]
+        if let Some(observer) = &self.observer {
+            return crate::metrics::observe_enc(observer, format, plaintext, enc);
+        }
+        enc(format?)
+    }
+
+    /// Encrypt `plaintext` once with `scheme` and iterate over its obtext in
+    /// each built-in encoding ([`Encoding::ALL`]).
+    ///
+    /// Each obtext is encoded lazily by `next()`, so taking only the first
+    /// rendering or two never encodes the rest.  All renderings carry the
+    /// same ciphertext, even for probabilistic schemes.
+    ///
+    /// On a [restricted](Self::new_restricted) instance, every one of the
+    /// formats, one per encoding, must be allowed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::UnsupportedScheme`] for z-tier and legacy schemes,
+    /// which `Omnib` does not encrypt with.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), oboron::Error> {
+    /// # #[cfg(feature = "apsv")]
+    /// # {
+    /// # use oboron::{Encoding, Omnib, Scheme};
+    /// # let key = oboron::generate_key();
+    /// let omb = Omnib::new(&key)?;
+    /// let (encoding, ot) = omb.encodings_of("hello", Scheme::Apsv)?.next().unwrap();
+    /// assert_eq!(encoding, Encoding::C32);
+    /// assert_eq!(omb.dec(&ot, "apsv.c32")?, "hello");
+    /// # }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn encodings_of(&self, plaintext: &str, scheme: Scheme) -> Result<EncodingsOf, Error> {
+        if scheme.is_ztier_or_legacy() {
+            return Err(Error::UnsupportedScheme { scheme });
+        }
+        crate::error::check_input_len(plaintext, self.max_input_len)?;
+        for encoding in Encoding::ALL {
+            self.check_allowed(Format::new(scheme, encoding))?;
+        }
+        Ok(EncodingsOf {
+            payload: crate::enc::enc_to_payload(plaintext, scheme, self.masterkey.key())?,
+            next: 0,
+        })
     }
 
     /// Decode and decrypt obtext with the specified format.
     ///
-    /// Accepts either a format string (`&str`) or a `Format` instance.
+    /// Accepts either a format string (`&str`) or a `Format` instance; as
+    /// with [`enc`](Self::enc), strings are cached and a `Format` skips
+    /// parsing altogether.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::EncodingMismatch`] if the obtext contains characters
+    /// outside the requested encoding's alphabet that fit another encoding,
+    /// e.g. a `b64` obtext decoded as `c32`; use [`autodec`](Self::autodec)
+    /// when the encoding is not known.  [`dec_report`](Self::dec_report)
+    /// returns the same errors wrapped in a structured report.
     ///
     /// # Examples
     ///
@@ -108,12 +430,159 @@ impl Omnib {
     /// ```
     #[inline]
     pub fn dec(&self, obtext: &str, format: impl IntoFormat) -> Result<String, Error> {
-        let format = format.into_format()?;
+        self.dec_resolved(obtext, self.format_cache.resolve(format))
+    }
+
+    /// [`dec`](Self::dec) with the format already resolved, so parse errors
+    /// are reported like any other.
+    fn dec_resolved(&self, obtext: &str, format: Result<Format, Error>) -> Result<String, Error> {
+        #[cfg(feature = "metrics")]
+        let observed_format = format.as_ref().ok().copied();
+        let dec = || {
+            crate::error::check_input_len(obtext, self.max_input_len)?;
+            let format = format?;
+            self.check_allowed(format)?;
+            self.dec_format(obtext, format)
+        };
+        #[cfg(feature = "metrics")]
+        if let Some(observer) = &self.observer {
+            let operation = crate::ObOperation::Dec;
+            return crate::metrics::observe_dec(observer, operation, observed_format, obtext, dec);
+        }
+        dec()
+    }
+
+    /// Decode+decrypt obtext with a validated, allowed format.
+    fn dec_format(&self, obtext: &str, format: Format) -> Result<String, Error> {
+        #[cfg(feature = "legacy")]
+        if format.scheme() == Scheme::Legacy {
+            let secret = crate::dec_auto::legacy_secret(&self.masterkey);
+            return crate::ztier::dec_legacy_strict(secret, obtext);
+        }
+        if let Err(e) = crate::encoding::check_encoding(obtext, format.encoding()) {
+            if self.constant_time_dec {
+                crate::dec::dummy_decrypt(obtext, format, self.masterkey.key());
+            }
+            return Err(e);
+        }
+        if self.constant_time_dec {
+            return crate::dec::dec_from_format_uniform(obtext, format, self.masterkey.key());
+        }
         crate::dec::dec_from_format(obtext, format, self.masterkey.key())
     }
 
+    /// Like [`dec`](Self::dec), but obtext that fails to decode or decrypt
+    /// returns [`Error::DecodeFailed`], whose [`DecodeFailure`] records the
+    /// input length, the encoding tried and the failing stage, e.g. to
+    /// aggregate failure reasons in logs.  Its `cause` is the error `dec`
+    /// would have returned; other errors are returned unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), oboron::Error> {
+    /// # #[cfg(feature = "aasv")]
+    /// # {
+    /// # use oboron::{DecodeStage, Error, Omnib};
+    /// # let key = oboron::generate_key();
+    /// let omb = Omnib::new(&key)?;
+    /// match omb.dec_report("not obtext!", "aasv.c32") {
+    ///     Err(Error::DecodeFailed(report)) => assert_eq!(report.stage, DecodeStage::Decode),
+    ///     other => panic!("unexpected {:?}", other),
+    /// }
+    /// # }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn dec_report(&self, obtext: &str, format: impl IntoFormat) -> Result<String, Error> {
+        let format = self.format_cache.resolve(format);
+        let encoding = format.as_ref().ok().map(|format| format.encoding());
+        self.dec_resolved(obtext, format)
+            .map_err(|e| match encoding {
+                Some(encoding) => DecodeFailure::wrap(e, obtext, vec![encoding]),
+                None => e,
+            })
+    }
+
+    /// Like [`autodec`](Self::autodec), but obtext that fails to decode or
+    /// decrypt returns [`Error::DecodeFailed`] listing every encoding tried,
+    /// as for [`dec_report`](Self::dec_report).
+    pub fn autodec_report(&self, obtext: &str) -> Result<String, Error> {
+        self.autodec(obtext).map_err(|error| {
+            let encodings = match &self.allowed_formats {
+                Some(allowed) => {
+                    let mut encodings = Vec::new();
+                    for format in allowed {
+                        if !encodings.contains(&format.encoding()) {
+                            encodings.push(format.encoding());
+                        }
+                    }
+                    encodings
+                }
+                None => crate::dec_auto::attempted_encodings(
+                    obtext,
+                    self.max_autodec_attempts,
+                    self.preferred_encoding,
+                    true,
+                ),
+            };
+            DecodeFailure::wrap(error, obtext, encodings)
+        })
+    }
+
+    /// Decode+decrypt obtext whose scheme and encoding are known
+    /// out-of-band, e.g. in a closed system where every obtext is `aasv.c32`.
+    ///
+    /// Equivalent to [`dec`](Self::dec) with `Format::new(scheme, encoding)`:
+    /// the obtext is decoded with exactly `encoding` and decrypted with
+    /// exactly `scheme`, with no autodetection, and obtext of any other
+    /// scheme is rejected.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), oboron::Error> {
+    /// # #[cfg(feature = "aasv")]
+    /// # {
+    /// # use oboron::{Encoding, Omnib, Scheme};
+    /// # let key = oboron::generate_key();
+    /// let omb = Omnib::new(&key)?;
+    /// let ot = omb.enc("hello", "aasv.c32")?;
+    /// assert_eq!(omb.dec_known(&ot, Scheme::Aasv, Encoding::C32)?, "hello");
+    /// # }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn dec_known(
+        &self,
+        obtext: &str,
+        scheme: Scheme,
+        encoding: Encoding,
+    ) -> Result<String, Error> {
+        self.dec(obtext, Format::new(scheme, encoding))
+    }
+
     /// Decode+decrypt with automatic scheme and encoding detection.
     ///
+    /// With the `legacy` feature, obtexts with the legacy shape (reversed
+    /// lowercase base32 of whole AES blocks) are also tried as legacy, right
+    /// after the [preferred encoding](Self::with_preferred_encoding) if any
+    /// and before the others.  Legacy is unauthenticated, so a secure
+    /// scheme that decodes the same obtext still wins.  The legacy secret is
+    /// the first 32 bytes of the key, as for [`Obz`](crate::ztier::Obz)
+    /// built from a 64-byte key, so keyless `Omnib` reads keyless `Legacy`
+    /// obtexts.
+    ///
+    /// Without the `legacy` feature, such obtexts fail with
+    /// [`Error::LegacySchemeNotEnabled`] instead of the last decode error,
+    /// pointing at the missing feature.
+    ///
+    /// On a [restricted](Self::new_restricted) instance, only the allowed
+    /// formats are tried, as with [`autodec_within`](Self::autodec_within),
+    /// and obtext detected as another format fails with
+    /// [`Error::FormatNotAllowed`].
+    ///
     /// # Examples
     ///
     /// ```rust
@@ -131,7 +600,296 @@ impl Omnib {
     /// # }
     /// ```
     pub fn autodec(&self, obtext: &str) -> Result<String, Error> {
-        crate::dec_auto::dec_any_format(&self.masterkey, obtext)
+        #[cfg(feature = "metrics")]
+        if let Some(observer) = &self.observer {
+            let autodec = || self.autodec_unobserved(obtext);
+            let operation = crate::ObOperation::Autodec;
+            return crate::metrics::observe_dec(observer, operation, None, obtext, autodec);
+        }
+        self.autodec_unobserved(obtext)
+    }
+
+    /// [`autodec`](Self::autodec) without reporting to the observer, for
+    /// use inside methods that report themselves.
+    fn autodec_unobserved(&self, obtext: &str) -> Result<String, Error> {
+        crate::error::check_input_len(obtext, self.max_input_len)?;
+        if let Some(allowed) = &self.allowed_formats {
+            return self.dec_within(obtext, allowed).map_err(
+                |e| match crate::dec_auto::detect_format(obtext) {
+                    Some(format) if !allowed.contains(&format) => {
+                        Error::FormatNotAllowed { format }
+                    }
+                    _ => e,
+                },
+            );
+        }
+        crate::dec_auto::dec_any_format_bounded(
+            &self.masterkey,
+            obtext,
+            self.max_autodec_attempts,
+            self.preferred_encoding,
+            true,
+        )
+        .map_err(|error| Self::autodec_error(obtext, error))
+    }
+
+    /// The error `autodec` returns for an obtext no candidate decoded:
+    /// without the `legacy` feature, legacy-shaped obtext points at the
+    /// missing feature instead of the last decode error.
+    #[cfg_attr(feature = "legacy", allow(unused_variables))]
+    fn autodec_error(obtext: &str, error: Error) -> Error {
+        #[cfg(not(feature = "legacy"))]
+        if !matches!(error, Error::AutodetectExhausted { .. })
+            && crate::dec_auto::has_legacy_shape(obtext)
+        {
+            return Error::LegacySchemeNotEnabled;
+        }
+        error
+    }
+
+    /// Like [`autodec`](Self::autodec), but on failure returns an
+    /// [`AutodecReport`](crate::AutodecReport) listing every encoding and
+    /// scheme tried and why each failed.
+    ///
+    /// Successful decodes cost the same as `autodec`: the report is only
+    /// built, by replaying the attempts, once every candidate has failed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), oboron::Error> {
+    /// # #[cfg(feature = "aasv")]
+    /// # {
+    /// # use oboron::{Encoding, Error, Omnib};
+    /// # let key = oboron::generate_key();
+    /// # let omb = Omnib::new(&key)?;
+    /// let report = omb.autodec_verbose("not obtext").unwrap_err();
+    /// assert!(report.attempts().iter().any(|a| a.encoding == Encoding::B64));
+    /// assert!(report.to_string().starts_with("autodetection failed: "));
+    /// // Converts into the error `autodec` returns for the same obtext
+    /// assert_eq!(Error::from(report), omb.autodec("not obtext").unwrap_err());
+    /// # }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn autodec_verbose(&self, obtext: &str) -> Result<String, crate::AutodecReport> {
+        #[cfg(feature = "metrics")]
+        if let Some(observer) = &self.observer {
+            // The observer takes an `Error`: report the report's, keep the report
+            let mut report = None;
+            let autodec = || {
+                self.autodec_verbose_unobserved(obtext).map_err(|r| {
+                    let error = r.error().clone();
+                    report = Some(r);
+                    error
+                })
+            };
+            let operation = crate::ObOperation::Autodec;
+            let result = crate::metrics::observe_dec(observer, operation, None, obtext, autodec);
+            return result.map_err(|_| report.expect("set on every error"));
+        }
+        self.autodec_verbose_unobserved(obtext)
+    }
+
+    fn autodec_verbose_unobserved(&self, obtext: &str) -> Result<String, crate::AutodecReport> {
+        crate::error::check_input_len(obtext, self.max_input_len)
+            .map_err(crate::AutodecReport::rejected)?;
+        if self.allowed_formats.is_some() {
+            return self
+                .autodec_unobserved(obtext)
+                .map_err(crate::AutodecReport::rejected);
+        }
+        let error = match crate::dec_auto::dec_any_format_bounded(
+            &self.masterkey,
+            obtext,
+            self.max_autodec_attempts,
+            self.preferred_encoding,
+            true,
+        ) {
+            Ok(plaintext) => return Ok(plaintext),
+            Err(error) => error,
+        };
+        Err(crate::AutodecReport::replay(
+            &self.masterkey,
+            obtext,
+            Self::autodec_error(obtext, error),
+            self.max_autodec_attempts,
+            self.preferred_encoding,
+            true,
+        ))
+    }
+
+    /// Decode+decrypt trying only the given candidate formats, in order.
+    ///
+    /// Unlike [`autodec`](Self::autodec), no detection heuristics run: each
+    /// format is tried as with [`dec`](Self::dec) and the first success is
+    /// returned.  Obtexts of any other scheme or encoding are rejected, which
+    /// is both faster and stricter when the possible formats are known.
+    /// With the `legacy` feature, the `LEGACY` format is a candidate too.
+    ///
+    /// # Errors
+    ///
+    /// If no candidate matches, returns the error from the last candidate
+    /// ([`Error::InvalidFormat`] if `formats` is empty), or
+    /// [`Error::FormatNotAllowed`] if a candidate is outside the formats of a
+    /// [restricted](Self::new_restricted) instance.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), oboron::Error> {
+    /// # #[cfg(all(feature = "aasv", feature = "aags"))]
+    /// # {
+    /// # use oboron::{Omnib, AASV_B64, AASV_C32};
+    /// # let key = oboron::generate_key();
+    /// # let omb = Omnib::new(&key)?;
+    /// let allowed = [AASV_C32, AASV_B64];
+    ///
+    /// let ot = omb.enc("hello", AASV_B64)?;
+    /// assert_eq!(omb.autodec_within(&ot, &allowed)?, "hello");
+    ///
+    /// let other = omb.enc("hello", "aags.c32")?;
+    /// assert!(omb.autodec_within(&other, &allowed).is_err());
+    /// # }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn autodec_within(&self, obtext: &str, formats: &[Format]) -> Result<String, Error> {
+        #[cfg(feature = "metrics")]
+        if let Some(observer) = &self.observer {
+            let autodec = || self.dec_within(obtext, formats);
+            let operation = crate::ObOperation::Autodec;
+            return crate::metrics::observe_dec(observer, operation, None, obtext, autodec);
+        }
+        self.dec_within(obtext, formats)
+    }
+
+    /// [`autodec_within`](Self::autodec_within) without reporting to the
+    /// observer.
+    fn dec_within(&self, obtext: &str, formats: &[Format]) -> Result<String, Error> {
+        crate::error::check_input_len(obtext, self.max_input_len)?;
+        let mut last_err = Error::InvalidFormat;
+        for &format in formats {
+            self.check_allowed(format)?;
+            match self.dec_format(obtext, format) {
+                Ok(plaintext) => return Ok(plaintext),
+                Err(e) => last_err = e,
+            }
+        }
+        Err(last_err)
+    }
+
+    /// Every format under which `obtext` decrypts with this key, e.g. to
+    /// audit data that more than one format could have produced.
+    ///
+    /// Each enabled scheme except the z-tier and legacy ones is tried with
+    /// every encoding, as with [`dec`](Self::dec).  An obtext of an AEAD
+    /// scheme normally has exactly one candidate, since its tag only
+    /// verifies when read the right way; one of an unauthenticated scheme
+    /// (`upbc`, the mocks) may have several.  Obtext longer than
+    /// [`max_input_len`](Self::max_input_len) has none, and formats a
+    /// [restricted](Self::new_restricted) instance does not allow are
+    /// skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), oboron::Error> {
+    /// # #[cfg(feature = "aasv")]
+    /// # {
+    /// # use oboron::{Omnib, AASV_B64};
+    /// # let omb = Omnib::new(&oboron::generate_key())?;
+    /// let ot = omb.enc("hello", AASV_B64)?;
+    /// assert_eq!(omb.candidate_formats(&ot), [AASV_B64]);
+    /// # }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn candidate_formats(&self, obtext: &str) -> Vec<Format> {
+        use crate::Encoding;
+
+        if crate::error::check_input_len(obtext, self.max_input_len).is_err() {
+            return Vec::new();
+        }
+        crate::supported_schemes()
+            .iter()
+            .filter(|scheme| !scheme.is_ztier_or_legacy())
+            .flat_map(|&scheme| Encoding::ALL.map(|encoding| Format::new(scheme, encoding)))
+            .filter(|&format| self.check_allowed(format).is_ok())
+            .filter(|&format| {
+                crate::dec::dec_from_format(obtext, format, self.masterkey.key()).is_ok()
+            })
+            .collect()
+    }
+
+    /// Like [`autodec`](Self::autodec), but first strips a trailing
+    /// `#<label>` as added by [`Ob::enc_labeled`](crate::Ob::enc_labeled).
+    /// The label is not trusted: the format is still autodetected.
+    #[inline]
+    pub fn autodec_labeled(&self, obtext: &str) -> Result<String, Error> {
+        self.autodec(crate::ob::strip_label(obtext))
+    }
+
+    /// Like [`autodec`](Self::autodec), but takes the obtext as raw bytes,
+    /// e.g. straight off the wire.
+    ///
+    /// Every encoding is ASCII, so the bytes are checked against the obtext
+    /// alphabets instead of being validated as UTF-8 first; any other byte
+    /// returns [`Error::InvalidObtextByte`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), oboron::Error> {
+    /// # #[cfg(feature = "aasv")]
+    /// # {
+    /// # use oboron::Omnib;
+    /// # let omb = Omnib::new(&oboron::generate_key())?;
+    /// let ot = omb.enc("hello", "aasv.b64")?;
+    /// assert_eq!(omb.autodec_bytes(ot.as_bytes())?, "hello");
+    /// # }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn autodec_bytes(&self, obtext: &[u8]) -> Result<String, Error> {
+        self.autodec(crate::encoding::obtext_from_bytes(obtext)?)
+    }
+
+    /// Autodecode one segment of a larger token, such as the obtext in
+    /// `v1.<obtext>.sig`.
+    ///
+    /// The token is split on `delimiter` and the segment at `index`
+    /// (0-based) is passed to [`autodec`](Self::autodec).  Obtext never
+    /// contains `.`, `:` or `/`, so any of these separates it cleanly.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::MissingSegment`] if the token has no segment at
+    /// `index`, and any error `autodec` returns for the segment.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), oboron::Error> {
+    /// # #[cfg(feature = "aasv")]
+    /// # {
+    /// # use oboron::Omnib;
+    /// # let key = oboron::generate_key();
+    /// # let omb = Omnib::new(&key)?;
+    /// let token = format!("v1.{}.sig", omb.enc("hello", "aasv.b64")?);
+    /// assert_eq!(omb.dec_segment(&token, ".", 1)?, "hello");
+    /// # }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn dec_segment(&self, token: &str, delimiter: &str, index: usize) -> Result<String, Error> {
+        match token.split(delimiter).nth(index) {
+            Some(segment) => self.autodec(segment),
+            None => Err(Error::MissingSegment {
+                index,
+                count: token.split(delimiter).count(),
+            }),
+        }
     }
 
     /// Get the key used by this instance.
@@ -139,6 +897,18 @@ impl Omnib {
         self.masterkey.key_base64()
     }
 
+    /// Whether `other` uses the same key, compared in constant time as
+    /// with [`Ob::same_key`](crate::Ob::same_key).
+    pub fn same_key(&self, other: &Omnib) -> bool {
+        self.masterkey == other.masterkey
+    }
+
+    /// Get the key wrapped in a [`SecretString`](secrecy::SecretString).
+    #[cfg(feature = "secrecy")]
+    pub fn key_secret(&self) -> secrecy::SecretString {
+        self.masterkey.key_base64().into()
+    }
+
     #[cfg(feature = "hex-keys")]
     pub fn key_hex(&self) -> String {
         self.masterkey.key_hex()
@@ -156,6 +926,14 @@ impl Omnib {
     pub fn from_key_hex(key_hex: &str) -> Result<Self, Error> {
         Ok(Self {
             masterkey: MasterKey::from_hex(key_hex)?,
+            max_input_len: None,
+            max_autodec_attempts: None,
+            constant_time_dec: false,
+            allowed_formats: None,
+            preferred_encoding: None,
+            format_cache: FormatCache::default(),
+            #[cfg(feature = "metrics")]
+            observer: None,
         })
     }
 
@@ -163,6 +941,39 @@ impl Omnib {
     pub fn from_bytes(key_bytes: &[u8; 64]) -> Result<Self, Error> {
         Ok(Self {
             masterkey: MasterKey::from_bytes(key_bytes)?,
+            max_input_len: None,
+            max_autodec_attempts: None,
+            constant_time_dec: false,
+            allowed_formats: None,
+            preferred_encoding: None,
+            format_cache: FormatCache::default(),
+            #[cfg(feature = "metrics")]
+            observer: None,
         })
     }
 }
+
+/// Iterator over one ciphertext rendered in each encoding, returned by
+/// [`Omnib::encodings_of`].
+#[derive(Debug, Clone)]
+pub struct EncodingsOf {
+    payload: Vec<u8>,
+    next: usize,
+}
+
+impl Iterator for EncodingsOf {
+    type Item = (Encoding, String);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let encoding = *Encoding::ALL.get(self.next)?;
+        self.next += 1;
+        Some((encoding, crate::encoding::encode(&self.payload, encoding)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = Encoding::ALL.len() - self.next;
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for EncodingsOf {}
@@ -1,14 +1,18 @@
 //! Scheme identifiers for oboron encryption schemes.
 
+#[cfg(feature = "custom-schemes")]
+use crate::custom_scheme::{self, CustomScheme};
 use crate::{constants, error::Error};
 
 /// Scheme identifier for oboron encoding schemes.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Scheme {
     #[cfg(feature = "aags")]
     Aags,
     #[cfg(feature = "apgs")]
     Apgs,
+    #[cfg(feature = "apgc")]
+    Apgc,
     #[cfg(feature = "aasv")]
     Aasv,
     #[cfg(feature = "apsv")]
@@ -23,11 +27,22 @@ pub enum Scheme {
     Mock1,
     #[cfg(feature = "mock")]
     Mock2,
+    #[cfg(feature = "mock")]
+    Mock3,
+    #[cfg(feature = "mock")]
+    Mock4,
     #[cfg(feature = "zmock")]
     Zmock1,
     // Legacy
     #[cfg(feature = "legacy")]
     Legacy,
+    // Application-defined
+    #[cfg(feature = "custom-schemes")]
+    Custom(CustomScheme),
+    // Test double: reported by `NullOb` only, never parsed or decoded
+    #[cfg(feature = "testing")]
+    #[doc(hidden)]
+    Null,
 }
 
 impl Scheme {
@@ -38,6 +53,8 @@ impl Scheme {
             Scheme::Aags => "aags",
             #[cfg(feature = "apgs")]
             Scheme::Apgs => "apgs",
+            #[cfg(feature = "apgc")]
+            Scheme::Apgc => "apgc",
             #[cfg(feature = "aasv")]
             Scheme::Aasv => "aasv",
             #[cfg(feature = "apsv")]
@@ -52,11 +69,66 @@ impl Scheme {
             Scheme::Mock1 => "mock1",
             #[cfg(feature = "mock")]
             Scheme::Mock2 => "mock2",
+            #[cfg(feature = "mock")]
+            Scheme::Mock3 => "mock3",
+            #[cfg(feature = "mock")]
+            Scheme::Mock4 => "mock4",
             #[cfg(feature = "zmock")]
             Scheme::Zmock1 => "zmock1",
             // Legacy
             #[cfg(feature = "legacy")]
             Scheme::Legacy => "legacy",
+            // Application-defined
+            #[cfg(feature = "custom-schemes")]
+            Scheme::Custom(c) => c.imp().name(),
+            // Test double
+            #[cfg(feature = "testing")]
+            Scheme::Null => "null",
+        }
+    }
+
+    /// Human-readable description for display, e.g. `"AES-SIV, deterministic"`.
+    ///
+    /// Not accepted by [`Scheme::from_str`]; use [`as_str`](Self::as_str)
+    /// for values that are parsed back.  Custom schemes are described by
+    /// their name.
+    pub fn description(&self) -> &'static str {
+        match self {
+            #[cfg(feature = "aags")]
+            Scheme::Aags => "AES-GCM-SIV, deterministic",
+            #[cfg(feature = "apgs")]
+            Scheme::Apgs => "AES-GCM-SIV, probabilistic",
+            #[cfg(feature = "apgc")]
+            Scheme::Apgc => "AES-GCM, probabilistic",
+            #[cfg(feature = "aasv")]
+            Scheme::Aasv => "AES-SIV, deterministic",
+            #[cfg(feature = "apsv")]
+            Scheme::Apsv => "AES-SIV, probabilistic",
+            #[cfg(feature = "upbc")]
+            Scheme::Upbc => "AES-CBC, probabilistic, unauthenticated",
+            // Z-tier
+            #[cfg(feature = "zrbcx")]
+            Scheme::Zrbcx => "AES-CBC, deterministic, obfuscation only",
+            // Testing
+            #[cfg(feature = "mock")]
+            Scheme::Mock1 => "Identity, testing only",
+            #[cfg(feature = "mock")]
+            Scheme::Mock2 => "Reversed plaintext, testing only",
+            #[cfg(feature = "mock")]
+            Scheme::Mock3 => "Keyed byte substitution, length-preserving, testing only",
+            #[cfg(feature = "mock")]
+            Scheme::Mock4 => "Word characters substituted, delimiters kept, demo only",
+            #[cfg(feature = "zmock")]
+            Scheme::Zmock1 => "Z-tier identity, testing only",
+            // Legacy
+            #[cfg(feature = "legacy")]
+            Scheme::Legacy => "Legacy AES-CBC, compatibility only",
+            // Application-defined
+            #[cfg(feature = "custom-schemes")]
+            Scheme::Custom(c) => c.imp().name(),
+            // Test double
+            #[cfg(feature = "testing")]
+            Scheme::Null => "No-op test double, not encryption",
         }
     }
 
@@ -72,6 +144,8 @@ impl Scheme {
             Scheme::Aags => true,
             #[cfg(feature = "apgs")]
             Scheme::Apgs => false,
+            #[cfg(feature = "apgc")]
+            Scheme::Apgc => false,
             #[cfg(feature = "aasv")]
             Scheme::Aasv => true,
             #[cfg(feature = "apsv")]
@@ -86,11 +160,21 @@ impl Scheme {
             Scheme::Mock1 => true,
             #[cfg(feature = "mock")]
             Scheme::Mock2 => true,
+            #[cfg(feature = "mock")]
+            Scheme::Mock3 => true,
+            #[cfg(feature = "mock")]
+            Scheme::Mock4 => true,
             #[cfg(feature = "zmock")]
             Scheme::Zmock1 => true,
             // Legacy
             #[cfg(feature = "legacy")]
             Scheme::Legacy => true,
+            // Application-defined
+            #[cfg(feature = "custom-schemes")]
+            Scheme::Custom(c) => c.imp().is_deterministic(),
+            // Test double
+            #[cfg(feature = "testing")]
+            Scheme::Null => true,
         }
     }
 
@@ -99,6 +183,182 @@ impl Scheme {
         !self.is_deterministic()
     }
 
+    /// Check if this is a z-tier scheme (zrbcx, zmock1): obfuscation-grade,
+    /// keyed by the 32-byte z-tier secret and used through
+    /// [`Obz`](crate::ztier::Obz) rather than [`Ob`](crate::Ob).
+    ///
+    /// Legacy is not counted, although it shares the z-tier secret.
+    pub fn is_ztier(&self) -> bool {
+        match self {
+            #[cfg(feature = "zrbcx")]
+            Scheme::Zrbcx => true,
+            #[cfg(feature = "zmock")]
+            Scheme::Zmock1 => true,
+            #[allow(unreachable_patterns)]
+            _ => false,
+        }
+    }
+
+    /// Whether this scheme is z-tier or legacy, i.e. used through
+    /// [`Obz`](crate::ztier::Obz) or [`Legacy`](crate::ztier::Legacy)
+    /// rather than [`Ob`](crate::Ob).
+    pub fn is_ztier_or_legacy(&self) -> bool {
+        match self {
+            #[cfg(feature = "legacy")]
+            Scheme::Legacy => true,
+            _ => self.is_ztier(),
+        }
+    }
+
+    /// Check if this is a testing scheme (mock1-mock4, zmock1): no real
+    /// encryption, not for production data.
+    pub fn is_testing(&self) -> bool {
+        match self {
+            #[cfg(feature = "mock")]
+            Scheme::Mock1 | Scheme::Mock2 | Scheme::Mock3 | Scheme::Mock4 => true,
+            #[cfg(feature = "zmock")]
+            Scheme::Zmock1 => true,
+            #[cfg(feature = "testing")]
+            Scheme::Null => true,
+            #[allow(unreachable_patterns)]
+            _ => false,
+        }
+    }
+
+    /// Check if this scheme verifies an authentication tag on decryption
+    /// (the a-tier AEAD schemes).
+    ///
+    /// Only then does a successful `dec` prove the key and the obtext
+    /// right; the other schemes may "decrypt" with a wrong key or corrupted
+    /// obtext into garbage.  Custom schemes are not considered
+    /// authenticated.
+    pub fn is_authenticated(&self) -> bool {
+        match self {
+            #[cfg(feature = "aags")]
+            Scheme::Aags => true,
+            #[cfg(feature = "apgs")]
+            Scheme::Apgs => true,
+            #[cfg(feature = "apgc")]
+            Scheme::Apgc => true,
+            #[cfg(feature = "aasv")]
+            Scheme::Aasv => true,
+            #[cfg(feature = "apsv")]
+            Scheme::Apsv => true,
+            #[allow(unreachable_patterns)]
+            _ => false,
+        }
+    }
+
+    /// Check if decryption depends on the key.
+    ///
+    /// False for the identity and reversal mocks (mock1, mock2, zmock1),
+    /// which ignore it, so their obtext decodes the same under any key.
+    /// Custom schemes are assumed keyed.
+    pub fn is_keyed(&self) -> bool {
+        match self {
+            #[cfg(feature = "mock")]
+            Scheme::Mock1 | Scheme::Mock2 => false,
+            #[cfg(feature = "zmock")]
+            Scheme::Zmock1 => false,
+            #[cfg(feature = "testing")]
+            Scheme::Null => false,
+            #[allow(unreachable_patterns)]
+            _ => true,
+        }
+    }
+
+    /// Bytes of random nonce (or IV) each obtext carries, before encoding.
+    ///
+    /// 12 for apgs and apgc, 16 for apsv and upbc, and 0 for the
+    /// deterministic schemes, which store none.  Together with the 16-byte
+    /// tag of the AEAD schemes and the 2-byte scheme marker this accounts
+    /// for the expansion from plaintext to payload.
+    pub fn nonce_len(&self) -> usize {
+        match self {
+            #[cfg(feature = "aags")]
+            Scheme::Aags => 0,
+            #[cfg(feature = "apgs")]
+            Scheme::Apgs => crate::obcrypt::APGS_NONCE_SIZE,
+            #[cfg(feature = "apgc")]
+            Scheme::Apgc => crate::obcrypt::APGC_NONCE_SIZE,
+            #[cfg(feature = "aasv")]
+            Scheme::Aasv => 0,
+            #[cfg(feature = "apsv")]
+            Scheme::Apsv => crate::obcrypt::APSV_NONCE_SIZE,
+            #[cfg(feature = "upbc")]
+            Scheme::Upbc => crate::obcrypt::UPBC_IV_SIZE,
+            // Z-tier
+            #[cfg(feature = "zrbcx")]
+            Scheme::Zrbcx => 0,
+            // Testing
+            #[cfg(feature = "mock")]
+            Scheme::Mock1 | Scheme::Mock2 | Scheme::Mock3 | Scheme::Mock4 => 0,
+            #[cfg(feature = "zmock")]
+            Scheme::Zmock1 => 0,
+            // Legacy
+            #[cfg(feature = "legacy")]
+            Scheme::Legacy => 0,
+            // Application-defined
+            #[cfg(feature = "custom-schemes")]
+            Scheme::Custom(c) => c.imp().nonce_len(),
+            // Test double
+            #[cfg(feature = "testing")]
+            Scheme::Null => 0,
+        }
+    }
+
+    /// Minimum plaintext length in bytes accepted by `enc` for this scheme.
+    ///
+    /// Every scheme, including legacy, the mock schemes and custom schemes,
+    /// currently requires at least one byte; shorter plaintext is rejected
+    /// with [`Error::PlaintextTooShort`].  There is no maximum, short of a
+    /// limit set with `with_max_input_len`.
+    pub fn min_plaintext_len(&self) -> usize {
+        1
+    }
+
+    /// Whether obtext of this scheme is reversed, for porters writing an
+    /// interoperable decoder.
+    ///
+    /// Only legacy reverses: its obtext is the lowercase base32 of the
+    /// ciphertext, reversed character by character.  Every other scheme
+    /// (custom schemes included) encodes its payload, ciphertext followed by
+    /// the mixed scheme marker, in byte order; zrbcx mixes its first and last
+    /// cipher blocks, but does not reverse them.
+    pub fn uses_reversal(&self) -> bool {
+        match self {
+            #[cfg(feature = "aags")]
+            Scheme::Aags => false,
+            #[cfg(feature = "apgs")]
+            Scheme::Apgs => false,
+            #[cfg(feature = "apgc")]
+            Scheme::Apgc => false,
+            #[cfg(feature = "aasv")]
+            Scheme::Aasv => false,
+            #[cfg(feature = "apsv")]
+            Scheme::Apsv => false,
+            #[cfg(feature = "upbc")]
+            Scheme::Upbc => false,
+            // Z-tier
+            #[cfg(feature = "zrbcx")]
+            Scheme::Zrbcx => false,
+            // Testing
+            #[cfg(feature = "mock")]
+            Scheme::Mock1 | Scheme::Mock2 | Scheme::Mock3 | Scheme::Mock4 => false,
+            #[cfg(feature = "zmock")]
+            Scheme::Zmock1 => false,
+            // Legacy
+            #[cfg(feature = "legacy")]
+            Scheme::Legacy => true,
+            // Application-defined
+            #[cfg(feature = "custom-schemes")]
+            Scheme::Custom(_) => false,
+            // Test double
+            #[cfg(feature = "testing")]
+            Scheme::Null => false,
+        }
+    }
+
     /// Get the 2-byte scheme marker for this scheme.
     pub fn marker(&self) -> [u8; 2] {
         match self {
@@ -106,6 +366,8 @@ impl Scheme {
             Scheme::Aags => constants::AAGS_MARKER,
             #[cfg(feature = "apgs")]
             Scheme::Apgs => constants::APGS_MARKER,
+            #[cfg(feature = "apgc")]
+            Scheme::Apgc => constants::APGC_MARKER,
             #[cfg(feature = "aasv")]
             Scheme::Aasv => constants::AASV_MARKER,
             #[cfg(feature = "apsv")]
@@ -120,14 +382,35 @@ impl Scheme {
             Scheme::Mock1 => constants::MOCK1_MARKER,
             #[cfg(feature = "mock")]
             Scheme::Mock2 => constants::MOCK2_MARKER,
+            #[cfg(feature = "mock")]
+            Scheme::Mock3 => constants::MOCK3_MARKER,
+            #[cfg(feature = "mock")]
+            Scheme::Mock4 => constants::MOCK4_MARKER,
             #[cfg(feature = "zmock")]
             Scheme::Zmock1 => constants::ZMOCK1_MARKER,
             // Legacy
             #[cfg(feature = "legacy")]
             Scheme::Legacy => unreachable!("legacy does not use a scheme marker"),
+            // Application-defined
+            #[cfg(feature = "custom-schemes")]
+            Scheme::Custom(c) => c.imp().marker(),
+            // Test double
+            #[cfg(feature = "testing")]
+            Scheme::Null => constants::NULL_MARKER,
         }
     }
 
+    /// Alternative names accepted by [`Scheme::from_str`] for this scheme,
+    /// e.g. `ob32` and `32` for `aasv`.  Output always uses
+    /// [`as_str`](Self::as_str).
+    pub fn aliases(&self) -> impl Iterator<Item = &'static str> {
+        let name = self.as_str();
+        SCHEME_ALIASES
+            .iter()
+            .filter(move |(_, n)| *n == name)
+            .map(|(alias, _)| *alias)
+    }
+
     /// Legacy compatibility:  get single byte representation (deprecated)
     #[deprecated(
         since = "1.0.0",
@@ -139,15 +422,136 @@ impl Scheme {
     }
 }
 
+/// Every scheme name oboron knows about, paired with the cargo feature that
+/// enables it (independent of which features are compiled in).
+const KNOWN_SCHEMES: &[(&str, &str)] = &[
+    ("aags", "aags"),
+    ("apgs", "apgs"),
+    ("apgc", "apgc"),
+    ("aasv", "aasv"),
+    ("apsv", "apsv"),
+    ("upbc", "upbc"),
+    ("zrbcx", "zrbcx"),
+    ("mock1", "mock"),
+    ("mock2", "mock"),
+    ("mock3", "mock"),
+    ("mock4", "mock"),
+    ("zmock1", "zmock"),
+    ("legacy", "legacy"),
+];
+
+/// Alternative scheme names, paired with the canonical name they stand
+/// for: the pre-1.0 `obNN` names, their short forms without the leading
+/// zero, and the bare tier numbers used by the CLI.
+const SCHEME_ALIASES: &[(&str, &str)] = &[
+    ("ob00", "legacy"),
+    ("00", "legacy"),
+    ("ob01", "zrbcx"),
+    ("ob1", "zrbcx"),
+    ("01", "zrbcx"),
+    ("ob21p", "upbc"),
+    ("21p", "upbc"),
+    ("ob31", "aags"),
+    ("31", "aags"),
+    ("ob31p", "apgs"),
+    ("31p", "apgs"),
+    ("ob32", "aasv"),
+    ("32", "aasv"),
+    ("ob32p", "apsv"),
+    ("32p", "apsv"),
+    ("ob70", "mock1"),
+    ("70", "mock1"),
+    ("ob71", "mock2"),
+    ("71", "mock2"),
+    ("ob72", "mock3"),
+    ("72", "mock3"),
+    ("ob73", "mock4"),
+    ("73", "mock4"),
+];
+
+/// Resolve an alias to its canonical scheme name; other names pass through.
+fn resolve_alias(name: &str) -> &str {
+    SCHEME_ALIASES
+        .iter()
+        .find(|(alias, _)| *alias == name)
+        .map_or(name, |(_, canonical)| canonical)
+}
+
+/// All built-in schemes enabled in this build.
+///
+/// Schemes added with `register_scheme` (feature `custom-schemes`) are not
+/// included.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[cfg(feature = "aasv")]
+/// assert!(oboron::supported_schemes().contains(&oboron::Scheme::Aasv));
+/// ```
+pub fn supported_schemes() -> &'static [Scheme] {
+    &[
+        #[cfg(feature = "aags")]
+        Scheme::Aags,
+        #[cfg(feature = "apgs")]
+        Scheme::Apgs,
+        #[cfg(feature = "apgc")]
+        Scheme::Apgc,
+        #[cfg(feature = "aasv")]
+        Scheme::Aasv,
+        #[cfg(feature = "apsv")]
+        Scheme::Apsv,
+        #[cfg(feature = "upbc")]
+        Scheme::Upbc,
+        // Z-tier
+        #[cfg(feature = "zrbcx")]
+        Scheme::Zrbcx,
+        // Testing
+        #[cfg(feature = "mock")]
+        Scheme::Mock1,
+        #[cfg(feature = "mock")]
+        Scheme::Mock2,
+        #[cfg(feature = "mock")]
+        Scheme::Mock3,
+        #[cfg(feature = "mock")]
+        Scheme::Mock4,
+        #[cfg(feature = "zmock")]
+        Scheme::Zmock1,
+        // Legacy
+        #[cfg(feature = "legacy")]
+        Scheme::Legacy,
+    ]
+}
+
+/// If `name` is a known scheme that is compiled out of this build, return
+/// the matching [`Error::SchemeNotEnabled`].
+pub(crate) fn disabled_scheme_error(name: &str) -> Option<Error> {
+    let name = name.to_lowercase();
+    let name = resolve_alias(&name);
+    let &(scheme, feature) = KNOWN_SCHEMES.iter().find(|(n, _)| *n == name)?;
+    if supported_schemes().iter().any(|s| s.as_str() == scheme) {
+        return None;
+    }
+    Some(Error::SchemeNotEnabled { scheme, feature })
+}
+
+/// Whether `name` is reserved by a built-in scheme or alias, enabled or not.
+#[cfg(feature = "custom-schemes")]
+pub(crate) fn is_builtin_name(name: &str) -> bool {
+    KNOWN_SCHEMES.iter().any(|(n, _)| *n == name)
+        || SCHEME_ALIASES.iter().any(|(alias, _)| *alias == name)
+}
+
 impl std::str::FromStr for Scheme {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.to_lowercase().as_str() {
+        match resolve_alias(&s.to_lowercase()) {
             #[cfg(feature = "aags")]
             "aags" => Ok(Scheme::Aags),
             #[cfg(feature = "apgs")]
             "apgs" => Ok(Scheme::Apgs),
+            #[cfg(feature = "apgc")]
+            "apgc" => Ok(Scheme::Apgc),
             #[cfg(feature = "aasv")]
             "aasv" => Ok(Scheme::Aasv),
             #[cfg(feature = "apsv")]
@@ -162,12 +566,22 @@ impl std::str::FromStr for Scheme {
             "mock1" => Ok(Scheme::Mock1),
             #[cfg(feature = "mock")]
             "mock2" => Ok(Scheme::Mock2),
+            #[cfg(feature = "mock")]
+            "mock3" => Ok(Scheme::Mock3),
+            #[cfg(feature = "mock")]
+            "mock4" => Ok(Scheme::Mock4),
             #[cfg(feature = "zmock")]
             "zmock1" => Ok(Scheme::Zmock1),
             // Legacy
             #[cfg(feature = "legacy")]
             "legacy" => Ok(Scheme::Legacy),
-            _ => Err(Error::UnknownScheme),
+            other => {
+                #[cfg(feature = "custom-schemes")]
+                if let Some(scheme) = custom_scheme::by_name(other) {
+                    return Ok(scheme);
+                }
+                Err(disabled_scheme_error(other).unwrap_or(Error::UnknownScheme))
+            }
         }
     }
 }
@@ -177,3 +591,104 @@ impl std::fmt::Display for Scheme {
         write!(f, "{}", self.as_str())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_known_schemes_cover_enum() {
+        for scheme in supported_schemes() {
+            assert!(
+                KNOWN_SCHEMES.iter().any(|(n, _)| *n == scheme.as_str()),
+                "{} missing from KNOWN_SCHEMES",
+                scheme
+            );
+        }
+    }
+
+    #[test]
+    fn test_aliases_resolve_to_known_schemes() {
+        for (alias, name) in SCHEME_ALIASES {
+            assert!(
+                KNOWN_SCHEMES.iter().any(|(n, _)| n == name),
+                "{} -> {}",
+                alias,
+                name
+            );
+            assert!(!KNOWN_SCHEMES.iter().any(|(n, _)| n == alias));
+        }
+    }
+
+    #[test]
+    fn test_descriptions_are_distinct() {
+        let schemes = supported_schemes();
+        for (i, a) in schemes.iter().enumerate() {
+            assert!(!a.description().is_empty());
+            assert_ne!(a.description(), a.as_str());
+            for b in &schemes[i + 1..] {
+                assert_ne!(a.description(), b.description());
+            }
+        }
+    }
+
+    #[test]
+    fn test_ztier_schemes() {
+        for scheme in supported_schemes() {
+            let name = scheme.as_str();
+            assert_eq!(scheme.is_ztier(), name.starts_with('z'), "{}", scheme);
+            assert_eq!(
+                scheme.is_ztier_or_legacy(),
+                name.starts_with('z') || name == "legacy",
+                "{}",
+                scheme
+            );
+        }
+    }
+
+    #[test]
+    fn test_only_atier_schemes_are_authenticated() {
+        for scheme in supported_schemes() {
+            assert_eq!(
+                scheme.is_authenticated(),
+                scheme.as_str().starts_with('a'),
+                "{}",
+                scheme
+            );
+        }
+    }
+
+    #[test]
+    fn test_testing_schemes() {
+        for scheme in supported_schemes() {
+            assert_eq!(
+                scheme.is_testing(),
+                scheme.as_str().contains("mock"),
+                "{}",
+                scheme
+            );
+        }
+    }
+
+    #[test]
+    fn test_disabled_scheme_error() {
+        // Enabled or unknown names never report "not enabled"
+        for scheme in supported_schemes() {
+            assert_eq!(disabled_scheme_error(scheme.as_str()), None);
+        }
+        assert_eq!(disabled_scheme_error("nope"), None);
+        assert_eq!("nope".parse::<Scheme>(), Err(Error::UnknownScheme));
+    }
+
+    #[cfg(not(feature = "aags"))]
+    #[test]
+    fn test_disabled_scheme_from_str() {
+        assert_eq!(
+            "aags".parse::<Scheme>(),
+            Err(Error::SchemeNotEnabled {
+                scheme: "aags",
+                feature: "aags"
+            })
+        );
+    }
+}
@@ -0,0 +1,346 @@
+//! Database column integration for `sqlx` and `diesel`.
+//!
+//! [`Encrypted<C>`] holds a *plaintext* string in memory and is stored as an
+//! obtext in a TEXT column: it is encrypted when bound as a query parameter and
+//! decrypted when read back from a row.  The codec used for both directions is
+//! supplied by the [`CodecContext`] type parameter `C`.
+//!
+//! [`Obtext`](crate::Obtext) is also usable as a column type; it is stored
+//! verbatim, for callers that encrypt and decrypt explicitly.
+//!
+//! # Construction pattern
+//!
+//! SQL encode/decode traits do not receive any runtime state, so the codec
+//! lives in a `static` that is initialized once at startup, typically a
+//! `OnceCell<Ob>`:
+//!
+//! ```rust
+//! # #[cfg(feature = "aasv")]
+//! # {
+//! use once_cell::sync::OnceCell;
+//! use oboron::sql::{CodecContext, Encrypted};
+//! use oboron::Ob;
+//!
+//! static USERS_CODEC: OnceCell<Ob> = OnceCell::new();
+//!
+//! pub struct UsersCodec;
+//!
+//! impl CodecContext for UsersCodec {
+//!     type Codec = Ob;
+//!
+//!     fn codec() -> Option<&'static Ob> {
+//!         USERS_CODEC.get()
+//!     }
+//! }
+//!
+//! pub type EncryptedEmail = Encrypted<UsersCodec>;
+//!
+//! // At startup, before any query runs:
+//! # let key = oboron::generate_key();
+//! USERS_CODEC.set(Ob::new("aasv.b64", &key).unwrap()).ok();
+//!
+//! let email = EncryptedEmail::new("alice@example.com");
+//! assert_eq!(&*email, "alice@example.com");
+//! # }
+//! ```
+//!
+//! Use a separate context type (and static) per key or format.  Binding or
+//! reading an [`Encrypted`] value before its context is initialized fails with
+//! an error rather than panicking.
+//!
+//! # Backends
+//!
+//! - `sqlx`: `Type`, `Encode` and `Decode` are implemented for every database
+//!   whose `String` type is supported.
+//! - `diesel`: `FromSql<Text, _>` is implemented for every backend; `ToSql` is
+//!   implemented for PostgreSQL and MySQL.
+
+#![cfg(any(feature = "sqlx", feature = "diesel"))]
+
+use crate::ObtextCodec;
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::Deref;
+
+/// Supplies the codec used by [`Encrypted<Self>`] columns.
+pub trait CodecContext {
+    /// The codec type, e.g. [`Ob`](crate::Ob) or a fixed-format codec.
+    type Codec: ObtextCodec + 'static;
+
+    /// Return the codec, or `None` if it has not been initialized yet.
+    fn codec() -> Option<&'static Self::Codec>;
+}
+
+/// A plaintext string stored encrypted in a TEXT column.
+///
+/// See the [module documentation](self) for how to supply the codec.
+#[cfg_attr(
+    feature = "diesel",
+    derive(diesel::expression::AsExpression, diesel::deserialize::FromSqlRow),
+    diesel(sql_type = diesel::sql_types::Text)
+)]
+pub struct Encrypted<C> {
+    plaintext: String,
+    _context: PhantomData<fn() -> C>,
+}
+
+impl<C> Encrypted<C> {
+    /// Wrap a plaintext value.
+    pub fn new(plaintext: impl Into<String>) -> Self {
+        Self {
+            plaintext: plaintext.into(),
+            _context: PhantomData,
+        }
+    }
+
+    /// Borrow the plaintext.
+    pub fn as_str(&self) -> &str {
+        &self.plaintext
+    }
+
+    /// Unwrap into the plaintext `String`.
+    pub fn into_inner(self) -> String {
+        self.plaintext
+    }
+}
+
+impl<C: CodecContext> Encrypted<C> {
+    fn codec() -> Result<&'static C::Codec, NotInitialized> {
+        C::codec().ok_or(NotInitialized)
+    }
+
+    fn to_obtext(&self) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(Self::codec()?.enc(&self.plaintext)?)
+    }
+
+    fn from_obtext(obtext: &str) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(Self::new(Self::codec()?.dec(obtext)?))
+    }
+}
+
+// Manual impls so that `C` (a marker type) needs no bounds.
+impl<C> Clone for Encrypted<C> {
+    fn clone(&self) -> Self {
+        Self::new(self.plaintext.clone())
+    }
+}
+
+impl<C> PartialEq for Encrypted<C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.plaintext == other.plaintext
+    }
+}
+
+impl<C> Eq for Encrypted<C> {}
+
+// The plaintext is redacted, so logging a row does not leak it
+impl<C> fmt::Debug for Encrypted<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Encrypted(<redacted>)")
+    }
+}
+
+impl<C> Deref for Encrypted<C> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.plaintext
+    }
+}
+
+impl<C> From<String> for Encrypted<C> {
+    fn from(s: String) -> Self {
+        Self::new(s)
+    }
+}
+
+impl<C> From<&str> for Encrypted<C> {
+    fn from(s: &str) -> Self {
+        Self::new(s)
+    }
+}
+
+/// Error returned when a [`CodecContext`] is used before initialization.
+#[derive(Debug)]
+struct NotInitialized;
+
+impl fmt::Display for NotInitialized {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("oboron codec context used before initialization")
+    }
+}
+
+impl std::error::Error for NotInitialized {}
+
+#[cfg(feature = "sqlx")]
+mod sqlx_impl {
+    use super::{CodecContext, Encrypted};
+    use crate::Obtext;
+    use sqlx::encode::IsNull;
+    use sqlx::error::BoxDynError;
+    use sqlx::{Database, Decode, Encode, Type};
+
+    impl<C, DB: Database> Type<DB> for Encrypted<C>
+    where
+        String: Type<DB>,
+    {
+        fn type_info() -> DB::TypeInfo {
+            <String as Type<DB>>::type_info()
+        }
+
+        fn compatible(ty: &DB::TypeInfo) -> bool {
+            <String as Type<DB>>::compatible(ty)
+        }
+    }
+
+    impl<'q, C: CodecContext, DB: Database> Encode<'q, DB> for Encrypted<C>
+    where
+        String: Encode<'q, DB>,
+    {
+        fn encode_by_ref(
+            &self,
+            buf: &mut <DB as Database>::ArgumentBuffer<'q>,
+        ) -> Result<IsNull, BoxDynError> {
+            <String as Encode<'q, DB>>::encode(self.to_obtext()?, buf)
+        }
+    }
+
+    impl<'r, C: CodecContext, DB: Database> Decode<'r, DB> for Encrypted<C>
+    where
+        String: Decode<'r, DB>,
+    {
+        fn decode(value: <DB as Database>::ValueRef<'r>) -> Result<Self, BoxDynError> {
+            Self::from_obtext(&<String as Decode<'r, DB>>::decode(value)?)
+        }
+    }
+
+    impl<DB: Database> Type<DB> for Obtext
+    where
+        String: Type<DB>,
+    {
+        fn type_info() -> DB::TypeInfo {
+            <String as Type<DB>>::type_info()
+        }
+
+        fn compatible(ty: &DB::TypeInfo) -> bool {
+            <String as Type<DB>>::compatible(ty)
+        }
+    }
+
+    impl<'q, DB: Database> Encode<'q, DB> for Obtext
+    where
+        String: Encode<'q, DB>,
+    {
+        fn encode_by_ref(
+            &self,
+            buf: &mut <DB as Database>::ArgumentBuffer<'q>,
+        ) -> Result<IsNull, BoxDynError> {
+            <String as Encode<'q, DB>>::encode(self.as_str().to_owned(), buf)
+        }
+    }
+
+    impl<'r, DB: Database> Decode<'r, DB> for Obtext
+    where
+        String: Decode<'r, DB>,
+    {
+        fn decode(value: <DB as Database>::ValueRef<'r>) -> Result<Self, BoxDynError> {
+            Ok(Obtext::from(<String as Decode<'r, DB>>::decode(value)?))
+        }
+    }
+}
+
+#[cfg(feature = "diesel")]
+mod diesel_impl {
+    use super::{CodecContext, Encrypted};
+    use crate::Obtext;
+    use diesel::backend::Backend;
+    use diesel::deserialize::{self, FromSql};
+    use diesel::mysql::Mysql;
+    use diesel::pg::Pg;
+    use diesel::serialize::{self, IsNull, Output, ToSql};
+    use diesel::sql_types::Text;
+    use std::io::Write;
+
+    // Pg and Mysql send TEXT parameters as raw bytes, so the freshly
+    // encrypted obtext can be written straight into the output buffer.
+    macro_rules! impl_to_sql_bytes {
+        ($backend:ty) => {
+            impl<C: CodecContext> ToSql<Text, $backend> for Encrypted<C> {
+                fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, $backend>) -> serialize::Result {
+                    out.write_all(self.to_obtext()?.as_bytes())?;
+                    Ok(IsNull::No)
+                }
+            }
+
+            impl ToSql<Text, $backend> for Obtext {
+                fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, $backend>) -> serialize::Result {
+                    out.write_all(self.as_bytes())?;
+                    Ok(IsNull::No)
+                }
+            }
+        };
+    }
+
+    impl_to_sql_bytes!(Pg);
+    impl_to_sql_bytes!(Mysql);
+
+    impl<C: CodecContext, DB: Backend> FromSql<Text, DB> for Encrypted<C>
+    where
+        String: FromSql<Text, DB>,
+    {
+        fn from_sql(bytes: DB::RawValue<'_>) -> deserialize::Result<Self> {
+            Self::from_obtext(&<String as FromSql<Text, DB>>::from_sql(bytes)?)
+        }
+    }
+
+    impl<DB: Backend> FromSql<Text, DB> for Obtext
+    where
+        String: FromSql<Text, DB>,
+    {
+        fn from_sql(bytes: DB::RawValue<'_>) -> deserialize::Result<Self> {
+            let obtext = <String as FromSql<Text, DB>>::from_sql(bytes)?;
+            Ok(Obtext::from(obtext))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Uninitialized;
+
+    impl CodecContext for Uninitialized {
+        type Codec = crate::Ob;
+
+        fn codec() -> Option<&'static crate::Ob> {
+            None
+        }
+    }
+
+    #[test]
+    fn test_uninitialized_context_is_an_error() {
+        let value = Encrypted::<Uninitialized>::new("secret");
+        assert!(value.to_obtext().is_err());
+        assert!(Encrypted::<Uninitialized>::from_obtext("abc").is_err());
+    }
+
+    #[test]
+    fn test_debug_redacts_plaintext() {
+        let value = Encrypted::<Uninitialized>::new("alice@example.com");
+        assert_eq!(format!("{:?}", value), "Encrypted(<redacted>)");
+    }
+
+    #[cfg(feature = "diesel")]
+    #[test]
+    fn test_diesel_impls() {
+        use diesel::deserialize::FromSql;
+        use diesel::serialize::ToSql;
+        use diesel::sql_types::Text;
+
+        fn assert_column<T: ToSql<Text, DB> + FromSql<Text, DB>, DB: diesel::backend::Backend>() {}
+        assert_column::<Encrypted<Uninitialized>, diesel::pg::Pg>();
+        assert_column::<Encrypted<Uninitialized>, diesel::mysql::Mysql>();
+        assert_column::<crate::Obtext, diesel::pg::Pg>();
+    }
+}
@@ -0,0 +1,104 @@
+//! Test doubles for code that is generic over [`ObtextCodec`].
+//!
+//! [`NullOb`] stands in for a real codec in downstream tests: it needs no
+//! key, does no cryptography and produces obtext that is obviously not
+//! encrypted, so tests can check what was passed through without the cost
+//! or opacity of real ciphertext.
+
+#![cfg(feature = "testing")]
+
+use crate::{Encoding, Error, Format, ObtextCodec, Scheme};
+
+/// A no-op [`ObtextCodec`] for tests: `enc` prefixes the plaintext with
+/// [`PREFIX`](Self::PREFIX) and `dec` strips it again.
+///
+/// This is NOT encryption: the plaintext is carried as is.  Enable the
+/// `testing` feature on the dev-dependency only; it is not part of `full`.
+///
+/// # Format and key id
+///
+/// [`scheme`](ObtextCodec::scheme) is a sentinel that only `NullOb`
+/// reports, displayed as `null` (so [`format`](ObtextCodec::format) is
+/// `null.c32`).  It cannot be parsed from a string, no real codec
+/// encrypts or decrypts with it, and it never matches the scheme of real
+/// obtext, so code that branches on the scheme cannot mistake `NullOb`
+/// for a real codec.  Decode `NullOb` output with `NullOb` only.
+///
+/// [`key_id`](ObtextCodec::key_id) is the trait's default,
+/// [`UNKNOWN_KEY_ID`](crate::UNKNOWN_KEY_ID): `NullOb` has no key.
+///
+/// # Examples
+///
+/// ```rust
+/// use oboron::{NullOb, ObtextCodec};
+///
+/// fn process<O: ObtextCodec>(ob: &O, id: &str) -> Result<String, oboron::Error> {
+///     ob.enc(id)
+/// }
+///
+/// let ot = process(&NullOb, "user-42")?;
+/// assert_eq!(ot, "null:user-42");
+/// assert_eq!(NullOb.dec(&ot)?, "user-42");
+/// # Ok::<(), oboron::Error>(())
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct NullOb;
+
+impl NullOb {
+    /// Prefix `enc` puts in front of the plaintext
+    pub const PREFIX: &'static str = "null:";
+}
+
+impl ObtextCodec for NullOb {
+    /// Prefix `plaintext` with [`PREFIX`](Self::PREFIX).  Empty plaintext
+    /// is rejected, as by the real codecs.
+    fn enc(&self, plaintext: &str) -> Result<String, Error> {
+        crate::error::check_plaintext_len(plaintext, self.scheme())?;
+        Ok([Self::PREFIX, plaintext].concat())
+    }
+
+    /// Strip [`PREFIX`](Self::PREFIX), rejecting obtext without it with
+    /// [`Error::SchemeMarkerMismatch`].
+    fn dec(&self, obtext: &str) -> Result<String, Error> {
+        match obtext.strip_prefix(Self::PREFIX) {
+            Some(plaintext) if !plaintext.is_empty() => Ok(plaintext.to_string()),
+            Some(_) => Err(Error::EmptyPayload),
+            None => Err(Error::SchemeMarkerMismatch),
+        }
+    }
+
+    fn format(&self) -> Format {
+        Format::new(self.scheme(), self.encoding())
+    }
+
+    fn scheme(&self) -> Scheme {
+        Scheme::Null
+    }
+
+    fn encoding(&self) -> Encoding {
+        Encoding::C32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_null_ob_roundtrip() {
+        let ot = NullOb.enc("hello world").unwrap();
+        assert_eq!(ot, "null:hello world");
+        assert_eq!(NullOb.dec(&ot).unwrap(), "hello world");
+        assert_eq!(NullOb.self_test(), Ok(()));
+    }
+
+    #[test]
+    fn test_null_ob_errors() {
+        assert_eq!(
+            NullOb.enc(""),
+            Err(Error::PlaintextTooShort { len: 0, min: 1 })
+        );
+        assert_eq!(NullOb.dec("null:"), Err(Error::EmptyPayload));
+        assert_eq!(NullOb.dec("hello"), Err(Error::SchemeMarkerMismatch));
+    }
+}
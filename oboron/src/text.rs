@@ -0,0 +1,115 @@
+//! Typed wrappers for plaintext and obtext strings.
+//!
+//! Both plaintext and obtext are plain `String`s in the core API, which makes
+//! it easy to pass one where the other is expected.  [`Plaintext`] and
+//! [`Obtext`] are thin newtypes that let the compiler catch such mix-ups when
+//! used with [`ObtextCodec::enc_typed`](crate::ObtextCodec::enc_typed) and
+//! [`ObtextCodec::dec_typed`](crate::ObtextCodec::dec_typed).
+
+use std::fmt;
+use std::ops::Deref;
+
+macro_rules! impl_text_newtype {
+    ($(#[$attr:meta])* $name:ident, $what:expr) => {
+        #[doc = concat!("A ", $what, " string.")]
+        #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+        #[must_use]
+        $(#[$attr])*
+        pub struct $name(String);
+
+        impl $name {
+            #[doc = concat!("Wrap a string as ", $what, ".")]
+            #[inline]
+            pub fn new(s: impl Into<String>) -> Self {
+                $name(s.into())
+            }
+
+            /// Borrow the underlying string slice.
+            #[inline]
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+
+            /// Unwrap into the underlying `String`.
+            #[inline]
+            pub fn into_string(self) -> String {
+                self.0
+            }
+        }
+
+        impl Deref for $name {
+            type Target = str;
+
+            #[inline]
+            fn deref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl AsRef<str> for $name {
+            #[inline]
+            fn as_ref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(&self.0)
+            }
+        }
+
+        impl From<String> for $name {
+            #[inline]
+            fn from(s: String) -> Self {
+                $name(s)
+            }
+        }
+
+        impl From<&str> for $name {
+            #[inline]
+            fn from(s: &str) -> Self {
+                $name(s.to_string())
+            }
+        }
+
+        impl From<$name> for String {
+            #[inline]
+            fn from(t: $name) -> Self {
+                t.0
+            }
+        }
+    };
+}
+
+impl_text_newtype!(Plaintext, "plaintext");
+impl_text_newtype!(
+    #[cfg_attr(
+        feature = "diesel",
+        derive(diesel::expression::AsExpression, diesel::deserialize::FromSqlRow),
+        diesel(sql_type = diesel::sql_types::Text)
+    )]
+    Obtext,
+    "obtext (encrypted and encoded)"
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conversions() {
+        let pt = Plaintext::from("hello");
+        assert_eq!(pt.as_str(), "hello");
+        assert_eq!(&*pt, "hello");
+        assert_eq!(pt.len(), 5); // via Deref<Target = str>
+        assert_eq!(pt.to_string(), "hello");
+        assert_eq!(String::from(pt.clone()), "hello");
+        assert_eq!(pt, Plaintext::new(String::from("hello")));
+
+        let ot: Obtext = String::from("abc").into();
+        assert_eq!(ot.as_ref(), "abc");
+        assert_eq!(format!("{}", ot), "abc");
+        assert_eq!(ot.into_string(), "abc");
+    }
+}
@@ -75,10 +75,8 @@ impl Legacy {
 
 impl ObtextCodec for Legacy {
     fn enc(&self, plaintext: &str) -> Result<String, Error> {
+        crate::error::check_plaintext_len(plaintext, Scheme::Legacy)?;
         let plaintext_bytes = plaintext.as_bytes();
-        if plaintext_bytes.is_empty() {
-            return Err(Error::EmptyPlaintext);
-        }
 
         // Encrypt using legacy AES-CBC
         let ciphertext = encrypt_legacy(self.zsecret.master_secret(), plaintext_bytes)?;
@@ -127,6 +125,18 @@ impl ObtextCodec for Legacy {
     fn encoding(&self) -> Encoding {
         Encoding::B32
     }
+
+    fn key_id(&self) -> String {
+        self.zsecret.fingerprint()
+    }
+
+    // Legacy obtext is reversed lowercase base32, so hash the ciphertext
+    // directly rather than decoding the obtext
+    fn dedup_key(&self, plaintext: &str) -> Result<String, Error> {
+        crate::error::check_plaintext_len(plaintext, Scheme::Legacy)?;
+        let ciphertext = encrypt_legacy(self.zsecret.master_secret(), plaintext.as_bytes())?;
+        Ok(crate::codec::dedup_digest(&ciphertext))
+    }
 }
 
 // Inherent methods
@@ -206,6 +216,10 @@ pub(crate) fn decrypt_legacy(secret: &[u8; 32], data: &[u8]) -> Result<Vec<u8>,
 
     type Aes128CbcDec = Decryptor<Aes128>;
 
+    if data.is_empty() {
+        return Err(Error::EmptyPayload);
+    }
+
     // Decrypt with AES-128-CBC
     if data.len() % AES_BLOCK_SIZE != 0 {
         return Err(Error::InvalidBlockLength);
@@ -230,3 +244,28 @@ pub(crate) fn decrypt_legacy(secret: &[u8; 32], data: &[u8]) -> Result<Vec<u8>,
 
     Ok(buffer)
 }
+
+/// Decode `obtext` as legacy only if it has the legacy shape: reversed
+/// lowercase RFC base32 of whole AES blocks, decrypting to valid UTF-8 with
+/// less than a block of `=` padding.
+///
+/// Unlike the z-tier autodetection fallback this needs no output heuristic,
+/// so secure-tier autodetection can treat legacy as a regular candidate.
+pub(crate) fn dec_legacy_strict(secret: &[u8; 32], obtext: &str) -> Result<String, Error> {
+    if !obtext
+        .bytes()
+        .all(|b| b.is_ascii_lowercase() || (b'2'..=b'7').contains(&b))
+    {
+        return Err(Error::InvalidB32);
+    }
+    let reversed: Vec<u8> = obtext.bytes().rev().collect();
+    let ciphertext = crate::base32::BASE32_RFC_LOWER
+        .decode(&reversed)
+        .map_err(|_| Error::InvalidB32)?;
+
+    let plaintext_bytes = decrypt_legacy(secret, &ciphertext)?;
+    if plaintext_bytes.is_empty() || ciphertext.len() - plaintext_bytes.len() >= AES_BLOCK_SIZE {
+        return Err(Error::InvalidLegacyOutput);
+    }
+    String::from_utf8(plaintext_bytes).map_err(|_| Error::InvalidUtf8)
+}
@@ -39,5 +39,7 @@ pub(crate) use zmock1::{decrypt_zmock1, encrypt_zmock1};
 #[cfg(feature = "zrbcx")]
 pub(crate) use zrbcx::{decrypt_zrbcx, encrypt_zrbcx};
 
+#[cfg(feature = "legacy")]
+pub(crate) use legacy::dec_legacy_strict;
 #[cfg(feature = "legacy")]
 pub use legacy::Legacy;
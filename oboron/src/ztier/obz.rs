@@ -145,11 +145,11 @@ impl Obz {
     /// # Ok(())
     /// # }
     /// ```
-    pub fn set_format(&mut self, format: impl IntoFormat) -> Result<(), Error> {
+    pub fn set_format(&mut self, format: impl IntoFormat) -> Result<&mut Self, Error> {
         let format = format.into_format()?;
         validate_ztier_scheme(format.scheme())?;
         self.format = format;
-        Ok(())
+        Ok(self)
     }
 
     /// Set the scheme while keeping the current encoding.
@@ -169,10 +169,10 @@ impl Obz {
     /// # Ok(())
     /// # }
     /// ```
-    pub fn set_scheme(&mut self, scheme: Scheme) -> Result<(), Error> {
+    pub fn set_scheme(&mut self, scheme: Scheme) -> Result<&mut Self, Error> {
         validate_ztier_scheme(scheme)?;
         self.format = Format::new(scheme, self.format.encoding());
-        Ok(())
+        Ok(self)
     }
 
     /// Set the encoding while keeping the current scheme.
@@ -192,9 +192,9 @@ impl Obz {
     /// # Ok(())
     /// # }
     /// ```
-    pub fn set_encoding(&mut self, encoding: Encoding) -> Result<(), Error> {
+    pub fn set_encoding(&mut self, encoding: Encoding) -> Result<&mut Self, Error> {
         self.format = Format::new(self.format.scheme(), encoding);
-        Ok(())
+        Ok(self)
     }
 
     /// Decode and decrypt obtext with scheme autodetection.
@@ -379,6 +379,21 @@ impl ObtextCodec for Obz {
     fn encoding(&self) -> Encoding {
         self.format.encoding()
     }
+
+    fn key_id(&self) -> String {
+        self.zsecret.fingerprint()
+    }
+
+    fn dedup_key(&self, plaintext: &str) -> Result<String, Error> {
+        #[cfg(feature = "legacy")]
+        if self.format.scheme() == Scheme::Legacy {
+            let legacy = super::legacy::Legacy::from_master_secret(self.zsecret.master_secret())?;
+            return <super::legacy::Legacy as ObtextCodec>::dedup_key(&legacy, plaintext);
+        }
+        let obtext = <Self as ObtextCodec>::enc(self, plaintext)?;
+        let payload = crate::encoding::decode(&obtext, self.format.encoding())?;
+        Ok(crate::codec::dedup_digest(&payload))
+    }
 }
 
 // Add inherent methods that delegate to trait methods
@@ -102,6 +102,10 @@ macro_rules! impl_zcodec {
             fn encoding(&self) -> Encoding {
                 $encoding
             }
+
+            fn key_id(&self) -> String {
+                self.zsecret.fingerprint()
+            }
         }
 
         // Inherent methods (same as before)
@@ -28,7 +28,7 @@ pub(crate) fn dec_from_format_ztier(
     let scheme_marker = [buffer[len - 2] ^ first_byte, buffer[len - 1] ^ first_byte];
 
     if scheme_marker != format.scheme().marker() {
-        return Err(Error::SchemeMarkerMismatch);
+        return Err(crate::dec::marker_mismatch(format.scheme(), scheme_marker));
     }
 
     buffer.truncate(len - SCHEME_MARKER_SIZE);
@@ -39,7 +39,8 @@ pub(crate) fn dec_any_scheme_ztier(
         }
     };
 
-    if buffer.len() < SCHEME_MARKER_SIZE {
+    // A valid payload holds at least one ciphertext byte besides the marker
+    if buffer.len() <= SCHEME_MARKER_SIZE {
         // Payload too short for modern scheme - try legacy
         #[cfg(feature = "legacy")]
         {
@@ -49,13 +50,12 @@ pub(crate) fn dec_any_scheme_ztier(
         return Err(Error::PayloadTooShort);
     }
 
-    // Step 2: XOR the last two bytes with the first two to undo mixing
+    // Step 2 & 3: XOR the last two bytes with the first to undo mixing, and
+    // extract the 2-byte scheme marker.  `buffer[0]` is read once up front:
+    // XORing in place would clobber it when the marker overlaps the first byte.
     let len = buffer.len();
-    buffer[len - 1] ^= buffer[0];
-    buffer[len - 2] ^= buffer[0];
-
-    // Step 3: Extract 2-byte scheme marker from end
-    let scheme_marker = [buffer[len - 2], buffer[len - 1]];
+    let first_byte = buffer[0];
+    let scheme_marker = [buffer[len - 2] ^ first_byte, buffer[len - 1] ^ first_byte];
     buffer.truncate(len - SCHEME_MARKER_SIZE);
 
     // Step 4: Match scheme marker and decrypt with available Z-TIER schemes
@@ -164,9 +164,11 @@ pub(crate) fn dec_any_scheme_hex_ztier(zsecret: &ZSecret, obtext: &str) -> Resul
 /// If the most likely encoding fails, it falls back to trying other encodings.
 ///
 /// Detection logic:
-/// 1. If text contains '-', '_', or uppercase letters -> B64 (definitive)
-/// 2. Else if text contains non-hex lowercase letters (g-z) -> Try Base32, fallback to B64
-/// 3. Else -> Try Hex, fallback to Base32, then B64
+/// 1. If text contains '-', '_', or mixed-case letters -> B64 (definitive)
+/// 2. Else if text contains uppercase letters -> Try uppercase Hex if they are
+///    all A-F, then B32, fallback to B64
+/// 3. Else if text contains non-hex lowercase letters (g-z) -> Try C32, fallback to B64
+/// 4. Else -> Try Hex, fallback to C32, then B64
 pub(crate) fn dec_any_format_ztier(zsecret: &ZSecret, obtext: &str) -> Result<String, Error> {
     // Check for B64 indicators:  '-', '_', or mixed case letters (definitive)
     if obtext.contains('-')
@@ -181,7 +183,13 @@ pub(crate) fn dec_any_format_ztier(zsecret: &ZSecret, obtext: &str) -> Result<St
 
     // Check for uppercase letters, indicating B32
     if obtext.chars().any(|c| c.is_ascii_uppercase()) {
-        // Try B32 first, fallback to B64 (no point trying hex)
+        // All uppercase letters within A-F: try uppercase hex first
+        if !obtext.chars().any(|c| c.is_ascii_uppercase() && c > 'F') {
+            if let Ok(result) = dec_any_scheme_ztier(zsecret, Encoding::HexUpper, obtext) {
+                return Ok(result);
+            }
+        }
+        // Try B32, fallback to B64 (no point trying lowercase hex)
         if let Ok(result) = dec_any_scheme_b32_ztier(zsecret, obtext) {
             return Ok(result);
         }
@@ -210,3 +218,35 @@ pub(crate) fn dec_any_format_ztier(zsecret: &ZSecret, obtext: &str) -> Result<St
     }
     dec_any_scheme_b64_ztier(zsecret, obtext)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::base32::{BASE32_CROCKFORD, BASE32_RFC};
+    use data_encoding::{BASE64URL_NOPAD, HEXLOWER};
+
+    #[test]
+    fn test_degenerate_payloads_error_without_panic() {
+        let zsecret = ZSecret::from_bytes(&[0u8; 32]).unwrap();
+        for len in 0..=SCHEME_MARKER_SIZE {
+            for byte in [0x00, 0x01, 0x7f, 0xff] {
+                let payload = vec![byte; len];
+                for (encoding, obtext) in [
+                    (Encoding::C32, BASE32_CROCKFORD.encode(&payload)),
+                    (Encoding::B32, BASE32_RFC.encode(&payload)),
+                    (Encoding::B64, BASE64URL_NOPAD.encode(&payload)),
+                    (Encoding::Hex, HEXLOWER.encode(&payload)),
+                ] {
+                    assert!(
+                        dec_any_scheme_ztier(&zsecret, encoding, &obtext).is_err(),
+                        "{}-byte payload {:?} in {:?} should fail",
+                        len,
+                        obtext,
+                        encoding
+                    );
+                    assert!(dec_any_format_ztier(&zsecret, &obtext).is_err());
+                }
+            }
+        }
+    }
+}
@@ -2,12 +2,7 @@
 
 #![cfg(feature = "ztier")]
 
-use crate::{
-    base32::{BASE32_CROCKFORD, BASE32_RFC},
-    error::Error,
-    Encoding, Format, Scheme,
-};
-use data_encoding::{BASE64URL_NOPAD, HEXLOWER};
+use crate::{error::Error, Format, Scheme};
 
 #[cfg(feature = "zmock")]
 use crate::encrypt_zmock1;
@@ -21,9 +16,7 @@ pub(crate) fn enc_to_format_ztier(
     format: Format,
     secret: &[u8; 32],
 ) -> Result<String, Error> {
-    if plaintext.is_empty() {
-        return Err(Error::EmptyPlaintext);
-    }
+    crate::error::check_plaintext_len(plaintext, format.scheme())?;
 
     // Encrypt using z-tier scheme-specific function
     let mut ciphertext: Vec<u8> = match format.scheme() {
@@ -43,10 +36,5 @@ pub(crate) fn enc_to_format_ztier(
     ciphertext.push(marker[1] ^ first_byte);
 
     // Encode
-    Ok(match format.encoding() {
-        Encoding::C32 => BASE32_CROCKFORD.encode(&ciphertext),
-        Encoding::B32 => BASE32_RFC.encode(&ciphertext),
-        Encoding::B64 => BASE64URL_NOPAD.encode(&ciphertext),
-        Encoding::Hex => HEXLOWER.encode(&ciphertext),
-    })
+    Ok(crate::encoding::encode(&ciphertext, format.encoding()))
 }
@@ -13,7 +13,7 @@ type Aes128CbcEnc = Encryptor<Aes128>;
 type Aes128CbcDec = Decryptor<Aes128>;
 
 /// Encrypt plaintext bytes using deterministic AES-CBC (zrbcx scheme).
-/// Returns raw ciphertext bytes **reversed** for prefix entropy maximization.
+/// The first cipher block is XORed with the last for prefix entropy.
 /// Not cryptographically secure - for obfuscation only.
 #[inline(always)]
 pub fn encrypt_zrbcx(secret: &[u8; 32], plaintext_bytes: &[u8]) -> Result<Vec<u8>, Error> {
@@ -54,14 +54,18 @@ pub fn encrypt_zrbcx(secret: &[u8; 32], plaintext_bytes: &[u8]) -> Result<Vec<u8
 }
 
 /// Decrypt ciphertext using deterministic AES-CBC (zrbcx scheme).
-/// Expects **reversed** ciphertext.  Returns plaintext bytes with padding removed.
+/// Expects ciphertext as returned by [`encrypt_zrbcx`], first block mixed with
+/// the last.  Returns plaintext bytes with padding removed.
 #[inline(always)]
 pub fn decrypt_zrbcx(secret: &[u8; 32], data: &[u8]) -> Result<Vec<u8>, Error> {
     // Decrypt with AES-128-CBC
     let len = data.len();
 
-    if len % AES_BLOCK_SIZE != 0 {
-        return Err(Error::InvalidBlockLength);
+    if len == 0 || len % AES_BLOCK_SIZE != 0 {
+        return Err(Error::MalformedCiphertext {
+            scheme: crate::Scheme::Zrbcx,
+            len,
+        });
     }
 
     let mut buffer = data.to_vec();
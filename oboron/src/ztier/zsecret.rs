@@ -70,6 +70,12 @@ impl ZSecret {
         hex::encode(&self.secret)
     }
 
+    /// Short, non-secret identifier of the secret (8 lowercase hex characters).
+    #[inline]
+    pub(crate) fn fingerprint(&self) -> String {
+        crate::masterkey::fingerprint(&self.secret)
+    }
+
     /// Get the secret as raw bytes. (internal)
     #[inline(always)]
     pub(crate) fn master_secret(&self) -> &[u8; 32] {
@@ -120,3 +120,34 @@ fn test_autodetect_all_formats() {
         assert_eq!(original, pt2, "Failed for format upbc");
     }
 }
+
+#[test]
+#[cfg(all(feature = "aasv", feature = "aags"))]
+fn test_autodec_within_allowlist() {
+    use oboron::{AASV_B64, AASV_C32};
+
+    let omb = Omnib::new_keyless().unwrap();
+    let allowed = [AASV_C32, AASV_B64];
+
+    for format in allowed {
+        let ot = omb.enc("allowed", format).unwrap();
+        assert_eq!(omb.autodec_within(&ot, &allowed).unwrap(), "allowed");
+    }
+
+    // Other schemes and encodings are rejected, though autodec accepts them
+    for format in ["aags.c32", "aags.b64", "aasv.hex"] {
+        let ot = omb.enc("unexpected", format).unwrap();
+        assert_eq!(omb.autodec(&ot).unwrap(), "unexpected");
+        assert!(
+            omb.autodec_within(&ot, &allowed).is_err(),
+            "{} accepted by allowlist",
+            format
+        );
+    }
+
+    let ot = omb.enc("allowed", AASV_C32).unwrap();
+    assert_eq!(
+        omb.autodec_within(&ot, &[]),
+        Err(oboron::Error::InvalidFormat)
+    );
+}
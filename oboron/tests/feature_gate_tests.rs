@@ -136,6 +136,69 @@ mod keyless_disabled {
     }
 }
 
+// Test that known-but-disabled schemes are reported as such
+#[cfg(all(feature = "aasv", not(feature = "aags")))]
+mod aags_disabled {
+    use oboron::*;
+
+    #[test]
+    fn test_set_format_reports_disabled_scheme() {
+        let mut ob = Ob::new("aasv.c32", &generate_key()).unwrap();
+        let err = ob.set_format("aags.c32").unwrap_err();
+        assert_eq!(
+            err,
+            Error::SchemeNotEnabled {
+                scheme: "aags",
+                feature: "aags"
+            }
+        );
+        assert!(err.to_string().contains("`aags` feature"));
+
+        // Unknown formats are still InvalidFormat
+        assert_eq!(ob.set_format("nope.c32"), Err(Error::InvalidFormat));
+    }
+}
+
+#[cfg(all(feature = "aasv", not(feature = "mock")))]
+mod mock_disabled {
+    use oboron::*;
+
+    #[test]
+    fn test_disabled_mock_scheme() {
+        assert_eq!(
+            Format::from_str("mock1.b64"),
+            Err(Error::SchemeNotEnabled {
+                scheme: "mock1",
+                feature: "mock"
+            })
+        );
+        assert!(!supported_schemes().is_empty());
+    }
+}
+
+// Test that legacy-shaped obtext names the missing feature
+#[cfg(all(feature = "aasv", feature = "keyless", not(feature = "legacy")))]
+mod legacy_disabled {
+    use oboron::*;
+
+    #[test]
+    fn test_autodec_reports_disabled_legacy() {
+        let omb = Omnib::new_keyless().unwrap();
+        // Keyless legacy obtext of "hello"
+        let err = omb.autodec("yveb6bzj6daq6zmquekykwr65g").unwrap_err();
+        assert_eq!(err, Error::LegacySchemeNotEnabled);
+        assert!(err.to_string().contains("`legacy` feature"));
+
+        // Other failures are reported as before
+        assert_ne!(
+            omb.autodec("not obtext"),
+            Err(Error::LegacySchemeNotEnabled)
+        );
+        let ot = omb.enc("hello", "aasv.c32").unwrap();
+        assert_eq!(omb.autodec(&ot).unwrap(), "hello");
+    }
+}
+
 // Cross-feature validation tests
 #[cfg(all(feature = "bytes-keys", feature = "hex-keys"))]
 mod combined_features {
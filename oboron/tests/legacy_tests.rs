@@ -2,8 +2,10 @@
 //!
 //! These tests are isolated from main tests to ensure legacy functionality works independently.
 //!
-//! **Note**: Omnib and convenience functions (enc_keyless, dec_keyless) will NOT work
-//! with legacy scheme. Use the Legacy struct directly instead:
+//! **Note**: `Omnib::autodec` reads legacy obtexts (see `test_omnib_autodec_legacy`),
+//! as do `Omnib::dec` and `Omnib::autodec_within` given the `LEGACY` format, but
+//! `Omnib::enc` and the convenience functions (enc_keyless, dec_keyless) do not
+//! handle the legacy scheme. Use the Legacy struct directly instead:
 //!
 //! ```ignore
 //! use oboron::ztier::Legacy;
@@ -217,6 +219,91 @@ fn test_legacy_lowercase_obtext() {
     // Legacy should produce lowercase RFC base32 (a-z, 2-7) obtext
     let ob = Legacy::new_keyless().unwrap();
     let ot = ob.enc("test").unwrap();
-    assert!(ot.chars().all(|c| matches!(c, 'a'..='z' | '2'..='7')),
-        "Legacy obtext should be lowercase RFC base32 (a-z, 2-7): {}", ot);
+    assert!(
+        ot.chars().all(|c| matches!(c, 'a'..='z' | '2'..='7')),
+        "Legacy obtext should be lowercase RFC base32 (a-z, 2-7): {}",
+        ot
+    );
+}
+
+#[cfg(all(feature = "aasv", feature = "keyless", feature = "bytes-keys"))]
+#[test]
+fn test_omnib_autodec_legacy() {
+    use oboron::Omnib;
+
+    let omb = Omnib::new_keyless().unwrap();
+    let legacy = Legacy::new_keyless().unwrap();
+    for pt in [
+        "a",
+        "hello world",
+        "exactly sixteen!",
+        "a much longer legacy plaintext",
+    ] {
+        let ot = legacy.enc(pt).unwrap();
+        assert_eq!(omb.autodec(&ot).unwrap(), pt);
+    }
+
+    // Secure-scheme obtexts are unaffected
+    let ot = omb.enc("modern", "aasv.c32").unwrap();
+    assert_eq!(omb.autodec(&ot).unwrap(), "modern");
+
+    // The legacy secret is the first half of the key
+    let key = oboron::generate_key_bytes();
+    let omb = Omnib::from_bytes(&key).unwrap();
+    let secret: [u8; 32] = key[..32].try_into().unwrap();
+    let ot = Legacy::from_bytes(&secret).unwrap().enc("keyed").unwrap();
+    assert_eq!(omb.autodec(&ot).unwrap(), "keyed");
+
+    // ...so another key does not decode it
+    let other = Omnib::from_bytes(&oboron::generate_key_bytes()).unwrap();
+    assert!(other.autodec(&ot).is_err());
+}
+
+#[cfg(all(feature = "aasv", feature = "keyless"))]
+#[test]
+fn test_omnib_autodec_tries_legacy_in_candidate_order() {
+    use oboron::{Encoding, Error, Omnib, Scheme};
+    use std::num::NonZeroUsize;
+
+    let legacy = Legacy::new_keyless().unwrap();
+    let ot = legacy.enc("migrated").unwrap();
+
+    // Legacy comes before the secure-scheme encodings: one attempt suffices
+    let bounded = Omnib::new_keyless()
+        .unwrap()
+        .with_max_autodec_attempts(NonZeroUsize::MIN);
+    assert_eq!(bounded.autodec(&ot).unwrap(), "migrated");
+
+    // ...unless another encoding is preferred, which keeps its place
+    let preferred = bounded.with_preferred_encoding(Encoding::C32);
+    assert!(matches!(
+        preferred.autodec(&ot),
+        Err(Error::AutodetectExhausted { attempts: 1, .. })
+    ));
+
+    // Failed legacy attempts are reported in order
+    let omb = Omnib::new_keyless().unwrap();
+    let garbage = "a".repeat(26);
+    let report = omb.autodec_verbose(&garbage).unwrap_err();
+    assert_eq!(report.attempts()[0].scheme, Some(Scheme::Legacy));
+    assert_eq!(Error::from(report), omb.autodec(&garbage).unwrap_err());
+
+    // Obtext without the legacy shape is never tried as legacy
+    let report = omb.autodec_verbose("NotAnObtextAtAll").unwrap_err();
+    assert!(report
+        .attempts()
+        .iter()
+        .all(|a| a.scheme != Some(Scheme::Legacy)));
+
+    // Legacy is a candidate format for autodec_within
+    assert_eq!(
+        omb.autodec_within(&ot, &[oboron::LEGACY]).unwrap(),
+        "migrated"
+    );
+    assert_eq!(
+        omb.autodec_within(&ot, &[oboron::AASV_C32, oboron::LEGACY])
+            .unwrap(),
+        "migrated"
+    );
+    assert!(omb.autodec_within(&ot, &[oboron::AASV_C32]).is_err());
 }
@@ -0,0 +1,145 @@
+#![cfg(all(feature = "sqlx", feature = "aasv"))]
+
+use oboron::sql::{CodecContext, Encrypted};
+use oboron::{Ob, Obtext, ObtextCodec};
+use once_cell::sync::OnceCell;
+use sqlx::sqlite::SqlitePool;
+use sqlx::Row;
+
+static CODEC: OnceCell<Ob> = OnceCell::new();
+
+struct TestCodec;
+
+impl CodecContext for TestCodec {
+    type Codec = Ob;
+
+    fn codec() -> Option<&'static Ob> {
+        Some(CODEC.get_or_init(|| Ob::new_keyless("aasv.b64").unwrap()))
+    }
+}
+
+struct Unset;
+
+impl CodecContext for Unset {
+    type Codec = Ob;
+
+    fn codec() -> Option<&'static Ob> {
+        None
+    }
+}
+
+async fn memory_pool() -> SqlitePool {
+    let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+    sqlx::query("CREATE TABLE users (id INTEGER PRIMARY KEY, email TEXT NOT NULL)")
+        .execute(&pool)
+        .await
+        .unwrap();
+    pool
+}
+
+#[tokio::test]
+async fn test_encrypted_roundtrip_through_sqlite() {
+    let pool = memory_pool().await;
+
+    let email = Encrypted::<TestCodec>::new("alice@example.com");
+    sqlx::query("INSERT INTO users (id, email) VALUES (1, ?)")
+        .bind(&email)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    // The column holds the obtext, not the plaintext
+    let stored: String = sqlx::query_scalar("SELECT email FROM users WHERE id = 1")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_ne!(stored, "alice@example.com");
+    assert_eq!(
+        TestCodec::codec().unwrap().dec(&stored).unwrap(),
+        "alice@example.com"
+    );
+
+    // Reading decrypts transparently
+    let row = sqlx::query("SELECT email FROM users WHERE id = 1")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    let read: Encrypted<TestCodec> = row.get("email");
+    assert_eq!(read, email);
+}
+
+#[tokio::test]
+async fn test_deterministic_lookup_by_encrypted_value() {
+    let pool = memory_pool().await;
+
+    for (id, email) in [(1, "alice@example.com"), (2, "bob@example.com")] {
+        sqlx::query("INSERT INTO users (id, email) VALUES (?, ?)")
+            .bind(id)
+            .bind(Encrypted::<TestCodec>::new(email))
+            .execute(&pool)
+            .await
+            .unwrap();
+    }
+
+    // aasv is deterministic, so equality queries on the encrypted column work
+    let id: i64 = sqlx::query_scalar("SELECT id FROM users WHERE email = ?")
+        .bind(Encrypted::<TestCodec>::new("bob@example.com"))
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(id, 2);
+}
+
+#[tokio::test]
+async fn test_obtext_stored_verbatim() {
+    let pool = memory_pool().await;
+    let ot: Obtext = TestCodec::codec()
+        .unwrap()
+        .enc_typed("carol@example.com".into())
+        .unwrap();
+
+    sqlx::query("INSERT INTO users (id, email) VALUES (1, ?)")
+        .bind(&ot)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let read: Obtext = sqlx::query_scalar("SELECT email FROM users WHERE id = 1")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(read, ot);
+
+    let decrypted: Encrypted<TestCodec> =
+        sqlx::query_scalar("SELECT email FROM users WHERE id = 1")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(&*decrypted, "carol@example.com");
+}
+
+#[tokio::test]
+async fn test_uninitialized_context_fails() {
+    let pool = memory_pool().await;
+
+    let result = sqlx::query("INSERT INTO users (id, email) VALUES (1, ?)")
+        .bind(Encrypted::<Unset>::new("dave@example.com"))
+        .execute(&pool)
+        .await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn test_invalid_obtext_fails_to_decode() {
+    let pool = memory_pool().await;
+    sqlx::query("INSERT INTO users (id, email) VALUES (1, 'not-an-obtext')")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let result: Result<Encrypted<TestCodec>, _> =
+        sqlx::query_scalar("SELECT email FROM users WHERE id = 1")
+            .fetch_one(&pool)
+            .await;
+    assert!(result.is_err());
+}
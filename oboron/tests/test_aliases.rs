@@ -0,0 +1,88 @@
+//! Tests for scheme and encoding aliases accepted when parsing.
+
+#![cfg(all(feature = "aasv", feature = "apsv", feature = "keyless"))]
+
+use oboron::{Encoding, Error, Format, Omnib, Scheme};
+
+#[test]
+fn test_scheme_aliases() {
+    assert_eq!("ob32".parse::<Scheme>(), Ok(Scheme::Aasv));
+    assert_eq!("32".parse::<Scheme>(), Ok(Scheme::Aasv));
+    assert_eq!("OB32P".parse::<Scheme>(), Ok(Scheme::Apsv));
+    assert_eq!("32p".parse::<Scheme>(), Ok(Scheme::Apsv));
+
+    // Canonical names are kept for output
+    let scheme: Scheme = "ob32".parse().unwrap();
+    assert_eq!(scheme.to_string(), "aasv");
+    assert_eq!(Scheme::Aasv.aliases().collect::<Vec<_>>(), ["ob32", "32"]);
+}
+
+#[cfg(feature = "zrbcx")]
+#[test]
+fn test_scheme_alias_without_leading_zero() {
+    assert_eq!("ob1".parse::<Scheme>(), Ok(Scheme::Zrbcx));
+    assert_eq!("ob01".parse::<Scheme>(), Ok(Scheme::Zrbcx));
+}
+
+#[test]
+fn test_encoding_aliases() {
+    assert_eq!("b64url".parse::<Encoding>(), Ok(Encoding::B64));
+    assert_eq!("base64url".parse::<Encoding>(), Ok(Encoding::B64));
+    assert_eq!("crockford".parse::<Encoding>(), Ok(Encoding::C32));
+    assert_eq!("base32".parse::<Encoding>(), Ok(Encoding::B32));
+    assert_eq!(Encoding::B64.to_string(), "b64");
+}
+
+#[test]
+fn test_aliases_in_format_strings() {
+    let format = Format::from_str("ob32.b64url").unwrap();
+    assert_eq!(format, Format::new(Scheme::Aasv, Encoding::B64));
+    assert_eq!(format.to_string(), "aasv.b64");
+
+    let omb = Omnib::new_keyless().unwrap();
+    let ot = omb.enc("aliased", "32.base64url").unwrap();
+    assert_eq!(ot, omb.enc("aliased", "aasv.b64").unwrap());
+    assert_eq!(omb.dec(&ot, "ob32.b64url").unwrap(), "aliased");
+}
+
+#[test]
+fn test_bare_scheme_parses_to_default_format() {
+    let default = Format::new(Scheme::Aasv, Encoding::C32);
+    assert_eq!(Format::from_str("ob32"), Ok(default));
+    assert_eq!(Format::from_str("aasv"), Ok(default));
+    assert_eq!("32".parse::<Format>(), Ok(default));
+    assert_eq!(default.to_string(), "aasv.c32");
+
+    // An explicit encoding still wins
+    assert_eq!(
+        Format::from_str("ob32.b64"),
+        Ok(Format::new(Scheme::Aasv, Encoding::B64))
+    );
+
+    let omb = Omnib::new_keyless().unwrap();
+    let ot = omb.enc("bare", "ob32").unwrap();
+    assert_eq!(ot, omb.enc("bare", "aasv.c32").unwrap());
+    assert_eq!(omb.dec(&ot, "ob32").unwrap(), "bare");
+
+    // Unknown names are still rejected
+    assert_eq!(Format::from_str("ob33"), Err(Error::InvalidFormat));
+}
+
+#[test]
+fn test_unknown_tokens_rejected() {
+    for token in ["ob", "ob33", "33", "ob32x", "aasv32", ""] {
+        assert_eq!(
+            token.parse::<Scheme>(),
+            Err(Error::UnknownScheme),
+            "{token}"
+        );
+    }
+    for token in ["b64u", "base65", "url", ""] {
+        assert_eq!(
+            token.parse::<Encoding>(),
+            Err(Error::UnknownEncoding),
+            "{token}"
+        );
+    }
+    assert!(Format::from_str("ob33.b64url").is_err());
+}
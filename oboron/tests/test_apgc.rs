@@ -0,0 +1,96 @@
+//! Tests for the apgc scheme (standard AES-256-GCM, random nonce).
+
+#![cfg(feature = "apgc")]
+
+use oboron::{ApgcB32, ApgcB64, ApgcC32, ApgcHex, Error, ObtextCodec, Omnib, Scheme};
+
+#[test]
+fn test_apgc_roundtrip_all_encodings() {
+    let key = oboron::generate_key();
+    let plaintext = "Test apgc with different encodings";
+
+    let codecs: Vec<Box<dyn ObtextCodec>> = vec![
+        Box::new(ApgcC32::new(&key).unwrap()),
+        Box::new(ApgcB32::new(&key).unwrap()),
+        Box::new(ApgcB64::new(&key).unwrap()),
+        Box::new(ApgcHex::new(&key).unwrap()),
+    ];
+    for codec in codecs {
+        assert_eq!(codec.scheme(), Scheme::Apgc);
+        let ot = codec.enc(plaintext).unwrap();
+        assert_eq!(codec.dec(&ot).unwrap(), plaintext);
+    }
+}
+
+#[test]
+fn test_apgc_is_probabilistic() {
+    let ob = ApgcC32::from_bytes(&[0u8; 64]).unwrap();
+    let ot1 = ob.enc("Hello, World!").unwrap();
+    let ot2 = ob.enc("Hello, World!").unwrap();
+
+    assert_ne!(ot1, ot2, "each enc should use a fresh nonce");
+    assert_eq!(ob.dec(&ot1).unwrap(), "Hello, World!");
+    assert_eq!(ob.dec(&ot2).unwrap(), "Hello, World!");
+    assert!(!Scheme::Apgc.is_deterministic());
+}
+
+#[test]
+fn test_apgc_autodec() {
+    let omb = Omnib::new(&oboron::generate_key()).unwrap();
+    for format in ["apgc.c32", "apgc.b32", "apgc.b64", "apgc.hex"] {
+        let ot = omb.enc("autodetected", format).unwrap();
+        assert_eq!(omb.autodec(&ot).unwrap(), "autodetected");
+        assert_eq!(omb.dec(&ot, format).unwrap(), "autodetected");
+    }
+}
+
+#[test]
+fn test_apgc_key_depends_on_whole_master_key() {
+    let key = [7u8; 64];
+    let ot = ApgcHex::from_bytes(&key).unwrap().enc("key split").unwrap();
+
+    // The AES-GCM key is derived from all 64 bytes
+    for i in [0, 31, 32, 63] {
+        let mut other = key;
+        other[i] ^= 1;
+        let ob = ApgcHex::from_bytes(&other).unwrap();
+        assert_eq!(ob.dec(&ot), Err(Error::DecryptionFailed));
+    }
+}
+
+#[cfg(feature = "aasv")]
+#[test]
+fn test_apgc_and_aasv_keys_are_independent() {
+    use oboron::AasvHex;
+
+    // Two keys agreeing on bytes 0..32, the first aasv SIV key, give
+    // unrelated apgc keys: an apgc obtext does not decrypt under the other
+    let mut key = [1u8; 64];
+    let ot = ApgcHex::from_bytes(&key)
+        .unwrap()
+        .enc("independent")
+        .unwrap();
+    key[32..].fill(2);
+    assert_eq!(
+        ApgcHex::from_bytes(&key).unwrap().dec(&ot),
+        Err(Error::DecryptionFailed)
+    );
+
+    // And the same master key still serves both schemes
+    let aasv = AasvHex::from_bytes(&key).unwrap();
+    let apgc = ApgcHex::from_bytes(&key).unwrap();
+    assert_eq!(aasv.dec(&aasv.enc("both").unwrap()).unwrap(), "both");
+    assert_eq!(apgc.dec(&apgc.enc("both").unwrap()).unwrap(), "both");
+}
+
+#[test]
+fn test_apgc_rejects_tampering() {
+    let ob = ApgcHex::from_bytes(&[3u8; 64]).unwrap();
+    let ot = ob.enc("do not touch").unwrap();
+
+    // Change one ciphertext byte, past the 12-byte nonce
+    let mut tampered: Vec<char> = ot.chars().collect();
+    tampered[28] = if tampered[28] == '0' { '1' } else { '0' };
+    let tampered: String = tampered.into_iter().collect();
+    assert_eq!(ob.dec(&tampered), Err(Error::DecryptionFailed));
+}
@@ -0,0 +1,108 @@
+//! Tests for `Omnib::with_max_autodec_attempts`, bounding autodetection.
+
+#![cfg(all(feature = "aasv", feature = "keyless"))]
+
+use oboron::{Encoding, Error, Omnib};
+use std::num::NonZeroUsize;
+
+/// Mixed case with non-hex letters: tried as b64, b32, c32 and hex
+const UNMATCHED: &str = "NotAnObtextAtAll";
+
+fn attempts(n: usize) -> NonZeroUsize {
+    NonZeroUsize::new(n).unwrap()
+}
+
+#[test]
+fn test_unbounded_autodec_tries_every_candidate() {
+    let omb = Omnib::new_keyless().unwrap();
+    assert_eq!(omb.max_autodec_attempts(), None);
+    assert!(!matches!(
+        omb.autodec(UNMATCHED),
+        Err(Error::AutodetectExhausted { .. }) | Ok(_)
+    ));
+    // Not legacy-shaped, so legacy builds make no legacy attempt
+    let report = omb.autodec_verbose(UNMATCHED).unwrap_err();
+    let tried: Vec<Encoding> = report.attempts().iter().map(|a| a.encoding).collect();
+    assert_eq!(
+        tried,
+        [Encoding::B64, Encoding::B32, Encoding::C32, Encoding::Hex]
+    );
+}
+
+#[test]
+fn test_bounded_autodec_gives_up() {
+    let omb = Omnib::new_keyless()
+        .unwrap()
+        .with_max_autodec_attempts(attempts(2));
+    assert_eq!(omb.max_autodec_attempts(), Some(attempts(2)));
+    let err = omb.autodec(UNMATCHED).unwrap_err();
+    assert!(matches!(
+        err,
+        Error::AutodetectExhausted { attempts: 2, .. }
+    ));
+    assert_eq!(omb.autodec_bytes(UNMATCHED.as_bytes()), Err(err.clone()));
+
+    let report = omb.autodec_verbose(UNMATCHED).unwrap_err();
+    assert_eq!(report.error(), &err);
+    assert_eq!(report.attempts().len(), 2);
+    assert_eq!(report.attempts()[0].encoding, Encoding::B64);
+
+    // The last attempt's own error is kept
+    assert_eq!(err.root_cause(), &report.attempts()[1].error);
+    assert!(err
+        .to_string()
+        .ends_with(&report.attempts()[1].error.to_string()));
+}
+
+#[test]
+fn test_bounded_autodec_still_decodes() {
+    let omb = Omnib::new_keyless()
+        .unwrap()
+        .with_max_autodec_attempts(attempts(1));
+    for format in ["aasv.c32", "aasv.b32", "aasv.b64", "aasv.hex"] {
+        let ot = omb.enc("bounded", format).unwrap();
+        assert_eq!(omb.autodec(&ot).unwrap(), "bounded", "{}", format);
+    }
+
+    // A limit above the number of candidates changes nothing
+    let omb = Omnib::new_keyless()
+        .unwrap()
+        .with_max_autodec_attempts(attempts(10));
+    assert!(!matches!(
+        omb.autodec(UNMATCHED),
+        Err(Error::AutodetectExhausted { .. }) | Ok(_)
+    ));
+}
+
+#[test]
+fn test_preferred_encoding_is_tried_first() {
+    let omb = Omnib::new_keyless()
+        .unwrap()
+        .with_preferred_encoding(Encoding::Hex);
+    assert_eq!(omb.preferred_encoding(), Some(Encoding::Hex));
+
+    // Hex obtext decodes on the first attempt
+    let bounded = Omnib::new_keyless()
+        .unwrap()
+        .with_preferred_encoding(Encoding::Hex)
+        .with_max_autodec_attempts(attempts(1));
+    let ot = bounded.enc("preferred", "aasv.hex").unwrap();
+    assert_eq!(bounded.autodec(&ot).unwrap(), "preferred");
+
+    // The preferred encoding leads the heuristic order, without repeats
+    let report = omb.autodec_verbose(UNMATCHED).unwrap_err();
+    let tried: Vec<Encoding> = report.attempts().iter().map(|a| a.encoding).collect();
+    assert_eq!(
+        tried,
+        [Encoding::Hex, Encoding::B64, Encoding::B32, Encoding::C32]
+    );
+
+    // Other encodings are still detected
+    let ot = omb.enc("preferred", "aasv.b64").unwrap();
+    assert_eq!(omb.autodec(&ot).unwrap(), "preferred");
+    let bounded = bounded.with_preferred_encoding(Encoding::C32);
+    assert!(matches!(
+        bounded.autodec(UNMATCHED),
+        Err(Error::AutodetectExhausted { attempts: 1, .. })
+    ));
+}
@@ -0,0 +1,42 @@
+//! Tests for `autodec_bytes`, autodecoding obtext given as raw bytes.
+
+#![cfg(all(feature = "aasv", feature = "apsv", feature = "keyless"))]
+
+use oboron::{Error, Ob, Omnib};
+
+#[test]
+fn test_autodec_bytes_all_encodings() {
+    let omb = Omnib::new_keyless().unwrap();
+    let ob = Ob::new_keyless("aasv.c32").unwrap();
+    for format in ["aasv.c32", "aasv.b32", "aasv.b64", "aasv.hex", "apsv.b64"] {
+        let ot = omb.enc("from the wire", format).unwrap();
+        let bytes: Vec<u8> = ot.into_bytes();
+        assert_eq!(omb.autodec_bytes(&bytes).unwrap(), "from the wire");
+        assert_eq!(ob.autodec_bytes(&bytes).unwrap(), "from the wire");
+    }
+}
+
+#[test]
+fn test_autodec_bytes_rejects_non_alphabet_bytes() {
+    let omb = Omnib::new_keyless().unwrap();
+    let mut bytes = omb.enc("from the wire", "aasv.b64").unwrap().into_bytes();
+
+    // A non-ASCII byte is rejected before any decoding is attempted
+    bytes[3] = 0xc3;
+    assert_eq!(
+        omb.autodec_bytes(&bytes),
+        Err(Error::InvalidObtextByte {
+            byte: 0xc3,
+            offset: 3
+        })
+    );
+
+    // So is ASCII outside every alphabet, such as whitespace
+    assert_eq!(
+        omb.autodec_bytes(b"abc\n"),
+        Err(Error::InvalidObtextByte {
+            byte: b'\n',
+            offset: 3
+        })
+    );
+}
@@ -0,0 +1,54 @@
+//! Tests for `Omnib::autodec_verbose` failure reports.
+
+#![cfg(all(feature = "aasv", feature = "keyless"))]
+
+use oboron::{Encoding, Error, Omnib, Scheme};
+
+#[test]
+fn test_autodec_verbose_success_matches_autodec() {
+    let omb = Omnib::new_keyless().unwrap();
+    for format in ["aasv.c32", "aasv.b32", "aasv.b64", "aasv.hex"] {
+        let ot = omb.enc("verbose", format).unwrap();
+        assert_eq!(omb.autodec_verbose(&ot).unwrap(), "verbose");
+    }
+}
+
+#[test]
+fn test_autodec_verbose_reports_attempted_encodings() {
+    let omb = Omnib::new_keyless().unwrap();
+
+    // Lowercase hex digits only: tried as hex, then c32, then b64
+    let report = omb.autodec_verbose("0123abcd").unwrap_err();
+    let encodings: Vec<Encoding> = report.attempts().iter().map(|a| a.encoding).collect();
+    assert_eq!(encodings, [Encoding::Hex, Encoding::C32, Encoding::B64]);
+    assert_eq!(Some(report.error()), omb.autodec("0123abcd").err().as_ref());
+
+    let text = report.to_string();
+    for name in ["hex", "c32", "b64"] {
+        assert!(text.contains(name), "{:?} missing from {:?}", name, text);
+    }
+}
+
+#[test]
+fn test_autodec_verbose_names_scheme_of_tampered_obtext() {
+    let omb = Omnib::new_keyless().unwrap();
+    let other = Omnib::new(&oboron::generate_key()).unwrap();
+    let ot = other.enc("wrong key", "aasv.b64").unwrap();
+
+    let report = omb.autodec_verbose(&ot).unwrap_err();
+    let b64 = report
+        .attempts()
+        .iter()
+        .find(|a| a.encoding == Encoding::B64)
+        .unwrap();
+    assert_eq!(b64.scheme, Some(Scheme::Aasv));
+    assert_eq!(Error::from(report.clone()), *report.error());
+}
+
+#[test]
+fn test_autodec_verbose_rejects_oversized_input() {
+    let omb = Omnib::new_keyless().unwrap().with_max_input_len(8);
+    let report = omb.autodec_verbose("0123456789").unwrap_err();
+    assert!(report.attempts().is_empty());
+    assert!(matches!(report.error(), Error::InputTooLarge { .. }));
+}
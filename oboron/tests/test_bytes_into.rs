@@ -0,0 +1,42 @@
+//! Tests for `enc_bytes_into` / `dec_bytes_into`, the buffer-reusing bytes API.
+
+#![cfg(all(feature = "aasv", feature = "apsv", feature = "keyless"))]
+
+use oboron::{Error, Ob, ObtextCodec};
+
+#[test]
+fn test_dec_bytes_into_reuses_buffer() {
+    let ob = Ob::new_keyless("aasv.b64").unwrap();
+    let mut buf = Vec::with_capacity(64);
+    let capacity = buf.capacity();
+
+    for pt in ["first plaintext", "x", "a somewhat longer third one", "4"] {
+        let ot = ob.enc(pt).unwrap();
+        ob.dec_bytes_into(&ot, &mut buf).unwrap();
+        assert_eq!(buf, pt.as_bytes());
+    }
+    assert_eq!(buf.capacity(), capacity);
+
+    // Failed decrypts leave the buffer empty rather than stale
+    assert!(ob.dec_bytes_into("not an obtext", &mut buf).is_err());
+    assert!(buf.is_empty());
+}
+
+#[test]
+fn test_enc_bytes_into_reuses_buffer() {
+    let ob = Ob::new_keyless("apsv.c32").unwrap();
+    let mut ot = String::new();
+    let mut pt = Vec::new();
+
+    for data in [&b"alpha"[..], b"beta", b"gamma delta"] {
+        ob.enc_bytes_into(data, &mut ot).unwrap();
+        ob.dec_bytes_into(&ot, &mut pt).unwrap();
+        assert_eq!(pt, data);
+    }
+
+    assert_eq!(
+        ob.enc_bytes_into(&[0xff, 0xfe], &mut ot),
+        Err(Error::InvalidUtf8)
+    );
+    assert!(ot.is_empty());
+}
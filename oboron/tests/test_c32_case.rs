@@ -0,0 +1,54 @@
+//! Tests for case-insensitive Crockford base32 decoding, with the `i`, `l`
+//! and `o` substitutes of the spec.
+
+#![cfg(all(feature = "aasv", feature = "keyless"))]
+
+use oboron::encoding::{decode, encode};
+use oboron::{Encoding, Error, Ob, Omnib};
+
+/// A c32 obtext containing both `0` and `1`, so substitutes can be tested
+fn obtext(ob: &Ob) -> String {
+    (0..)
+        .map(|i| ob.enc(&format!("retyped {}", i)).unwrap())
+        .find(|ot| ot.contains('0') && ot.contains('1'))
+        .unwrap()
+}
+
+#[test]
+fn test_uppercase_and_substitutes_decode_to_same_payload() {
+    let ob = Ob::new_keyless("aasv.c32").unwrap();
+    let ot = obtext(&ob);
+    let payload = decode(&ot, Encoding::C32).unwrap();
+
+    let upper = ot.to_uppercase();
+    let substituted = ot.replace('0', "O").replacen('1', "I", 1).replace('1', "l");
+    for retyped in [&upper, &substituted, &substituted.to_uppercase()] {
+        assert_eq!(
+            decode(retyped, Encoding::C32).unwrap(),
+            payload,
+            "{}",
+            retyped
+        );
+        assert_eq!(ob.dec(retyped).unwrap(), ob.dec(&ot).unwrap());
+    }
+
+    // Omnib's encoding check accepts it too
+    let omb = Omnib::new_keyless().unwrap();
+    assert_eq!(omb.dec(&upper, "aasv.c32").unwrap(), ob.dec(&ot).unwrap());
+}
+
+#[test]
+fn test_encoding_stays_lowercase() {
+    let ob = Ob::new_keyless("aasv.c32").unwrap();
+    let ot = obtext(&ob);
+    assert_eq!(ot, ot.to_lowercase());
+
+    let payload = decode(&ot.to_uppercase(), Encoding::C32).unwrap();
+    assert_eq!(encode(&payload, Encoding::C32), ot);
+}
+
+#[test]
+fn test_u_is_still_rejected() {
+    assert_eq!(decode("0u", Encoding::C32), Err(Error::InvalidC32));
+    assert_eq!(decode("0U", Encoding::C32), Err(Error::InvalidC32));
+}
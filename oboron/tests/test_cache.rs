@@ -0,0 +1,63 @@
+//! Tests for `ObCache`, the per-format encoder cache.
+
+#![cfg(all(feature = "aasv", feature = "aags", feature = "keyless"))]
+
+use oboron::{Encoding, Error, Format, ObCache, Omnib, Scheme, AASV_B64};
+
+#[test]
+fn test_cache_reuses_encoder_per_format() {
+    let mut cache = ObCache::new_keyless().unwrap();
+    assert!(cache.is_empty());
+
+    let first: *const _ = cache.get("aasv.b64").unwrap();
+    let again: *const _ = cache.get(AASV_B64).unwrap();
+    assert!(std::ptr::eq(first, again));
+    assert_eq!(cache.len(), 1);
+
+    cache.get("aags.c32").unwrap();
+    cache.enc("hello", "aags.c32").unwrap();
+    assert_eq!(cache.len(), 2);
+    assert_eq!(
+        cache.get("aags.c32").unwrap().format(),
+        Format::new(Scheme::Aags, Encoding::C32)
+    );
+}
+
+#[test]
+fn test_cache_matches_omnib() {
+    let key = oboron::generate_key();
+    let mut cache = ObCache::new(&key).unwrap();
+    let omb = Omnib::new(&key).unwrap();
+
+    for format in ["aasv.c32", "aasv.b32", "aasv.b64", "aasv.hex", "aags.b64"] {
+        for _ in 0..2 {
+            let ot = cache.enc("cached", format).unwrap();
+            assert_eq!(ot, omb.enc("cached", format).unwrap());
+            assert_eq!(omb.dec(&ot, format).unwrap(), "cached");
+            assert_eq!(cache.dec(&ot, format).unwrap(), "cached");
+        }
+    }
+    assert_eq!(cache.len(), 5);
+    assert_eq!(cache.key(), key);
+}
+
+#[test]
+fn test_cache_errors() {
+    let mut cache = ObCache::new_keyless().unwrap();
+    assert_eq!(cache.enc("x", "nope.b64"), Err(Error::InvalidFormat));
+
+    // No typed codec for hexu; Omnib handles it
+    let hexu = Format::new(Scheme::Aasv, Encoding::HexUpper);
+    let err = cache.enc("x", "aasv.hexu").unwrap_err();
+    assert_eq!(err, Error::UnsupportedFormat { format: hexu });
+    assert!(err.to_string().contains("aasv.hexu"), "{}", err);
+    assert_eq!(
+        cache.dec("X", hexu),
+        Err(Error::UnsupportedFormat { format: hexu })
+    );
+    assert!(cache.is_empty());
+    let omb = Omnib::new_keyless().unwrap();
+    assert_eq!(omb.dec(&omb.enc("x", hexu).unwrap(), hexu).unwrap(), "x");
+    assert!(cache.dec("not an obtext", "aasv.b64").is_err());
+    assert!(ObCache::new("not a key").is_err());
+}
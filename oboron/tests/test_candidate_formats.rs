@@ -0,0 +1,43 @@
+//! Tests for `Omnib::candidate_formats`, listing every format an obtext
+//! decrypts under.
+
+#![cfg(all(feature = "aasv", feature = "mock"))]
+
+use oboron::{Encoding, Format, Omnib, Scheme, AASV_B64, AASV_C32, MOCK1_C32, MOCK1_HEX};
+
+#[test]
+fn test_aead_obtext_has_one_candidate() {
+    let omb = Omnib::new(&oboron::generate_key()).unwrap();
+    for format in [AASV_C32, AASV_B64] {
+        for i in 0..20 {
+            let ot = omb.enc(&format!("audit {}", i), format).unwrap();
+            assert_eq!(omb.candidate_formats(&ot), [format], "{}", ot);
+        }
+    }
+}
+
+#[test]
+fn test_mock_obtext_may_have_several_candidates() {
+    let omb = Omnib::new(&oboron::generate_key()).unwrap();
+    let ot = omb.enc("audit", MOCK1_C32).unwrap();
+    assert!(omb.candidate_formats(&ot).contains(&MOCK1_C32));
+
+    // Digits only: valid hex in either case, and mock1 does not authenticate
+    let ot = omb.enc("99999999", MOCK1_HEX).unwrap();
+    assert!(ot.bytes().all(|b| b.is_ascii_digit()), "{}", ot);
+    let hexu = Format::new(Scheme::Mock1, Encoding::HexUpper);
+    assert_eq!(omb.candidate_formats(&ot), [MOCK1_HEX, hexu]);
+    for format in omb.candidate_formats(&ot) {
+        assert_eq!(omb.dec(&ot, format).unwrap(), "99999999");
+    }
+}
+
+#[test]
+fn test_non_obtext_has_no_candidates() {
+    let omb = Omnib::new(&oboron::generate_key()).unwrap();
+    assert!(omb.candidate_formats("not obtext!").is_empty());
+
+    let omb = omb.with_max_input_len(4);
+    let ot = omb.enc("abc", AASV_C32).unwrap();
+    assert!(omb.candidate_formats(&ot).is_empty());
+}
@@ -0,0 +1,34 @@
+//! Tests for the `ObtextCodec::scheme_is` and `encoding_is` predicates.
+
+#![cfg(all(feature = "aasv", feature = "aags"))]
+
+use oboron::{AasvC32, Encoding, Ob, ObtextCodec, Scheme};
+
+/// Generic call site, as the predicates are meant to be used
+fn is_aasv_c32<C: ObtextCodec>(codec: &C) -> bool {
+    codec.scheme_is(Scheme::Aasv) && codec.encoding_is(Encoding::C32)
+}
+
+#[test]
+fn test_predicates_on_typed_codec() {
+    let ob = AasvC32::new(&oboron::generate_key()).unwrap();
+    assert!(ob.scheme_is(Scheme::Aasv));
+    assert!(!ob.scheme_is(Scheme::Aags));
+    assert!(ob.encoding_is(Encoding::C32));
+    assert!(!ob.encoding_is(Encoding::B64));
+    assert!(is_aasv_c32(&ob));
+}
+
+#[test]
+fn test_predicates_follow_format_changes() {
+    let mut ob = Ob::new("aasv.c32", &oboron::generate_key()).unwrap();
+    assert!(is_aasv_c32(&ob));
+
+    ob.set_encoding(Encoding::B64).unwrap();
+    assert!(ob.encoding_is(Encoding::B64));
+    assert!(!is_aasv_c32(&ob));
+
+    ob.set_scheme(Scheme::Aags).unwrap();
+    assert!(ob.scheme_is(Scheme::Aags));
+    assert!(!ob.scheme_is(Scheme::Aasv));
+}
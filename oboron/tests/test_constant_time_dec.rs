@@ -0,0 +1,63 @@
+//! Tests for `with_constant_time_dec`, uniform-time rejects on `dec`.
+//!
+//! Timing is not measured; these check that the padded path returns the
+//! same results as the regular one.
+
+#![cfg(all(feature = "aasv", feature = "apsv", feature = "keyless"))]
+
+use oboron::{Error, Ob, Omnib};
+
+/// Obtexts rejected at each step of `dec`, for an `aasv.b64` decoder
+fn rejects(omb: &Omnib) -> Vec<String> {
+    let valid = omb.enc("constant time", "aasv.b64").unwrap();
+    let mut tampered = valid.clone().into_bytes();
+    tampered[4] = if tampered[4] == b'A' { b'B' } else { b'A' };
+    vec![
+        "!!not base64!!".to_string(),                  // encoding
+        "AAAA".to_string(),                            // truncated payload
+        omb.enc("constant time", "apsv.b64").unwrap(), // scheme marker
+        String::from_utf8(tampered).unwrap(),          // authentication
+    ]
+}
+
+#[test]
+fn test_ob_constant_time_dec_matches_regular_dec() {
+    let ob = Ob::new_keyless("aasv.b64").unwrap();
+    let ct = Ob::new_keyless("aasv.b64")
+        .unwrap()
+        .with_constant_time_dec();
+    assert!(!ob.constant_time_dec());
+    assert!(ct.constant_time_dec());
+
+    let ot = ob.enc("constant time").unwrap();
+    assert_eq!(ct.dec(&ot).unwrap(), "constant time");
+
+    for obtext in rejects(&Omnib::new_keyless().unwrap()) {
+        let expected = ob.dec(&obtext);
+        assert!(expected.is_err(), "{}", obtext);
+        assert_eq!(ct.dec(&obtext), expected, "{}", obtext);
+    }
+}
+
+#[test]
+fn test_omnib_constant_time_dec_matches_regular_dec() {
+    let omb = Omnib::new_keyless().unwrap();
+    let ct = Omnib::new_keyless().unwrap().with_constant_time_dec();
+    assert!(ct.constant_time_dec());
+
+    let ot = omb.enc("constant time", "aasv.b64").unwrap();
+    assert_eq!(ct.dec(&ot, "aasv.b64").unwrap(), "constant time");
+
+    for obtext in rejects(&omb) {
+        let expected = omb.dec(&obtext, "aasv.b64");
+        assert!(expected.is_err(), "{}", obtext);
+        assert_eq!(ct.dec(&obtext, "aasv.b64"), expected, "{}", obtext);
+    }
+
+    // Encoding mismatches are still reported as such
+    let b64 = omb.enc("constant time", "aasv.b64").unwrap();
+    assert!(matches!(
+        ct.dec(&b64, "aasv.hex"),
+        Err(Error::EncodingMismatch { .. })
+    ));
+}
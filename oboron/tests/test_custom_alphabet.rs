@@ -0,0 +1,167 @@
+//! Tests for custom obtext alphabets (`Encoding::Custom`).
+
+#![cfg(all(feature = "aasv", feature = "apsv", feature = "keyless"))]
+
+use oboron::encoding::{decode, encode, Alphabet};
+use oboron::{Encoding, Error, Format, Ob, Omnib, Scheme};
+
+/// Digits and consonants, so codes never spell words
+const NO_VOWELS: &str = "0123456789bcdfghjkmnpqrstvwxyzBC";
+
+fn has_vowel(text: &str) -> bool {
+    text.contains(['a', 'e', 'i', 'o', 'u', 'A', 'E', 'I', 'O', 'U'])
+}
+
+#[test]
+fn test_no_vowel_alphabet_roundtrip() {
+    let key = oboron::generate_key();
+    let ob = Ob::new_custom_alphabet(Scheme::Aasv, NO_VOWELS, &key).unwrap();
+    let alphabet = Alphabet::new(NO_VOWELS).unwrap();
+    assert_eq!(ob.encoding(), Encoding::Custom(alphabet));
+
+    for plaintext in ["a", "https://example.com/a/long/path?q=1", "数据"] {
+        let code = ob.enc(plaintext).unwrap();
+        assert!(!has_vowel(&code), "{}", code);
+        assert!(code.bytes().all(|b| NO_VOWELS.as_bytes().contains(&b)));
+        assert_eq!(ob.dec(&code).unwrap(), plaintext);
+        assert_eq!(ob.autodec(&code).unwrap(), plaintext);
+        // Deterministic: the same URL always gets the same code
+        assert_eq!(ob.enc(plaintext).unwrap(), code);
+    }
+
+    // Probabilistic schemes work too
+    let ob = Ob::new_custom_alphabet(Scheme::Apsv, NO_VOWELS, &key).unwrap();
+    let code = ob.enc("hello").unwrap();
+    assert!(!has_vowel(&code));
+    assert_eq!(ob.dec(&code).unwrap(), "hello");
+}
+
+#[test]
+fn test_custom_alphabet_carries_same_payload() {
+    let ob = Ob::new_custom_alphabet(Scheme::Aasv, NO_VOWELS, &oboron::generate_key()).unwrap();
+    let code = ob.enc("payload").unwrap();
+    let payload = decode(&code, ob.encoding()).unwrap();
+    assert_eq!(code.len(), ob.encoding().encoded_len(payload.len()));
+    assert_eq!(encode(&payload, Encoding::C32).len(), code.len());
+
+    // The standard alphabets reproduce the built-in encodings
+    for (symbols, builtin) in [
+        ("0123456789abcdef", Encoding::Hex),
+        ("ABCDEFGHIJKLMNOPQRSTUVWXYZ234567", Encoding::B32),
+        (
+            "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_",
+            Encoding::B64,
+        ),
+    ] {
+        let encoding = Encoding::Custom(Alphabet::new(symbols).unwrap());
+        let text = encode(&payload, encoding);
+        assert_eq!(text, encode(&payload, builtin), "{}", builtin);
+        assert_eq!(decode(&text, encoding).unwrap(), payload);
+    }
+}
+
+#[test]
+fn test_custom_alphabet_needs_matching_instance() {
+    let key = oboron::generate_key();
+    let ob = Ob::new_custom_alphabet(Scheme::Aasv, NO_VOWELS, &key).unwrap();
+    let code = ob.enc("secret").unwrap();
+
+    // A different alphabet of the same radix decodes to the wrong payload
+    let other =
+        Ob::new_custom_alphabet(Scheme::Aasv, "bcdfghjkmnpqrstvwxyz0123456789BC", &key).unwrap();
+    assert!(other.dec(&code).is_err());
+
+    // Format autodetection does not know custom alphabets
+    assert!(Omnib::new(&key).unwrap().autodec(&code).is_err());
+
+    // Symbols outside the alphabet are rejected
+    assert!(ob.dec("aeiou").is_err());
+    assert_eq!(
+        decode("bcdA", Encoding::Custom(Alphabet::new(NO_VOWELS).unwrap())),
+        Err(Error::InvalidCustom)
+    );
+}
+
+#[test]
+fn test_invalid_alphabets() {
+    for symbols in [
+        "",
+        "0",
+        "0123456789",
+        "01234567890123456",
+        "abcd efg",
+        "aabb",
+    ] {
+        assert!(
+            matches!(Alphabet::new(symbols), Err(Error::InvalidAlphabet { .. })),
+            "{:?}",
+            symbols
+        );
+    }
+    assert!(Ob::new_custom_alphabet(Scheme::Aasv, "0123", &oboron::generate_key()).is_ok());
+    assert!(matches!(
+        Ob::new_custom_alphabet(Scheme::Aasv, "012", &oboron::generate_key()),
+        Err(Error::InvalidAlphabet { .. })
+    ));
+
+    // A format string must name the symbols
+    assert!("aasv.custom".parse::<Format>().is_err());
+    assert!("aasv.custom:012".parse::<Format>().is_err());
+    assert!(matches!(
+        "custom:012".parse::<Encoding>(),
+        Err(Error::InvalidAlphabet { .. })
+    ));
+}
+
+#[test]
+fn test_parsing_does_not_create_alphabets() {
+    // Valid, but not created with `Alphabet::new` in this process
+    let symbols = "zyxwvtsrqpnmkjhgfdcb9876543210CB";
+    let name = format!("aasv.custom:{}", symbols);
+    assert!(name.parse::<Format>().is_err());
+    assert_eq!(
+        format!("custom:{}", symbols).parse::<Encoding>(),
+        Err(Error::UnknownEncoding)
+    );
+
+    let alphabet = Alphabet::new(symbols).unwrap();
+    assert_eq!(
+        name.parse::<Format>(),
+        Ok(Format::new(Scheme::Aasv, Encoding::Custom(alphabet)))
+    );
+}
+
+#[test]
+fn test_custom_alphabet_format_string_roundtrip() {
+    let format = Format::new(
+        Scheme::Aasv,
+        Encoding::Custom(Alphabet::new(NO_VOWELS).unwrap()),
+    );
+    let name = format!("aasv.custom:{}", NO_VOWELS);
+    assert_eq!(format.to_string(), name);
+    assert_eq!(name.parse::<Format>(), Ok(format));
+    // The prefix is case-insensitive, the symbols are not
+    let upper = format!("aasv.CUSTOM:{}", NO_VOWELS);
+    assert_eq!(upper.parse::<Format>(), Ok(format));
+
+    // Obtext made from the parsed format decodes under the original
+    let key = oboron::generate_key();
+    let ob = Ob::new(name.as_str(), &key).unwrap();
+    let code = ob.enc("roundtrip").unwrap();
+    let omb = Omnib::new(&key).unwrap();
+    assert_eq!(omb.dec(&code, format).unwrap(), "roundtrip");
+}
+
+#[test]
+fn test_alphabet_from_runtime_string() {
+    // e.g. read from configuration
+    let symbols: String = NO_VOWELS.chars().rev().collect();
+    let alphabet = Alphabet::new(&symbols).unwrap();
+    assert_eq!(alphabet.as_str(), symbols);
+    assert_eq!(alphabet.radix(), 32);
+    assert_eq!(Alphabet::new(&symbols).unwrap(), alphabet);
+    assert_ne!(Alphabet::new(NO_VOWELS).unwrap(), alphabet);
+
+    let ob = Ob::new_custom_alphabet(Scheme::Aasv, &symbols, &oboron::generate_key()).unwrap();
+    assert_eq!(ob.dec(&ob.enc("runtime").unwrap()).unwrap(), "runtime");
+}
@@ -0,0 +1,139 @@
+//! Tests for application-defined schemes registered via `register_scheme`.
+//!
+//! The registry is process-wide, so each test registers its own scheme
+//! name and marker.
+
+#![cfg(all(feature = "custom-schemes", feature = "keyless"))]
+
+use oboron::{register_scheme, Encoding, Error, Format, Ob, Omnib, Scheme, SchemeImpl};
+
+/// Toy scheme: XOR with the key, byte-wise (NOT secure).
+struct Xor {
+    name: &'static str,
+    marker: [u8; 2],
+}
+
+impl SchemeImpl for Xor {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn marker(&self) -> [u8; 2] {
+        self.marker
+    }
+
+    fn is_deterministic(&self) -> bool {
+        true
+    }
+
+    fn encrypt(&self, key: &[u8; 64], plaintext: &[u8]) -> Result<Vec<u8>, Error> {
+        Ok(xor(key, plaintext))
+    }
+
+    fn decrypt(&self, key: &[u8; 64], data: &[u8]) -> Result<Vec<u8>, Error> {
+        Ok(xor(key, data))
+    }
+}
+
+fn xor(key: &[u8; 64], data: &[u8]) -> Vec<u8> {
+    data.iter()
+        .zip(key.iter().cycle())
+        .map(|(b, k)| b ^ k)
+        .collect()
+}
+
+fn register_xor(name: &'static str, marker: [u8; 2]) -> Scheme {
+    register_scheme(Box::new(Xor { name, marker })).unwrap()
+}
+
+#[test]
+fn test_custom_scheme_omnib_roundtrip() {
+    let scheme = register_xor("xor", [0x03, 0x4d]);
+    assert_eq!(scheme.as_str(), "xor");
+    assert!(scheme.is_deterministic());
+
+    let omb = Omnib::new_keyless().unwrap();
+    for encoding in ["c32", "b32", "b64", "hex"] {
+        let format = format!("xor.{}", encoding);
+        let ot = omb.enc("hello custom", format.as_str()).unwrap();
+        assert_eq!(omb.dec(&ot, format.as_str()).unwrap(), "hello custom");
+        assert_eq!(omb.autodec(&ot).unwrap(), "hello custom");
+    }
+}
+
+#[test]
+fn test_custom_scheme_parsing() {
+    let scheme = register_xor("xorparse", [0x03, 0x4e]);
+    assert_eq!("xorparse".parse::<Scheme>(), Ok(scheme));
+
+    let format: Format = "xorparse.b64".parse().unwrap();
+    assert_eq!(format, Format::new(scheme, Encoding::B64));
+    assert_eq!(format.to_string(), "xorparse.b64");
+
+    assert_eq!(
+        "xorparse.nope".parse::<Format>(),
+        Err(Error::UnknownEncoding)
+    );
+}
+
+#[test]
+fn test_custom_scheme_with_ob() {
+    register_xor("xorob", [0x03, 0x4f]);
+    let ob = Ob::new_keyless("xorob.c32").unwrap();
+    let ot = ob.enc("via ob").unwrap();
+    assert_eq!(ob.dec(&ot).unwrap(), "via ob");
+    assert_eq!(ob.autodec(&ot).unwrap(), "via ob");
+}
+
+#[cfg(feature = "aasv")]
+#[test]
+fn test_builtin_schemes_unaffected() {
+    register_xor("xorbuiltin", [0x03, 0x50]);
+    let omb = Omnib::new_keyless().unwrap();
+
+    let ot = omb.enc("built-in", "aasv.c32").unwrap();
+    assert_eq!(omb.autodec(&ot).unwrap(), "built-in");
+
+    // A built-in obtext fails to decode under the custom scheme's format
+    assert_eq!(
+        omb.dec(&ot, "xorbuiltin.c32"),
+        Err(Error::SchemeMismatch {
+            expected: "xorbuiltin".parse().unwrap(),
+            found: Scheme::Aasv,
+        })
+    );
+}
+
+#[test]
+fn test_register_conflicts() {
+    register_xor("xorconflict", [0x03, 0x51]);
+
+    let dup_name = register_scheme(Box::new(Xor {
+        name: "xorconflict",
+        marker: [0x03, 0x52],
+    }));
+    assert_eq!(
+        dup_name,
+        Err(Error::SchemeConflict {
+            scheme: "xorconflict"
+        })
+    );
+
+    let dup_marker = register_scheme(Box::new(Xor {
+        name: "xorconflict2",
+        marker: [0x03, 0x51],
+    }));
+    assert_eq!(
+        dup_marker,
+        Err(Error::SchemeConflict {
+            scheme: "xorconflict2"
+        })
+    );
+
+    // Built-in name, reserved even when its feature is disabled
+    let builtin = register_scheme(Box::new(Xor {
+        name: "zrbcx",
+        marker: [0x03, 0x53],
+    }));
+    assert_eq!(builtin, Err(Error::SchemeConflict { scheme: "zrbcx" }));
+}
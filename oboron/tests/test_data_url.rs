@@ -0,0 +1,85 @@
+//! Tests for `Ob::enc_data_url`, obtext embedded in a `data:` URL.
+
+#![cfg(all(feature = "aasv", feature = "apsv", feature = "keyless"))]
+
+use oboron::{Error, Ob, AASV_B64, APSV_B64};
+
+#[test]
+fn test_data_url_round_trip() {
+    for format in ["aasv.c32", "aasv.b32", "aasv.b64", "aasv.hex", "apsv.b64"] {
+        let ob = Ob::new_keyless(format).unwrap();
+        for mime in ["application/octet-stream", "text/plain;charset=utf-8", ""] {
+            let url = ob.enc_data_url("embedded", mime).unwrap();
+            let (header, obtext) = url.split_once(',').unwrap();
+
+            assert_eq!(header, format!("data:{};ob={}", mime, format));
+            assert_eq!(ob.dec(obtext).unwrap(), "embedded");
+            assert_eq!(ob.dec_data_url(&url).unwrap(), "embedded");
+        }
+    }
+}
+
+#[test]
+fn test_data_url_rejects_malformed() {
+    let ob = Ob::new_keyless("aasv.b64").unwrap();
+    let url = ob.enc_data_url("embedded", "text/plain").unwrap();
+    let obtext = url.split_once(',').unwrap().1;
+
+    for bad in [
+        obtext.to_string(),
+        format!("data:text/plain;{}", obtext),
+        format!("data:text/plain,{}", obtext),
+        format!("http:text/plain;ob=aasv.b64,{}", obtext),
+    ] {
+        assert_eq!(ob.dec_data_url(&bad), Err(Error::InvalidDataUrl), "{}", bad);
+    }
+    assert_eq!(
+        ob.dec_data_url(&format!("data:text/plain;ob=nope.b64,{}", obtext)),
+        Err(Error::InvalidFormat)
+    );
+    assert_eq!(
+        ob.dec_data_url(&format!("data:text/plain;ob=apsv.b64,{}", obtext)),
+        Err(Error::FormatMismatch {
+            expected: AASV_B64,
+            found: APSV_B64
+        })
+    );
+    assert_eq!(ob.enc_data_url("x", "a,b"), Err(Error::InvalidDataUrl));
+
+    // Only the name enc_data_url writes is accepted, not an alias of it
+    assert_eq!(
+        ob.dec_data_url(&format!("data:text/plain;ob=AASV.base64,{}", obtext)),
+        Err(Error::InvalidDataUrl)
+    );
+}
+
+#[test]
+fn test_data_url_rejects_mime_with_format_param() {
+    let ob = Ob::new_keyless("aasv.b64").unwrap();
+    for mime in ["text/plain;ob=apsv.b64", "text/plain; ob=aasv.b64"] {
+        assert_eq!(ob.enc_data_url("x", mime), Err(Error::InvalidDataUrl));
+    }
+    // "ob" in the media type or in another parameter's value is fine
+    for mime in ["application/ob=x", "text/plain;name=ob=x"] {
+        let url = ob.enc_data_url("x", mime).unwrap();
+        assert_eq!(ob.dec_data_url(&url).unwrap(), "x");
+    }
+}
+
+#[test]
+fn test_data_url_custom_alphabet_round_trip() {
+    let key = oboron::generate_key();
+    let alphabet = "0123456789bcdfghjkmnpqrstvwxyzBC";
+    let ob = Ob::new_custom_alphabet(oboron::Scheme::Aasv, alphabet, &key).unwrap();
+    let url = ob.enc_data_url("embedded", "text/plain").unwrap();
+    assert!(url.starts_with(&format!("data:text/plain;ob=aasv.custom:{},", alphabet)));
+    assert_eq!(ob.dec_data_url(&url).unwrap(), "embedded");
+
+    // An alphabet named only by the URL is rejected, not registered
+    let other = "zyxwvtsrqpnmkjhgfdcb9876543210BC";
+    let url = url.replace(alphabet, other);
+    assert_eq!(ob.dec_data_url(&url), Err(Error::InvalidFormat));
+    assert!(format!("custom:{}", other)
+        .parse::<oboron::Encoding>()
+        .is_err());
+}
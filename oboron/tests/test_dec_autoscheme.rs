@@ -0,0 +1,30 @@
+//! Tests for `Ob::dec_autoscheme`, decoding with a fixed encoding and any scheme.
+
+#![cfg(all(feature = "aasv", feature = "apsv", feature = "keyless"))]
+
+use oboron::Ob;
+
+#[test]
+fn test_dec_autoscheme_matches_dec_on_own_format() {
+    for format in ["aasv.c32", "aasv.b64", "apsv.hex"] {
+        let ob = Ob::new_keyless(format).unwrap();
+        let ot = ob.enc("same result").unwrap();
+        assert_eq!(ob.dec_autoscheme(&ot), ob.dec(&ot), "{}", format);
+        assert_eq!(ob.dec_autoscheme("not obtext").ok(), None);
+    }
+}
+
+#[test]
+fn test_dec_autoscheme_accepts_other_schemes_only() {
+    let ob = Ob::new_keyless("aasv.b64").unwrap();
+
+    // Same encoding, other scheme: dec is strict, dec_autoscheme is not
+    let ot = Ob::new_keyless("apsv.b64").unwrap().enc("cross").unwrap();
+    assert!(ob.dec(&ot).is_err());
+    assert_eq!(ob.dec_autoscheme(&ot).unwrap(), "cross");
+
+    // Other encoding: only autodec falls back to encoding detection
+    let ot = Ob::new_keyless("aasv.hex").unwrap().enc("cross").unwrap();
+    assert!(ob.dec_autoscheme(&ot).is_err());
+    assert_eq!(ob.autodec(&ot).unwrap(), "cross");
+}
@@ -0,0 +1,45 @@
+//! Tests for `Omnib::dec_known`, decoding with an out-of-band format.
+
+#![cfg(all(
+    feature = "aags",
+    feature = "aasv",
+    feature = "apsv",
+    feature = "keyless"
+))]
+
+use oboron::{AasvC32, ApsvB64, Encoding, Error, Omnib, Scheme};
+
+#[test]
+fn test_dec_known_matches_fixed_format_codec() {
+    let omb = Omnib::new_keyless().unwrap();
+
+    let aasv = AasvC32::new_keyless().unwrap();
+    let ot = aasv.enc("known").unwrap();
+    assert_eq!(
+        omb.dec_known(&ot, Scheme::Aasv, Encoding::C32),
+        aasv.dec(&ot)
+    );
+
+    let apsv = ApsvB64::new_keyless().unwrap();
+    let ot = apsv.enc("known").unwrap();
+    assert_eq!(
+        omb.dec_known(&ot, Scheme::Apsv, Encoding::B64).unwrap(),
+        apsv.dec(&ot).unwrap()
+    );
+}
+
+#[test]
+fn test_dec_known_rejects_other_format() {
+    let omb = Omnib::new_keyless().unwrap();
+    let ot = omb.enc("known", "aasv.c32").unwrap();
+
+    assert_eq!(
+        omb.dec_known(&ot, Scheme::Aags, Encoding::C32),
+        Err(Error::SchemeMismatch {
+            expected: Scheme::Aags,
+            found: Scheme::Aasv,
+        })
+    );
+    // No encoding autodetection either
+    assert!(omb.dec_known(&ot, Scheme::Aasv, Encoding::B64).is_err());
+}
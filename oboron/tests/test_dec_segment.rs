@@ -0,0 +1,40 @@
+//! Tests for `Omnib::dec_segment`, decoding obtext embedded in a token.
+
+#![cfg(all(feature = "aasv", feature = "apsv", feature = "keyless"))]
+
+use oboron::{Error, Omnib};
+
+#[test]
+fn test_dec_segment_middle_of_dotted_token() {
+    let omb = Omnib::new_keyless().unwrap();
+    for format in ["aasv.c32", "aasv.b32", "aasv.b64", "aasv.hex", "apsv.b64"] {
+        let ot = omb.enc("embedded", format).unwrap();
+        let token = format!("v1.{}.sig", ot);
+        assert_eq!(omb.dec_segment(&token, ".", 1).unwrap(), "embedded");
+    }
+}
+
+#[test]
+fn test_dec_segment_other_delimiters() {
+    let omb = Omnib::new_keyless().unwrap();
+    let ot = omb.enc("embedded", "aasv.b64").unwrap();
+
+    let token = format!("{}:tail", ot);
+    assert_eq!(omb.dec_segment(&token, ":", 0).unwrap(), "embedded");
+    let token = format!("a::b::{}", ot);
+    assert_eq!(omb.dec_segment(&token, "::", 2).unwrap(), "embedded");
+}
+
+#[test]
+fn test_dec_segment_errors() {
+    let omb = Omnib::new_keyless().unwrap();
+    let ot = omb.enc("embedded", "aasv.c32").unwrap();
+    let token = format!("v1.{}.sig", ot);
+
+    assert_eq!(
+        omb.dec_segment(&token, ".", 3),
+        Err(Error::MissingSegment { index: 3, count: 3 })
+    );
+    // The segment is present but is not obtext
+    assert!(omb.dec_segment(&token, ".", 0).is_err());
+}
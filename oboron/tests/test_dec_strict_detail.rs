@@ -0,0 +1,39 @@
+//! Tests for `ObtextCodec::dec_strict_detail`, dec plus the verified scheme.
+
+#![cfg(all(
+    feature = "aasv",
+    feature = "apsv",
+    feature = "upbc",
+    feature = "keyless"
+))]
+
+use oboron::{AasvB64, Ob, ObtextCodec, Scheme};
+
+#[test]
+fn test_dec_strict_detail_returns_instance_scheme() {
+    for format in ["aasv.c32", "apsv.b64", "upbc.hex"] {
+        let ob = Ob::new_keyless(format).unwrap();
+        let ot = ob.enc("audited").unwrap();
+        let (plaintext, scheme) = ob.dec_strict_detail(&ot).unwrap();
+        assert_eq!(plaintext, "audited");
+        assert_eq!(scheme, ob.scheme(), "{}", format);
+    }
+
+    let typed = AasvB64::new_keyless().unwrap();
+    let ot = typed.enc("audited").unwrap();
+    assert_eq!(
+        typed.dec_strict_detail(&ot),
+        Ok(("audited".to_string(), Scheme::Aasv))
+    );
+}
+
+#[test]
+fn test_dec_strict_detail_rejects_other_scheme() {
+    let ot = Ob::new_keyless("apsv.b64").unwrap().enc("audited").unwrap();
+    let ob = Ob::new_keyless("aasv.b64").unwrap();
+    assert!(ob.dec_strict_detail(&ot).is_err());
+    assert_eq!(
+        ob.dec_strict_detail("not obtext").err(),
+        ob.dec("not obtext").err()
+    );
+}
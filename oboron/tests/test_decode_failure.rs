@@ -0,0 +1,82 @@
+//! Tests for `Error::DecodeFailed`, the structured report of a failed dec.
+
+#![cfg(all(feature = "aasv", feature = "keyless"))]
+
+use oboron::{DecodeFailure, DecodeStage, Encoding, Error, Omnib};
+
+fn failure(result: Result<String, Error>) -> DecodeFailure {
+    match result {
+        Err(Error::DecodeFailed(failure)) => failure,
+        other => panic!("expected DecodeFailed, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_encoding_failure_is_decode_stage() {
+    let omb = Omnib::new_keyless().unwrap();
+    let report = failure(omb.dec_report("not obtext!", "aasv.c32"));
+
+    assert_eq!(report.stage, DecodeStage::Decode);
+    assert_eq!(report.input_len, 11);
+    assert_eq!(report.encodings, [Encoding::C32]);
+    assert_eq!(*report.cause, Error::InvalidC32);
+    assert_eq!(
+        report.to_string(),
+        format!(
+            "decode failed on 11-byte obtext (tried c32): {}",
+            Error::InvalidC32
+        )
+    );
+}
+
+#[test]
+fn test_crypto_failure_is_decrypt_stage() {
+    let omb = Omnib::new_keyless().unwrap();
+    let ot = omb.enc("tamper with me", "aasv.c32").unwrap();
+    let other = Omnib::new(&oboron::generate_key()).unwrap();
+    let report = failure(other.dec_report(&ot, "aasv.c32"));
+
+    assert_eq!(report.stage, DecodeStage::Decrypt);
+    assert_eq!(report.input_len, ot.len());
+    assert_eq!(report.encodings, [Encoding::C32]);
+    assert_eq!(*report.cause, Error::DecryptionFailed);
+    assert!(report.to_string().starts_with("decrypt failed on "));
+}
+
+#[test]
+fn test_autodec_lists_encodings_tried() {
+    let omb = Omnib::new_keyless().unwrap();
+    let ot = omb.enc("wrong key", "aasv.b64").unwrap();
+    let other = Omnib::new(&oboron::generate_key()).unwrap();
+    let err = other.autodec_report(&ot).unwrap_err();
+
+    let report = failure(Err(err.clone()));
+    assert!(report.encodings.contains(&Encoding::B64));
+    assert_eq!(err.root_cause(), &*report.cause);
+    assert_eq!(other.autodec(&ot).unwrap_err(), *report.cause);
+}
+
+#[test]
+fn test_plain_dec_paths_are_not_wrapped() {
+    let omb = Omnib::new_keyless().unwrap();
+    let ot = omb.enc("wrong key", "aasv.c32").unwrap();
+    let other = Omnib::new(&oboron::generate_key()).unwrap();
+
+    assert_eq!(other.dec(&ot, "aasv.c32"), Err(Error::DecryptionFailed));
+    assert!(!matches!(other.autodec(&ot), Err(Error::DecodeFailed(_))));
+    assert_eq!(
+        other.autodec_within(&ot, &[oboron::AASV_C32]),
+        Err(Error::DecryptionFailed)
+    );
+}
+
+#[test]
+fn test_other_errors_are_not_wrapped() {
+    let omb = Omnib::new_keyless().unwrap().with_max_input_len(4);
+    assert_eq!(
+        omb.dec_report("too long", "aasv.c32"),
+        Err(Error::InputTooLarge { len: 8, max: 4 })
+    );
+    assert_eq!(omb.dec_report("ab", "nope.c32"), Err(Error::InvalidFormat));
+    assert_eq!(Error::InvalidFormat.root_cause(), &Error::InvalidFormat);
+}
@@ -0,0 +1,72 @@
+//! Tests for `ObtextCodec::dedup_key`, an encoding-independent index key
+//! for deterministic schemes.
+
+#![cfg(all(feature = "aasv", feature = "keyless"))]
+
+use oboron::{AasvB64, AasvC32, Error, Ob, ObtextCodec};
+
+#[test]
+fn test_dedup_key_shared_across_encodings() {
+    let c32 = Ob::new_keyless("aasv.c32").unwrap();
+    let b64 = Ob::new_keyless("aasv.b64").unwrap();
+    assert_ne!(c32.enc("secret").unwrap(), b64.enc("secret").unwrap());
+
+    let key = c32.dedup_key("secret").unwrap();
+    assert_eq!(key.len(), 64);
+    assert_eq!(b64.dedup_key("secret").unwrap(), key);
+    for encoding in ["b32", "hex", "hexu"] {
+        let ob = Ob::new_keyless(format!("aasv.{}", encoding).as_str()).unwrap();
+        assert_eq!(ob.dedup_key("secret").unwrap(), key);
+    }
+
+    // Fixed-format codecs agree
+    assert_eq!(
+        AasvC32::new_keyless().unwrap().dedup_key("secret"),
+        Ok(key.clone())
+    );
+    assert_eq!(
+        AasvB64::new_keyless().unwrap().dedup_key("secret"),
+        Ok(key.clone())
+    );
+
+    // The key is not the plaintext hash: a different key gives a different dedup key
+    let other = Ob::new("aasv.c32", &oboron::generate_key()).unwrap();
+    assert_ne!(other.dedup_key("secret").unwrap(), key);
+    assert_ne!(c32.dedup_key("secret2").unwrap(), key);
+}
+
+#[cfg(feature = "apsv")]
+#[test]
+fn test_dedup_key_rejects_probabilistic() {
+    let ob = Ob::new_keyless("apsv.c32").unwrap();
+    assert_eq!(
+        ob.dedup_key("secret"),
+        Err(Error::ProbabilisticScheme { scheme: "apsv" })
+    );
+}
+
+#[test]
+fn test_dedup_key_empty_plaintext() {
+    let ob = Ob::new_keyless("aasv.c32").unwrap();
+    assert_eq!(
+        ob.dedup_key(""),
+        Err(Error::PlaintextTooShort { len: 0, min: 1 })
+    );
+}
+
+#[cfg(feature = "legacy")]
+#[test]
+fn test_dedup_key_legacy() {
+    use oboron::ztier::{Legacy, Obz};
+
+    let legacy = Legacy::new_keyless().unwrap();
+    let key = legacy.dedup_key("secret").unwrap();
+    assert_eq!(legacy.dedup_key("secret").unwrap(), key);
+    assert_eq!(
+        Obz::new_keyless("legacy")
+            .unwrap()
+            .dedup_key("secret")
+            .unwrap(),
+        key
+    );
+}
@@ -0,0 +1,67 @@
+//! Tests for key-free format detection via `detect_format`.
+
+#![cfg(feature = "keyless")]
+
+use oboron::{detect_format, supported_schemes, Encoding, Format, Ob, ObtextCodec, Scheme};
+
+fn codec(format: Format) -> Option<Box<dyn ObtextCodec>> {
+    Some(match format.scheme() {
+        #[cfg(feature = "legacy")]
+        Scheme::Legacy => return None,
+        #[cfg(feature = "zrbcx")]
+        Scheme::Zrbcx => Box::new(oboron::ztier::Obz::new_keyless(format).unwrap()),
+        #[cfg(feature = "zmock")]
+        Scheme::Zmock1 => Box::new(oboron::ztier::Obz::new_keyless(format).unwrap()),
+        _ => Box::new(Ob::new_keyless(format).unwrap()),
+    })
+}
+
+#[test]
+fn test_detect_every_format() {
+    for &scheme in supported_schemes() {
+        for encoding in Encoding::ALL {
+            let format = Format::new(scheme, encoding);
+            let Some(codec) = codec(format) else {
+                continue;
+            };
+            for plaintext in ["a", "hello world", "0123456789abcdef0123456789abcdef"] {
+                let ot = codec.enc(plaintext).unwrap();
+                let expected = match encoding {
+                    // All digits: hexu obtext is also hex obtext, detected as such
+                    Encoding::HexUpper if !ot.bytes().any(|b| b.is_ascii_alphabetic()) => {
+                        Format::new(scheme, Encoding::Hex)
+                    }
+                    _ => format,
+                };
+                assert_eq!(detect_format(&ot), Some(expected), "{} {:?}", format, ot);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_detect_rejects_non_obtext() {
+    assert_eq!(detect_format(""), None);
+    assert_eq!(detect_format("not obtext!"), None);
+    assert_eq!(detect_format("00"), None);
+}
+
+#[cfg(feature = "aasv")]
+#[test]
+fn test_detect_upper_case_hex() {
+    let ot = oboron::enc_keyless("shouted", "aasv.hex").unwrap();
+    let upper = ot.to_uppercase();
+    assert_ne!(upper, ot);
+    let hexu = Format::new(oboron::AASV_HEX.scheme(), Encoding::HexUpper);
+    assert_eq!(detect_format(&upper), Some(hexu));
+    assert_eq!(detect_format(&ot), Some(oboron::AASV_HEX));
+}
+
+#[cfg(feature = "aasv")]
+#[test]
+fn test_detect_after_transcode() {
+    let ot = oboron::enc_keyless("transcoded", "aasv.c32").unwrap();
+    let b64 = oboron::encoding::transcode(&ot, Encoding::C32, Encoding::B64).unwrap();
+    assert_eq!(detect_format(&b64), Some(oboron::AASV_B64));
+    assert_eq!(oboron::dec_keyless(&b64, "aasv.b64").unwrap(), "transcoded");
+}
@@ -0,0 +1,74 @@
+//! Every enabled scheme rejects too-short plaintext with the same error.
+
+#![cfg(feature = "keyless")]
+
+use oboron::{supported_schemes, Encoding, Error, Format, Ob, ObtextCodec, Omnib, Scheme};
+
+fn codec(format: Format) -> Box<dyn ObtextCodec> {
+    match format.scheme() {
+        #[cfg(feature = "legacy")]
+        Scheme::Legacy => Box::new(oboron::ztier::Legacy::new_keyless().unwrap()),
+        #[cfg(feature = "zrbcx")]
+        Scheme::Zrbcx => Box::new(oboron::ztier::Obz::new_keyless(format).unwrap()),
+        #[cfg(feature = "zmock")]
+        Scheme::Zmock1 => Box::new(oboron::ztier::Obz::new_keyless(format).unwrap()),
+        _ => Box::new(Ob::new_keyless(format).unwrap()),
+    }
+}
+
+#[test]
+fn test_every_scheme_rejects_empty_plaintext() {
+    for &scheme in supported_schemes() {
+        assert_eq!(scheme.min_plaintext_len(), 1, "{}", scheme);
+        let codec = codec(Format::new(scheme, Encoding::C32));
+        assert_eq!(
+            codec.enc(""),
+            Err(Error::PlaintextTooShort { len: 0, min: 1 }),
+            "{}",
+            scheme
+        );
+
+        // The minimum itself is accepted
+        let pt = "x".repeat(scheme.min_plaintext_len());
+        let ot = codec.enc(&pt).unwrap();
+        assert_eq!(codec.dec(&ot).unwrap(), pt, "{}", scheme);
+    }
+}
+
+#[test]
+fn test_runtime_interfaces_reject_empty_plaintext() {
+    let omb = Omnib::new_keyless().unwrap();
+    for &scheme in supported_schemes() {
+        #[cfg(feature = "legacy")]
+        if scheme == Scheme::Legacy {
+            continue;
+        }
+        let format = Format::new(scheme, Encoding::B64);
+        if !scheme.is_ztier() {
+            assert_eq!(
+                omb.enc("", format),
+                Err(Error::PlaintextTooShort { len: 0, min: 1 })
+            );
+        }
+        assert_eq!(
+            oboron::enc_keyless("", &format.to_string()),
+            Err(Error::PlaintextTooShort { len: 0, min: 1 }),
+            "{}",
+            format
+        );
+    }
+}
+
+#[cfg(feature = "mock")]
+#[test]
+fn test_fixed_format_codecs_reject_empty_plaintext() {
+    let key = oboron::generate_key();
+    assert_eq!(
+        oboron::Mock1C32::new(&key).unwrap().enc(""),
+        Err(Error::PlaintextTooShort { len: 0, min: 1 })
+    );
+    assert_eq!(
+        oboron::Mock2Hex::new(&key).unwrap().enc(""),
+        Err(Error::PlaintextTooShort { len: 0, min: 1 })
+    );
+}
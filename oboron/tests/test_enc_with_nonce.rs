@@ -0,0 +1,77 @@
+//! Tests for `enc_with_nonce` on the probabilistic typed codecs.
+
+#![cfg(all(
+    feature = "apgs",
+    feature = "apgc",
+    feature = "apsv",
+    feature = "keyless"
+))]
+
+use oboron::{ApgcB64, ApgsC32, ApsvHex, Encoding, Error, Omnib};
+
+#[test]
+fn test_enc_with_nonce_is_reproducible() {
+    let nonce12 = [7u8; 12];
+    let nonce16 = [7u8; 16];
+
+    let apgs = ApgsC32::new_keyless().unwrap();
+    let ot = apgs.enc_with_nonce("counter", &nonce12).unwrap();
+    assert_eq!(ot, apgs.enc_with_nonce("counter", &nonce12).unwrap());
+    assert_ne!(ot, apgs.enc_with_nonce("counter", &[8u8; 12]).unwrap());
+    assert_eq!(apgs.dec(&ot).unwrap(), "counter");
+
+    let apgc = ApgcB64::new_keyless().unwrap();
+    let ot = apgc.enc_with_nonce("counter", &nonce12).unwrap();
+    assert_eq!(ot, apgc.enc_with_nonce("counter", &nonce12).unwrap());
+    assert_eq!(apgc.dec(&ot).unwrap(), "counter");
+
+    let apsv = ApsvHex::new_keyless().unwrap();
+    let ot = apsv.enc_with_nonce("counter", &nonce16).unwrap();
+    assert_eq!(ot, apsv.enc_with_nonce("counter", &nonce16).unwrap());
+    assert_eq!(apsv.dec(&ot).unwrap(), "counter");
+    assert_eq!(
+        Omnib::new_keyless().unwrap().autodec(&ot).unwrap(),
+        "counter"
+    );
+}
+
+#[test]
+fn test_enc_with_nonce_carries_the_nonce() {
+    let nonce: Vec<u8> = (0..16).collect();
+    let apsv = ApsvHex::new_keyless().unwrap();
+    let ot = apsv.enc_with_nonce("counter", &nonce).unwrap();
+    let payload = oboron::encoding::decode(&ot, Encoding::Hex).unwrap();
+    assert_eq!(&payload[..16], &nonce[..]);
+}
+
+#[test]
+fn test_enc_with_nonce_wrong_length() {
+    let apgs = ApgsC32::new_keyless().unwrap();
+    assert_eq!(
+        apgs.enc_with_nonce("counter", &[0u8; 16]),
+        Err(Error::InvalidNonceLength {
+            expected: 12,
+            actual: 16
+        })
+    );
+
+    let apsv = ApsvHex::new_keyless().unwrap();
+    assert_eq!(
+        apsv.enc_with_nonce("counter", &[0u8; 12]),
+        Err(Error::InvalidNonceLength {
+            expected: 16,
+            actual: 12
+        })
+    );
+    assert_eq!(
+        apsv.enc_with_nonce("counter", &[]),
+        Err(Error::InvalidNonceLength {
+            expected: 16,
+            actual: 0
+        })
+    );
+    assert_eq!(
+        apsv.enc_with_nonce("", &[0u8; 16]),
+        Err(Error::PlaintextTooShort { len: 0, min: 1 })
+    );
+}
@@ -0,0 +1,103 @@
+//! Property tests: obtext only ever contains characters from its encoding's
+//! alphabet, for every enabled scheme × encoding.
+//!
+//! Guards the URL/filename-safety guarantee: e.g. base64 obtext must never
+//! contain `+`, `/` or `=`, and c32 obtext must be lowercase-only.
+
+use oboron::{supported_schemes, Encoding, Format, Ob, ObtextCodec, Scheme};
+use proptest::prelude::*;
+
+const C32_ALPHABET: &str = "0123456789abcdefghjkmnpqrstvwxyz";
+const B32_ALPHABET: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+const B64_ALPHABET: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+const HEX_ALPHABET: &str = "0123456789abcdef";
+const HEXU_ALPHABET: &str = "0123456789ABCDEF";
+/// Legacy obtext is always lowercase RFC 4648 base32, whatever the format.
+#[cfg(feature = "legacy")]
+const LEGACY_ALPHABET: &str = "abcdefghijklmnopqrstuvwxyz234567";
+
+fn alphabet(encoding: Encoding) -> &'static str {
+    match encoding {
+        Encoding::C32 => C32_ALPHABET,
+        Encoding::B32 => B32_ALPHABET,
+        Encoding::B64 => B64_ALPHABET,
+        Encoding::Hex => HEX_ALPHABET,
+        Encoding::HexUpper => HEXU_ALPHABET,
+        Encoding::Custom(alphabet) => alphabet.as_str(),
+        other => panic!("no alphabet listed for {}", other),
+    }
+}
+
+/// One codec per enabled scheme × encoding, with the alphabet its output
+/// must stay within.
+fn codecs() -> Vec<(Format, Box<dyn ObtextCodec>, &'static str)> {
+    let mut codecs: Vec<(Format, Box<dyn ObtextCodec>, &'static str)> = Vec::new();
+    for &scheme in supported_schemes() {
+        for encoding in Encoding::ALL {
+            let format = Format::new(scheme, encoding);
+            let codec: Box<dyn ObtextCodec> = match scheme {
+                #[cfg(feature = "legacy")]
+                Scheme::Legacy => continue,
+                #[cfg(feature = "zrbcx")]
+                Scheme::Zrbcx => Box::new(oboron::ztier::Obz::new_keyless(format).unwrap()),
+                #[cfg(feature = "zmock")]
+                Scheme::Zmock1 => Box::new(oboron::ztier::Obz::new_keyless(format).unwrap()),
+                _ => Box::new(Ob::new_keyless(format).unwrap()),
+            };
+            codecs.push((format, codec, alphabet(encoding)));
+        }
+    }
+    #[cfg(feature = "legacy")]
+    {
+        let legacy = oboron::ztier::Legacy::new_keyless().unwrap();
+        codecs.push((legacy.format(), Box::new(legacy), LEGACY_ALPHABET));
+    }
+    codecs
+}
+
+#[test]
+fn test_alphabets_are_disjoint_from_url_reserved_chars() {
+    for encoding in Encoding::ALL {
+        assert!(
+            !alphabet(encoding).contains(['+', '/', '=', '.']),
+            "{:?} alphabet contains a URL-reserved character",
+            encoding
+        );
+    }
+    assert!(!C32_ALPHABET.chars().any(|c| c.is_ascii_uppercase()));
+}
+
+proptest! {
+    #[test]
+    fn prop_obtext_stays_within_encoding_alphabet(plaintext in "\\PC{1,64}") {
+        for (format, codec, alphabet) in codecs() {
+            let ot = codec.enc(&plaintext).unwrap();
+            prop_assert!(!ot.is_empty());
+            prop_assert!(
+                ot.chars().all(|c| alphabet.contains(c)),
+                "{} produced {:?}, outside its alphabet",
+                format,
+                ot
+            );
+            if format.encoding() == Encoding::C32 {
+                prop_assert!(!ot.chars().any(|c| c.is_ascii_uppercase()));
+            }
+        }
+    }
+
+    #[test]
+    fn prop_binary_like_plaintext_stays_within_alphabet(
+        bytes in proptest::collection::vec(1u8..=0x7f, 1..256)
+    ) {
+        let plaintext = String::from_utf8(bytes).unwrap();
+        for (format, codec, alphabet) in codecs() {
+            let ot = codec.enc(&plaintext).unwrap();
+            prop_assert!(
+                ot.chars().all(|c| alphabet.contains(c)),
+                "{} produced {:?}, outside its alphabet",
+                format,
+                ot
+            );
+        }
+    }
+}
@@ -0,0 +1,127 @@
+//! Tests for the public byte-level `oboron::encoding::{encode, decode}` API.
+
+use oboron::encoding::{canonicalize, decode, encode, transcode};
+use oboron::{Encoding, Error};
+use proptest::prelude::*;
+
+proptest! {
+    #[test]
+    fn prop_encode_decode_roundtrip(bytes in proptest::collection::vec(any::<u8>(), 0..512)) {
+        for encoding in Encoding::ALL {
+            let text = encode(&bytes, encoding);
+            prop_assert_eq!(decode(&text, encoding).unwrap(), bytes.clone());
+        }
+    }
+}
+
+#[test]
+fn test_encoded_and_decoded_len() {
+    for len in 0..=200 {
+        let bytes = vec![0xa5; len];
+        for encoding in Encoding::ALL {
+            let text = encode(&bytes, encoding);
+            assert_eq!(encoding.encoded_len(len), text.len(), "{}", encoding);
+            assert_eq!(encoding.decoded_len(text.len()), len, "{}", encoding);
+        }
+    }
+    // Padding only overestimates
+    for (padded, encoding) in [
+        ("AD7RA33CN5ZG63Q=", Encoding::B32),
+        ("AP8Qbw==", Encoding::B64),
+    ] {
+        let actual = decode(padded, encoding).unwrap().len();
+        assert!(encoding.decoded_len(padded.len()) >= actual);
+    }
+}
+
+#[test]
+fn test_known_vectors() {
+    let bytes = b"\x00\xff\x10oboron";
+    assert_eq!(encode(bytes, Encoding::C32), "03zh0vv2dxs6yvg");
+    assert_eq!(encode(bytes, Encoding::B32), "AD7RA33CN5ZG63Q");
+    assert_eq!(encode(bytes, Encoding::B64), "AP8Qb2Jvcm9u");
+    assert_eq!(encode(bytes, Encoding::Hex), "00ff106f626f726f6e");
+    assert_eq!(encode(bytes, Encoding::HexUpper), "00FF106F626F726F6E");
+    assert_eq!(encode(&[], Encoding::C32), "");
+}
+
+#[test]
+fn test_decode_errors() {
+    assert_eq!(decode("ilou", Encoding::C32), Err(Error::InvalidC32));
+    assert_eq!(decode("ad7ra33c", Encoding::B32), Err(Error::InvalidB32));
+    assert_eq!(decode("AP8Q+w==", Encoding::B64), Err(Error::InvalidB64));
+    assert_eq!(decode("00FF", Encoding::Hex), Err(Error::InvalidHex));
+    assert_eq!(decode("00ff", Encoding::HexUpper), Err(Error::InvalidHex));
+    assert_eq!(decode("abc", Encoding::Hex), Err(Error::InvalidHex));
+}
+
+#[test]
+fn test_decode_error_messages_name_alphabet() {
+    let message = |text, encoding| decode(text, encoding).unwrap_err().to_string();
+
+    let c32 = message("ilou", Encoding::C32);
+    assert!(c32.contains("base32crockford"), "{c32}");
+    assert!(c32.contains("0-9a-z excluding i, l, o, u"), "{c32}");
+
+    let b32 = message("ad7ra33c", Encoding::B32);
+    assert!(b32.contains("base32rfc"), "{b32}");
+    assert!(b32.contains("A-Z and 2-7"), "{b32}");
+
+    let b64 = message("AP8Q+w==", Encoding::B64);
+    assert!(b64.contains("base64"), "{b64}");
+    assert!(b64.contains("A-Za-z0-9-_"), "{b64}");
+
+    for encoding in [Encoding::Hex, Encoding::HexUpper] {
+        let hex = message("xyz0", encoding);
+        assert!(hex.contains("0-9a-f"), "{hex}");
+        assert!(hex.contains("0-9A-F for hexu"), "{hex}");
+    }
+}
+
+#[cfg(all(feature = "aasv", feature = "keyless"))]
+#[test]
+fn test_matches_obtext_encoding() {
+    let ob = oboron::Omnib::new_keyless().unwrap();
+    for encoding in Encoding::ALL {
+        let format = oboron::Format::new(oboron::Scheme::Aasv, encoding);
+        let ot = ob.enc("byte layer", format).unwrap();
+        let payload = decode(&ot, encoding).unwrap();
+        assert_eq!(encode(&payload, encoding), ot);
+    }
+}
+
+#[test]
+fn test_transcode() {
+    let text = encode(b"\x00\xff\x10oboron", Encoding::C32);
+    assert_eq!(
+        transcode(&text, Encoding::C32, Encoding::B64).unwrap(),
+        "AP8Qb2Jvcm9u"
+    );
+    assert_eq!(
+        transcode("AP8Qb2Jvcm9u", Encoding::B64, Encoding::C32).unwrap(),
+        text
+    );
+    assert_eq!(
+        transcode(&text, Encoding::Hex, Encoding::B64),
+        Err(Error::InvalidHex)
+    );
+}
+
+#[cfg(all(feature = "aasv", feature = "keyless"))]
+#[test]
+fn test_canonicalize() {
+    use oboron::{Format, Omnib, Scheme};
+
+    let omb = Omnib::new_keyless().unwrap();
+    let b64 = omb.enc("canonical", "aasv.b64").unwrap();
+    let hex = transcode(&b64, Encoding::B64, Encoding::Hex).unwrap();
+
+    let canonical = canonicalize(&b64, Encoding::B64).unwrap();
+    assert_eq!(canonicalize(&hex, Encoding::Hex).unwrap(), canonical);
+    assert_eq!(canonical, omb.enc("canonical", "aasv.c32").unwrap());
+    assert_eq!(canonicalize(&canonical, Encoding::C32).unwrap(), canonical);
+
+    let format = Format::new(Scheme::Aasv, Encoding::B64);
+    assert_eq!(format.canonical_encoding(), Encoding::CANONICAL);
+    assert_eq!(omb.autodec(&canonical).unwrap(), "canonical");
+}
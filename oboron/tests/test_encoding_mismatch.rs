@@ -0,0 +1,85 @@
+//! Tests for `Omnib::dec` rejecting obtext whose characters rule out the
+//! requested encoding.
+
+#![cfg(all(feature = "aasv", feature = "keyless"))]
+
+use oboron::{Encoding, Error, Omnib};
+
+/// A b64 obtext with characters outside the c32 alphabet
+fn b64_obtext(omb: &Omnib) -> String {
+    (0..)
+        .map(|i| omb.enc(&format!("plaintext {}", i), "aasv.b64").unwrap())
+        .find(|ot| ot.bytes().any(|b| b.is_ascii_uppercase()))
+        .unwrap()
+}
+
+#[test]
+fn test_b64_obtext_as_c32_is_encoding_mismatch() {
+    let omb = Omnib::new_keyless().unwrap();
+    let ot = b64_obtext(&omb);
+
+    assert_eq!(
+        omb.dec(&ot, "aasv.c32"),
+        Err(Error::EncodingMismatch {
+            requested: Encoding::C32,
+            detected: Encoding::B64,
+        })
+    );
+    let message = omb.dec(&ot, "aasv.c32").unwrap_err().to_string();
+    assert!(message.contains("autodec"), "{}", message);
+
+    // The obtext itself is fine
+    assert!(omb.autodec(&ot).is_ok());
+}
+
+#[test]
+fn test_autodec_within_checks_each_candidate_encoding() {
+    let omb = Omnib::new_keyless().unwrap();
+    let ot = b64_obtext(&omb);
+
+    assert_eq!(
+        omb.autodec_within(&ot, &[oboron::AASV_C32]),
+        Err(Error::EncodingMismatch {
+            requested: Encoding::C32,
+            detected: Encoding::B64,
+        })
+    );
+    assert!(omb
+        .autodec_within(&ot, &[oboron::AASV_C32, oboron::AASV_B64])
+        .is_ok());
+}
+
+#[test]
+fn test_detected_encoding_is_narrowest_fit() {
+    let omb = Omnib::new_keyless().unwrap();
+
+    let ot = omb.enc("hex", "aasv.hex").unwrap();
+    assert_eq!(
+        omb.dec(&ot, "aasv.b32"),
+        Err(Error::EncodingMismatch {
+            requested: Encoding::B32,
+            detected: Encoding::Hex,
+        })
+    );
+
+    let ot = omb.enc("b32", "aasv.b32").unwrap();
+    assert_eq!(
+        omb.dec(&ot, "aasv.hex"),
+        Err(Error::EncodingMismatch {
+            requested: Encoding::Hex,
+            detected: Encoding::B32,
+        })
+    );
+}
+
+#[test]
+fn test_matching_or_unknown_alphabet_is_not_mismatch() {
+    let omb = Omnib::new_keyless().unwrap();
+    for format in ["aasv.c32", "aasv.b32", "aasv.b64", "aasv.hex"] {
+        let ot = omb.enc("fits", format).unwrap();
+        assert_eq!(omb.dec(&ot, format).unwrap(), "fits");
+    }
+
+    // Characters no encoding uses are left to the decoder
+    assert_eq!(omb.dec("not obtext!", "aasv.c32"), Err(Error::InvalidC32));
+}
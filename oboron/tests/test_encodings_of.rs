@@ -0,0 +1,64 @@
+//! Tests for `Omnib::encodings_of`, one ciphertext in every encoding.
+
+#![cfg(all(feature = "aasv", feature = "apsv", feature = "keyless"))]
+
+use oboron::{same_ciphertext, Encoding, Error, Format, Omnib, Scheme};
+
+#[test]
+fn test_encodings_of_matches_enc() {
+    let omb = Omnib::new_keyless().unwrap();
+    let rendered: Vec<(Encoding, String)> =
+        omb.encodings_of("hello", Scheme::Aasv).unwrap().collect();
+
+    // aasv is deterministic, so each rendering equals a fresh enc
+    let expected: Vec<(Encoding, String)> = Encoding::ALL
+        .into_iter()
+        .map(|e| (e, omb.enc("hello", Format::new(Scheme::Aasv, e)).unwrap()))
+        .collect();
+    assert_eq!(rendered, expected);
+}
+
+#[test]
+fn test_encodings_of_probabilistic_shares_ciphertext() {
+    let omb = Omnib::new_keyless().unwrap();
+    let mut iter = omb.encodings_of("hello", Scheme::Apsv).unwrap();
+    assert_eq!(iter.len(), Encoding::ALL.len());
+
+    let (first_encoding, first) = iter.next().unwrap();
+    assert_eq!(iter.len(), Encoding::ALL.len() - 1);
+    for (encoding, ot) in iter {
+        assert!(same_ciphertext(&first, first_encoding, &ot, encoding).unwrap());
+        assert_eq!(
+            omb.dec(&ot, Format::new(Scheme::Apsv, encoding)).unwrap(),
+            "hello"
+        );
+    }
+}
+
+#[test]
+fn test_encodings_of_errors() {
+    let omb = Omnib::new_keyless().unwrap().with_max_input_len(4);
+    assert_eq!(
+        omb.encodings_of("hello", Scheme::Aasv).err(),
+        Some(Error::InputTooLarge { len: 5, max: 4 })
+    );
+    assert_eq!(
+        omb.encodings_of("", Scheme::Aasv).err(),
+        Some(Error::PlaintextTooShort { len: 0, min: 1 })
+    );
+}
+
+#[test]
+fn test_encodings_of_rejects_ztier_and_legacy() {
+    let omb = Omnib::new_keyless().unwrap();
+    for &scheme in oboron::supported_schemes() {
+        if scheme.is_ztier_or_legacy() {
+            assert_eq!(
+                omb.encodings_of("hello", scheme).err(),
+                Some(Error::UnsupportedScheme { scheme }),
+                "{}",
+                scheme
+            );
+        }
+    }
+}
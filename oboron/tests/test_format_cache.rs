@@ -0,0 +1,47 @@
+//! Tests for `Omnib`'s cache of parsed format strings.
+
+#![cfg(all(feature = "aasv", feature = "keyless"))]
+
+use oboron::{Error, Omnib, AASV_B64};
+use std::sync::Arc;
+
+#[test]
+fn test_repeated_format_strings_match_formats() {
+    let omb = Omnib::new_keyless().unwrap();
+    for format in ["aasv.b64", "AASV.B64", "ob32.base64", "aasv.b64url"] {
+        for _ in 0..3 {
+            let ot = omb.enc("cached", format).unwrap();
+            assert_eq!(ot, omb.enc("cached", AASV_B64).unwrap(), "{}", format);
+            assert_eq!(omb.dec(&ot, format).unwrap(), "cached");
+            assert_eq!(omb.dec(&ot, String::from(format)).unwrap(), "cached");
+        }
+    }
+
+    // Invalid strings keep failing
+    for _ in 0..2 {
+        assert_eq!(omb.enc("cached", "nope.b64"), Err(Error::InvalidFormat));
+    }
+}
+
+#[test]
+fn test_format_cache_is_shared_across_threads() {
+    fn assert_sync<T: Send + Sync>() {}
+    assert_sync::<Omnib>();
+
+    let omb = Arc::new(Omnib::new_keyless().unwrap());
+    let expected = omb.enc("threads", AASV_B64).unwrap();
+    let handles: Vec<_> = (0..4)
+        .map(|i| {
+            let omb = Arc::clone(&omb);
+            std::thread::spawn(move || {
+                let format = ["AASV.B64", "ob32.b64", "Aasv.b64url", "aasv.base64"][i];
+                (0..50)
+                    .map(|_| omb.enc("threads", format).unwrap())
+                    .collect::<Vec<_>>()
+            })
+        })
+        .collect();
+    for handle in handles {
+        assert!(handle.join().unwrap().iter().all(|ot| *ot == expected));
+    }
+}
@@ -0,0 +1,51 @@
+//! Tests for `Format::shares_encoding` and `Format::autodetect_compatible`.
+
+#![cfg(all(
+    feature = "aasv",
+    feature = "apsv",
+    feature = "mock",
+    feature = "keyless"
+))]
+
+use oboron::{Format, Ob};
+
+fn format(s: &str) -> Format {
+    Format::from_str(s).unwrap()
+}
+
+#[test]
+fn test_same_encoding_is_compatible() {
+    let aasv = format("aasv.c32");
+    let mock1 = format("mock1.c32");
+    assert!(aasv.shares_encoding(&mock1));
+    assert!(aasv.autodetect_compatible(&mock1));
+    assert!(mock1.autodetect_compatible(&aasv));
+    assert!(aasv.autodetect_compatible(&format("apsv.c32")));
+    assert!(aasv.autodetect_compatible(&aasv));
+
+    // The predicate matches what autodec actually does
+    let ot = Ob::new_keyless(mock1).unwrap().enc("shared").unwrap();
+    assert_eq!(
+        Ob::new_keyless(aasv).unwrap().autodec(&ot).unwrap(),
+        "shared"
+    );
+}
+
+#[test]
+fn test_different_encoding_is_incompatible() {
+    let aasv = format("aasv.c32");
+    for other in ["mock1.b32", "aasv.b64", "apsv.hex", "aasv.hexu"] {
+        let other = format(other);
+        assert!(!aasv.shares_encoding(&other), "{}", other);
+        assert!(!aasv.autodetect_compatible(&other), "{}", other);
+    }
+}
+
+#[cfg(feature = "zrbcx")]
+#[test]
+fn test_ztier_is_incompatible_with_secure_schemes() {
+    let zrbcx = format("zrbcx.c32");
+    assert!(zrbcx.shares_encoding(&format("aasv.c32")));
+    assert!(!zrbcx.autodetect_compatible(&format("aasv.c32")));
+    assert!(zrbcx.autodetect_compatible(&zrbcx));
+}
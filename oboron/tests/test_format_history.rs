@@ -0,0 +1,45 @@
+//! Tests for `Ob::format_history` (feature `debug-history`).
+
+#![cfg(all(
+    feature = "debug-history",
+    feature = "aasv",
+    feature = "mock",
+    feature = "keyless"
+))]
+
+use oboron::{Encoding, Format, Ob, Scheme};
+
+#[test]
+fn test_history_records_formats_in_order() {
+    let mut ob = Ob::new_keyless("aasv.c32").unwrap();
+    assert!(ob.format_history().is_empty());
+
+    ob.set_format("mock1.b64").unwrap();
+    ob.set_scheme(Scheme::Aasv).unwrap();
+    ob.set_encoding(Encoding::Hex).unwrap();
+    assert!(ob.set_format("nope.c32").is_err());
+
+    assert_eq!(
+        ob.format_history(),
+        [
+            Format::new(Scheme::Mock1, Encoding::B64),
+            Format::new(Scheme::Aasv, Encoding::B64),
+            Format::new(Scheme::Aasv, Encoding::Hex),
+        ]
+    );
+}
+
+#[test]
+fn test_history_keeps_last_formats() {
+    let mut ob = Ob::new_keyless("aasv.c32").unwrap();
+    let formats = ["aasv.b64", "mock1.hex", "mock2.c32"];
+    for i in 0..Ob::FORMAT_HISTORY_LEN + 2 {
+        ob.set_format(formats[i % formats.len()]).unwrap();
+    }
+
+    let history = ob.format_history();
+    assert_eq!(history.len(), Ob::FORMAT_HISTORY_LEN);
+    // The two oldest entries were dropped
+    assert_eq!(history[0], Format::from_str(formats[2]).unwrap());
+    assert_eq!(*history.last().unwrap(), ob.format());
+}
@@ -0,0 +1,51 @@
+//! Tests for the `from_env` constructors reading `OBORON_KEY`/`OBORON_FORMAT`.
+
+#![cfg(all(feature = "std", feature = "aasv", feature = "keyless"))]
+
+use oboron::{Error, Ob, Omnib, AASV_B64, FORMAT_ENV_VAR, KEY_ENV_VAR};
+
+// The environment is shared by every test in this binary, so all cases run
+// in one test.
+#[test]
+fn test_from_env() {
+    std::env::remove_var(KEY_ENV_VAR);
+    std::env::remove_var(FORMAT_ENV_VAR);
+    let missing_format = Error::MissingEnvVar {
+        name: FORMAT_ENV_VAR,
+    };
+    let missing_key = Error::MissingEnvVar { name: KEY_ENV_VAR };
+    assert_eq!(Ob::from_env().err(), Some(missing_format.clone()));
+    assert_eq!(Ob::from_env_keyless().err(), Some(missing_format));
+    assert_eq!(Omnib::from_env().err(), Some(missing_key.clone()));
+
+    std::env::set_var(FORMAT_ENV_VAR, "aasv.b64");
+    assert_eq!(Ob::from_env().err(), Some(missing_key));
+    let keyless = Ob::from_env_keyless().unwrap();
+    assert_eq!(keyless.format(), AASV_B64);
+    assert_eq!(keyless.key(), Ob::new_keyless(AASV_B64).unwrap().key());
+
+    let key = oboron::generate_key();
+    std::env::set_var(KEY_ENV_VAR, &key);
+    let ob = Ob::from_env().unwrap();
+    assert_eq!(ob.format(), AASV_B64);
+    let ot = ob.enc("from env").unwrap();
+    assert_eq!(
+        Ob::new(AASV_B64, &key).unwrap().dec(&ot).unwrap(),
+        "from env"
+    );
+    assert_eq!(Omnib::from_env().unwrap().autodec(&ot).unwrap(), "from env");
+
+    // Invalid values report the usual errors
+    std::env::set_var(FORMAT_ENV_VAR, "nope.b64");
+    assert_eq!(Ob::from_env().err(), Some(Error::InvalidFormat));
+    std::env::set_var(FORMAT_ENV_VAR, "aasv.b64");
+    std::env::set_var(KEY_ENV_VAR, &key[1..]);
+    assert!(matches!(
+        Ob::from_env(),
+        Err(Error::KeyWrongLength { got: 85, .. })
+    ));
+    assert!(Omnib::from_env().is_err());
+
+    std::env::remove_var(KEY_ENV_VAR);
+    std::env::remove_var(FORMAT_ENV_VAR);
+}
@@ -0,0 +1,84 @@
+//! Tests for the uppercase hex encoding (`hexu`).
+
+#![cfg(feature = "keyless")]
+
+use oboron::{supported_schemes, Encoding, Error, Format, Ob, Omnib};
+
+#[test]
+fn test_hexu_names() {
+    assert_eq!("hexu".parse::<Encoding>(), Ok(Encoding::HexUpper));
+    assert_eq!("hexupper".parse::<Encoding>(), Ok(Encoding::HexUpper));
+    assert_eq!(Encoding::HexUpper.as_str(), "hexu");
+    // Names are case-insensitive: "HEX" is lowercase hex
+    assert_eq!("HEX".parse::<Encoding>(), Ok(Encoding::Hex));
+
+    for &scheme in supported_schemes() {
+        if scheme.as_str() == "legacy" {
+            assert_eq!("legacy.hexu".parse::<Format>(), Err(Error::InvalidFormat));
+            continue;
+        }
+        let format = Format::new(scheme, Encoding::HexUpper);
+        let name = format!("{}.hexu", scheme);
+        assert_eq!(format.to_string(), name);
+        assert_eq!(name.parse::<Format>(), Ok(format));
+        assert_eq!(format!("{}.hexupper", scheme).parse::<Format>(), Ok(format));
+    }
+}
+
+#[test]
+fn test_hexu_roundtrip() {
+    let omb = Omnib::new_keyless().unwrap();
+    for &scheme in supported_schemes() {
+        if scheme.is_ztier_or_legacy() {
+            continue;
+        }
+        let format = Format::new(scheme, Encoding::HexUpper);
+        let ob = Ob::new_keyless(format).unwrap();
+        for pt in ["a", "hello world", "0123456789abcdef0123456789abcdef"] {
+            let ot = ob.enc(pt).unwrap();
+            assert!(
+                ot.chars().all(|c| matches!(c, '0'..='9' | 'A'..='F')),
+                "{}: {:?}",
+                format,
+                ot
+            );
+            assert_eq!(ob.dec(&ot).unwrap(), pt);
+            assert_eq!(omb.dec(&ot, format).unwrap(), pt);
+
+            // Same payload as lowercase hex
+            let lower = oboron::encoding::transcode(&ot, Encoding::HexUpper, Encoding::Hex);
+            assert_eq!(lower.unwrap(), ot.to_lowercase());
+        }
+    }
+}
+
+#[test]
+fn test_hexu_autodec() {
+    let omb = Omnib::new_keyless().unwrap();
+    for &scheme in supported_schemes() {
+        if scheme.is_ztier_or_legacy() {
+            continue;
+        }
+        let format = Format::new(scheme, Encoding::HexUpper);
+        let ot = omb.enc("autodetect me", format).unwrap();
+        assert_eq!(omb.autodec(&ot).unwrap(), "autodetect me", "{}", format);
+        assert_eq!(oboron::autodec_keyless(&ot).unwrap(), "autodetect me");
+        assert_eq!(oboron::detect_format(&ot), Some(format));
+
+        // Ob with a different encoding falls back to full autodetection
+        let ob = Ob::new_keyless(Format::new(scheme, Encoding::B64)).unwrap();
+        assert_eq!(ob.autodec(&ot).unwrap(), "autodetect me");
+    }
+}
+
+#[cfg(feature = "zrbcx")]
+#[test]
+fn test_hexu_ztier() {
+    use oboron::ztier::Obz;
+    let obz = Obz::new_keyless("zrbcx.hexu").unwrap();
+    let ot = obz.enc("z-tier upper").unwrap();
+    assert_eq!(ot, ot.to_uppercase());
+    assert_eq!(obz.dec(&ot).unwrap(), "z-tier upper");
+    let other = Obz::new_keyless("zrbcx.c32").unwrap();
+    assert_eq!(other.autodec(&ot).unwrap(), "z-tier upper");
+}
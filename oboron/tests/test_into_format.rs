@@ -0,0 +1,30 @@
+//! Tests for the forms of format accepted by `Omnib::enc`/`dec`.
+
+#![cfg(all(feature = "aasv", feature = "keyless"))]
+
+use oboron::{Encoding, Format, Omnib, Scheme, AASV_B64};
+
+#[test]
+fn test_every_format_form_gives_same_result() {
+    let omb = Omnib::new_keyless().unwrap();
+    let format = Format::new(Scheme::Aasv, Encoding::B64);
+    let expected = omb.enc("formats", "aasv.b64").unwrap();
+
+    assert_eq!(omb.enc("formats", format).unwrap(), expected);
+    assert_eq!(omb.enc("formats", AASV_B64).unwrap(), expected);
+    assert_eq!(
+        omb.enc("formats", String::from("aasv.b64")).unwrap(),
+        expected
+    );
+    assert_eq!(
+        omb.enc("formats", (Scheme::Aasv, Encoding::B64)).unwrap(),
+        expected
+    );
+
+    assert_eq!(omb.dec(&expected, "aasv.b64").unwrap(), "formats");
+    assert_eq!(omb.dec(&expected, format).unwrap(), "formats");
+    assert_eq!(
+        omb.dec(&expected, (Scheme::Aasv, Encoding::B64)).unwrap(),
+        "formats"
+    );
+}
@@ -0,0 +1,65 @@
+//! Tests for `ObtextCodec::key_id`.
+//!
+//! `key_id` does not need the `bytes-keys` feature, but tests always build
+//! with `full` (see the self dev-dependency), which enables it; CI calls
+//! `key_id` from a scratch crate built with only `aasv` (the "key_id
+//! without bytes-keys" step in tests.yml).
+
+#![cfg(feature = "aasv")]
+
+use oboron::{AasvC32, Encoding, Error, Format, Ob, ObtextCodec, Scheme};
+
+#[test]
+fn test_key_id_matches_fingerprint() {
+    let key = oboron::generate_key();
+    let ob = Ob::new("aasv.b64", &key).unwrap();
+    assert_eq!(ob.key_id(), ob.fingerprint());
+    assert_eq!(ob.key_id().len(), 8);
+
+    // Same key, any codec
+    let codec = AasvC32::new(&key).unwrap();
+    assert_eq!(codec.key_id(), ob.key_id());
+
+    let other = Ob::new("aasv.b64", &oboron::generate_key()).unwrap();
+    assert_ne!(other.key_id(), ob.key_id());
+}
+
+#[cfg(all(feature = "zrbcx", feature = "keyless"))]
+#[test]
+fn test_key_id_ztier() {
+    use oboron::ztier::Obz;
+
+    let obz = Obz::new_keyless("zrbcx.c32").unwrap();
+    assert_eq!(obz.key_id().len(), 8);
+    assert_eq!(
+        obz.key_id(),
+        Obz::new_keyless("zrbcx.b64").unwrap().key_id()
+    );
+}
+
+/// A codec that does not override `key_id`
+struct Keyless;
+
+impl ObtextCodec for Keyless {
+    fn enc(&self, plaintext: &str) -> Result<String, Error> {
+        Ok(plaintext.to_string())
+    }
+    fn dec(&self, obtext: &str) -> Result<String, Error> {
+        Ok(obtext.to_string())
+    }
+    fn format(&self) -> Format {
+        oboron::AASV_C32
+    }
+    fn scheme(&self) -> Scheme {
+        Scheme::Aasv
+    }
+    fn encoding(&self) -> Encoding {
+        Encoding::C32
+    }
+}
+
+#[test]
+fn test_key_id_default_is_not_a_fingerprint() {
+    assert_eq!(Keyless.key_id(), oboron::UNKNOWN_KEY_ID);
+    assert!(!Keyless.key_id().bytes().all(|b| b.is_ascii_hexdigit()));
+}
@@ -0,0 +1,48 @@
+//! Tests for key generation from a caller-provided RNG.
+
+use oboron::generate_key_with_rng;
+use rand::{rngs::StdRng, SeedableRng};
+
+#[test]
+fn test_same_seed_same_key() {
+    let a = generate_key_with_rng(&mut StdRng::seed_from_u64(42));
+    let b = generate_key_with_rng(&mut StdRng::seed_from_u64(42));
+    assert_eq!(a, b);
+    assert_eq!(a.len(), 86);
+    assert!(!a.contains(['-', '_']));
+}
+
+#[test]
+fn test_different_seeds_differ() {
+    let a = generate_key_with_rng(&mut StdRng::seed_from_u64(1));
+    let b = generate_key_with_rng(&mut StdRng::seed_from_u64(2));
+    assert_ne!(a, b);
+
+    // Successive keys from one generator differ too
+    let mut rng = StdRng::seed_from_u64(1);
+    assert_eq!(generate_key_with_rng(&mut rng), a);
+    assert_ne!(generate_key_with_rng(&mut rng), a);
+}
+
+#[cfg(feature = "bytes-keys")]
+#[test]
+fn test_key_bytes_with_rng() {
+    use data_encoding::BASE64URL_NOPAD;
+    use oboron::generate_key_bytes_with_rng;
+
+    let bytes = generate_key_bytes_with_rng(&mut StdRng::seed_from_u64(42));
+    assert_eq!(
+        bytes,
+        generate_key_bytes_with_rng(&mut StdRng::seed_from_u64(42))
+    );
+    let key = generate_key_with_rng(&mut StdRng::seed_from_u64(42));
+    assert_eq!(BASE64URL_NOPAD.encode(&bytes), key);
+}
+
+#[cfg(feature = "aasv")]
+#[test]
+fn test_seeded_key_is_usable() {
+    let key = generate_key_with_rng(&mut StdRng::seed_from_u64(42));
+    let ob = oboron::Ob::new("aasv.c32", &key).unwrap();
+    assert_eq!(ob.dec(&ob.enc("fixture").unwrap()).unwrap(), "fixture");
+}
@@ -0,0 +1,56 @@
+//! Tests for `set_test_key`.  The override is process-global, so this file
+//! is its own test binary and every test sets the same key.
+
+#![cfg(all(feature = "keyless", feature = "aasv", feature = "bytes-keys"))]
+
+use oboron::{Error, Ob, Omnib, HARDCODED_KEY_BYTES};
+
+const TEST_KEY: [u8; 64] = [0x5a; 64];
+
+fn install_test_key() {
+    match oboron::set_test_key(&TEST_KEY) {
+        Ok(()) | Err(Error::KeylessKeyInUse) => {}
+        Err(e) => panic!("unexpected error: {}", e),
+    }
+}
+
+#[test]
+fn test_keyless_uses_test_key() {
+    install_test_key();
+
+    let keyless = Ob::new_keyless("aasv.b64").unwrap();
+    let explicit = Ob::from_bytes("aasv.b64", &TEST_KEY).unwrap();
+    let default = Ob::from_bytes("aasv.b64", &HARDCODED_KEY_BYTES).unwrap();
+
+    let ot = keyless.enc("ci data").unwrap();
+    assert_eq!(explicit.dec(&ot).unwrap(), "ci data");
+    assert!(default.dec(&ot).is_err());
+
+    // Still deterministic across instances and entry points
+    assert_eq!(
+        Ob::new_keyless("aasv.b64").unwrap().enc("ci data").unwrap(),
+        ot
+    );
+    let omb = Omnib::new_keyless().unwrap();
+    assert_eq!(omb.enc("ci data", "aasv.b64").unwrap(), ot);
+    assert_eq!(
+        oboron::AasvB64::new_keyless()
+            .unwrap()
+            .enc("ci data")
+            .unwrap(),
+        ot
+    );
+}
+
+#[test]
+fn test_test_key_can_only_be_set_once() {
+    install_test_key();
+    assert_eq!(
+        oboron::set_test_key(&[1u8; 64]),
+        Err(Error::KeylessKeyInUse)
+    );
+
+    let ot = Ob::new_keyless("aasv.hex").unwrap().enc("x").unwrap();
+    let explicit = Ob::from_bytes("aasv.hex", &TEST_KEY).unwrap();
+    assert_eq!(explicit.dec(&ot).unwrap(), "x");
+}
@@ -0,0 +1,39 @@
+//! Tests for `Ob::enc_labeled`, obtext with a trailing format label.
+
+#![cfg(all(feature = "aasv", feature = "apsv", feature = "keyless"))]
+
+use oboron::{Ob, Omnib};
+
+#[test]
+fn test_labeled_decodes_like_bare() {
+    for format in ["aasv.c32", "aasv.b32", "aasv.b64", "aasv.hex", "apsv.b64"] {
+        let ob = Ob::new_keyless(format).unwrap();
+        let labeled = ob.enc_labeled("labeled").unwrap();
+        let (obtext, label) = labeled.split_once('#').unwrap();
+
+        assert_eq!(label, format);
+        assert_eq!(ob.dec(obtext).unwrap(), "labeled");
+        assert_eq!(ob.dec_labeled(&labeled).unwrap(), "labeled");
+        assert_eq!(ob.dec_labeled(obtext).unwrap(), "labeled");
+        assert_eq!(
+            Omnib::new_keyless()
+                .unwrap()
+                .autodec_labeled(&labeled)
+                .unwrap(),
+            "labeled"
+        );
+    }
+}
+
+#[test]
+fn test_label_does_not_affect_payload() {
+    let ob = Ob::new_keyless("aasv.c32").unwrap();
+    let labeled = ob.enc_labeled("labeled").unwrap();
+    assert_eq!(labeled, format!("{}#aasv.c32", ob.enc("labeled").unwrap()));
+
+    // The label is ignored, even when it names another format
+    let (obtext, _) = labeled.split_once('#').unwrap();
+    let relabeled = format!("{}#apsv.hex", obtext);
+    assert_eq!(ob.dec_labeled(&relabeled).unwrap(), "labeled");
+    assert!(ob.dec(&labeled).is_err());
+}
@@ -0,0 +1,76 @@
+//! Tests for `Error::MalformedCiphertext`, rejecting a payload whose length
+//! no obtext of its scheme can have before decrypting it.
+
+#![cfg(feature = "keyless")]
+
+use oboron::encoding::{decode, encode};
+use oboron::{Encoding, Error, Scheme};
+
+/// Re-encode `obtext` with `n` payload bytes removed after the first one,
+/// keeping the first byte and the (mixed) scheme marker intact
+fn drop_bytes(obtext: &str, n: usize) -> String {
+    let mut payload = decode(obtext, Encoding::C32).unwrap();
+    payload.drain(1..1 + n);
+    encode(&payload, Encoding::C32)
+}
+
+#[cfg(feature = "upbc")]
+#[test]
+fn test_upbc_partial_block_is_malformed() {
+    use oboron::{Ob, Omnib};
+
+    let ob = Ob::new_keyless("upbc.c32").unwrap();
+    let ot = ob.enc("sixteen byte str").unwrap();
+    let len = decode(&ot, Encoding::C32).unwrap().len() - 2;
+
+    let bad = drop_bytes(&ot, 1);
+    let expected = Err(Error::MalformedCiphertext {
+        scheme: Scheme::Upbc,
+        len: len - 1,
+    });
+    assert_eq!(ob.dec(&bad), expected);
+    assert_eq!(
+        Omnib::new_keyless().unwrap().dec(&bad, "upbc.c32"),
+        expected
+    );
+}
+
+#[cfg(all(feature = "aags", feature = "apsv"))]
+#[test]
+fn test_aead_ciphertext_shorter_than_nonce_and_tag_is_malformed() {
+    use oboron::Ob;
+
+    // 16-byte nonce + 16-byte tag + 1 byte; drop 10 to stay above the
+    // 17-byte aags minimum but below apsv's
+    let ob = Ob::new_keyless("apsv.c32").unwrap();
+    let bad = drop_bytes(&ob.enc("x").unwrap(), 10);
+    assert_eq!(
+        ob.dec(&bad),
+        Err(Error::MalformedCiphertext {
+            scheme: Scheme::Apsv,
+            len: 23,
+        })
+    );
+    let message = ob.dec(&bad).unwrap_err().to_string();
+    assert!(
+        message.contains("23-byte ciphertext cannot be apsv"),
+        "{}",
+        message
+    );
+}
+
+#[cfg(feature = "zrbcx")]
+#[test]
+fn test_zrbcx_partial_block_is_malformed() {
+    use oboron::ztier::Obz;
+
+    let obz = Obz::new_keyless("zrbcx.c32").unwrap();
+    let bad = drop_bytes(&obz.enc("obfuscated").unwrap(), 3);
+    assert_eq!(
+        obz.dec(&bad),
+        Err(Error::MalformedCiphertext {
+            scheme: Scheme::Zrbcx,
+            len: 13,
+        })
+    );
+}
@@ -0,0 +1,51 @@
+//! Tests for the `with_max_input_len` input size limit on `Ob` and `Omnib`.
+
+#![cfg(all(feature = "aasv", feature = "keyless"))]
+
+use oboron::{Error, Ob, Omnib, AASV_B64};
+
+const MAX: usize = 64;
+
+fn too_large(len: usize) -> Result<String, Error> {
+    Err(Error::InputTooLarge { len, max: MAX })
+}
+
+#[test]
+fn test_ob_rejects_oversized_input() {
+    let ob = Ob::new_keyless("aasv.b64").unwrap().with_max_input_len(MAX);
+    assert_eq!(ob.max_input_len(), Some(MAX));
+
+    // Within the limit: accepted
+    let ot = ob.enc(&"x".repeat(MAX / 4)).unwrap();
+    assert!(ot.len() <= MAX);
+    assert_eq!(ob.dec(&ot).unwrap(), "x".repeat(MAX / 4));
+    assert!(ob.enc(&"x".repeat(MAX)).is_ok());
+
+    // Over the limit: rejected with the specific error
+    let big = "x".repeat(MAX + 1);
+    assert_eq!(ob.enc(&big), too_large(MAX + 1));
+    assert_eq!(ob.dec(&big), too_large(MAX + 1));
+    assert_eq!(ob.autodec(&big), too_large(MAX + 1));
+}
+
+#[test]
+fn test_omnib_rejects_oversized_input() {
+    let omb = Omnib::new_keyless().unwrap().with_max_input_len(MAX);
+    let ot = omb.enc("short", AASV_B64).unwrap();
+    assert_eq!(omb.autodec(&ot).unwrap(), "short");
+
+    let big = "y".repeat(10 * MAX);
+    assert_eq!(omb.enc(&big, AASV_B64), too_large(10 * MAX));
+    assert_eq!(omb.dec(&big, AASV_B64), too_large(10 * MAX));
+    assert_eq!(omb.autodec(&big), too_large(10 * MAX));
+    assert_eq!(omb.autodec_within(&big, &[AASV_B64]), too_large(10 * MAX));
+}
+
+#[test]
+fn test_unlimited_by_default() {
+    let omb = Omnib::new_keyless().unwrap();
+    assert_eq!(omb.max_input_len(), None);
+    let big = "z".repeat(1 << 16);
+    let ot = omb.enc(&big, AASV_B64).unwrap();
+    assert_eq!(omb.dec(&ot, AASV_B64).unwrap(), big);
+}
@@ -0,0 +1,179 @@
+//! Tests for `ObObserver` instrumentation hooks (feature `metrics`).
+
+#![cfg(all(feature = "metrics", feature = "aasv", feature = "keyless"))]
+
+use oboron::{Error, Format, Ob, ObObserver, ObOperation, Omnib, AASV_B64};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Counts hook calls and records the last error reported
+#[derive(Default)]
+struct CountingObserver {
+    encs: AtomicUsize,
+    decs: AtomicUsize,
+    errors: AtomicUsize,
+    last_dec: Mutex<Option<(Option<Format>, usize, usize)>>,
+    last_error: Mutex<Option<(ObOperation, Option<Format>, Error)>>,
+}
+
+impl CountingObserver {
+    fn counts(&self) -> (usize, usize, usize) {
+        (
+            self.encs.load(Ordering::SeqCst),
+            self.decs.load(Ordering::SeqCst),
+            self.errors.load(Ordering::SeqCst),
+        )
+    }
+}
+
+impl ObObserver for CountingObserver {
+    fn on_enc(&self, format: Format, in_len: usize, out_len: usize, _elapsed: Duration) {
+        assert_eq!(format, AASV_B64);
+        assert_eq!(in_len, "hello".len());
+        assert!(out_len > in_len);
+        self.encs.fetch_add(1, Ordering::SeqCst);
+    }
+
+    fn on_dec(&self, format: Option<Format>, in_len: usize, out_len: usize, _elapsed: Duration) {
+        *self.last_dec.lock().unwrap() = Some((format, in_len, out_len));
+        self.decs.fetch_add(1, Ordering::SeqCst);
+    }
+
+    fn on_error(
+        &self,
+        operation: ObOperation,
+        format: Option<Format>,
+        _in_len: usize,
+        error: &Error,
+        _elapsed: Duration,
+    ) {
+        *self.last_error.lock().unwrap() = Some((operation, format, error.clone()));
+        self.errors.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[test]
+fn test_ob_hooks_fire_on_enc_dec_and_error() {
+    let observer = Arc::new(CountingObserver::default());
+    let ob = Ob::new_keyless(AASV_B64)
+        .unwrap()
+        .with_observer(observer.clone());
+    assert!(ob.observer().is_some());
+
+    let ot = ob.enc("hello").unwrap();
+    assert_eq!(observer.counts(), (1, 0, 0));
+
+    assert_eq!(ob.dec(&ot).unwrap(), "hello");
+    assert_eq!(observer.counts(), (1, 1, 0));
+    assert_eq!(
+        *observer.last_dec.lock().unwrap(),
+        Some((Some(AASV_B64), ot.len(), 5))
+    );
+
+    assert_eq!(ob.autodec(&ot).unwrap(), "hello");
+    assert_eq!(observer.counts(), (1, 2, 0));
+    assert_eq!(
+        *observer.last_dec.lock().unwrap(),
+        Some((None, ot.len(), 5))
+    );
+
+    let err = ob.dec("!!").unwrap_err();
+    assert_eq!(observer.counts(), (1, 2, 1));
+    assert_eq!(
+        *observer.last_error.lock().unwrap(),
+        Some((ObOperation::Dec, Some(AASV_B64), err))
+    );
+
+    let err = ob.enc("").unwrap_err();
+    assert_eq!(observer.counts(), (1, 2, 2));
+    assert_eq!(
+        *observer.last_error.lock().unwrap(),
+        Some((ObOperation::Enc, Some(AASV_B64), err))
+    );
+}
+
+#[test]
+fn test_omnib_hooks_fire_on_enc_dec_and_error() {
+    let observer = Arc::new(CountingObserver::default());
+    let omb = Omnib::new_keyless()
+        .unwrap()
+        .with_observer(observer.clone());
+
+    let ot = omb.enc("hello", "aasv.b64").unwrap();
+    assert_eq!(omb.dec(&ot, AASV_B64).unwrap(), "hello");
+    assert_eq!(omb.autodec(&ot).unwrap(), "hello");
+    assert_eq!(observer.counts(), (1, 2, 0));
+
+    // A format string that does not parse is reported without a format
+    let err = omb.dec(&ot, "nope.b64").unwrap_err();
+    assert_eq!(observer.counts(), (1, 2, 1));
+    assert_eq!(
+        *observer.last_error.lock().unwrap(),
+        Some((ObOperation::Dec, None, err))
+    );
+
+    let err = omb.autodec("not obtext").unwrap_err();
+    assert_eq!(observer.counts(), (1, 2, 2));
+    assert_eq!(
+        *observer.last_error.lock().unwrap(),
+        Some((ObOperation::Autodec, None, err))
+    );
+}
+
+#[test]
+fn test_no_observer_by_default() {
+    assert!(Ob::new_keyless(AASV_B64).unwrap().observer().is_none());
+    assert!(Omnib::new_keyless().unwrap().observer().is_none());
+}
+
+#[test]
+fn test_detecting_methods_report_once() {
+    let observer = Arc::new(CountingObserver::default());
+    let ob = Ob::new_keyless(AASV_B64)
+        .unwrap()
+        .with_observer(observer.clone());
+    let ot = ob.enc("hello").unwrap();
+    assert_eq!(ob.dec_autoscheme(&ot).unwrap(), "hello");
+    assert_eq!(observer.counts(), (1, 1, 0));
+    assert_eq!(
+        *observer.last_dec.lock().unwrap(),
+        Some((None, ot.len(), 5))
+    );
+
+    let omb = Omnib::new_keyless()
+        .unwrap()
+        .with_observer(observer.clone());
+    assert_eq!(omb.autodec_within(&ot, &[AASV_B64]).unwrap(), "hello");
+    assert_eq!(observer.counts(), (1, 2, 0));
+    assert_eq!(omb.autodec_verbose(&ot).unwrap(), "hello");
+    assert_eq!(observer.counts(), (1, 3, 0));
+
+    let report = omb.autodec_verbose("not obtext").unwrap_err();
+    assert_eq!(observer.counts(), (1, 3, 1));
+    assert_eq!(
+        *observer.last_error.lock().unwrap(),
+        Some((ObOperation::Autodec, None, report.error().clone()))
+    );
+
+    // Restricted autodec goes through autodec_within, and reports once too
+    let omb = Omnib::new_restricted(oboron::HARDCODED_KEY_BASE64, &["aasv.b64"])
+        .unwrap()
+        .with_observer(observer.clone());
+    assert_eq!(omb.autodec(&ot).unwrap(), "hello");
+    assert_eq!(observer.counts(), (1, 4, 1));
+}
+
+#[test]
+fn test_omnib_enc_with_bad_format_reports_without_format() {
+    let observer = Arc::new(CountingObserver::default());
+    let omb = Omnib::new_keyless()
+        .unwrap()
+        .with_observer(observer.clone());
+    let err = omb.enc("hello", "nope.b64").unwrap_err();
+    assert_eq!(observer.counts(), (0, 0, 1));
+    assert_eq!(
+        *observer.last_error.lock().unwrap(),
+        Some((ObOperation::Enc, None, err))
+    );
+}
@@ -0,0 +1,65 @@
+//! Tests for mock3 (length-preserving keyed substitution)
+//!
+//! mock3 ciphertext is exactly as long as the plaintext; the obtext payload
+//! only adds the 2-byte scheme marker.
+
+#![cfg(feature = "mock")]
+
+use oboron::{Format, Omnib, Scheme};
+
+#[test]
+fn test_mock3_roundtrip_all_encodings() {
+    let key = oboron::generate_key();
+    let omb = Omnib::new(&key).unwrap();
+
+    for format in ["mock3.c32", "mock3.b32", "mock3.b64", "mock3.hex"] {
+        let ot = omb.enc("hello world", format).unwrap();
+        assert_eq!(omb.dec(&ot, format).unwrap(), "hello world");
+        assert_eq!(omb.autodec(&ot).unwrap(), "hello world");
+    }
+}
+
+#[test]
+fn test_mock3_preserves_length() {
+    let key = oboron::generate_key();
+    let ob = oboron::Mock3Hex::new(&key).unwrap();
+
+    for plaintext in ["a", "abc123", "Hello 世界", &"z".repeat(500)] {
+        let ot = ob.enc(plaintext).unwrap();
+        // Hex: two characters per byte; payload = ciphertext + 2-byte marker
+        assert_eq!(ot.len() / 2 - 2, plaintext.len());
+        assert_eq!(ob.dec(&ot).unwrap(), plaintext);
+    }
+}
+
+#[test]
+fn test_mock3_is_key_dependent_and_deterministic() {
+    let key1 = oboron::generate_key();
+    let key2 = oboron::generate_key();
+    let ob1 = oboron::Mock3C32::new(&key1).unwrap();
+    let ob2 = oboron::Mock3C32::new(&key2).unwrap();
+
+    let ot = ob1.enc("same input").unwrap();
+    assert_eq!(ot, ob1.enc("same input").unwrap());
+    assert_ne!(ot, ob2.enc("same input").unwrap());
+    assert_ne!(
+        ot,
+        oboron::Mock1C32::new(&key1)
+            .unwrap()
+            .enc("same input")
+            .unwrap()
+    );
+}
+
+#[test]
+fn test_mock3_format_parsing() {
+    let format: Format = "mock3.b64".parse().unwrap();
+    assert_eq!(format.scheme(), Scheme::Mock3);
+    assert!(Scheme::Mock3.is_deterministic());
+    assert_eq!(
+        oboron::Mock3B64::new(&oboron::generate_key())
+            .unwrap()
+            .format(),
+        format
+    );
+}
@@ -0,0 +1,83 @@
+//! Tests for mock4 (word-only substitution, demo scheme)
+//!
+//! mock4 rotates ASCII letters and digits within their class and passes
+//! every other byte through, so the ciphertext keeps the plaintext's
+//! length, letter case and delimiters.
+
+#![cfg(feature = "mock")]
+
+use oboron::{Format, Omnib, Scheme};
+
+/// Decode the ciphertext from a mock4 hex obtext (payload minus the
+/// trailing 2-byte scheme marker).
+fn hex_ciphertext(obtext: &str) -> Vec<u8> {
+    let payload: Vec<u8> = (0..obtext.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&obtext[i..i + 2], 16).unwrap())
+        .collect();
+    payload[..payload.len() - 2].to_vec()
+}
+
+#[test]
+fn test_mock4_roundtrip_all_encodings() {
+    let key = oboron::generate_key();
+    let omb = Omnib::new(&key).unwrap();
+
+    for format in ["mock4.c32", "mock4.b32", "mock4.b64", "mock4.hex"] {
+        let ot = omb.enc("hello, world!", format).unwrap();
+        assert_eq!(omb.dec(&ot, format).unwrap(), "hello, world!");
+        assert_eq!(omb.autodec(&ot).unwrap(), "hello, world!");
+    }
+}
+
+#[test]
+fn test_mock4_keeps_delimiters() {
+    let key = oboron::generate_key();
+    let ob = oboron::Mock4Hex::new(&key).unwrap();
+
+    for plaintext in [
+        "user@example.com",
+        "Hello, World! 42",
+        "path/to/file-name_v2.txt",
+        "Grüße, 世界",
+    ] {
+        let ot = ob.enc(plaintext).unwrap();
+        let ciphertext = hex_ciphertext(&ot);
+        assert_eq!(ciphertext.len(), plaintext.len());
+        for (&p, &c) in plaintext.as_bytes().iter().zip(&ciphertext) {
+            if p.is_ascii_alphanumeric() {
+                assert_eq!(p.is_ascii_uppercase(), c.is_ascii_uppercase());
+                assert_eq!(p.is_ascii_digit(), c.is_ascii_digit());
+            } else {
+                assert_eq!(p, c, "{plaintext:?}");
+            }
+        }
+        assert_eq!(ob.dec(&ot).unwrap(), plaintext);
+    }
+}
+
+#[test]
+fn test_mock4_is_key_dependent_and_deterministic() {
+    let key1 = oboron::generate_key();
+    let key2 = oboron::generate_key();
+    let ob1 = oboron::Mock4C32::new(&key1).unwrap();
+    let ob2 = oboron::Mock4C32::new(&key2).unwrap();
+
+    let ot = ob1.enc("same input words").unwrap();
+    assert_eq!(ot, ob1.enc("same input words").unwrap());
+    assert_ne!(ot, ob2.enc("same input words").unwrap());
+}
+
+#[test]
+fn test_mock4_format_parsing() {
+    let format: Format = "mock4.b64".parse().unwrap();
+    assert_eq!(format.scheme(), Scheme::Mock4);
+    assert_eq!("ob73".parse::<Scheme>().unwrap(), Scheme::Mock4);
+    assert!(Scheme::Mock4.is_deterministic());
+    assert_eq!(
+        oboron::Mock4B64::new(&oboron::generate_key())
+            .unwrap()
+            .format(),
+        format
+    );
+}
@@ -0,0 +1,57 @@
+//! Tests for `NullOb`, the no-op test double.
+
+#![cfg(feature = "testing")]
+
+use oboron::{Error, Format, NullOb, Ob, ObtextCodec, Omnib, Scheme};
+
+/// Application code under test, generic over the codec
+fn process<O: ObtextCodec>(ob: &O, ids: &[&str]) -> Result<Vec<String>, Error> {
+    ids.iter().map(|id| ob.enc(id)).collect()
+}
+
+#[test]
+fn test_null_ob_through_generic_code() {
+    let obtexts = process(&NullOb, &["alice", "bob"]).unwrap();
+    assert_eq!(obtexts, ["null:alice", "null:bob"]);
+    for (ot, id) in obtexts.iter().zip(["alice", "bob"]) {
+        assert_eq!(NullOb.dec(ot).unwrap(), id);
+    }
+
+    // Also usable as a trait object
+    let boxed: Box<dyn ObtextCodec> = Box::new(NullOb);
+    assert_eq!(boxed.dec(&boxed.enc("carol").unwrap()).unwrap(), "carol");
+}
+
+#[test]
+fn test_null_ob_reports_sentinel_format() {
+    assert_eq!(NullOb.scheme().as_str(), "null");
+    assert_eq!(NullOb.format().to_string(), "null.c32");
+    assert!(NullOb.dec("not from NullOb").is_err());
+    // Not a scheme any string parses to, nor any real codec reports
+    assert!("null".parse::<Scheme>().is_err());
+    assert!(NullOb.format().to_string().parse::<Format>().is_err());
+    assert!(oboron::supported_schemes().iter().all(|s| *s != NullOb.scheme()));
+
+    // No real codec encrypts or decrypts with it, nor decodes NullOb output
+    let key = oboron::generate_key();
+    let omb = Omnib::new(&key).unwrap();
+    assert_eq!(
+        omb.enc("hello", NullOb.format()),
+        Err(Error::UnknownScheme)
+    );
+    let ot = NullOb.enc("hello").unwrap();
+    assert!(omb.dec(&ot, NullOb.format()).is_err());
+    assert!(omb.autodec(&ot).is_err());
+    let real = Ob::new("mock1.c32", &key).unwrap().enc("hello").unwrap();
+    assert!(omb.dec(&real, NullOb.format()).is_err());
+    assert!(NullOb.dec(&real).is_err());
+}
+
+#[test]
+fn test_null_ob_key_id_is_unknown() {
+    assert_eq!(NullOb.key_id(), oboron::UNKNOWN_KEY_ID);
+    // Never a real fingerprint, which is hex
+    let ob = Ob::new("mock1.c32", &oboron::generate_key()).unwrap();
+    assert!(ob.key_id().bytes().all(|b| b.is_ascii_hexdigit()));
+    assert!(!NullOb.key_id().bytes().all(|b| b.is_ascii_hexdigit()));
+}
@@ -1,4 +1,4 @@
-use oboron::{Encoding, Ob, ObtextCodec, Scheme};
+use oboron::{Encoding, Format, Ob, Obtext, ObtextCodec, Plaintext, Scheme};
 
 #[test]
 fn test_ob_basic_roundtrip() {
@@ -12,6 +12,43 @@ fn test_ob_basic_roundtrip() {
     assert_eq!(pt2, plaintext);
 }
 
+#[test]
+fn test_ob_typed_roundtrip() {
+    let key = [0u8; 64];
+    let ob = Ob::from_bytes("mock1.c32", &key).expect("Failed to create Ob");
+
+    let ot: Obtext = ob
+        .enc_typed(Plaintext::from("Hello, typed!"))
+        .expect("Failed to enc");
+    assert_eq!(ot.as_str(), ob.enc("Hello, typed!").unwrap());
+
+    let pt2: Plaintext = ob.dec_typed(&ot).expect("Failed to dec");
+    assert_eq!(&*pt2, "Hello, typed!");
+}
+
+#[test]
+#[cfg(all(feature = "aasv", feature = "bytes-keys", feature = "seed-keys"))]
+fn test_ob_from_bytes_32() {
+    let seed = [3u8; 32];
+    let ob1 = Ob::from_bytes_32("aasv.b64", &seed).expect("Failed to create Ob");
+    let ob2 = Ob::from_bytes_32("aasv.b64", &seed).expect("Failed to create Ob");
+    assert_eq!(ob1.key(), ob2.key());
+
+    let ot = ob1.enc("seeded").expect("Failed to enc");
+    assert_eq!(ob2.dec(&ot).expect("Failed to dec"), "seeded");
+
+    let seed_b64 = data_encoding::BASE64URL_NOPAD.encode(&seed);
+    let ob3 = Ob::from_base64_32("aasv.b64", &seed_b64).expect("Failed to create Ob");
+    assert_eq!(ob3.key(), ob1.key());
+    assert_eq!(ob3.enc("seeded").unwrap(), ot);
+
+    // Not interchangeable with a native 64-byte key
+    let mut native = [0u8; 64];
+    native[..32].copy_from_slice(&seed);
+    let ob_native = Ob::from_bytes("aasv.b64", &native).expect("Failed to create Ob");
+    assert!(ob_native.dec(&ot).is_err());
+}
+
 #[test]
 #[cfg(feature = "aasv")]
 fn test_ob_deterministic() {
@@ -86,6 +123,17 @@ fn test_ob_with_format_instance() {
     assert_eq!(ob.encoding(), Encoding::B64);
 }
 
+#[test]
+fn test_ob_from_scheme() {
+    let key = oboron::generate_key();
+    let ob = Ob::from_scheme(Scheme::Mock1, &key).unwrap();
+    assert_eq!(ob.format(), Ob::new("mock1.c32", &key).unwrap().format());
+    assert_eq!(ob.format(), Format::with_scheme(Scheme::Mock1));
+
+    let ot = ob.enc("default encoding").unwrap();
+    assert_eq!(ob.dec(&ot).unwrap(), "default encoding");
+}
+
 #[test]
 fn test_ob_format_getter() {
     let key = [0u8; 64];
@@ -177,3 +225,38 @@ fn test_ob_generic_usage() {
     let ot = enc_with_oboron(&ob, "generic test");
     assert!(ot.len() > 0);
 }
+
+#[test]
+fn test_ob_display_is_format() {
+    let key = [0u8; 64];
+    for fmt in ["mock1.c32", "mock1.b64", "mock2.hex"] {
+        let mut ob = Ob::from_bytes(fmt, &key).expect("Failed to create Ob");
+        assert_eq!(ob.to_string(), fmt);
+        assert_eq!(format!("{}", ob), ob.format().to_string());
+
+        ob.set_encoding(Encoding::B32).unwrap();
+        assert!(ob.to_string().ends_with(".b32"));
+    }
+
+    let ob = Ob::new_keyless("mock1.c32").unwrap();
+    assert!(!ob.to_string().contains(&ob.key()));
+}
+
+#[test]
+fn test_ob_chained_setters() {
+    let key = [0u8; 64];
+    let mut ob = Ob::from_bytes("mock1.c32", &key).expect("Failed to create Ob");
+    ob.set_scheme(Scheme::Mock2)
+        .unwrap()
+        .set_encoding(Encoding::Hex)
+        .unwrap();
+    assert_eq!(ob.format(), Format::new(Scheme::Mock2, Encoding::Hex));
+
+    let ot = ob.enc("chained").unwrap();
+    let fresh = Ob::from_bytes("mock2.hex", &key).unwrap();
+    assert_eq!(fresh.dec(&ot).unwrap(), "chained");
+
+    // A failing setter leaves the format unchanged
+    assert!(ob.set_format("nope.c32").is_err());
+    assert_eq!(ob.format(), Format::new(Scheme::Mock2, Encoding::Hex));
+}
@@ -0,0 +1,54 @@
+//! Tests that `Omnib::enc`/`dec` give the same result whether the format is
+//! passed as a string or as a pre-parsed `Format` (see the
+//! `omnib_format_arg` benchmark group for the call overhead of each).
+
+#![cfg(feature = "keyless")]
+
+use oboron::{Encoding, Format, Omnib, Scheme};
+
+const SCHEMES: &[Scheme] = &[
+    #[cfg(feature = "aags")]
+    Scheme::Aags,
+    #[cfg(feature = "apgs")]
+    Scheme::Apgs,
+    #[cfg(feature = "aasv")]
+    Scheme::Aasv,
+    #[cfg(feature = "apsv")]
+    Scheme::Apsv,
+    #[cfg(feature = "upbc")]
+    Scheme::Upbc,
+];
+
+#[test]
+fn test_format_and_string_args_agree() {
+    let omb = Omnib::new_keyless().unwrap();
+
+    for &scheme in SCHEMES {
+        for encoding in Encoding::ALL {
+            let format = Format::new(scheme, encoding);
+            let name = format.to_string();
+
+            let from_format = omb.enc("same", format).unwrap();
+            let from_str = omb.enc("same", name.as_str()).unwrap();
+            if scheme.is_deterministic() {
+                assert_eq!(from_format, from_str, "{}", name);
+            }
+
+            for ot in [&from_format, &from_str] {
+                assert_eq!(omb.dec(ot, format).unwrap(), "same", "{}", name);
+                assert_eq!(omb.dec(ot, name.as_str()).unwrap(), "same", "{}", name);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_parsed_string_equals_format() {
+    for &scheme in SCHEMES {
+        for encoding in Encoding::ALL {
+            let format = Format::new(scheme, encoding);
+            let parsed: Format = format.to_string().parse().unwrap();
+            assert_eq!(parsed, format);
+        }
+    }
+}
@@ -0,0 +1,52 @@
+//! Tests for `Format::output_is_fixed_length` and `Scheme::nonce_len`.
+
+#![cfg(all(
+    feature = "aags",
+    feature = "apgs",
+    feature = "apgc",
+    feature = "aasv",
+    feature = "apsv"
+))]
+
+use oboron::{Encoding, Format, Omnib, Scheme};
+
+#[test]
+fn test_fixed_length_follows_determinism() {
+    for scheme in [Scheme::Aags, Scheme::Aasv] {
+        let format = Format::new(scheme, Encoding::B64);
+        assert!(format.output_is_fixed_length(), "{}", format);
+        assert_eq!(scheme.nonce_len(), 0);
+    }
+    for (scheme, nonce_len) in [(Scheme::Apgs, 12), (Scheme::Apgc, 12), (Scheme::Apsv, 16)] {
+        let format = Format::new(scheme, Encoding::B64);
+        assert!(!format.output_is_fixed_length(), "{}", format);
+        assert_eq!(scheme.nonce_len(), nonce_len);
+    }
+}
+
+#[test]
+fn test_nonce_len_accounts_for_expansion() {
+    let omb = Omnib::new(&oboron::generate_key()).unwrap();
+    let hex_len = |format: &str| omb.enc("expansion", format).unwrap().len();
+
+    // Each probabilistic scheme is its deterministic counterpart plus a nonce
+    assert_eq!(
+        hex_len("apgs.hex"),
+        hex_len("aags.hex") + 2 * Scheme::Apgs.nonce_len()
+    );
+    assert_eq!(
+        hex_len("apgc.hex"),
+        hex_len("aags.hex") + 2 * Scheme::Apgc.nonce_len()
+    );
+    assert_eq!(
+        hex_len("apsv.hex"),
+        hex_len("aasv.hex") + 2 * Scheme::Apsv.nonce_len()
+    );
+
+    // Fixed-length formats reproduce their obtext; the others do not
+    for format in ["aags.c32", "aasv.c32", "apgs.c32", "apgc.c32", "apsv.c32"] {
+        let fixed = Format::from_str(format).unwrap().output_is_fixed_length();
+        let same = omb.enc("expansion", format).unwrap() == omb.enc("expansion", format).unwrap();
+        assert_eq!(same, fixed, "{}", format);
+    }
+}
@@ -0,0 +1,40 @@
+//! Tests for decoding obtexts pasted with trailing `=` padding.
+
+#![cfg(all(feature = "aasv", feature = "keyless"))]
+
+use oboron::{Ob, Omnib};
+
+/// Append the `=` padding another tool would emit for a `block`-sized
+/// encoding.
+fn pad(obtext: &str, block: usize) -> String {
+    let padding = (block - obtext.len() % block) % block;
+    format!("{}{}", obtext, "=".repeat(padding))
+}
+
+#[test]
+fn test_padded_obtext_decodes_like_unpadded() {
+    let omb = Omnib::new_keyless().unwrap();
+    for (format, block) in [("aasv.b64", 4), ("aasv.b32", 8)] {
+        // Lengths that need padding, and one that does not
+        for plaintext in ["a", "ab", "abc", "hello world", "padding!"] {
+            let ot = omb.enc(plaintext, format).unwrap();
+            assert!(!ot.ends_with('='), "enc must not pad: {:?}", ot);
+
+            let padded = pad(&ot, block);
+            assert_eq!(omb.dec(&padded, format).unwrap(), plaintext);
+            assert_eq!(
+                omb.dec(&padded, format).unwrap(),
+                omb.dec(&ot, format).unwrap()
+            );
+            assert_eq!(omb.autodec(&padded).unwrap(), plaintext);
+        }
+    }
+}
+
+#[test]
+fn test_padded_obtext_with_ob() {
+    let ob = Ob::new_keyless("aasv.b64").unwrap();
+    let ot = ob.enc("pasted from elsewhere").unwrap();
+    assert_eq!(ob.dec(&pad(&ot, 4)).unwrap(), "pasted from elsewhere");
+    assert!(ob.dec(&format!("{}===", ot)).is_err());
+}
@@ -0,0 +1,47 @@
+//! Tests for `Omnib::new_restricted`, the per-instance format allowlist.
+
+#![cfg(all(feature = "aasv", feature = "aags"))]
+
+use oboron::{Error, Omnib, AAGS_C32, AASV_B64, AASV_C32};
+
+#[test]
+fn test_restricted_allowed_formats_work() {
+    let key = oboron::generate_key();
+    let omb = Omnib::new_restricted(&key, &["aasv.c32", "aasv.b64"]).unwrap();
+    assert_eq!(omb.allowed_formats(), Some(&[AASV_C32, AASV_B64][..]));
+
+    for format in [AASV_C32, AASV_B64] {
+        let ot = omb.enc("hello", format).unwrap();
+        assert_eq!(omb.dec(&ot, format).unwrap(), "hello");
+        assert_eq!(omb.autodec(&ot).unwrap(), "hello");
+        assert_eq!(omb.candidate_formats(&ot), [format]);
+    }
+    assert_eq!(Omnib::new(&key).unwrap().allowed_formats(), None);
+}
+
+#[test]
+fn test_restricted_rejects_other_valid_formats() {
+    let key = oboron::generate_key();
+    let omb = Omnib::new_restricted(&key, &["aasv.c32"]).unwrap();
+    let not_allowed = Err(Error::FormatNotAllowed { format: AAGS_C32 });
+
+    assert_eq!(omb.enc("hello", "aags.c32"), not_allowed);
+
+    // Valid obtext of a disallowed format, made by an unrestricted instance
+    let ot = Omnib::new(&key).unwrap().enc("hello", AAGS_C32).unwrap();
+    assert_eq!(omb.dec(&ot, AAGS_C32), not_allowed);
+    assert_eq!(omb.autodec(&ot), not_allowed);
+    assert_eq!(omb.autodec_within(&ot, &[AAGS_C32]), not_allowed);
+    assert!(omb.candidate_formats(&ot).is_empty());
+    assert!(omb.encodings_of("hello", oboron::Scheme::Aasv).is_err());
+}
+
+#[test]
+fn test_restricted_validates_formats_up_front() {
+    let key = oboron::generate_key();
+    assert!(matches!(
+        Omnib::new_restricted(&key, &["aasv.c32", "nope.b64"]),
+        Err(Error::InvalidFormat)
+    ));
+    assert!(Omnib::new_restricted("not a key", &["aasv.c32"]).is_err());
+}
@@ -0,0 +1,54 @@
+//! Tests for key-free cross-encoding ciphertext comparison.
+
+#![cfg(all(feature = "aasv", feature = "keyless"))]
+
+use oboron::{same_ciphertext, Encoding, Error, Omnib};
+
+#[test]
+fn test_same_ciphertext_across_encodings() {
+    let omb = Omnib::new_keyless().unwrap();
+    let c32 = omb.enc("duplicate token", "aasv.c32").unwrap();
+    let b64 = omb.enc("duplicate token", "aasv.b64").unwrap();
+    assert_ne!(c32, b64);
+    assert!(same_ciphertext(&c32, Encoding::C32, &b64, Encoding::B64).unwrap());
+
+    // Every encoding pair agrees
+    for a_enc in Encoding::ALL {
+        let a = omb
+            .enc("duplicate token", format!("aasv.{}", a_enc).as_str())
+            .unwrap();
+        for b_enc in Encoding::ALL {
+            let b = omb
+                .enc("duplicate token", format!("aasv.{}", b_enc).as_str())
+                .unwrap();
+            assert!(same_ciphertext(&a, a_enc, &b, b_enc).unwrap());
+        }
+    }
+}
+
+#[test]
+fn test_different_ciphertexts_compare_unequal() {
+    let omb = Omnib::new_keyless().unwrap();
+    let a = omb.enc("first token", "aasv.c32").unwrap();
+    let b = omb.enc("second token", "aasv.b64").unwrap();
+    assert!(!same_ciphertext(&a, Encoding::C32, &b, Encoding::B64).unwrap());
+
+    // Different lengths
+    let long = omb.enc("a much longer token value", "aasv.b64").unwrap();
+    assert!(!same_ciphertext(&a, Encoding::C32, &long, Encoding::B64).unwrap());
+
+    // Same plaintext under a different key
+    let other = Omnib::new(&oboron::generate_key()).unwrap();
+    let c = other.enc("first token", "aasv.b64").unwrap();
+    assert!(!same_ciphertext(&a, Encoding::C32, &c, Encoding::B64).unwrap());
+}
+
+#[test]
+fn test_invalid_encoding_is_an_error() {
+    let omb = Omnib::new_keyless().unwrap();
+    let a = omb.enc("token", "aasv.c32").unwrap();
+    assert_eq!(
+        same_ciphertext(&a, Encoding::C32, "not hex!", Encoding::Hex),
+        Err(Error::InvalidHex)
+    );
+}
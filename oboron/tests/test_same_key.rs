@@ -0,0 +1,38 @@
+//! Tests for `Ob::same_key` and `Omnib::same_key`, constant-time key equality.
+
+#![cfg(all(feature = "aasv", feature = "apsv", feature = "bytes-keys"))]
+
+use oboron::{Ob, Omnib};
+
+fn key_bytes(last: u8) -> [u8; 64] {
+    let mut key = [0u8; 64];
+    for (i, byte) in key.iter_mut().enumerate() {
+        *byte = i as u8;
+    }
+    key[63] = last;
+    key
+}
+
+#[test]
+fn test_ob_same_key_ignores_format() {
+    let ob = Ob::from_bytes("aasv.b64", &key_bytes(0)).unwrap();
+    assert!(ob.same_key(&ob));
+    assert!(ob.same_key(&Ob::from_bytes("apsv.c32", &key_bytes(0)).unwrap()));
+    // Keys built from other representations of the same bytes are equal
+    assert!(ob.same_key(&Ob::new("aasv.hex", &ob.key()).unwrap()));
+}
+
+#[test]
+fn test_ob_different_keys_differ() {
+    let ob = Ob::from_bytes("aasv.b64", &key_bytes(0)).unwrap();
+    // A difference in the last byte only
+    assert!(!ob.same_key(&Ob::from_bytes("aasv.b64", &key_bytes(1)).unwrap()));
+    assert!(!ob.same_key(&Ob::new("aasv.b64", &oboron::generate_key()).unwrap()));
+}
+
+#[test]
+fn test_omnib_same_key() {
+    let omb = Omnib::from_bytes(&key_bytes(0)).unwrap();
+    assert!(omb.same_key(&Omnib::new(&omb.key()).unwrap()));
+    assert!(!omb.same_key(&Omnib::from_bytes(&key_bytes(1)).unwrap()));
+}
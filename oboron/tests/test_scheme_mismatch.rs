@@ -0,0 +1,53 @@
+//! Tests that strict decoding tells a wrong-scheme obtext apart from one
+//! that fails authentication.
+
+#![cfg(all(feature = "aags", feature = "aasv", feature = "keyless"))]
+
+use oboron::encoding::{decode, encode};
+use oboron::{AagsC32, AasvC32, Encoding, Error, Ob, Omnib, Scheme};
+
+#[test]
+fn test_wrong_scheme_reports_found_scheme() {
+    let aags = Ob::new_keyless("aags.c32").unwrap();
+    let aasv = Ob::new_keyless("aasv.c32").unwrap();
+    let expected = Err(Error::SchemeMismatch {
+        expected: Scheme::Aags,
+        found: Scheme::Aasv,
+    });
+
+    let ot = aasv.enc("wrong scheme").unwrap();
+    assert_eq!(aags.dec(&ot), expected);
+    assert_eq!(Omnib::new_keyless().unwrap().dec(&ot, "aags.c32"), expected);
+    assert_eq!(AagsC32::new_keyless().unwrap().dec(&ot), expected);
+
+    // And the other way round
+    let ot = aags.enc("wrong scheme").unwrap();
+    assert_eq!(
+        AasvC32::new_keyless().unwrap().dec(&ot),
+        Err(Error::SchemeMismatch {
+            expected: Scheme::Aasv,
+            found: Scheme::Aags,
+        })
+    );
+}
+
+#[test]
+fn test_tampered_tag_is_decryption_failure() {
+    let ob = Ob::new_keyless("aasv.c32").unwrap();
+    let ot = ob.enc("tamper with me").unwrap();
+
+    // Flip a bit away from the first byte, which the marker is XORed with
+    let mut payload = decode(&ot, Encoding::C32).unwrap();
+    payload[1] ^= 0x01;
+    let tampered = encode(&payload, Encoding::C32);
+
+    assert_eq!(ob.dec(&tampered), Err(Error::DecryptionFailed));
+}
+
+#[test]
+fn test_unknown_marker_is_marker_mismatch() {
+    let ob = Ob::new_keyless("aasv.c32").unwrap();
+    let payload = [0x42u8; 24];
+    let ot = encode(&payload, Encoding::C32);
+    assert_eq!(ob.dec(&ot), Err(Error::SchemeMarkerMismatch));
+}
@@ -0,0 +1,44 @@
+//! Tests for the `secrecy` feature: `SecretString` key input and output.
+
+#![cfg(all(feature = "secrecy", feature = "aasv", feature = "apsv"))]
+
+use oboron::secrecy::{ExposeSecret, SecretString};
+use oboron::{Ob, Omnib};
+
+#[test]
+fn test_ob_new_secret_roundtrip() {
+    let key = SecretString::from(oboron::generate_key());
+    let ob = Ob::new_secret("aasv.c32", &key).unwrap();
+    let ot = ob.enc("wrapped").unwrap();
+    assert_eq!(ob.dec(&ot).unwrap(), "wrapped");
+
+    // Same key material as the plain constructor
+    let plain = Ob::new("aasv.c32", key.expose_secret()).unwrap();
+    assert_eq!(plain.dec(&ot).unwrap(), "wrapped");
+    assert_eq!(ob.key_secret().expose_secret(), key.expose_secret());
+}
+
+#[test]
+fn test_omnib_new_secret_roundtrip() {
+    let key = SecretString::from(oboron::generate_key());
+    let omb = Omnib::new_secret(&key).unwrap();
+    for format in ["aasv.b64", "apsv.hex"] {
+        let ot = omb.enc("wrapped", format).unwrap();
+        assert_eq!(omb.autodec(&ot).unwrap(), "wrapped");
+    }
+    assert_eq!(omb.key_secret().expose_secret(), key.expose_secret());
+}
+
+#[test]
+fn test_secret_key_is_redacted_and_validated() {
+    let key = SecretString::from(oboron::generate_key());
+    let shown = format!(
+        "{:?}",
+        Ob::new_secret("aasv.b64", &key).unwrap().key_secret()
+    );
+    assert!(!shown.contains(key.expose_secret()));
+
+    let bad = SecretString::from("not a key");
+    assert!(Ob::new_secret("aasv.b64", &bad).is_err());
+    assert!(Omnib::new_secret(&bad).is_err());
+}
@@ -0,0 +1,90 @@
+//! Tests for `ObtextCodec::self_test`, the health-check round trip.
+
+#![cfg(feature = "keyless")]
+
+use oboron::{supported_schemes, Encoding, Error, Format, Ob, ObtextCodec, Scheme};
+
+#[test]
+fn test_self_test_all_schemes() {
+    for &scheme in supported_schemes() {
+        if scheme.is_ztier_or_legacy() {
+            continue;
+        }
+        for encoding in Encoding::ALL {
+            let ob = Ob::new_keyless(Format::new(scheme, encoding)).unwrap();
+            assert_eq!(ob.self_test(), Ok(()), "{}", ob.format());
+        }
+    }
+}
+
+#[test]
+fn test_self_test_ztier_formats_on_ob_fail_without_panic() {
+    // Z-tier and legacy go through Obz/Legacy; Ob reports an error for them
+    for &scheme in supported_schemes() {
+        if !scheme.is_ztier_or_legacy() {
+            continue;
+        }
+        let ob = Ob::new_keyless(Format::new(scheme, Encoding::B32)).unwrap();
+        assert_eq!(
+            ob.self_test(),
+            Err(Error::UnsupportedScheme { scheme }),
+            "{}",
+            scheme
+        );
+    }
+}
+
+#[cfg(feature = "ztier")]
+#[test]
+fn test_self_test_ztier() {
+    use oboron::ztier::{Legacy, Obz};
+
+    for &scheme in supported_schemes() {
+        if !scheme.is_ztier() {
+            continue;
+        }
+        let obz = Obz::new_keyless(Format::new(scheme, Encoding::C32)).unwrap();
+        assert_eq!(obz.self_test(), Ok(()), "{}", scheme);
+    }
+    #[cfg(feature = "legacy")]
+    assert_eq!(Legacy::new_keyless().unwrap().self_test(), Ok(()));
+}
+
+#[cfg(feature = "aasv")]
+#[test]
+fn test_self_test_typed_codecs() {
+    let key = oboron::generate_key();
+    assert_eq!(oboron::AasvC32::new(&key).unwrap().self_test(), Ok(()));
+    assert_eq!(oboron::new("aasv.hex", &key).unwrap().self_test(), Ok(()));
+}
+
+/// A codec whose dec does not invert its enc.
+struct Broken;
+
+impl ObtextCodec for Broken {
+    fn enc(&self, plaintext: &str) -> Result<String, Error> {
+        Ok(plaintext.to_string())
+    }
+    fn dec(&self, obtext: &str) -> Result<String, Error> {
+        Ok(obtext.to_uppercase())
+    }
+    fn format(&self) -> Format {
+        Format::new(supported_schemes()[0], Encoding::C32)
+    }
+    fn scheme(&self) -> Scheme {
+        self.format().scheme()
+    }
+    fn encoding(&self) -> Encoding {
+        Encoding::C32
+    }
+}
+
+#[test]
+fn test_self_test_detects_bad_round_trip() {
+    assert_eq!(
+        Broken.self_test(),
+        Err(Error::SelfTestFailed {
+            format: Broken.format()
+        })
+    );
+}
@@ -0,0 +1,73 @@
+//! Tests for `Arc`-shared codecs used from several threads at once.
+
+#![cfg(all(feature = "aasv", feature = "keyless"))]
+
+use oboron::{Ob, Omnib};
+use std::sync::Arc;
+use std::thread;
+
+const THREADS: usize = 8;
+const ROUNDS: usize = 50;
+
+#[test]
+fn test_shared_omnib_across_threads() {
+    let omb: Arc<Omnib> = Omnib::shared_keyless().unwrap();
+
+    let workers: Vec<_> = (0..THREADS)
+        .map(|t| {
+            let omb = Arc::clone(&omb);
+            thread::spawn(move || {
+                for r in 0..ROUNDS {
+                    let pt = format!("thread {} round {}", t, r);
+                    for format in ["aasv.c32", "aasv.b64"] {
+                        let ot = omb.enc(&pt, format).unwrap();
+                        assert_eq!(omb.dec(&ot, format).unwrap(), pt);
+                        assert_eq!(omb.autodec(&ot).unwrap(), pt);
+                    }
+                }
+                omb.enc(&format!("thread {}", t), "aasv.c32").unwrap()
+            })
+        })
+        .collect();
+
+    // Deterministic scheme: results match a single-threaded instance
+    let local = Omnib::new_keyless().unwrap();
+    for (t, worker) in workers.into_iter().enumerate() {
+        let ot = worker.join().unwrap();
+        assert_eq!(ot, local.enc(&format!("thread {}", t), "aasv.c32").unwrap());
+    }
+}
+
+#[test]
+fn test_shared_ob_across_threads() {
+    let ob: Arc<Ob> = Ob::shared_keyless("aasv.b64").unwrap();
+
+    let workers: Vec<_> = (0..THREADS)
+        .map(|t| {
+            let ob = Arc::clone(&ob);
+            thread::spawn(move || {
+                for r in 0..ROUNDS {
+                    let pt = format!("thread {} round {}", t, r);
+                    let ot = ob.enc(&pt).unwrap();
+                    assert_eq!(ob.dec(&ot).unwrap(), pt);
+                }
+            })
+        })
+        .collect();
+    for worker in workers {
+        worker.join().unwrap();
+    }
+}
+
+#[test]
+fn test_shared_matches_new() {
+    let key = oboron::generate_key();
+    let shared = Omnib::shared(&key).unwrap();
+    let plain = Omnib::new(&key).unwrap();
+    let ot = shared.enc("same key", "aasv.hex").unwrap();
+    assert_eq!(plain.dec(&ot, "aasv.hex").unwrap(), "same key");
+
+    let shared = Ob::shared("aasv.hex", &key).unwrap();
+    assert_eq!(shared.dec(&ot).unwrap(), "same key");
+    assert!(Ob::shared("aasv.hex", "not a key").is_err());
+}
@@ -0,0 +1,60 @@
+//! Tests that `Scheme::uses_reversal` matches the obtext each scheme emits.
+
+#![cfg(feature = "keyless")]
+
+use oboron::encoding::decode;
+use oboron::{supported_schemes, Encoding, Format, Ob, Scheme};
+
+/// Obtext of `plaintext` under `scheme`, in c32 where the scheme has encodings
+fn enc(scheme: Scheme, plaintext: &str) -> String {
+    match scheme.as_str() {
+        #[cfg(feature = "legacy")]
+        "legacy" => oboron::ztier::Legacy::new_keyless()
+            .unwrap()
+            .enc(plaintext)
+            .unwrap(),
+        #[cfg(feature = "ztier")]
+        "zrbcx" | "zmock1" => oboron::ztier::Obz::new_keyless(Format::new(scheme, Encoding::C32))
+            .unwrap()
+            .enc(plaintext)
+            .unwrap(),
+        _ => Ob::new_keyless(Format::new(scheme, Encoding::C32))
+            .unwrap()
+            .enc(plaintext)
+            .unwrap(),
+    }
+}
+
+#[test]
+fn test_uses_reversal_matches_obtext_layout() {
+    for &scheme in supported_schemes() {
+        let obtext = enc(scheme, "reversal check");
+        if scheme.uses_reversal() {
+            continue;
+        }
+        // In byte order, the mixed marker is the last two payload bytes
+        let payload = decode(&obtext, Encoding::C32).unwrap();
+        let len = payload.len();
+        let marker = [payload[len - 2] ^ payload[0], payload[len - 1] ^ payload[0]];
+        assert_eq!(marker, scheme.marker(), "{}", scheme);
+    }
+}
+
+#[cfg(feature = "legacy")]
+#[test]
+fn test_legacy_obtext_is_reversed() {
+    assert!(Scheme::Legacy.uses_reversal());
+
+    // Same first block, so the same leading ciphertext (legacy is plain
+    // CBC with a fixed IV), which reversal moves to the end of the obtext
+    let a = enc(Scheme::Legacy, "0123456789abcdef and then a");
+    let b = enc(Scheme::Legacy, "0123456789abcdef and then b");
+    let common_suffix = a
+        .bytes()
+        .rev()
+        .zip(b.bytes().rev())
+        .take_while(|(x, y)| x == y)
+        .count();
+    assert!(common_suffix >= 25, "{} {}", a, b);
+    assert_ne!(a.as_bytes()[0], b.as_bytes()[0]);
+}
@@ -0,0 +1,61 @@
+//! Tests for `verify_key`, checking a key against existing obtext.
+
+#![cfg(all(feature = "aasv", feature = "apsv", feature = "upbc"))]
+
+use oboron::{verify_key, Error, Omnib};
+
+#[test]
+fn test_verify_key_matching_key() {
+    let key = oboron::generate_key();
+    let omb = Omnib::new(&key).unwrap();
+    for format in ["aasv.c32", "aasv.b64", "apsv.hex", "upbc.b32"] {
+        let ot = omb.enc("existing data", format).unwrap();
+        assert_eq!(verify_key(&ot, &key), Ok(true), "{}", format);
+    }
+}
+
+#[test]
+fn test_verify_key_wrong_key() {
+    let key = oboron::generate_key();
+    let other = oboron::generate_key();
+    let omb = Omnib::new(&key).unwrap();
+    for format in ["aasv.c32", "aasv.b32", "aasv.b64", "aasv.hex", "apsv.b64"] {
+        let ot = omb.enc("existing data", format).unwrap();
+        assert_eq!(verify_key(&ot, &other), Ok(false), "{}", format);
+    }
+}
+
+#[test]
+fn test_verify_key_errors() {
+    let key = oboron::generate_key();
+    for garbage in ["", "not obtext!", "zz", "AAAA"] {
+        assert!(verify_key(garbage, &key).is_err(), "{:?}", garbage);
+    }
+    let ot = Omnib::new(&key).unwrap().enc("data", "aasv.c32").unwrap();
+    assert!(matches!(
+        verify_key(&ot, "not a key"),
+        Err(Error::KeyWrongLength { .. })
+    ));
+}
+
+#[cfg(feature = "mock")]
+#[test]
+fn test_verify_key_unkeyed_mocks() {
+    let key = oboron::generate_key();
+    let other = oboron::generate_key();
+    let omb = Omnib::new(&key).unwrap();
+    for format in ["mock1.c32", "mock2.b64"] {
+        let ot = omb.enc("existing data", format).unwrap();
+        // Any key "decrypts" it, so neither answer would mean anything
+        for k in [&key, &other] {
+            assert!(
+                matches!(verify_key(&ot, k), Err(Error::UnkeyedScheme { .. })),
+                "{}",
+                format
+            );
+        }
+    }
+    // mock3 is keyed
+    let ot = omb.enc("existing data", "mock3.c32").unwrap();
+    assert_eq!(verify_key(&ot, &key), Ok(true));
+}